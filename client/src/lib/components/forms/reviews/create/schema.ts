@@ -25,8 +25,11 @@ export const createSoftwareSchema = z.object({
     .max(100, "Name cannot exceed 100 characters"),
   software_version: z
     .string()
-    .regex(/^\d+\.\d+\.\d+$/, "Version must be in the format x.y.z")
-    .max(12, "Version cannot exceed 12 characters"),
+    .regex(
+      /^[vV]?\d+(\.\d+){0,3}(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$/,
+      "Version must be 1 to 4 numbers separated by dots, e.g. 2024.1 or 3.2.1-beta",
+    )
+    .max(50, "Version cannot exceed 50 characters"),
   developer_name: z
     .string()
     .min(1, "Developer name required")
@@ -97,7 +100,7 @@ export const reviewNotesSchema = z.object({
   review_notes: z
     .string()
     .min(1, "Notes are required")
-    .max(255, "Notes cannot exceed 255 characters"),
+    .max(10000, "Notes cannot exceed 10000 characters"),
 });
 
 export type ReviewGroup1Schema = z.infer<typeof reviewGroup1Schema>;
@@ -53,7 +53,7 @@ export const editSofwareReviewSchema = z.object({
   review_notes: z
     .string()
     .min(1, "Notes are required")
-    .max(255, "Notes cannot exceed 255 characters"),
+    .max(10000, "Notes cannot exceed 10000 characters"),
 });
 
 export type EditSoftwareReviewSchema = z.infer<typeof editSofwareReviewSchema>;
@@ -7,8 +7,11 @@ export const editSoftwareSchema = z.object({
     .max(100, "Name cannot exceed 100 characters"),
   software_version: z
     .string()
-    .regex(/^\d+\.\d+\.\d+$/, "Version must be in the format x.y.z")
-    .max(12, "Version cannot exceed 12 characters"),
+    .regex(
+      /^[vV]?\d+(\.\d+){0,3}(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$/,
+      "Version must be 1 to 4 numbers separated by dots, e.g. 2024.1 or 3.2.1-beta",
+    )
+    .max(50, "Version cannot exceed 50 characters"),
   developer_name: z
     .string()
     .min(1, "Developer name required")
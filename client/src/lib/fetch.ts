@@ -24,7 +24,7 @@ class HttpError extends Error {
     try {
       const errorBody = await this.response.json();
       return (
-        errorBody.error ||
+        errorBody.detail ||
         this.response.statusText ||
         "an unexpected error occurred"
       );
@@ -48,7 +48,10 @@ export async function fetchRequest<T>(
     credentials: "include",
   };
 
-  if (requestBody) {
+  // Files are uploaded as multipart form data, which sets its own content type
+  if (requestBody instanceof FormData) {
+    fetchOptions.body = requestBody;
+  } else if (requestBody) {
     fetchOptions.headers = {
       "Content-Type": "application/json",
       ...(fetchOptions.headers || {}), // Preserve existing headers
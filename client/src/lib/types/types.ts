@@ -42,6 +42,10 @@ export type SoftwareResponse = {
   software: Array<{ software: Software }>;
 };
 
+export type SimilarSoftwareResponse = {
+  similar_software: Array<{ software: Software; similarity: number }>;
+};
+
 export type Requester = {
   id: string;
   name: string;
@@ -83,9 +87,27 @@ export type SoftwareReview = {
   is_supported_by_current_os: "TRUE" | "FALSE" | "NOT_SURE";
   exported: boolean;
   review_notes: string;
+  reviewed_version: string;
+  newer_version_available: boolean;
   created_at: string;
 };
 
+export type ReviewVerificationStatus =
+  | "valid"
+  | "metadata_only"
+  | "outdated"
+  | "tampered";
+
+export type ReviewVerification = {
+  review_id: string;
+  status: ReviewVerificationStatus;
+  report_version: number;
+  current_version: number;
+  content_hash: string;
+  signed: boolean;
+  signature_valid: boolean | null;
+};
+
 export type SoftwareReviewResponse = {
   metadata: Metadata;
   software_reviews: Array<{ software_review: SoftwareReview }>;
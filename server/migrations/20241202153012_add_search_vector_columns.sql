@@ -0,0 +1,38 @@
+-- Escape HTML special characters so highlighted snippets returned by `ts_headline` are safe to render
+CREATE OR REPLACE FUNCTION html_escape(value TEXT)
+RETURNS TEXT AS $$
+    SELECT replace(replace(replace(replace(value, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;');
+$$ LANGUAGE sql IMMUTABLE;
+
+-- software (name and developer are weighted above the description)
+ALTER TABLE software
+ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', software_name), 'A') ||
+    setweight(to_tsvector('simple', developer_name), 'B') ||
+    setweight(to_tsvector('simple', description), 'C')
+) STORED;
+
+-- requester (email is indexed both whole and split on '@' so either part can be searched)
+ALTER TABLE requester
+ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', name), 'A') ||
+    setweight(to_tsvector('simple', email || ' ' || replace(email, '@', ' ')), 'A') ||
+    setweight(to_tsvector('simple', department), 'C')
+) STORED;
+
+-- software request (TeamDynamix ticket #)
+ALTER TABLE software_request
+ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', td_request_id), 'A')
+) STORED;
+
+-- software review
+ALTER TABLE software_review
+ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', review_notes), 'B')
+) STORED;
+
+CREATE INDEX idx_software_search_vector ON software USING GIN (search_vector);
+CREATE INDEX idx_requester_search_vector ON requester USING GIN (search_vector);
+CREATE INDEX idx_software_request_search_vector ON software_request USING GIN (search_vector);
+CREATE INDEX idx_software_review_search_vector ON software_review USING GIN (search_vector);
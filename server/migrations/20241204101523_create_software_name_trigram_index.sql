@@ -0,0 +1,5 @@
+-- Provides trigram matching used to detect software with similar names
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- software
+CREATE INDEX idx_software_software_name_trgm ON software USING GIN (software_name gin_trgm_ops);
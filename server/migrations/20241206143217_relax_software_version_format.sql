@@ -0,0 +1,40 @@
+-- Versions are validated by the API (see `SoftwareVersion`), which accepts formats such as
+-- `2024.1`, `10.0.19045.3803`, `3.2.1-beta` and `v5` that the x.y.z CHECK rejected
+ALTER TABLE software
+DROP CONSTRAINT software_software_version_check;
+
+ALTER TABLE software
+ALTER COLUMN software_version TYPE VARCHAR(50);
+
+-- Sort key computed by the API from the version, ordered byte by byte ("C" collation) so
+-- the software list can be sorted by version precedence rather than alphabetically
+ALTER TABLE software
+ADD COLUMN software_version_key TEXT COLLATE "C";
+
+-- Existing versions are all x.y.z, so the key is each component padded to 10 digits, a
+-- zero 4th component and `~` to mark a release
+UPDATE software
+SET software_version_key =
+    lpad(split_part(software_version, '.', 1), 10, '0') || '.' ||
+    lpad(split_part(software_version, '.', 2), 10, '0') || '.' ||
+    lpad(split_part(software_version, '.', 3), 10, '0') || '.' ||
+    lpad('0', 10, '0') || '~';
+
+ALTER TABLE software
+ALTER COLUMN software_version_key SET NOT NULL;
+
+CREATE INDEX idx_software_software_version_key ON software (software_version_key);
+
+-- The software version at the time of the review, so reviews can report when a newer
+-- version of the software has since been requested
+ALTER TABLE software_review
+ADD COLUMN reviewed_version VARCHAR(50);
+
+UPDATE software_review sr
+SET reviewed_version = s.software_version
+FROM software_request r
+INNER JOIN software s ON r.software_id = s.id
+WHERE sr.software_request_id = r.id;
+
+ALTER TABLE software_review
+ALTER COLUMN reviewed_version SET NOT NULL;
@@ -0,0 +1,5 @@
+-- The reviewer a software request is assigned to, if any
+ALTER TABLE software_request
+ADD COLUMN assignee_id UUID REFERENCES user_account(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_software_request_assignee_id ON software_request (assignee_id);
@@ -0,0 +1,35 @@
+-- When a review stops counting as a current approval, and when the expiry worker flagged
+-- it as nearing that point
+ALTER TABLE software_review
+ADD COLUMN expires_at TIMESTAMPTZ,
+ADD COLUMN expiry_flagged_at TIMESTAMPTZ;
+
+-- Existing reviews use the default validity periods, by their outcome: 180 days with
+-- concerns, 365 days when unsure and 730 days otherwise
+UPDATE software_review
+SET expires_at = COALESCE(created_at, NOW()) + CASE
+    WHEN 'FALSE' IN (
+        is_supported, is_current_version, is_reputation_good,
+        is_installation_from_developer, is_supported_by_current_os
+    ) OR 'TRUE' IN (
+        is_local_admin_required, is_connected_to_brockport_cloud,
+        is_connected_to_cloud_services_or_client, is_security_or_optimization_software
+    ) THEN INTERVAL '180 days'
+    WHEN 'NOT_SURE' IN (
+        is_supported, is_current_version, is_reputation_good,
+        is_installation_from_developer, is_supported_by_current_os,
+        is_local_admin_required, is_connected_to_brockport_cloud,
+        is_connected_to_cloud_services_or_client, is_security_or_optimization_software
+    ) THEN INTERVAL '365 days'
+    ELSE INTERVAL '730 days'
+END;
+
+ALTER TABLE software_review
+ALTER COLUMN expires_at SET NOT NULL;
+
+CREATE INDEX idx_software_review_expires_at ON software_review (expires_at);
+
+-- Set when the review of a software request expires and the request goes back in the
+-- review queue, cleared once it is reviewed again
+ALTER TABLE software_request
+ADD COLUMN reopened_at TIMESTAMPTZ;
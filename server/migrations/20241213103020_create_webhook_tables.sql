@@ -0,0 +1,54 @@
+CREATE TYPE webhook_delivery_status AS ENUM ('PENDING', 'DELIVERED', 'FAILED');
+
+-- Subscriptions to review lifecycle events, managed by admins
+CREATE TABLE webhook (
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    url VARCHAR(2048) NOT NULL CHECK (length(url) > 0),
+    event_types TEXT[] NOT NULL CHECK (
+        cardinality(event_types) > 0
+        AND event_types <@ ARRAY['review.created', 'review.updated', 'review.exported', 'review.deleted']
+    ),
+    secret TEXT NOT NULL CHECK (length(secret) > 0), -- Key used to sign every payload with HMAC-SHA256
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    version INT DEFAULT 1
+);
+
+-- One row per event and webhook, kept until it is delivered or runs out of attempts
+CREATE TABLE webhook_delivery (
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    webhook_id UUID NOT NULL REFERENCES webhook(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status webhook_delivery_status NOT NULL DEFAULT 'PENDING',
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Every attempt to deliver an event, for the delivery log
+CREATE TABLE webhook_delivery_attempt (
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    delivery_id UUID NOT NULL REFERENCES webhook_delivery(id) ON DELETE CASCADE,
+    attempt INT NOT NULL,
+    status_code INT, -- NULL when no response was received
+    error TEXT,
+    duration_ms BIGINT NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_webhook_timestamp_before_update
+    BEFORE UPDATE ON webhook
+    FOR EACH ROW
+    EXECUTE FUNCTION update_timestamp();
+
+CREATE TRIGGER update_webhook_delivery_timestamp_before_update
+    BEFORE UPDATE ON webhook_delivery
+    FOR EACH ROW
+    EXECUTE FUNCTION update_timestamp();
+
+CREATE INDEX idx_webhook_delivery_pending ON webhook_delivery (next_attempt_at) WHERE status = 'PENDING';
+CREATE INDEX idx_webhook_delivery_webhook_id ON webhook_delivery (webhook_id, created_at);
+CREATE INDEX idx_webhook_delivery_attempt_delivery_id ON webhook_delivery_attempt (delivery_id);
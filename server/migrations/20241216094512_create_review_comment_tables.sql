@@ -0,0 +1,31 @@
+-- Discussion on a software review. Deleted comments are kept, with `deleted_at` set, and
+-- are no longer listed or exported
+CREATE TABLE review_comment (
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    software_review_id UUID NOT NULL REFERENCES software_review(id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES user_account(id) ON DELETE RESTRICT, -- Will error if trying to delete the author's user account
+    body TEXT NOT NULL CHECK (length(body) > 0),
+    edited_at TIMESTAMPTZ, -- NULL until the body is changed
+    deleted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    version INT DEFAULT 1
+);
+
+-- Users mentioned in a comment. Each user is notified once per comment, when the mention
+-- is first saved
+CREATE TABLE review_comment_mention (
+    comment_id UUID NOT NULL REFERENCES review_comment(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES user_account(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id)
+);
+
+CREATE TRIGGER update_review_comment_timestamp_before_update
+    BEFORE UPDATE ON review_comment
+    FOR EACH ROW
+    EXECUTE FUNCTION update_timestamp();
+
+CREATE INDEX idx_review_comment_software_review_id ON review_comment (software_review_id, created_at) WHERE deleted_at IS NULL;
+CREATE INDEX idx_review_comment_author_id ON review_comment (author_id);
+CREATE INDEX idx_review_comment_mention_user_id ON review_comment_mention (user_id, created_at);
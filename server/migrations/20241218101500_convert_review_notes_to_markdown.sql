@@ -0,0 +1,48 @@
+-- Review notes are Markdown, so they are no longer limited to 255 characters. The limit is
+-- checked by the API, once the notes are sanitized
+
+-- The search vector is generated from the notes, so it is added again once they are changed
+ALTER TABLE software_review DROP COLUMN search_vector;
+
+ALTER TABLE software_review ALTER COLUMN review_notes TYPE TEXT;
+
+-- Existing notes are plain text. They are escaped so they show as they were written, and
+-- each line break is kept as a hard line break. Reviews with changed notes get a new
+-- version, so reports exported before the change verify as outdated rather than tampered
+UPDATE software_review
+SET review_notes = notes.markdown, version = software_review.version + 1
+FROM (
+    SELECT id, btrim(
+        regexp_replace(                                                   -- Line breaks within a paragraph
+            regexp_replace(                                               -- Numbers that would start a list
+                regexp_replace(                                           -- Characters that would start a list or underline a heading
+                    regexp_replace(                                       -- Characters that would start formatting
+                        regexp_replace(                                   -- Blank lines, which end a paragraph
+                            regexp_replace(                               -- Spaces around each line
+                                regexp_replace(replace(review_notes, E'\r\n', E'\n'), E'\r', E'\n', 'g'),
+                                '^[ \t]+|[ \t]+$', '', 'gn'
+                            ),
+                            E'\n{3,}', E'\n\n', 'g'
+                        ),
+                        '([\\`*_\[\]<>#|~!&])', '\\\1', 'g'
+                    ),
+                    '^([-+=])', '\\\1', 'gn'
+                ),
+                '^([0-9]+)([.)])', '\1\\\2', 'gn'
+            ),
+            E'([^\n])\n(?=[^\n])', E'\\1\\\\\n', 'g'
+        ),
+        E' \n\t'
+    ) AS markdown
+    FROM software_review
+) notes
+WHERE software_review.id = notes.id
+    AND software_review.review_notes <> notes.markdown
+    AND notes.markdown <> '';
+
+ALTER TABLE software_review
+ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', review_notes), 'B')
+) STORED;
+
+CREATE INDEX idx_software_review_search_vector ON software_review USING GIN (search_vector);
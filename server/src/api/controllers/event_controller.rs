@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::sse::{KeepAlive, Sse};
+use axum::response::IntoResponse;
+
+use crate::api::utils::Token;
+use crate::server::ServerState;
+use crate::Result;
+
+#[tracing::instrument(
+    name = "get events", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_events(
+    Token(token): Token,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let events = state.events.stream(
+        token,
+        state.token_cache.clone(),
+        Duration::from_secs(state.events_config.token_check_seconds.max(1)),
+    );
+
+    let keep_alive = KeepAlive::new().interval(Duration::from_secs(
+        state.events_config.keep_alive_seconds.max(1),
+    ));
+
+    Ok((
+        // Stops nginx from buffering the stream
+        [(header::HeaderName::from_static("x-accel-buffering"), "no")],
+        Sse::new(events).keep_alive(keep_alive),
+    ))
+}
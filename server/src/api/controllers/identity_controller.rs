@@ -0,0 +1,42 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+
+use crate::api::models::UserRole;
+use crate::api::services::get_email_domain_violations;
+use crate::api::utils::{Json, Token};
+use crate::server::ServerState;
+use crate::{Error, Result};
+
+#[tracing::instrument(
+    name = "get email domain violations", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_email_domain_violations(
+    Token(token): Token,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    let (users, requesters) = get_email_domain_violations(&state.identity, &state.db_pool).await?;
+
+    let response_body = json!({
+        "staff_email_domains": state.identity.staff_email_domains,
+        "requester_email_domains": state.identity.requester_email_domains,
+        "users": users,
+        "requesters": requesters
+    });
+
+    Ok((StatusCode::OK, Json(response_body)))
+}
@@ -0,0 +1,94 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::{json, Map};
+
+use crate::api::models::{ImportKind, ImportParams, ImportSummary, UserRole};
+use crate::api::services::import_records;
+use crate::api::utils::{Json, Multipart, Query, Token};
+use crate::server::ServerState;
+use crate::{Error, FieldErrorCode, FieldErrors, Result};
+
+#[tracing::instrument(
+    name = "import records", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, import_params, state, multipart),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_import_records(
+    Token(token): Token,
+    Query(import_params): Query<ImportParams>,
+    State(state): State<ServerState>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    // Each CSV file is sent as a part named after the records it contains
+    let mut files: Vec<(ImportKind, String)> = vec![];
+    let mut errors = FieldErrors::new();
+
+    for (name, csv) in multipart.text_parts().await? {
+        match name.parse::<ImportKind>() {
+            Ok(kind) if files.iter().any(|(file_kind, _)| *file_kind == kind) => errors.add(
+                name,
+                FieldErrorCode::ConflictingFields,
+                "only one file of each kind may be imported",
+            ),
+            Ok(kind) => files.push((kind, csv)),
+            Err(_) => errors.add(
+                name,
+                FieldErrorCode::InvalidValue,
+                "expected one of software, requesters, requests or reviews",
+            ),
+        }
+    }
+
+    if files.is_empty() && errors.is_empty() {
+        errors.add(
+            "",
+            FieldErrorCode::MissingField,
+            "at least one of software, requesters, requests or reviews must be provided",
+        );
+    }
+
+    errors.into_result()?;
+
+    let dry_run = import_params.dry_run.unwrap_or(false);
+
+    let import_results = import_records(
+        files,
+        dry_run,
+        token.sub,
+        &state.identity,
+        &state.review_expiry,
+        &state.db_pool,
+    )
+    .await?;
+
+    let mut summary = Map::new();
+    let mut results = Map::new();
+
+    for (kind, rows) in import_results {
+        summary.insert(
+            kind.as_str().to_string(),
+            json!(ImportSummary::from(rows.as_slice())),
+        );
+        results.insert(kind.as_str().to_string(), json!(rows));
+    }
+
+    let response_body = json!({
+        "dry_run": dry_run,
+        "summary": summary,
+        "results": results
+    });
+
+    Ok((StatusCode::OK, Json(response_body)))
+}
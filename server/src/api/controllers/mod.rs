@@ -1,30 +1,59 @@
 mod auth_controller;
+mod event_controller;
 mod health_controller;
+mod identity_controller;
+mod import_controller;
+mod report_controller;
 mod requester_controller;
+mod review_comment_controller;
+mod search_controller;
 mod software_controller;
 mod software_request_controller;
 mod software_review_controller;
+mod stats_controller;
+mod ticketing_controller;
 mod user_controller;
+mod webhook_controller;
 
 pub use auth_controller::{api_check_token, api_login, api_logout, api_revoke_user_token};
+pub use event_controller::api_get_events;
 pub use health_controller::api_health_check;
+pub use identity_controller::api_get_email_domain_violations;
+pub use import_controller::api_import_records;
+pub use report_controller::api_get_summary_report;
 pub use requester_controller::{
     api_create_requester, api_delete_requester, api_get_all_requesters, api_update_requester,
     UpdateRequesterPayload,
 };
+pub use review_comment_controller::{
+    api_create_review_comment, api_delete_review_comment, api_get_my_comment_mentions,
+    api_get_review_comments, api_update_review_comment,
+};
+pub use search_controller::api_search;
 pub use software_controller::{
-    api_create_software, api_delete_software, api_get_all_software, api_update_software,
-    UpdateSoftwarePayload,
+    api_create_software, api_delete_software, api_get_all_software, api_get_similar_software,
+    api_update_software, UpdateSoftwarePayload,
 };
 pub use software_request_controller::{
-    api_create_software_request, api_delete_software_request, api_get_all_software_requests,
-    api_update_software_request, UpdateSoftwareRequestPayload,
+    api_assign_software_request, api_create_software_request, api_delete_software_request,
+    api_get_all_software_requests, api_get_my_software_request_queue,
+    api_get_software_request_queue, api_update_software_request, UpdateSoftwareRequestPayload,
 };
 pub use software_review_controller::{
     api_create_software_review, api_delete_software_review, api_export_software_review,
-    api_get_all_software_reviews, api_update_software_review, UpdateSoftwareReviewPayload,
+    api_export_software_reviews, api_get_all_software_reviews, api_update_software_review,
+    api_verify_software_review, UpdateSoftwareReviewPayload,
+};
+pub use stats_controller::{
+    api_get_answer_stats, api_get_department_stats, api_get_export_stats, api_get_review_stats,
+    api_get_reviewer_workload_stats, api_get_unreviewed_request_stats,
 };
+pub use ticketing_controller::{api_get_ticket, api_post_review_to_ticket};
 pub use user_controller::{
     api_change_password, api_create_user, api_delete_user, api_get_all_users, api_update_user,
     UpdateUserPayload,
 };
+pub use webhook_controller::{
+    api_create_webhook, api_delete_webhook, api_get_all_webhooks, api_get_webhook_deliveries,
+    api_update_webhook, UpdateWebhookPayload,
+};
@@ -0,0 +1,28 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+
+use crate::api::models::SummaryReportParams;
+use crate::api::services::get_summary_report;
+use crate::api::utils::{generate_summary_pdf, Query, Token};
+use crate::server::ServerState;
+use crate::Result;
+
+#[tracing::instrument(
+    name = "get summary report", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_summary_report(
+    Token(token): Token,
+    Query(params): Query<SummaryReportParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let summary_report = get_summary_report(params, &state.db_pool).await?;
+
+    generate_summary_pdf(&summary_report, &state.report_template).await
+}
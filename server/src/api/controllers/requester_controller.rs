@@ -1,21 +1,22 @@
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use serde_json::json;
 
 use crate::api::models::Requester;
 use crate::api::services::{
-    create_requester, get_all_requesters, remove_requester, update_requester_details,
+    create_requester, export_all_requesters, get_all_requesters, remove_requester,
+    update_requester_details,
 };
-use crate::api::utils::{Json, Path, QueryExtractor, Token};
+use crate::api::utils::{export_response, ExportFormat, Json, Path, QueryExtractor, Token};
 use crate::server::ServerState;
 use crate::Result;
 
 #[tracing::instrument(
     name = "get all requesters", 
     // Any values in 'skip' won't be included in logs
-    skip(token, query_params, state),
+    skip(token, query_params, headers, state),
     fields(
         request_initiator = tracing::field::Empty,
     )
@@ -23,10 +24,17 @@ use crate::Result;
 pub async fn api_get_all_requesters(
     Token(token): Token,
     QueryExtractor(query_params): QueryExtractor,
+    headers: HeaderMap,
     State(state): State<ServerState>,
 ) -> Result<impl IntoResponse> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
+    // The same filters apply to an export, which streams every matching record
+    if let Some(format) = ExportFormat::from_request(query_params.0.format.as_deref(), &headers)? {
+        let rows = export_all_requesters(query_params.0, state.db_pool.clone())?;
+        return export_response(format, "requesters", rows).await;
+    }
+
     let (requesters, metadata) = get_all_requesters(query_params.0, &state.db_pool).await?;
 
     let response_body = json!({
@@ -38,7 +46,7 @@ pub async fn api_get_all_requesters(
         "requesters": requesters
     });
 
-    Ok((StatusCode::OK, Json(response_body)))
+    Ok((StatusCode::OK, Json(response_body)).into_response())
 }
 
 #[tracing::instrument(
@@ -58,7 +66,11 @@ pub async fn api_create_requester(
 
     payload.parse()?;
 
-    let _ = create_requester(&payload, &state.db_pool).await?;
+    state
+        .identity
+        .validate_requester_email("email", &payload.email)?;
+
+    let _ = create_requester(&payload, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::CREATED)
 }
@@ -78,7 +90,7 @@ pub async fn api_delete_requester(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    remove_requester(requester_id, &state.db_pool).await?;
+    remove_requester(requester_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -106,7 +118,11 @@ pub async fn api_update_requester(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    update_requester_details(payload, requester_id, &state.db_pool).await?;
+    if let Some(email) = &payload.email {
+        state.identity.validate_requester_email("email", email)?;
+    }
+
+    update_requester_details(payload, requester_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
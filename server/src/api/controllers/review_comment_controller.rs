@@ -0,0 +1,144 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+
+use crate::api::models::{CommentMentionParams, ReviewCommentPayload};
+use crate::api::services::{
+    create_review_comment, get_comment_mentions, get_review_comments, remove_review_comment,
+    update_review_comment_details,
+};
+use crate::api::utils::{Json, Path, Query, Token};
+use crate::server::ServerState;
+use crate::Result;
+
+#[tracing::instrument(
+    name = "get review comments",
+    // Any values in 'skip' won't be included in logs
+    skip(token, review_id, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_review_comments(
+    Token(token): Token,
+    Path(review_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let comments = get_review_comments(review_id, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "comments": comments }))))
+}
+
+#[tracing::instrument(
+    name = "create review comment",
+    // Any values in 'skip' won't be included in logs
+    skip(token, review_id, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_create_review_comment(
+    Token(token): Token,
+    Path(review_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+    Json(payload): Json<ReviewCommentPayload>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    payload.parse()?;
+
+    let comment_id = create_review_comment(
+        review_id,
+        &payload,
+        token.sub,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(json!({ "id": comment_id }))))
+}
+
+#[tracing::instrument(
+    name = "update review comment",
+    // Any values in 'skip' won't be included in logs
+    skip(token, review_id, comment_id, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_update_review_comment(
+    Token(token): Token,
+    Path((review_id, comment_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(state): State<ServerState>,
+    Json(payload): Json<ReviewCommentPayload>,
+) -> Result<StatusCode> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    payload.parse()?;
+
+    update_review_comment_details(
+        &payload,
+        review_id,
+        comment_id,
+        token.sub,
+        &state.comments,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(
+    name = "delete review comment",
+    // Any values in 'skip' won't be included in logs
+    skip(token, review_id, comment_id, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_delete_review_comment(
+    Token(token): Token,
+    Path((review_id, comment_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    State(state): State<ServerState>,
+) -> Result<StatusCode> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    remove_review_comment(
+        review_id,
+        comment_id,
+        token.sub,
+        &token.role,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Comments mentioning the requesting user, newest first
+#[tracing::instrument(
+    name = "get my comment mentions",
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_my_comment_mentions(
+    Token(token): Token,
+    Query(params): Query<CommentMentionParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let mentions = get_comment_mentions(token.sub, params, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "mentions": mentions }))))
+}
@@ -0,0 +1,39 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+
+use crate::api::models::SearchParams;
+use crate::api::services::search_entities;
+use crate::api::utils::{Json, Query, Token};
+use crate::server::ServerState;
+use crate::Result;
+
+#[tracing::instrument(
+    name = "global search", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, search_params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_search(
+    Token(token): Token,
+    Query(search_params): Query<SearchParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let (search_results, metadata) = search_entities(search_params, &state.db_pool).await?;
+
+    let response_body = json!({
+        "metadata": if metadata.total_records == 0 {
+            json!({})
+        } else {
+            json!(metadata)
+        },
+        "search_results": search_results
+    });
+
+    Ok((StatusCode::OK, Json(response_body)))
+}
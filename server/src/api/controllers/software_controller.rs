@@ -1,21 +1,22 @@
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::api::models::Software;
+use crate::api::models::{SimilarSoftwareParams, Software};
 use crate::api::services::{
-    create_software, get_all_software, remove_software, update_software_details,
+    create_software, export_all_software, get_all_software, get_similar_software, remove_software,
+    update_software_details,
 };
-use crate::api::utils::{Json, Path, QueryExtractor, Token};
+use crate::api::utils::{export_response, ExportFormat, Json, Path, Query, QueryExtractor, Token};
 use crate::server::ServerState;
 use crate::Result;
 
 #[tracing::instrument(
     name = "get all software", 
     // Any values in 'skip' won't be included in logs
-    skip(token, query_params, state),
+    skip(token, query_params, headers, state),
     fields(
         request_initiator = tracing::field::Empty,
     )
@@ -23,10 +24,17 @@ use crate::Result;
 pub async fn api_get_all_software(
     Token(token): Token,
     QueryExtractor(query_params): QueryExtractor,
+    headers: HeaderMap,
     State(state): State<ServerState>,
 ) -> Result<impl IntoResponse> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
+    // The same filters apply to an export, which streams every matching record
+    if let Some(format) = ExportFormat::from_request(query_params.0.format.as_deref(), &headers)? {
+        let rows = export_all_software(query_params.0, state.db_pool.clone())?;
+        return export_response(format, "software", rows).await;
+    }
+
     let (software, metadata) = get_all_software(query_params.0, &state.db_pool).await?;
 
     let response_body = json!({
@@ -38,6 +46,30 @@ pub async fn api_get_all_software(
         "software": software
     });
 
+    Ok((StatusCode::OK, Json(response_body)).into_response())
+}
+
+#[tracing::instrument(
+    name = "get similar software", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_similar_software(
+    Token(token): Token,
+    Query(params): Query<SimilarSoftwareParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let similar_software = get_similar_software(params, &state.db_pool).await?;
+
+    let response_body = json!({
+        "similar_software": similar_software
+    });
+
     Ok((StatusCode::OK, Json(response_body)))
 }
 
@@ -58,7 +90,7 @@ pub async fn api_create_software(
 
     payload.parse()?;
 
-    let _ = create_software(&payload, &state.db_pool).await?;
+    let _ = create_software(&payload, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::CREATED)
 }
@@ -78,7 +110,7 @@ pub async fn api_delete_software(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    remove_software(software_id, &state.db_pool).await?;
+    remove_software(software_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -107,7 +139,7 @@ pub async fn api_update_software(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    update_software_details(payload, software_id, &state.db_pool).await?;
+    update_software_details(payload, software_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,22 +1,25 @@
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::api::models::SoftwareRequest;
+use crate::api::models::{QueuedSoftwareRequest, SoftwareRequest};
 use crate::api::services::{
-    create_software_request, get_all_software_requests, remove_software_request,
+    assign_software_request, create_software_request, export_all_software_requests,
+    get_all_software_requests, get_unreviewed_software_requests, remove_software_request,
     update_software_request_details,
 };
-use crate::api::utils::{Json, Path, QueryExtractor, Token};
+use crate::api::utils::{
+    export_response, ExportFormat, Json, Metadata, Path, QueryExtractor, Token,
+};
 use crate::server::ServerState;
 use crate::Result;
 
 #[tracing::instrument(
     name = "get all software requests", 
     // Any values in 'skip' won't be included in logs
-    skip(token, query_params, state),
+    skip(token, query_params, headers, state),
     fields(
         request_initiator = tracing::field::Empty,
     )
@@ -24,10 +27,17 @@ use crate::Result;
 pub async fn api_get_all_software_requests(
     Token(token): Token,
     QueryExtractor(query_params): QueryExtractor,
+    headers: HeaderMap,
     State(state): State<ServerState>,
 ) -> Result<impl IntoResponse> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
+    // The same filters apply to an export, which streams every matching record
+    if let Some(format) = ExportFormat::from_request(query_params.0.format.as_deref(), &headers)? {
+        let rows = export_all_software_requests(query_params.0, state.db_pool.clone())?;
+        return export_response(format, "software-requests", rows).await;
+    }
+
     let (software_requests, metadata) =
         get_all_software_requests(query_params.0, &state.db_pool).await?;
 
@@ -40,7 +50,66 @@ pub async fn api_get_all_software_requests(
         "software_requests": software_requests
     });
 
-    Ok((StatusCode::OK, Json(response_body)))
+    Ok((StatusCode::OK, Json(response_body)).into_response())
+}
+
+#[tracing::instrument(
+    name = "get software request queue", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, query_params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_software_request_queue(
+    Token(token): Token,
+    QueryExtractor(query_params): QueryExtractor,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let (software_requests, metadata) =
+        get_unreviewed_software_requests(query_params.0, None, &state.db_pool).await?;
+
+    Ok(queue_response(software_requests, metadata))
+}
+
+// The queue of the requesting user, containing only the requests assigned to them
+#[tracing::instrument(
+    name = "get my software request queue", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, query_params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_my_software_request_queue(
+    Token(token): Token,
+    QueryExtractor(query_params): QueryExtractor,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let (software_requests, metadata) =
+        get_unreviewed_software_requests(query_params.0, Some(token.sub), &state.db_pool).await?;
+
+    Ok(queue_response(software_requests, metadata))
+}
+
+fn queue_response(
+    software_requests: Vec<QueuedSoftwareRequest>,
+    metadata: Metadata,
+) -> impl IntoResponse {
+    let response_body = json!({
+        "metadata": if metadata.total_records == 0 {
+            json!({})
+        } else {
+            json!(metadata)
+        },
+        "software_requests": software_requests
+    });
+
+    (StatusCode::OK, Json(response_body))
 }
 
 #[tracing::instrument(
@@ -60,7 +129,7 @@ pub async fn api_create_software_request(
 
     payload.parse()?;
 
-    let _ = create_software_request(&payload, &state.db_pool).await?;
+    let _ = create_software_request(&payload, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::CREATED)
 }
@@ -80,7 +149,7 @@ pub async fn api_delete_software_request(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    remove_software_request(request_id, &state.db_pool).await?;
+    remove_software_request(request_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -106,7 +175,41 @@ pub async fn api_update_software_request(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    update_software_request_details(payload, request_id, &state.db_pool).await?;
+    update_software_request_details(payload, request_id, &state.events, &state.db_pool).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// A `null` (or missing) `assignee_id` unassigns the request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssignSoftwareRequestPayload {
+    pub assignee_id: Option<uuid::Uuid>,
+}
+
+#[tracing::instrument(
+    name = "assign software request", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, request_id, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_assign_software_request(
+    Token(token): Token,
+    Path(request_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+    Json(payload): Json<AssignSoftwareRequestPayload>,
+) -> Result<StatusCode> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    assign_software_request(
+        request_id,
+        payload.assignee_id,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
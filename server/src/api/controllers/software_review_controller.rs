@@ -1,22 +1,29 @@
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::api::models::{ReviewOptions, SoftwareReviewPayload};
+use crate::api::models::{
+    ReviewExportPayload, ReviewOptions, ReviewReportParams, SoftwareReviewPayload,
+};
 use crate::api::services::{
-    create_software_review, get_all_software_reviews, get_software_review, remove_software_review,
-    update_review_exported, update_software_review_details,
+    create_software_review, export_all_software_reviews, export_software_reviews,
+    get_all_software_reviews, get_review_comments, get_review_export_ids, get_software_review,
+    remove_software_review, update_review_exported, update_software_review_details,
+    verify_software_review,
+};
+use crate::api::utils::{
+    export_response, generate_report, sanitize_markdown, zip_archive_response, ExportFormat, Json,
+    Multipart, Path, Query, QueryExtractor, Token,
 };
-use crate::api::utils::{generate_pdf, Json, Path, QueryExtractor, Token};
 use crate::server::ServerState;
 use crate::Result;
 
 #[tracing::instrument(
     name = "get all software reviews", 
     // Any values in 'skip' won't be included in logs
-    skip(token, query_params, state),
+    skip(token, query_params, headers, state),
     fields(
         request_initiator = tracing::field::Empty,
     )
@@ -24,10 +31,17 @@ use crate::Result;
 pub async fn api_get_all_software_reviews(
     Token(token): Token,
     QueryExtractor(query_params): QueryExtractor,
+    headers: HeaderMap,
     State(state): State<ServerState>,
 ) -> Result<impl IntoResponse> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
+    // The same filters apply to an export, which streams every matching record
+    if let Some(format) = ExportFormat::from_request(query_params.0.format.as_deref(), &headers)? {
+        let rows = export_all_software_reviews(query_params.0, state.db_pool.clone())?;
+        return export_response(format, "software-reviews", rows).await;
+    }
+
     let (software_reviews, metadata) =
         get_all_software_reviews(query_params.0, &state.db_pool).await?;
 
@@ -40,7 +54,7 @@ pub async fn api_get_all_software_reviews(
         "software_reviews": software_reviews
     });
 
-    Ok((StatusCode::OK, Json(response_body)))
+    Ok((StatusCode::OK, Json(response_body)).into_response())
 }
 
 #[tracing::instrument(
@@ -54,16 +68,35 @@ pub async fn api_get_all_software_reviews(
 pub async fn api_create_software_review(
     Token(token): Token,
     State(state): State<ServerState>,
-    Json(payload): Json<SoftwareReviewPayload>,
+    Json(mut payload): Json<SoftwareReviewPayload>,
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
+    // The notes are stored, and validated, as they will be shown
+    payload.review_notes = sanitize_markdown(&payload.review_notes);
+
     payload.parse()?;
-    payload.software_request.parse()?;
-    payload.software_request.software.parse()?;
-    payload.software_request.requester.parse()?;
 
-    create_software_review(&payload, &token.sub, &state.db_pool).await?;
+    // Only a new requester is checked, existing requesters are reported by the identity
+    // violations endpoint instead
+    if let Some(requester) = payload
+        .software_request
+        .as_ref()
+        .and_then(|software_request| software_request.requester.as_ref())
+    {
+        state
+            .identity
+            .validate_requester_email("software_request.requester.email", &requester.email)?;
+    }
+
+    create_software_review(
+        &payload,
+        &token.sub,
+        &state.review_expiry,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
 
     Ok(StatusCode::CREATED)
 }
@@ -83,7 +116,7 @@ pub async fn api_delete_software_review(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
 
-    remove_software_review(review_id, &state.db_pool).await?;
+    remove_software_review(review_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -118,14 +151,21 @@ pub async fn api_update_software_review(
 ) -> Result<StatusCode> {
     tracing::Span::current().record("review_initiator", tracing::field::display(&token.sub));
 
-    update_software_review_details(payload, review_id, &state.db_pool).await?;
+    update_software_review_details(
+        payload,
+        review_id,
+        &state.review_expiry,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 #[tracing::instrument(
     name = "export software review", 
-    skip(review_id, state),
+    skip(review_id, report_params, state),
     fields(
         request_initiator = tracing::field::Empty,
     )
@@ -133,6 +173,7 @@ pub async fn api_update_software_review(
 pub async fn api_export_software_review(
     Token(token): Token,
     Path(review_id): Path<uuid::Uuid>,
+    Query(report_params): Query<ReviewReportParams>,
     State(state): State<ServerState>,
 ) -> Result<impl IntoResponse> {
     tracing::Span::current().record("review_initiator", tracing::field::display(&token.sub));
@@ -140,14 +181,98 @@ pub async fn api_export_software_review(
     let (software_review_dto, software_review_version) =
         get_software_review(review_id, &state.db_pool).await?;
 
-    let pdf_response = generate_pdf(&software_review_dto).await?;
+    let comments = if report_params.include_comments {
+        get_review_comments(review_id, &state.db_pool).await?
+    } else {
+        vec![]
+    };
+
+    let report_response = generate_report(
+        &software_review_dto,
+        &comments,
+        software_review_version,
+        report_params.format.unwrap_or_default(),
+        &state.report_template,
+        &state.report_integrity,
+    )
+    .await?;
 
     update_review_exported(
         &software_review_dto.id,
         software_review_version,
+        &state.events,
         &state.db_pool,
     )
     .await?;
 
-    Ok(pdf_response)
+    Ok(report_response)
+}
+
+#[tracing::instrument(
+    name = "export software reviews", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_export_software_reviews(
+    Token(token): Token,
+    State(state): State<ServerState>,
+    Json(payload): Json<ReviewExportPayload>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let review_ids = get_review_export_ids(&payload, &state.db_pool).await?;
+
+    let filename = format!(
+        "software-reviews-{}.zip",
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+
+    let db_pool = state.db_pool.clone();
+    let events = state.events.clone();
+    let report_template = state.report_template.clone();
+    let report_integrity = state.report_integrity.clone();
+
+    // The PDFs are rendered as the archive is streamed, so failures are reported in the
+    // archive's manifest rather than the response status
+    zip_archive_response(&filename, move |archive| {
+        export_software_reviews(
+            review_ids,
+            archive,
+            report_template,
+            report_integrity,
+            events,
+            db_pool,
+        )
+    })
+}
+
+#[tracing::instrument(
+    name = "verify software review report", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state, multipart),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_verify_software_review(
+    Token(token): Token,
+    State(state): State<ServerState>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // The report is uploaded as a part named `file`
+    let pdf = multipart.file("file").await?;
+
+    let verification =
+        verify_software_review(&pdf, &state.report_integrity, &state.db_pool).await?;
+
+    let response_body = json!({
+        "verification": verification
+    });
+
+    Ok((StatusCode::OK, Json(response_body)))
 }
@@ -0,0 +1,133 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+
+use crate::api::models::StatsParams;
+use crate::api::services::{
+    get_answer_stats, get_department_stats, get_export_stats, get_review_stats,
+    get_reviewer_workload_stats, get_unreviewed_request_stats,
+};
+use crate::api::utils::{Json, Query, Token};
+use crate::server::ServerState;
+use crate::Result;
+
+#[tracing::instrument(
+    name = "get review stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_review_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_review_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
+
+#[tracing::instrument(
+    name = "get answer stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_answer_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_answer_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
+
+#[tracing::instrument(
+    name = "get export stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_export_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_export_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
+
+#[tracing::instrument(
+    name = "get unreviewed request stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_unreviewed_request_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_unreviewed_request_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
+
+#[tracing::instrument(
+    name = "get reviewer workload stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_reviewer_workload_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_reviewer_workload_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
+
+#[tracing::instrument(
+    name = "get department stats", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_department_stats(
+    Token(token): Token,
+    Query(params): Query<StatsParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let stats = get_department_stats(params, &state.stats_cache, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "stats": stats }))))
+}
@@ -0,0 +1,64 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde_json::json;
+
+use crate::api::services::{get_ticket, post_review_to_ticket};
+use crate::api::utils::{Json, Path, TeamDynamixClient, Token};
+use crate::server::ServerState;
+use crate::{Error, Result};
+
+fn ticketing_client(state: &ServerState) -> Result<&TeamDynamixClient> {
+    state
+        .ticketing
+        .as_deref()
+        .ok_or(Error::TicketingNotConfiguredError)
+}
+
+#[tracing::instrument(
+    name = "get ticket", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_ticket(
+    Token(token): Token,
+    Path(td_request_id): Path<String>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    let ticket = get_ticket(ticketing_client(&state)?, &td_request_id).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "software_request": ticket }))))
+}
+
+#[tracing::instrument(
+    name = "post software review to ticket", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_post_review_to_ticket(
+    Token(token): Token,
+    Path(review_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    post_review_to_ticket(
+        ticketing_client(&state)?,
+        review_id,
+        &state.report_template,
+        &state.report_integrity,
+        &state.events,
+        &state.db_pool,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -113,7 +113,11 @@ pub async fn api_create_user(
 
     payload.parse()?;
 
-    create_user(&payload, &state.db_pool).await?;
+    state
+        .identity
+        .validate_staff_email("email", &payload.email)?;
+
+    create_user(&payload, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::CREATED)
 }
@@ -139,7 +143,7 @@ pub async fn api_delete_user(
         _ => return Err(Error::AuthInvalidRoleError)?,
     }
 
-    remove_user(user_id, &state.db_pool).await?;
+    remove_user(user_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -173,7 +177,11 @@ pub async fn api_update_user(
         _ => return Err(Error::AuthInvalidRoleError)?,
     }
 
-    update_user_details(payload, user_id, &state.db_pool).await?;
+    if let Some(email) = &payload.email {
+        state.identity.validate_staff_email("email", email)?;
+    }
+
+    update_user_details(payload, user_id, &state.events, &state.db_pool).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
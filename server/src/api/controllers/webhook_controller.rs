@@ -0,0 +1,157 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use secrecy::SecretString;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::models::{UserRole, Webhook, WebhookDeliveryParams, WebhookEvent};
+use crate::api::services::{
+    create_webhook, get_all_webhooks, get_webhook_deliveries, remove_webhook,
+    update_webhook_details,
+};
+use crate::api::utils::{Json, Path, Query, Token};
+use crate::server::ServerState;
+use crate::{Error, Result};
+
+#[tracing::instrument(
+    name = "get all webhooks", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_all_webhooks(
+    Token(token): Token,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    let webhooks = get_all_webhooks(&state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "webhooks": webhooks }))))
+}
+
+#[tracing::instrument(
+    name = "create webhook", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_create_webhook(
+    Token(token): Token,
+    State(state): State<ServerState>,
+    Json(payload): Json<Webhook>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    payload.parse()?;
+
+    let webhook_id = create_webhook(&payload, &state.db_pool).await?;
+
+    Ok((StatusCode::CREATED, Json(json!({ "id": webhook_id }))))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateWebhookPayload {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<WebhookEvent>>,
+    pub secret: Option<SecretString>,
+    pub active: Option<bool>,
+}
+
+#[tracing::instrument(
+    name = "update webhook", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, webhook_id, state, payload),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_update_webhook(
+    Token(token): Token,
+    Path(webhook_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+    Json(payload): Json<UpdateWebhookPayload>,
+) -> Result<StatusCode> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    update_webhook_details(payload, webhook_id, &state.db_pool).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(
+    name = "delete webhook", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, webhook_id, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_delete_webhook(
+    Token(token): Token,
+    Path(webhook_id): Path<uuid::Uuid>,
+    State(state): State<ServerState>,
+) -> Result<StatusCode> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    remove_webhook(webhook_id, &state.db_pool).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(
+    name = "get webhook deliveries", 
+    // Any values in 'skip' won't be included in logs
+    skip(token, webhook_id, params, state),
+    fields(
+        request_initiator = tracing::field::Empty,
+    )
+)]
+pub async fn api_get_webhook_deliveries(
+    Token(token): Token,
+    Path(webhook_id): Path<uuid::Uuid>,
+    Query(params): Query<WebhookDeliveryParams>,
+    State(state): State<ServerState>,
+) -> Result<impl IntoResponse> {
+    tracing::Span::current().record("request_initiator", tracing::field::display(&token.sub));
+
+    // Only allow `ADMIN` users to access this endpoint
+    match token.role {
+        UserRole::ADMIN => (),
+        _ => return Err(Error::AuthInvalidRoleError)?,
+    }
+
+    let deliveries = get_webhook_deliveries(webhook_id, params, &state.db_pool).await?;
+
+    Ok((StatusCode::OK, Json(json!({ "deliveries": deliveries }))))
+}
@@ -1,20 +1,50 @@
+use axum::http::{header, HeaderValue, Uri};
 use axum::response::{IntoResponse, Response};
-use axum::Json;
+use axum::{Extension, Json};
 use serde_json::json;
+use tower_http::request_id::RequestId;
 
 use crate::Error;
 
-// Modify error responses before they are sent to the client
-pub async fn main_response_mapper(response: Response) -> Response {
+// Modify error responses before they are sent to the client. Errors are rendered as
+// `application/problem+json` (RFC 9457), including the request id so a reported error
+// can be matched to its log entries
+pub async fn main_response_mapper(
+    uri: Uri,
+    request_id: Option<Extension<RequestId>>,
+    response: Response,
+) -> Response {
     if let Some(service_error) = response.extensions().get::<Error>() {
         let (client_status, client_error) = service_error.client_status_and_error();
-        let client_error_body = json!({
-            "error": client_error,
+
+        let request_id = request_id
+            .as_ref()
+            .and_then(|Extension(request_id)| request_id.header_value().to_str().ok())
+            .unwrap_or_default();
+
+        let mut client_error_body = json!({
+            "type": client_error.problem_type(),
+            "title": client_error.title(),
+            "status": client_status.as_u16(),
+            "detail": client_error.to_string(),
+            "instance": uri.path(),
+            "request_id": request_id,
         });
 
+        if let Some(field_errors) = service_error.client_field_errors() {
+            client_error_body["errors"] = json!(field_errors);
+        }
+
         tracing::error!(error = ?service_error, "[ERROR]");
 
-        return (client_status, Json(client_error_body)).into_response();
+        let mut response = (client_status, Json(client_error_body)).into_response();
+
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/problem+json"),
+        );
+
+        return response;
     }
 
     response
@@ -0,0 +1,163 @@
+use serde::Serialize;
+
+use super::UserRole;
+
+// Records that clients keep in lists, and are told about when they change
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeEntity {
+    Software,
+    Requester,
+    SoftwareRequest,
+    SoftwareReview,
+    ReviewComment,
+    User,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+    // Only sent to the user mentioned in a comment
+    Mentioned,
+}
+
+// A change made through the API, sent to the clients listening on `/events`. Only the id
+// of the changed record is sent, so clients reload it (with its new version) themselves
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChangeEvent {
+    pub entity: ChangeEntity,
+    pub action: ChangeAction,
+    pub id: uuid::Uuid,
+    // The review a comment belongs to, so clients know which thread to reload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_id: Option<uuid::Uuid>,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    // Set when the event is only sent to one user
+    #[serde(skip)]
+    pub recipient_id: Option<uuid::Uuid>,
+}
+
+impl ChangeEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeEntity::Software => "software",
+            ChangeEntity::Requester => "requester",
+            ChangeEntity::SoftwareRequest => "software_request",
+            ChangeEntity::SoftwareReview => "software_review",
+            ChangeEntity::ReviewComment => "review_comment",
+            ChangeEntity::User => "user",
+        }
+    }
+}
+
+impl ChangeAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeAction::Created => "created",
+            ChangeAction::Updated => "updated",
+            ChangeAction::Deleted => "deleted",
+            ChangeAction::Mentioned => "mentioned",
+        }
+    }
+}
+
+impl ChangeEvent {
+    pub fn new(entity: ChangeEntity, action: ChangeAction, id: uuid::Uuid) -> Self {
+        ChangeEvent {
+            entity,
+            action,
+            id,
+            review_id: None,
+            occurred_at: chrono::Utc::now(),
+            recipient_id: None,
+        }
+    }
+
+    pub fn comment(action: ChangeAction, comment_id: uuid::Uuid, review_id: uuid::Uuid) -> Self {
+        ChangeEvent {
+            review_id: Some(review_id),
+            ..ChangeEvent::new(ChangeEntity::ReviewComment, action, comment_id)
+        }
+    }
+
+    // Tells `user_id` they were mentioned in a comment
+    pub fn mention(comment_id: uuid::Uuid, review_id: uuid::Uuid, user_id: uuid::Uuid) -> Self {
+        ChangeEvent {
+            recipient_id: Some(user_id),
+            ..ChangeEvent::comment(ChangeAction::Mentioned, comment_id, review_id)
+        }
+    }
+
+    // The SSE event name, e.g. `software_review.updated`
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.entity.as_str(), self.action.as_str())
+    }
+
+    // Users are only listed to admins, so only admins are told about changes to them
+    pub fn is_visible_to(&self, user_id: &uuid::Uuid, role: &UserRole) -> bool {
+        if let Some(recipient_id) = &self.recipient_id {
+            return recipient_id == user_id;
+        }
+
+        match self.entity {
+            ChangeEntity::User => matches!(role, UserRole::ADMIN),
+            _ => true,
+        }
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod change_event_tests {
+    use uuid::Uuid;
+
+    use super::{ChangeAction, ChangeEntity, ChangeEvent};
+    use crate::api::models::UserRole;
+
+    #[test]
+    fn event_name_combines_entity_and_action() {
+        let event = ChangeEvent::new(
+            ChangeEntity::SoftwareRequest,
+            ChangeAction::Deleted,
+            Uuid::new_v4(),
+        );
+
+        assert_eq!("software_request.deleted", event.name());
+    }
+
+    #[test]
+    fn user_changes_are_only_visible_to_admins() {
+        let event = ChangeEvent::new(ChangeEntity::User, ChangeAction::Updated, Uuid::new_v4());
+        let user_id = Uuid::new_v4();
+
+        assert!(event.is_visible_to(&user_id, &UserRole::ADMIN));
+        assert!(!event.is_visible_to(&user_id, &UserRole::REVIEWER));
+    }
+
+    #[test]
+    fn review_changes_are_visible_to_every_role() {
+        let event = ChangeEvent::new(
+            ChangeEntity::SoftwareReview,
+            ChangeAction::Created,
+            Uuid::new_v4(),
+        );
+
+        let user_id = Uuid::new_v4();
+
+        assert!(event.is_visible_to(&user_id, &UserRole::ADMIN));
+        assert!(event.is_visible_to(&user_id, &UserRole::REVIEWER));
+    }
+
+    #[test]
+    fn mentions_are_only_visible_to_the_mentioned_user() {
+        let mentioned_id = Uuid::new_v4();
+        let event = ChangeEvent::mention(Uuid::new_v4(), Uuid::new_v4(), mentioned_id);
+
+        assert_eq!("review_comment.mentioned", event.name());
+        assert!(event.is_visible_to(&mentioned_id, &UserRole::REVIEWER));
+        assert!(!event.is_visible_to(&Uuid::new_v4(), &UserRole::ADMIN));
+    }
+}
@@ -0,0 +1,354 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::utils::sanitize_markdown;
+use crate::{FieldError, FieldErrors, Result};
+
+use super::{
+    RequesterDTO, ReviewOptions, SoftwareDTO, SoftwareRequest, SoftwareRequestPayload,
+    SoftwareReviewPayload,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportParams {
+    pub dry_run: Option<bool>,
+}
+
+// The CSV files accepted by the import, named by their multipart part. Files are
+// imported in this order so later files can reference rows from earlier ones
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportKind {
+    Software,
+    Requesters,
+    Requests,
+    Reviews,
+}
+
+impl ImportKind {
+    pub const ALL: [ImportKind; 4] = [
+        ImportKind::Software,
+        ImportKind::Requesters,
+        ImportKind::Requests,
+        ImportKind::Reviews,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportKind::Software => "software",
+            ImportKind::Requesters => "requesters",
+            ImportKind::Requests => "requests",
+            ImportKind::Reviews => "reviews",
+        }
+    }
+
+    // Columns that must be present in the header row
+    pub fn required_columns(&self) -> &'static [&'static str] {
+        match self {
+            ImportKind::Software => &[
+                "software_name",
+                "software_version",
+                "developer_name",
+                "description",
+            ],
+            ImportKind::Requesters => &["name", "email", "department"],
+            ImportKind::Requests => &["td_request_id", "software_name", "requester_email"],
+            ImportKind::Reviews => &[
+                "td_request_id",
+                "software_name",
+                "software_version",
+                "developer_name",
+                "description",
+                "requester_name",
+                "requester_email",
+                "requester_department",
+                "is_supported",
+                "is_current_version",
+                "is_reputation_good",
+                "is_installation_from_developer",
+                "is_local_admin_required",
+                "is_connected_to_brockport_cloud",
+                "is_connected_to_cloud_services_or_client",
+                "is_security_or_optimization_software",
+                "is_supported_by_current_os",
+                "review_notes",
+            ],
+        }
+    }
+}
+
+impl std::str::FromStr for ImportKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ImportKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or(())
+    }
+}
+
+// A row of an imported CSV file, validated with the same rules as the matching JSON
+// payload
+pub trait ImportRow: serde::de::DeserializeOwned {
+    fn parse(&self) -> Result<()>;
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SoftwareImportRow {
+    pub software_name: String,
+    pub software_version: String,
+    pub developer_name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequesterImportRow {
+    pub name: String,
+    pub email: String,
+    pub department: String,
+}
+
+// A software request for existing (or previously imported) software and requester
+#[derive(Debug, Deserialize)]
+pub struct SoftwareRequestImportRow {
+    pub td_request_id: String,
+    pub software_name: String,
+    pub requester_email: String,
+}
+
+// A historical review. Each row carries the software and requester details so a legacy
+// review can be imported on its own. `reviewer_email` defaults to the importing user and
+// `reviewed_at` (YYYY-MM-DD) to the time of the import
+#[derive(Debug, Deserialize)]
+pub struct SoftwareReviewImportRow {
+    pub td_request_id: String,
+    pub software_name: String,
+    pub software_version: String,
+    pub developer_name: String,
+    pub description: String,
+    pub requester_name: String,
+    pub requester_email: String,
+    pub requester_department: String,
+    pub reviewer_email: Option<String>,
+    pub is_supported: ReviewOptions,
+    pub is_current_version: ReviewOptions,
+    pub is_reputation_good: ReviewOptions,
+    pub is_installation_from_developer: ReviewOptions,
+    pub is_local_admin_required: ReviewOptions,
+    pub is_connected_to_brockport_cloud: ReviewOptions,
+    pub is_connected_to_cloud_services_or_client: ReviewOptions,
+    pub is_security_or_optimization_software: ReviewOptions,
+    pub is_supported_by_current_os: ReviewOptions,
+    pub review_notes: String,
+    pub exported: Option<bool>,
+    pub reviewed_at: Option<chrono::NaiveDate>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    // A new record was created
+    Created,
+    // An existing record with the same name or email was used instead
+    Matched,
+    // The row was not imported, see `errors`
+    Rejected,
+}
+
+// The outcome of a single CSV row. `row` is the line number in the file, counting the
+// header as line 1
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    pub row: u64,
+    pub status: ImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl ImportRowResult {
+    pub fn imported(row: u64, status: ImportRowStatus, id: Uuid) -> Self {
+        ImportRowResult {
+            row,
+            status,
+            id: Some(id),
+            errors: vec![],
+        }
+    }
+
+    pub fn rejected(row: u64, errors: Vec<FieldError>) -> Self {
+        ImportRowResult {
+            row,
+            status: ImportRowStatus::Rejected,
+            id: None,
+            errors,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub matched: usize,
+    pub rejected: usize,
+}
+
+impl From<&[ImportRowResult]> for ImportSummary {
+    fn from(results: &[ImportRowResult]) -> Self {
+        let count = |status| {
+            results
+                .iter()
+                .filter(|result| result.status == status)
+                .count()
+        };
+
+        ImportSummary {
+            created: count(ImportRowStatus::Created),
+            matched: count(ImportRowStatus::Matched),
+            rejected: count(ImportRowStatus::Rejected),
+        }
+    }
+}
+
+impl SoftwareImportRow {
+    pub fn to_dto(&self) -> SoftwareDTO {
+        SoftwareDTO {
+            id: None,
+            software_name: self.software_name.clone(),
+            software_version: self.software_version.clone(),
+            developer_name: self.developer_name.clone(),
+            description: self.description.clone(),
+            created_at: None,
+        }
+    }
+}
+
+impl ImportRow for SoftwareImportRow {
+    fn parse(&self) -> Result<()> {
+        self.to_dto().parse()
+    }
+}
+
+impl RequesterImportRow {
+    pub fn to_dto(&self) -> RequesterDTO {
+        RequesterDTO {
+            id: None,
+            name: self.name.clone(),
+            email: self.email.clone(),
+            department: self.department.clone(),
+            created_at: None,
+        }
+    }
+}
+
+impl ImportRow for RequesterImportRow {
+    fn parse(&self) -> Result<()> {
+        self.to_dto().parse()
+    }
+}
+
+impl ImportRow for SoftwareRequestImportRow {
+    fn parse(&self) -> Result<()> {
+        // The software and requester ids are resolved from `software_name` and
+        // `requester_email` when the row is imported, so only the ticket is checked here
+        SoftwareRequest {
+            id: None,
+            td_request_id: self.td_request_id.clone(),
+            software_id: Uuid::nil(),
+            requester_id: Uuid::nil(),
+            created_at: None,
+            updated_at: None,
+            version: None,
+        }
+        .parse()
+    }
+}
+
+impl SoftwareReviewImportRow {
+    pub fn software(&self) -> SoftwareDTO {
+        SoftwareDTO {
+            id: None,
+            software_name: self.software_name.clone(),
+            software_version: self.software_version.clone(),
+            developer_name: self.developer_name.clone(),
+            description: self.description.clone(),
+            created_at: None,
+        }
+    }
+
+    pub fn requester(&self) -> RequesterDTO {
+        RequesterDTO {
+            id: None,
+            name: self.requester_name.clone(),
+            email: self.requester_email.clone(),
+            department: self.requester_department.clone(),
+            created_at: None,
+        }
+    }
+
+    pub fn to_payload(&self, reviewer_id: Uuid) -> SoftwareReviewPayload {
+        SoftwareReviewPayload {
+            id: None,
+            software_request_id: None,
+            software_request: Some(SoftwareRequestPayload {
+                td_request_id: self.td_request_id.clone(),
+                software_id: None,
+                software: Some(self.software()),
+                requester_id: None,
+                requester: Some(self.requester()),
+            }),
+            reviewer_id,
+            is_supported: self.is_supported.clone(),
+            is_current_version: self.is_current_version.clone(),
+            is_reputation_good: self.is_reputation_good.clone(),
+            is_installation_from_developer: self.is_installation_from_developer.clone(),
+            is_local_admin_required: self.is_local_admin_required.clone(),
+            is_connected_to_brockport_cloud: self.is_connected_to_brockport_cloud.clone(),
+            is_connected_to_cloud_services_or_client: self
+                .is_connected_to_cloud_services_or_client
+                .clone(),
+            is_security_or_optimization_software: self.is_security_or_optimization_software.clone(),
+            is_supported_by_current_os: self.is_supported_by_current_os.clone(),
+            exported: self.exported,
+            review_notes: sanitize_markdown(&self.review_notes),
+            created_at: None,
+            updated_at: None,
+            version: None,
+        }
+    }
+}
+
+impl ImportRow for SoftwareReviewImportRow {
+    // Validates the row as a review payload, reporting each error against the CSV column
+    // it came from (e.g. `software_request.requester.email` as `requester_email`)
+    fn parse(&self) -> Result<()> {
+        let Err(err) = self.to_payload(Uuid::nil()).parse() else {
+            return Ok(());
+        };
+
+        let crate::Error::ValidationError(errors) = err else {
+            return Err(err);
+        };
+
+        let mut column_errors = FieldErrors::new();
+
+        for error in errors.to_vec() {
+            let column = if let Some(field) = error.field.strip_prefix("software_request.software.")
+            {
+                field.to_string()
+            } else if let Some(field) = error.field.strip_prefix("software_request.requester.") {
+                format!("requester_{}", field)
+            } else if let Some(field) = error.field.strip_prefix("software_request.") {
+                field.to_string()
+            } else {
+                error.field
+            };
+
+            column_errors.add(column, error.code, error.message);
+        }
+
+        column_errors.into_result()
+    }
+}
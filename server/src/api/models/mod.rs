@@ -1,13 +1,56 @@
+mod change_event;
+mod import;
 mod requester;
+mod review_comment;
+mod review_export;
+mod review_verification;
+mod search;
 mod software;
 mod software_request;
 mod software_review;
+mod software_version;
+mod stats;
+mod summary_report;
+mod ticket;
 mod user;
+mod webhook;
 
+pub use change_event::{ChangeAction, ChangeEntity, ChangeEvent};
+pub use import::{
+    ImportKind, ImportParams, ImportRow, ImportRowResult, ImportRowStatus, ImportSummary,
+    RequesterImportRow, SoftwareImportRow, SoftwareRequestImportRow, SoftwareReviewImportRow,
+};
 pub use requester::{Requester, RequesterDTO};
-pub use software::{Software, SoftwareDTO};
-pub use software_request::{SoftwareRequest, SoftwareRequestDTO};
+pub use review_comment::{
+    CommentAuthor, CommentMention, CommentMentionParams, ReviewComment, ReviewCommentDTO,
+    ReviewCommentPayload,
+};
+pub use review_export::{
+    ReviewExportManifest, ReviewExportPayload, ReviewExportResult, ReviewExportStatus,
+    ReviewReportFormat, ReviewReportParams,
+};
+pub use review_verification::{ReviewVerification, ReviewVerificationStatus};
+pub use search::{SearchHit, SearchHitType, SearchParams};
+pub use software::{SimilarSoftware, SimilarSoftwareParams, Software, SoftwareDTO};
+pub use software_request::{
+    QueuedSoftwareRequest, RequestAssignee, SoftwareRequest, SoftwareRequestDTO,
+    SoftwareRequestPayload,
+};
 pub use software_review::{
     ReviewOptions, SoftwareReview, SoftwareReviewDTO, SoftwareReviewPayload,
 };
+pub use software_version::SoftwareVersion;
+pub use stats::{
+    AnswerStats, CriterionAnswers, DepartmentStats, ExportStats, PeriodCount, ReviewStats,
+    ReviewerWorkload, ReviewerWorkloadStats, StatsInterval, StatsParams, UnreviewedRequestStats,
+};
+pub use summary_report::{
+    date_period, period_bounds, LocalAdminSoftware, OutcomeCount, ReviewOutcome, SummaryCount,
+    SummaryReport, SummaryReportParams, TimeToReview,
+};
+pub use ticket::{Ticket, TicketRequester, TicketSoftware};
 pub use user::{User, UserDTO, UserRole};
+pub use webhook::{
+    PendingWebhookDelivery, Webhook, WebhookDTO, WebhookDelivery, WebhookDeliveryAttempt,
+    WebhookDeliveryParams, WebhookDeliveryStatus, WebhookEvent,
+};
@@ -1,8 +1,7 @@
 use serde::{Deserialize, Serialize};
-use unicode_segmentation::UnicodeSegmentation;
-use validator::ValidateEmail;
 
-use crate::{Error, Result};
+use crate::api::utils::{validation, ExportRow};
+use crate::{FieldErrorCode, FieldErrors, Result};
 
 #[derive(Debug, Deserialize)]
 pub struct Requester {
@@ -39,135 +38,105 @@ impl From<&Requester> for RequesterDTO {
 
 impl Requester {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_name(&self.name) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild name for requests",
-                &self.name
-            )));
+            errors.add(
+                "name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid name", &self.name),
+            );
         }
 
         if !Self::validate_email(&self.email) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild email for requests",
-                &self.email
-            )));
+            errors.add(
+                "email",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid email", &self.email),
+            );
         }
 
         if !Self::validate_department(&self.department) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild department for requests",
-                &self.department
-            )));
+            errors.add(
+                "department",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid department", &self.department),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_name(name: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let name_is_empty_or_whitespace = name.trim().is_empty();
-
-        let name_too_long = name.graphemes(true).count() > 100;
-        let name_contains_forbidden_chars = name.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(name_is_empty_or_whitespace || name_too_long || name_contains_forbidden_chars)
+        validation::validate_text(name, 100)
     }
 
-    fn validate_email(email: &String) -> bool {
-        // Check if the email contains exactly one '@' symbol and has a domain
-        let split = email.split('@').collect::<Vec<&str>>();
-
-        if split.len() != 2 || split[1].is_empty() {
-            return false;
-        }
-
-        // `ValidateEmail` validates email based on HTML5 spec
-        ValidateEmail::validate_email(&email)
+    fn validate_email(email: &str) -> bool {
+        validation::validate_email(email)
     }
 
     fn validate_department(department: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let department_is_empty_or_whitespace = department.trim().is_empty();
-
-        let department_too_long = department.graphemes(true).count() > 100;
-
-        let department_contains_forbidden_chars =
-            department.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(department_is_empty_or_whitespace
-            || department_too_long
-            || department_contains_forbidden_chars)
+        validation::validate_text(department, 100)
     }
 }
 
 impl RequesterDTO {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_name(&self.name) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild name for requests",
-                &self.name
-            )));
+            errors.add(
+                "name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid name", &self.name),
+            );
         }
 
         if !Self::validate_email(&self.email) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild email for requests",
-                &self.email
-            )));
+            errors.add(
+                "email",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid email", &self.email),
+            );
         }
 
         if !Self::validate_department(&self.department) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild department for requests",
-                &self.department
-            )));
+            errors.add(
+                "department",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid department", &self.department),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_name(name: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let name_is_empty_or_whitespace = name.trim().is_empty();
-
-        let name_too_long = name.graphemes(true).count() > 100;
-        let name_contains_forbidden_chars = name.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(name_is_empty_or_whitespace || name_too_long || name_contains_forbidden_chars)
+        validation::validate_text(name, 100)
     }
 
-    fn validate_email(email: &String) -> bool {
-        // Check if the email contains exactly one '@' symbol and has a domain
-        let split = email.split('@').collect::<Vec<&str>>();
-
-        if split.len() != 2 || split[1].is_empty() {
-            return false;
-        }
-
-        // `ValidateEmail` validates email based on HTML5 spec
-        ValidateEmail::validate_email(&email)
+    fn validate_email(email: &str) -> bool {
+        validation::validate_email(email)
     }
 
     fn validate_department(department: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let department_is_empty_or_whitespace = department.trim().is_empty();
-
-        let department_too_long = department.graphemes(true).count() > 100;
-
-        let department_contains_forbidden_chars =
-            department.chars().any(|c| forbidden_chars.contains(&c));
+        validation::validate_text(department, 100)
+    }
+}
 
-        // Return false if any of the above conditions are met
-        !(department_is_empty_or_whitespace
-            || department_too_long
-            || department_contains_forbidden_chars)
+impl ExportRow for RequesterDTO {
+    const COLUMNS: &'static [&'static str] = &["id", "name", "email", "department", "created_at"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.id.map(|id| id.to_string()).unwrap_or_default(),
+            self.name.clone(),
+            self.email.clone(),
+            self.department.clone(),
+            self.created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
     }
 }
 
@@ -203,13 +172,18 @@ mod name_tests {
     }
 
     #[test]
-    fn forbidden_characters_in_name_are_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let name = format!("Invalid Name {}", chars);
-            assert_eq!(Requester::validate_name(&name), false);
+    fn real_world_names_are_valid() {
+        for name in ["Conor O'Brien", "Mary-Jane Watson", "José Núñez"] {
+            assert!(Requester::validate_name(name));
         }
     }
 
+    #[test]
+    fn control_characters_in_name_are_invalid() {
+        let name = "John\nDoe".to_string();
+        assert!(!Requester::validate_name(&name));
+    }
+
     #[test]
     fn valid_name_is_accepted() {
         let name = "John Doe".to_string();
@@ -252,7 +226,7 @@ mod email_tests {
 
     #[test]
     fn valid_email_is_accepted() {
-        let email = SafeEmail().fake();
+        let email: String = SafeEmail().fake();
         assert_eq!(Requester::validate_email(&email), true);
     }
 }
@@ -270,10 +244,9 @@ mod department_tests {
     }
 
     #[test]
-    fn department_with_forbidden_chars_is_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let department = format!("Invalid Department {}", chars);
-            assert_eq!(Requester::validate_department(&department), false);
+    fn department_with_punctuation_is_valid() {
+        for department in ["Health & Human Services", "Admissions (Graduate)", "IT/ITS"] {
+            assert!(Requester::validate_department(department));
         }
     }
 
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::validation;
+use crate::{Error, FieldErrorCode, FieldErrors, Result};
+
+#[derive(Debug)]
+pub struct ReviewComment {
+    pub id: uuid::Uuid,
+    pub software_review_id: uuid::Uuid,
+    pub author_id: uuid::Uuid,
+    pub body: String,
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewCommentPayload {
+    // Other users are mentioned by their email, e.g. `@jane@brockport.edu`
+    pub body: String,
+}
+
+// Data Transfer Object (DTO) for Review Comments
+#[derive(Debug, Serialize)]
+pub struct ReviewCommentDTO {
+    pub id: uuid::Uuid,
+    pub software_review_id: uuid::Uuid,
+    pub author: CommentAuthor,
+    pub body: String,
+    // Set once the body has been changed
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentAuthor {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub email: String,
+}
+
+// A comment that mentions the logged in user
+#[derive(Debug, Serialize)]
+pub struct CommentMention {
+    pub comment: ReviewCommentDTO,
+    pub software_name: String,
+    pub td_request_id: String,
+    pub mentioned_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommentMentionParams {
+    pub limit: Option<i64>,
+}
+
+impl ReviewComment {
+    // The author can only change a comment for `edit_window_minutes` after posting it
+    pub fn is_editable(
+        &self,
+        edit_window_minutes: i64,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        chrono::Duration::try_minutes(edit_window_minutes)
+            .and_then(|window| self.created_at.checked_add_signed(window))
+            .is_some_and(|closes_at| now < closes_at)
+    }
+}
+
+impl ReviewCommentPayload {
+    pub const MAX_BODY_LENGTH: usize = 2000;
+
+    pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
+        if !validation::validate_multiline_text(&self.body, Self::MAX_BODY_LENGTH) {
+            errors.add(
+                "body",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "comment must be between 1 and {} characters",
+                    Self::MAX_BODY_LENGTH
+                ),
+            );
+        }
+
+        errors.into_result()
+    }
+
+    // Lowercase emails following an `@` at the start of a word. Punctuation after a
+    // mention, such as the full stop ending a sentence, isn't part of it
+    pub fn mentioned_emails(&self) -> Vec<String> {
+        let mut emails: Vec<String> = self
+            .body
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('@'))
+            .map(|mention| {
+                mention
+                    .trim_end_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase()
+            })
+            .filter(|email| validation::validate_email(email))
+            .collect();
+
+        emails.sort();
+        emails.dedup();
+
+        emails
+    }
+}
+
+impl CommentMentionParams {
+    pub const DEFAULT_LIMIT: i64 = 50;
+    pub const MAX_LIMIT: i64 = 100;
+
+    pub fn limit(&self) -> Result<i64> {
+        let limit = self.limit.unwrap_or(Self::DEFAULT_LIMIT);
+
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'limit' must be between 1 and {}",
+                Self::MAX_LIMIT
+            )));
+        }
+
+        Ok(limit)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod review_comment_tests {
+    use uuid::Uuid;
+
+    use super::{ReviewComment, ReviewCommentPayload};
+
+    fn payload(body: &str) -> ReviewCommentPayload {
+        ReviewCommentPayload {
+            body: body.to_string(),
+        }
+    }
+
+    fn comment(created_at: chrono::DateTime<chrono::Utc>) -> ReviewComment {
+        ReviewComment {
+            id: Uuid::new_v4(),
+            software_review_id: Uuid::new_v4(),
+            author_id: Uuid::new_v4(),
+            body: "Checked with the vendor.".to_string(),
+            edited_at: None,
+            created_at,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn mentions_are_found_by_email() {
+        let payload = payload(
+            "@Jane@brockport.edu can you check this? cc @bob@brockport.edu, and \
+             @jane@brockport.edu again.\n@carol@brockport.edu",
+        );
+
+        assert_eq!(
+            vec![
+                "bob@brockport.edu",
+                "carol@brockport.edu",
+                "jane@brockport.edu"
+            ],
+            payload.mentioned_emails()
+        );
+    }
+
+    #[test]
+    fn text_that_is_not_a_mention_is_ignored() {
+        let payload = payload("Email jane@brockport.edu, or @jane, or @ someone@example.com");
+
+        assert!(payload.mentioned_emails().is_empty());
+    }
+
+    #[test]
+    fn blank_or_long_comments_are_rejected() {
+        assert!(payload("Looks fine.\nNo concerns.").parse().is_ok());
+        assert!(payload(" \n ").parse().is_err());
+        assert!(
+            payload(&"a".repeat(ReviewCommentPayload::MAX_BODY_LENGTH + 1))
+                .parse()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn comments_can_only_be_edited_within_the_window() {
+        let now = chrono::Utc::now();
+
+        assert!(comment(now - chrono::Duration::minutes(5)).is_editable(15, now));
+        assert!(!comment(now - chrono::Duration::minutes(15)).is_editable(15, now));
+        assert!(!comment(now).is_editable(0, now));
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{FieldErrorCode, FieldErrors, Result};
+
+// The reviews to include in a batch PDF export: either the reviews in `review_ids`, or
+// every review matching `filter` (a `field:value` filter, as for the list of reviews)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewExportPayload {
+    pub review_ids: Option<Vec<Uuid>>,
+    pub filter: Option<String>,
+}
+
+impl ReviewExportPayload {
+    // Every PDF is rendered while the archive is streamed, so a single export is limited
+    pub const MAX_REVIEWS: usize = 200;
+
+    pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
+        match (&self.review_ids, &self.filter) {
+            (Some(review_ids), None) => {
+                if review_ids.is_empty() || review_ids.len() > Self::MAX_REVIEWS {
+                    errors.add(
+                        "review_ids",
+                        FieldErrorCode::InvalidValue,
+                        format!(
+                            "between 1 and {} review ids must be provided",
+                            Self::MAX_REVIEWS
+                        ),
+                    );
+                }
+            }
+            // The filter is checked against the review filters by the service
+            (None, Some(_)) => (),
+            _ => errors.add(
+                "review_ids",
+                FieldErrorCode::ConflictingFields,
+                "exactly one of review_ids or filter must be provided",
+            ),
+        }
+
+        errors.into_result()
+    }
+}
+
+// Query params of a single review export
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewReportParams {
+    // Defaults to PDF
+    pub format: Option<ReviewReportFormat>,
+    // Adds the review's comments to the report
+    #[serde(default)]
+    pub include_comments: bool,
+}
+
+// Formats a single review can be exported as. Every format is rendered from the same
+// report, so they show the same wording as the PDF
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewReportFormat {
+    #[default]
+    Pdf,
+    Html,
+    #[serde(alias = "md")]
+    Markdown,
+    Docx,
+}
+
+impl ReviewReportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReviewReportFormat::Pdf => "application/pdf",
+            ReviewReportFormat::Html => "text/html; charset=utf-8",
+            ReviewReportFormat::Markdown => "text/markdown; charset=utf-8",
+            ReviewReportFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReviewReportFormat::Pdf => "pdf",
+            ReviewReportFormat::Html => "html",
+            ReviewReportFormat::Markdown => "md",
+            ReviewReportFormat::Docx => "docx",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewExportStatus {
+    // The PDF is in the archive and the review was marked exported
+    Exported,
+    // The review is missing from the archive, or could not be marked exported. See `error`
+    Failed,
+}
+
+// The outcome of a single review. `file` is the name of the PDF in the archive, if it
+// was added
+#[derive(Debug, Serialize)]
+pub struct ReviewExportResult {
+    pub review_id: Uuid,
+    pub status: ReviewExportStatus,
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// Written to the archive as `manifest.json`, after every PDF
+#[derive(Debug, Serialize)]
+pub struct ReviewExportManifest {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub exported: usize,
+    pub failed: usize,
+    pub reviews: Vec<ReviewExportResult>,
+}
+
+impl ReviewExportResult {
+    pub fn exported(review_id: Uuid, file: String) -> Self {
+        ReviewExportResult {
+            review_id,
+            status: ReviewExportStatus::Exported,
+            file: Some(file),
+            error: None,
+        }
+    }
+
+    pub fn failed(review_id: Uuid, file: Option<String>, error: impl Into<String>) -> Self {
+        ReviewExportResult {
+            review_id,
+            status: ReviewExportStatus::Failed,
+            file,
+            error: Some(error.into()),
+        }
+    }
+}
+
+impl From<Vec<ReviewExportResult>> for ReviewExportManifest {
+    fn from(reviews: Vec<ReviewExportResult>) -> Self {
+        let exported = reviews
+            .iter()
+            .filter(|review| review.status == ReviewExportStatus::Exported)
+            .count();
+
+        ReviewExportManifest {
+            created_at: chrono::Utc::now(),
+            exported,
+            failed: reviews.len() - exported,
+            reviews,
+        }
+    }
+}
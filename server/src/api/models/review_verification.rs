@@ -0,0 +1,30 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewVerificationStatus {
+    // The report matches the review as it is stored, and its signature is valid
+    Valid,
+    // The report's stamp matches the review as it is stored, but the report isn't signed,
+    // so changes to its pages can't be detected
+    MetadataOnly,
+    // The review was changed after the report was exported
+    Outdated,
+    // The report doesn't match the version of the review it claims to be, or its
+    // signature is invalid
+    Tampered,
+}
+
+// The result of checking an uploaded report against the review it was rendered from.
+// `signed` is false for reports exported without a signing key configured
+#[derive(Debug, Serialize)]
+pub struct ReviewVerification {
+    pub review_id: Uuid,
+    pub status: ReviewVerificationStatus,
+    pub report_version: i32,
+    pub current_version: i32,
+    pub content_hash: String,
+    pub signed: bool,
+    pub signature_valid: Option<bool>,
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::validation;
+use crate::{Error, Result};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchParams {
+    pub q: String,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitType {
+    Software,
+    Requester,
+    SoftwareRequest,
+    SoftwareReview,
+}
+
+impl std::str::FromStr for SearchHitType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "software" => Ok(SearchHitType::Software),
+            "requester" => Ok(SearchHitType::Requester),
+            "software_request" => Ok(SearchHitType::SoftwareRequest),
+            "software_review" => Ok(SearchHitType::SoftwareReview),
+            other => Err(Error::ServerError(std::sync::Arc::new(
+                format!("'{}' is not a valid search hit type", other).into(),
+            ))),
+        }
+    }
+}
+
+// A single ranked result from the global search. `highlight` contains the matched
+// terms wrapped in `<mark>` tags, with the surrounding text already HTML escaped
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    #[serde(rename = "type")]
+    pub hit_type: SearchHitType,
+    pub id: uuid::Uuid,
+    pub title: String,
+    pub highlight: String,
+    pub rank: f32,
+}
+
+impl SearchParams {
+    pub fn parse(&self) -> Result<()> {
+        if !Self::validate_search_term(&self.q) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'{}' is an invalid search term",
+                &self.q
+            )));
+        }
+
+        if let Some(page) = &self.page {
+            if *page < 1 || *page > 10_000_000 {
+                return Err(Error::QueryParamValidationError(format!(
+                    "'{}' is an invaild page query param",
+                    page
+                )));
+            }
+        }
+
+        if let Some(per_page) = &self.per_page {
+            if *per_page < 1 || *per_page > 100 {
+                return Err(Error::QueryParamValidationError(format!(
+                    "'{}' is an invaild per_page query param",
+                    per_page
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_search_term(term: &str) -> bool {
+        validation::validate_text(term, 100)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod search_term_tests {
+    use super::SearchParams;
+
+    // Returns true if field is valid, false if invalid
+
+    #[test]
+    fn a_100_grapheme_search_term_is_valid() {
+        let term = "a".repeat(100);
+        assert!(SearchParams::validate_search_term(&term));
+    }
+
+    #[test]
+    fn a_101_grapheme_search_term_is_invalid() {
+        let term = "a".repeat(101);
+        assert!(!SearchParams::validate_search_term(&term));
+    }
+
+    #[test]
+    fn whitespace_only_search_term_is_invalid() {
+        let term = "   ".to_string();
+        assert!(!SearchParams::validate_search_term(&term));
+    }
+
+    #[test]
+    fn search_term_with_operators_is_valid() {
+        let term = "\"Adobe Acrobat\" -reader or slack".to_string();
+        assert!(SearchParams::validate_search_term(&term));
+    }
+}
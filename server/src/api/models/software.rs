@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Error, Result};
+use crate::api::utils::{validation, ExportRow};
+use crate::{Error, FieldErrorCode, FieldErrors, Result};
+
+use super::SoftwareVersion;
 
 #[derive(Debug, Deserialize)]
 pub struct Software {
@@ -26,6 +28,21 @@ pub struct SoftwareDTO {
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimilarSoftwareParams {
+    pub name: String,
+    pub limit: Option<i64>,
+}
+
+// Existing software whose name closely matches a provided name, along with the
+// trigram similarity score (0.0 to 1.0)
+#[derive(Debug, Serialize)]
+pub struct SimilarSoftware {
+    pub software: SoftwareDTO,
+    pub similarity: f32,
+}
+
 impl From<&Software> for SoftwareDTO {
     fn from(software: &Software) -> Self {
         SoftwareDTO {
@@ -41,181 +58,165 @@ impl From<&Software> for SoftwareDTO {
 
 impl Software {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_software_name(&self.software_name) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild software_name for software",
-                &self.software_name
-            )));
+            errors.add(
+                "software_name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid software name", &self.software_name),
+            );
         }
 
         if !Self::validate_software_version(&self.software_version) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild software version for software",
-                &self.software_version
-            )));
+            errors.add(
+                "software_version",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid software version",
+                    &self.software_version
+                ),
+            );
         }
 
         if !Self::validate_developer_name(&self.developer_name) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild developer_name for software",
-                &self.developer_name
-            )));
+            errors.add(
+                "developer_name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid developer name", &self.developer_name),
+            );
         }
 
         if !Self::validate_description(&self.description) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild description for software",
-                &self.description
-            )));
+            errors.add(
+                "description",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid description", &self.description),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_software_name(name: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let name_is_empty_or_whitespace = name.trim().is_empty();
-
-        let name_too_long = name.graphemes(true).count() > 100;
-        let name_contains_forbidden_chars = name.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(name_is_empty_or_whitespace || name_too_long || name_contains_forbidden_chars)
+        validation::validate_text(name, 100)
     }
 
     fn validate_software_version(version: &str) -> bool {
-        let parts: Vec<&str> = version.split('.').collect();
-
-        // Ensure exactly 3 parts (x.y.z)
-        if parts.len() != 3 {
-            return false;
-        }
-
-        // Return false if any of the above conditions are met
-        parts
-            .iter()
-            .all(|&part| !part.is_empty() && part.len() <= 4 && part.chars().all(char::is_numeric))
+        SoftwareVersion::parse(version).is_some()
     }
 
     fn validate_developer_name(developer: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let developer_is_empty_or_whitespace = developer.trim().is_empty();
-
-        let developer_too_long = developer.graphemes(true).count() > 100;
-        let developer_contains_forbidden_chars =
-            developer.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(developer_is_empty_or_whitespace
-            || developer_too_long
-            || developer_contains_forbidden_chars)
+        validation::validate_text(developer, 100)
     }
 
     fn validate_description(description: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let description_is_empty_or_whitespace = description.trim().is_empty();
-
-        let description_too_long = description.graphemes(true).count() > 255;
-        let description_contains_forbidden_chars =
-            description.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(description_is_empty_or_whitespace
-            || description_too_long
-            || description_contains_forbidden_chars)
+        validation::validate_text(description, 255)
     }
 }
 
 impl SoftwareDTO {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_software_name(&self.software_name) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild software_name for software",
-                &self.software_name
-            )));
+            errors.add(
+                "software_name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid software name", &self.software_name),
+            );
         }
 
         if !Self::validate_software_version(&self.software_version) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild software version for software",
-                &self.software_version
-            )));
+            errors.add(
+                "software_version",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid software version",
+                    &self.software_version
+                ),
+            );
         }
 
         if !Self::validate_developer_name(&self.developer_name) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild developer_name for software",
-                &self.developer_name
-            )));
+            errors.add(
+                "developer_name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid developer name", &self.developer_name),
+            );
         }
 
         if !Self::validate_description(&self.description) {
-            return Err(Error::ValidationError(format!(
-                "software payload: '{}' is an invaild description for software",
-                &self.description
-            )));
+            errors.add(
+                "description",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid description", &self.description),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_software_name(name: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let name_is_empty_or_whitespace = name.trim().is_empty();
-
-        let name_too_long = name.graphemes(true).count() > 100;
-        let name_contains_forbidden_chars = name.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(name_is_empty_or_whitespace || name_too_long || name_contains_forbidden_chars)
+        validation::validate_text(name, 100)
     }
 
     fn validate_software_version(version: &str) -> bool {
-        let parts: Vec<&str> = version.split('.').collect();
-
-        // Ensure exactly 3 parts (x.y.z)
-        if parts.len() != 3 {
-            return false;
-        }
-
-        // Return false if any of the above conditions are met
-        parts
-            .iter()
-            .all(|&part| !part.is_empty() && part.len() <= 4 && part.chars().all(char::is_numeric))
+        SoftwareVersion::parse(version).is_some()
     }
 
     fn validate_developer_name(developer: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let developer_is_empty_or_whitespace = developer.trim().is_empty();
-
-        let developer_too_long = developer.graphemes(true).count() > 100;
-        let developer_contains_forbidden_chars =
-            developer.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(developer_is_empty_or_whitespace
-            || developer_too_long
-            || developer_contains_forbidden_chars)
+        validation::validate_text(developer, 100)
     }
 
     fn validate_description(description: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
+        validation::validate_text(description, 255)
+    }
+}
 
-        let description_is_empty_or_whitespace = description.trim().is_empty();
+impl SimilarSoftwareParams {
+    pub fn parse(&self) -> Result<()> {
+        if !validation::validate_text(&self.name, 100) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'{}' is an invalid name query param",
+                &self.name
+            )));
+        }
+
+        if let Some(limit) = &self.limit {
+            if *limit < 1 || *limit > 20 {
+                return Err(Error::QueryParamValidationError(format!(
+                    "'{}' is an invalid limit query param",
+                    limit
+                )));
+            }
+        }
 
-        let description_too_long = description.graphemes(true).count() > 255;
-        let description_contains_forbidden_chars =
-            description.chars().any(|c| forbidden_chars.contains(&c));
+        Ok(())
+    }
+}
 
-        // Return false if any of the above conditions are met
-        !(description_is_empty_or_whitespace
-            || description_too_long
-            || description_contains_forbidden_chars)
+impl ExportRow for SoftwareDTO {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "software_name",
+        "software_version",
+        "developer_name",
+        "description",
+        "created_at",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.id.map(|id| id.to_string()).unwrap_or_default(),
+            self.software_name.clone(),
+            self.software_version.clone(),
+            self.developer_name.clone(),
+            self.description.clone(),
+            self.created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
     }
 }
 
@@ -251,13 +252,22 @@ mod name_tests {
     }
 
     #[test]
-    fn forbidden_characters_in_name_are_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let name = chars.to_string();
-            assert_eq!(Software::validate_software_name(&name), false);
+    fn punctuation_in_name_is_valid() {
+        for name in [
+            "Adobe Acrobat (64 bit)",
+            "Wi-Fi driver",
+            "Notepad++ $pecial",
+        ] {
+            assert!(Software::validate_software_name(name));
         }
     }
 
+    #[test]
+    fn control_characters_in_name_are_invalid() {
+        let name = "Test\u{0}Software".to_string();
+        assert!(!Software::validate_software_name(&name));
+    }
+
     #[test]
     fn valid_name_is_accepted() {
         let name = "Valid Software".to_string();
@@ -273,12 +283,9 @@ mod version_tests {
 
     #[test]
     fn software_version_with_non_numeric_parts_is_invalid() {
-        let versions = ["1.0.a", "1..0", "1.0.0.", ".1.0", "1.0.0.1"];
+        let versions = ["1.0.a", "1..0", "1.0.0.", ".1.0", "latest"];
         for &version in &versions {
-            assert_eq!(
-                Software::validate_software_version(&version.to_string()),
-                false
-            );
+            assert!(!Software::validate_software_version(version));
         }
     }
 
@@ -289,13 +296,15 @@ mod version_tests {
     }
 
     #[test]
-    fn software_version_with_invalid_format_is_invalid() {
-        let versions = ["1.0", "1.0.0.0", "1..0", "1.1.a"];
+    fn software_version_with_more_than_4_parts_is_invalid() {
+        assert!(!Software::validate_software_version("1.0.0.0.1"));
+    }
+
+    #[test]
+    fn software_version_formats_used_by_vendors_are_valid() {
+        let versions = ["2024.1", "10.0.19045.3803", "3.2.1-beta", "v5"];
         for &version in &versions {
-            assert_eq!(
-                Software::validate_software_version(&version.to_string()),
-                false
-            );
+            assert!(Software::validate_software_version(version));
         }
     }
 
@@ -331,10 +340,9 @@ mod developer_tests {
     }
 
     #[test]
-    fn developer_with_forbidden_chars_is_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let developer = format!("Developer {}", chars);
-            assert_eq!(Software::validate_developer_name(&developer), false);
+    fn developer_with_punctuation_is_valid() {
+        for developer in ["O'Reilly Media", "Hewlett-Packard", "AT&T (Enterprise)"] {
+            assert!(Software::validate_developer_name(developer));
         }
     }
 
@@ -370,11 +378,9 @@ mod description_tests {
     }
 
     #[test]
-    fn description_with_forbidden_chars_is_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let description = format!("Invalid Software {}", chars);
-            assert_eq!(Software::validate_description(&description), false);
-        }
+    fn description_with_punctuation_is_valid() {
+        let description = "PDF reader/editor (64-bit) that doesn't require \"admin\" rights";
+        assert!(Software::validate_description(description));
     }
 
     #[test]
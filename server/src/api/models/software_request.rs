@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use crate::api::utils::ExportRow;
+use crate::{FieldErrorCode, FieldErrors, Result};
 
 use super::{RequesterDTO, SoftwareDTO};
 
@@ -25,6 +26,38 @@ pub struct SoftwareRequestDTO {
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// Software request details used when creating a software review. The software and
+// requester can each either reference an existing record by id or provide the details
+// for a new record, but not both
+#[derive(Debug, Deserialize)]
+pub struct SoftwareRequestPayload {
+    pub td_request_id: String,
+    pub software_id: Option<uuid::Uuid>,
+    pub software: Option<SoftwareDTO>,
+    pub requester_id: Option<uuid::Uuid>,
+    pub requester: Option<RequesterDTO>,
+}
+
+// A software request without a review yet, or whose review expired, as listed in the
+// review queue
+#[derive(Debug, Serialize)]
+pub struct QueuedSoftwareRequest {
+    pub software_request: SoftwareRequestDTO,
+    pub assignee: Option<RequestAssignee>,
+    // Set when the request is back in the queue because its review expired
+    pub reopened_at: Option<chrono::DateTime<chrono::Utc>>,
+    // Whole days since the request was created, or reopened
+    pub age_days: i64,
+}
+
+// The user a software request is assigned to for review
+#[derive(Debug, Serialize)]
+pub struct RequestAssignee {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub email: String,
+}
+
 impl From<(&SoftwareRequest, SoftwareDTO, RequesterDTO)> for SoftwareRequestDTO {
     fn from((request, software, requester): (&SoftwareRequest, SoftwareDTO, RequesterDTO)) -> Self {
         SoftwareRequestDTO {
@@ -39,55 +72,117 @@ impl From<(&SoftwareRequest, SoftwareDTO, RequesterDTO)> for SoftwareRequestDTO
 
 impl SoftwareRequest {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_td_request_id(&self.td_request_id) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild td_request_id for requests",
-                self.td_request_id
-            )));
+            errors.add(
+                "td_request_id",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid ticket number, expected exactly 8 digits",
+                    self.td_request_id
+                ),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_td_request_id(id: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        if id.trim().is_empty() {
-            return false;
-        }
-
-        if id.chars().any(|c| forbidden_chars.contains(&c)) {
-            return false;
-        }
-
         id.len() == 8 && id.chars().all(|c| c.is_ascii_digit())
     }
 }
 
 impl SoftwareRequestDTO {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_td_request_id(&self.td_request_id) {
-            return Err(Error::ValidationError(format!(
-                "requests payload: '{}' is an invaild td_request_id for requests",
-                self.td_request_id
-            )));
+            errors.add(
+                "td_request_id",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid ticket number, expected exactly 8 digits",
+                    self.td_request_id
+                ),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
-    fn validate_td_request_id(id: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
+    pub fn validate_td_request_id(id: &str) -> bool {
+        id.len() == 8 && id.chars().all(|c| c.is_ascii_digit())
+    }
+}
 
-        if id.trim().is_empty() {
-            return false;
+impl SoftwareRequestPayload {
+    pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
+        if !SoftwareRequestDTO::validate_td_request_id(&self.td_request_id) {
+            errors.add(
+                "td_request_id",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid ticket number, expected exactly 8 digits",
+                    self.td_request_id
+                ),
+            );
         }
 
-        if id.chars().any(|c| forbidden_chars.contains(&c)) {
-            return false;
+        match (&self.software_id, &self.software) {
+            (Some(_), None) => (),
+            (None, Some(software)) => errors.nest("software", software.parse())?,
+            _ => errors.add(
+                "software",
+                FieldErrorCode::ConflictingFields,
+                "exactly one of software_id or software must be provided",
+            ),
         }
 
-        id.len() == 8 && id.chars().all(|c| c.is_ascii_digit())
+        match (&self.requester_id, &self.requester) {
+            (Some(_), None) => (),
+            (None, Some(requester)) => errors.nest("requester", requester.parse())?,
+            _ => errors.add(
+                "requester",
+                FieldErrorCode::ConflictingFields,
+                "exactly one of requester_id or requester must be provided",
+            ),
+        }
+
+        errors.into_result()
+    }
+}
+
+// The software and requester are flattened into columns of the request
+impl ExportRow for SoftwareRequestDTO {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "td_request_id",
+        "software_name",
+        "software_version",
+        "developer_name",
+        "requester_name",
+        "requester_email",
+        "requester_department",
+        "created_at",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.id.map(|id| id.to_string()).unwrap_or_default(),
+            self.td_request_id.clone(),
+            self.software.software_name.clone(),
+            self.software.software_version.clone(),
+            self.software.developer_name.clone(),
+            self.requester.name.clone(),
+            self.requester.email.clone(),
+            self.requester.department.clone(),
+            self.created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
     }
 }
 
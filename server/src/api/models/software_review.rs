@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
-use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
-use crate::{Error, Result};
+use crate::api::utils::{validation, ExportRow};
+use crate::{FieldErrorCode, FieldErrors, Result};
 
-use super::{SoftwareRequestDTO, UserDTO};
+use super::{ReviewOutcome, SoftwareRequestDTO, SoftwareRequestPayload, SoftwareVersion, UserDTO};
+
+// Review notes are Markdown, and are sanitized before they are validated (see
+// `sanitize_markdown`)
+const MAX_REVIEW_NOTES_LENGTH: usize = 10_000;
 
 #[derive(Debug, Deserialize)]
 pub struct SoftwareReview {
@@ -22,15 +26,20 @@ pub struct SoftwareReview {
     pub is_supported_by_current_os: ReviewOptions,
     pub exported: bool,
     pub review_notes: String,
+    pub reviewed_version: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: Option<i32>,
 }
 
+// Either `software_request_id` references an existing software request, or
+// `software_request` provides the details for a new one
 #[derive(Debug, Deserialize)]
 pub struct SoftwareReviewPayload {
     pub id: Option<Uuid>,
-    pub software_request: SoftwareRequestDTO,
+    pub software_request_id: Option<Uuid>,
+    pub software_request: Option<SoftwareRequestPayload>,
     pub reviewer_id: Uuid,
     pub is_supported: ReviewOptions,
     pub is_current_version: ReviewOptions,
@@ -84,6 +93,12 @@ pub struct SoftwareReviewDTO {
     pub is_supported_by_current_os: ReviewOptions,
     pub exported: bool,
     pub review_notes: String,
+    // The software version that was reviewed. `newer_version_available` is true when the
+    // software has since been updated to a newer version
+    pub reviewed_version: String,
+    pub newer_version_available: bool,
+    // The review no longer counts as a current approval after this time
+    pub expires_at: chrono::DateTime<chrono::Utc>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -91,6 +106,11 @@ impl From<(&SoftwareReview, SoftwareRequestDTO, UserDTO)> for SoftwareReviewDTO
     fn from(
         (review, software_request, reviewer): (&SoftwareReview, SoftwareRequestDTO, UserDTO),
     ) -> Self {
+        let newer_version_available = SoftwareVersion::is_newer(
+            &software_request.software.software_version,
+            &review.reviewed_version,
+        );
+
         SoftwareReviewDTO {
             id: review.id,
             software_request,
@@ -110,58 +130,178 @@ impl From<(&SoftwareReview, SoftwareRequestDTO, UserDTO)> for SoftwareReviewDTO
             is_supported_by_current_os: review.is_supported_by_current_os.clone(),
             exported: review.exported,
             review_notes: review.review_notes.clone(),
+            reviewed_version: review.reviewed_version.clone(),
+            newer_version_available,
+            expires_at: review.expires_at,
             created_at: review.created_at,
         }
     }
 }
 
+impl SoftwareReviewDTO {
+    pub fn outcome(&self) -> ReviewOutcome {
+        ReviewOutcome::from_answers([
+            &self.is_supported,
+            &self.is_current_version,
+            &self.is_reputation_good,
+            &self.is_installation_from_developer,
+            &self.is_local_admin_required,
+            &self.is_connected_to_brockport_cloud,
+            &self.is_connected_to_cloud_services_or_client,
+            &self.is_security_or_optimization_software,
+            &self.is_supported_by_current_os,
+        ])
+    }
+}
+
 impl SoftwareReview {
+    pub fn outcome(&self) -> ReviewOutcome {
+        ReviewOutcome::from_answers([
+            &self.is_supported,
+            &self.is_current_version,
+            &self.is_reputation_good,
+            &self.is_installation_from_developer,
+            &self.is_local_admin_required,
+            &self.is_connected_to_brockport_cloud,
+            &self.is_connected_to_cloud_services_or_client,
+            &self.is_security_or_optimization_software,
+            &self.is_supported_by_current_os,
+        ])
+    }
+
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_review_notes(&self.review_notes) {
-            return Err(Error::ValidationError(format!(
-                "software review payload: '{}' is invalid review notes",
-                &self.review_notes
-            )));
+            errors.add(
+                "review_notes",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "review notes must be between 1 and {} characters",
+                    MAX_REVIEW_NOTES_LENGTH
+                ),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_review_notes(notes: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let notes_is_empty_or_whitespace = notes.trim().is_empty();
-
-        let notes_too_long = notes.graphemes(true).count() > 255;
-        let notes_contains_forbidden_chars = notes.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(notes_is_empty_or_whitespace || notes_too_long || notes_contains_forbidden_chars)
+        validation::validate_multiline_text(notes, MAX_REVIEW_NOTES_LENGTH)
     }
 }
 
 impl SoftwareReviewPayload {
+    pub fn outcome(&self) -> ReviewOutcome {
+        ReviewOutcome::from_answers([
+            &self.is_supported,
+            &self.is_current_version,
+            &self.is_reputation_good,
+            &self.is_installation_from_developer,
+            &self.is_local_admin_required,
+            &self.is_connected_to_brockport_cloud,
+            &self.is_connected_to_cloud_services_or_client,
+            &self.is_security_or_optimization_software,
+            &self.is_supported_by_current_os,
+        ])
+    }
+
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
+        match (&self.software_request_id, &self.software_request) {
+            (Some(_), None) => (),
+            (None, Some(software_request)) => {
+                errors.nest("software_request", software_request.parse())?
+            }
+            _ => errors.add(
+                "software_request",
+                FieldErrorCode::ConflictingFields,
+                "exactly one of software_request_id or software_request must be provided",
+            ),
+        }
+
         if !Self::validate_review_notes(&self.review_notes) {
-            return Err(Error::ValidationError(format!(
-                "software review payload: '{}' is invalid review notes",
-                &self.review_notes
-            )));
+            errors.add(
+                "review_notes",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "review notes must be between 1 and {} characters",
+                    MAX_REVIEW_NOTES_LENGTH
+                ),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_review_notes(notes: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
+        validation::validate_multiline_text(notes, MAX_REVIEW_NOTES_LENGTH)
+    }
+}
 
-        let notes_is_empty_or_whitespace = notes.trim().is_empty();
+// The software request and reviewer are flattened into columns of the review
+impl ExportRow for SoftwareReviewDTO {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "td_request_id",
+        "software_name",
+        "software_version",
+        "reviewed_version",
+        "newer_version_available",
+        "developer_name",
+        "requester_name",
+        "requester_email",
+        "requester_department",
+        "reviewer_name",
+        "reviewer_email",
+        "is_supported",
+        "is_current_version",
+        "is_reputation_good",
+        "is_installation_from_developer",
+        "is_local_admin_required",
+        "is_connected_to_brockport_cloud",
+        "is_connected_to_cloud_services_or_client",
+        "is_security_or_optimization_software",
+        "is_supported_by_current_os",
+        "exported",
+        "review_notes",
+        "expires_at",
+        "created_at",
+    ];
 
-        let notes_too_long = notes.graphemes(true).count() > 255;
-        let notes_contains_forbidden_chars = notes.chars().any(|c| forbidden_chars.contains(&c));
+    fn values(&self) -> Vec<String> {
+        let software_request = &self.software_request;
 
-        // Return false if any of the above conditions are met
-        !(notes_is_empty_or_whitespace || notes_too_long || notes_contains_forbidden_chars)
+        vec![
+            self.id.to_string(),
+            software_request.td_request_id.clone(),
+            software_request.software.software_name.clone(),
+            software_request.software.software_version.clone(),
+            self.reviewed_version.clone(),
+            self.newer_version_available.to_string(),
+            software_request.software.developer_name.clone(),
+            software_request.requester.name.clone(),
+            software_request.requester.email.clone(),
+            software_request.requester.department.clone(),
+            self.reviewer.name.clone(),
+            self.reviewer.email.clone(),
+            self.is_supported.to_string(),
+            self.is_current_version.to_string(),
+            self.is_reputation_good.to_string(),
+            self.is_installation_from_developer.to_string(),
+            self.is_local_admin_required.to_string(),
+            self.is_connected_to_brockport_cloud.to_string(),
+            self.is_connected_to_cloud_services_or_client.to_string(),
+            self.is_security_or_optimization_software.to_string(),
+            self.is_supported_by_current_os.to_string(),
+            self.exported.to_string(),
+            self.review_notes.clone(),
+            self.expires_at.to_rfc3339(),
+            self.created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
     }
 }
 
@@ -173,8 +313,8 @@ mod review_notes_tests {
     // Returns true is field is vaild, false if invalid
 
     #[test]
-    fn a_255_grapheme_review_notes_is_vaild() {
-        let review_notes = "a".repeat(255);
+    fn a_10000_grapheme_review_notes_is_vaild() {
+        let review_notes = "a".repeat(10_000);
         assert_eq!(
             SoftwareReviewPayload::validate_review_notes(&review_notes),
             true
@@ -182,8 +322,8 @@ mod review_notes_tests {
     }
 
     #[test]
-    fn a_256_grapheme_review_notes_is_invaild() {
-        let review_notes = "a".repeat(256);
+    fn a_10001_grapheme_review_notes_is_invaild() {
+        let review_notes = "a".repeat(10_001);
         assert_eq!(
             SoftwareReviewPayload::validate_review_notes(&review_notes),
             false
@@ -209,14 +349,15 @@ mod review_notes_tests {
     }
 
     #[test]
-    fn forbidden_characters_in_review_notes_are_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let review_notes = chars.to_string();
-            assert_eq!(
-                SoftwareReviewPayload::validate_review_notes(&review_notes),
-                false
-            );
-        }
+    fn punctuation_and_line_breaks_in_review_notes_are_valid() {
+        let review_notes = "Vendor-signed installer (v2.1).\nDoesn't require admin rights.";
+        assert!(SoftwareReviewPayload::validate_review_notes(review_notes));
+    }
+
+    #[test]
+    fn control_characters_in_review_notes_are_invalid() {
+        let review_notes = "All conditions\u{0} satisfied.".to_string();
+        assert!(!SoftwareReviewPayload::validate_review_notes(&review_notes));
     }
 
     #[test]
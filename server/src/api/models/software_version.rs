@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+
+use crate::{Error, FieldError, FieldErrorCode, Result};
+
+// A parsed software version. Accepts what vendors actually publish rather than strict
+// semver:
+//
+// - an optional `v`/`V` prefix (`v5`)
+// - 1 to 4 dot separated numeric components (`5`, `2024.1`, `10.0.19045.3803`), which
+//   also covers calendar versions (`2024.11.02`, `24.04`)
+// - optional semver pre-release (`-beta.2`) and build metadata (`+build.5`)
+//
+// Ordering follows semver precedence: missing components count as 0 (`1.2` == `1.2.0`),
+// a pre-release sorts before its release, and build metadata is ignored
+#[derive(Clone, Debug)]
+pub struct SoftwareVersion {
+    components: Vec<u32>,
+    pre_release: Vec<String>,
+}
+
+impl SoftwareVersion {
+    pub const MAX_LENGTH: usize = 50;
+    const MAX_COMPONENTS: usize = 4;
+
+    // Returns `None` if `version` is not a supported version format
+    pub fn parse(version: &str) -> Option<Self> {
+        if version.is_empty() || version.len() > Self::MAX_LENGTH {
+            return None;
+        }
+
+        let version = version
+            .strip_prefix('v')
+            .or_else(|| version.strip_prefix('V'))
+            .unwrap_or(version);
+
+        let (version, build) = match version.split_once('+') {
+            Some((version, build)) => (version, Some(build)),
+            None => (version, None),
+        };
+
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre_release)) => (core, Some(pre_release)),
+            None => (version, None),
+        };
+
+        let components = core
+            .split('.')
+            .map(|component| {
+                if component.is_empty() || !component.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                component.parse::<u32>().ok()
+            })
+            .collect::<Option<Vec<u32>>>()?;
+
+        if components.len() > Self::MAX_COMPONENTS {
+            return None;
+        }
+
+        let pre_release = match pre_release {
+            Some(pre_release) => {
+                let identifiers: Vec<String> = pre_release.split('.').map(str::to_string).collect();
+
+                if !identifiers.iter().all(|id| Self::validate_identifier(id)) {
+                    return None;
+                }
+
+                // Numeric identifiers are compared as numbers so they must fit the sort key
+                if identifiers
+                    .iter()
+                    .any(|id| id.chars().all(|c| c.is_ascii_digit()) && id.parse::<u32>().is_err())
+                {
+                    return None;
+                }
+
+                identifiers
+            }
+            None => Vec::new(),
+        };
+
+        // Build metadata has no effect on precedence so it is only validated
+        if let Some(build) = build {
+            if !build.split('.').all(Self::validate_identifier) {
+                return None;
+            }
+        }
+
+        Some(SoftwareVersion {
+            components,
+            pre_release,
+        })
+    }
+
+    // Returns true if `current` is a newer version than `reviewed`. Versions that cannot
+    // be parsed are never reported as newer
+    pub fn is_newer(current: &str, reviewed: &str) -> bool {
+        match (Self::parse(current), Self::parse(reviewed)) {
+            (Some(current), Some(reviewed)) => current > reviewed,
+            _ => false,
+        }
+    }
+
+    // A string whose byte order matches the version precedence, stored alongside the
+    // version so the database can sort by it (the column uses the "C" collation):
+    //
+    // - each of the 4 components is zero padded to 10 digits
+    // - a release ends with `~`, which sorts after the `-` that starts a pre-release
+    // - numeric pre-release identifiers are zero padded and prefixed with `0`, others are
+    //   prefixed with `1`, so numeric identifiers sort first. Identifiers are separated
+    //   by `!`, which sorts before every character allowed in an identifier
+    pub fn sort_key(&self) -> String {
+        let mut key = (0..Self::MAX_COMPONENTS)
+            .map(|i| format!("{:010}", self.components.get(i).copied().unwrap_or(0)))
+            .collect::<Vec<String>>()
+            .join(".");
+
+        if self.pre_release.is_empty() {
+            key.push('~');
+        } else {
+            let identifiers = self
+                .pre_release
+                .iter()
+                .map(|id| match id.parse::<u32>() {
+                    Ok(number) => format!("0{:010}", number),
+                    Err(_) => format!("1{}", id),
+                })
+                .collect::<Vec<String>>()
+                .join("!");
+
+            key.push('-');
+            key.push_str(&identifiers);
+        }
+
+        key
+    }
+
+    // Identifiers are non-empty and only contain ASCII alphanumerics and hyphens
+    fn validate_identifier(identifier: &str) -> bool {
+        !identifier.is_empty()
+            && identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    }
+}
+
+impl std::str::FromStr for SoftwareVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s).ok_or_else(|| {
+            Error::ValidationError(
+                FieldError::new(
+                    "software_version",
+                    FieldErrorCode::InvalidValue,
+                    format!("'{}' is an invalid software version", s),
+                )
+                .into(),
+            )
+        })
+    }
+}
+
+impl Ord for SoftwareVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for SoftwareVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SoftwareVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SoftwareVersion {}
+
+// Unit Tests
+#[cfg(test)]
+mod parse_tests {
+    use super::SoftwareVersion;
+
+    #[test]
+    fn real_world_versions_are_valid() {
+        let versions = [
+            "5",
+            "v5",
+            "V5.1",
+            "2024.1",
+            "24.04",
+            "2024.11.02",
+            "1.0.0",
+            "10.0.19045.3803",
+            "3.2.1-beta",
+            "1.0.0-alpha.1",
+            "1.0.0-rc.1+build.5",
+            "2.4.0+20241102",
+            "1.0.0-x-y-z.--",
+        ];
+
+        for version in versions {
+            assert!(
+                SoftwareVersion::parse(version).is_some(),
+                "'{}' should be valid",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_versions_are_invalid() {
+        let versions = [
+            "",
+            "v",
+            "1.0.a",
+            "1..0",
+            ".1.0",
+            "1.0.0.",
+            "1.0.0.0.1",
+            "1.0.0-",
+            "1.0.0-beta..1",
+            "1.0.0+",
+            "1.0.0-beta_1",
+            "1.0 .0",
+            "latest",
+            "99999999999.0",
+        ];
+
+        for version in versions {
+            assert!(
+                SoftwareVersion::parse(version).is_none(),
+                "'{}' should be invalid",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn version_longer_than_max_length_is_invalid() {
+        let version = format!("1.0.0-{}", "a".repeat(SoftwareVersion::MAX_LENGTH));
+        assert!(SoftwareVersion::parse(&version).is_none());
+    }
+}
+
+#[cfg(test)]
+mod ordering_tests {
+    use super::SoftwareVersion;
+
+    fn version(version: &str) -> SoftwareVersion {
+        SoftwareVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn semver_precedence_is_followed() {
+        // Example from the semver specification, in ascending order
+        let versions = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+
+        for pair in versions.windows(2) {
+            assert!(
+                version(pair[0]) < version(pair[1]),
+                "'{}' should be lower than '{}'",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn components_are_compared_numerically() {
+        assert!(version("1.10.0") > version("1.9.0"));
+        assert!(version("10.0.19045.3803") > version("10.0.19045.999"));
+        assert!(version("2024.11.02") > version("2024.2"));
+    }
+
+    #[test]
+    fn missing_components_count_as_zero() {
+        assert_eq!(version("1.2"), version("1.2.0"));
+        assert_eq!(version("5"), version("5.0.0.0"));
+        assert!(version("1.2") < version("1.2.0.1"));
+    }
+
+    #[test]
+    fn prefix_and_build_metadata_are_ignored() {
+        assert_eq!(version("v5"), version("5"));
+        assert_eq!(version("1.0.0+build.1"), version("1.0.0+build.2"));
+    }
+
+    #[test]
+    fn shorter_pre_release_identifier_sorts_first() {
+        assert!(version("1.0.0-alpha.1") < version("1.0.0-alpha-1"));
+        assert!(version("1.0.0-alpha") < version("1.0.0-alphabet"));
+    }
+
+    #[test]
+    fn sort_key_matches_ordering() {
+        let mut versions = [
+            "3.2.1-beta",
+            "2024.1",
+            "v5",
+            "10.0.19045.3803",
+            "3.2.1",
+            "1.0",
+        ]
+        .map(version)
+        .to_vec();
+        let mut keys: Vec<String> = versions.iter().map(SoftwareVersion::sort_key).collect();
+
+        versions.sort();
+        keys.sort();
+
+        assert_eq!(
+            versions
+                .iter()
+                .map(SoftwareVersion::sort_key)
+                .collect::<Vec<String>>(),
+            keys
+        );
+    }
+
+    #[test]
+    fn newer_version_is_detected() {
+        assert!(SoftwareVersion::is_newer("2.0.0", "1.9.9"));
+        assert!(SoftwareVersion::is_newer("1.0.0", "1.0.0-rc.1"));
+        assert!(!SoftwareVersion::is_newer("1.0", "1.0.0"));
+        assert!(!SoftwareVersion::is_newer("1.0.0", "not a version"));
+    }
+}
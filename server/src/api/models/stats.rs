@@ -0,0 +1,195 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{date_period, SummaryCount};
+use crate::api::utils::ReviewCriterion;
+use crate::{Error, Result};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsParams {
+    // Dates are inclusive, in UTC
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // Only used by `/stats/reviews`
+    pub interval: Option<StatsInterval>,
+    // Only used by `/stats/departments`
+    pub limit: Option<i64>,
+}
+
+impl StatsParams {
+    // Used when `from` is not provided
+    pub const DEFAULT_DAYS: i64 = 365;
+    pub const DEFAULT_LIMIT: i64 = 10;
+    pub const MAX_LIMIT: i64 = 100;
+
+    // Returns the first and last day of the statistics. Defaults to the `DEFAULT_DAYS`
+    // days up to and including `to`, or today
+    pub fn period(&self) -> Result<(NaiveDate, NaiveDate)> {
+        date_period(self.from, self.to, Self::DEFAULT_DAYS)
+    }
+
+    pub fn interval(&self) -> StatsInterval {
+        self.interval.unwrap_or_default()
+    }
+
+    pub fn limit(&self) -> Result<i64> {
+        let limit = self.limit.unwrap_or(Self::DEFAULT_LIMIT);
+
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'limit' must be between 1 and {}",
+                Self::MAX_LIMIT
+            )));
+        }
+
+        Ok(limit)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsInterval {
+    #[default]
+    Week,
+    Month,
+}
+
+impl StatsInterval {
+    // Also the field passed to Postgres' `date_trunc`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsInterval::Week => "week",
+            StatsInterval::Month => "month",
+        }
+    }
+}
+
+// Reviews created within the week or month starting on `period_start`. Weeks start on
+// Monday
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PeriodCount {
+    pub period_start: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReviewStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub interval: StatsInterval,
+    // Every week or month in the period is listed, in order, even when it had no reviews
+    pub periods: Vec<PeriodCount>,
+}
+
+// Number of reviews with each answer for a `ReviewOptions` criterion
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CriterionAnswers {
+    pub criterion: ReviewCriterion,
+    #[serde(rename = "TRUE")]
+    pub true_count: i64,
+    #[serde(rename = "FALSE")]
+    pub false_count: i64,
+    #[serde(rename = "NOT_SURE")]
+    pub not_sure_count: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AnswerStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub criteria: Vec<CriterionAnswers>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub exported: i64,
+    pub not_exported: i64,
+}
+
+// Software requests made within the period that have no review yet. Ages are in days, up
+// to now
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UnreviewedRequestStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub count: i64,
+    pub oldest_created_at: Option<DateTime<Utc>>,
+    pub average_age_days: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReviewerWorkload {
+    pub user_id: uuid::Uuid,
+    pub name: String,
+    pub email: String,
+    pub reviews: i64,
+    pub exported_reviews: i64,
+    // Software requests assigned to the user that are still waiting for a review,
+    // regardless of the period
+    pub assigned_requests: i64,
+}
+
+// Every user is listed, including those without reviews in the period
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReviewerWorkloadStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub reviewers: Vec<ReviewerWorkload>,
+}
+
+// Departments of the requesters that made the most software requests in the period
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DepartmentStats {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub departments: Vec<SummaryCount>,
+}
+
+// Unit Tests
+#[cfg(test)]
+mod stats_params_tests {
+    use chrono::NaiveDate;
+
+    use super::{StatsInterval, StatsParams};
+
+    fn params(limit: Option<i64>) -> StatsParams {
+        StatsParams {
+            from: None,
+            to: None,
+            interval: None,
+            limit,
+        }
+    }
+
+    #[test]
+    fn interval_defaults_to_week() {
+        assert_eq!(StatsInterval::Week, params(None).interval());
+    }
+
+    #[test]
+    fn limit_defaults_when_missing() {
+        assert_eq!(StatsParams::DEFAULT_LIMIT, params(None).limit().unwrap());
+    }
+
+    #[test]
+    fn limit_out_of_range_is_invalid() {
+        assert!(params(Some(0)).limit().is_err());
+        assert!(params(Some(StatsParams::MAX_LIMIT + 1)).limit().is_err());
+        assert!(params(Some(StatsParams::MAX_LIMIT)).limit().is_ok());
+    }
+
+    #[test]
+    fn boundary_dates_are_invalid() {
+        for to in [NaiveDate::MAX, NaiveDate::MIN] {
+            let params = StatsParams {
+                to: Some(to),
+                ..params(None)
+            };
+
+            assert!(params.period().is_err(), "to: {}", to);
+        }
+    }
+}
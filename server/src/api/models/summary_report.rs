@@ -0,0 +1,298 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ReviewOptions;
+use crate::api::utils::ReviewCriterion;
+use crate::{Error, Result};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SummaryReportParams {
+    // Dates are inclusive, in UTC
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl SummaryReportParams {
+    // Used when `from` is not provided
+    pub const DEFAULT_DAYS: i64 = 30;
+
+    // Returns the first and last day of the report. Defaults to the `DEFAULT_DAYS` days
+    // up to and including `to`, or today
+    pub fn period(&self) -> Result<(NaiveDate, NaiveDate)> {
+        date_period(self.from, self.to, Self::DEFAULT_DAYS)
+    }
+}
+
+// Dates outside this range are rejected, so the arithmetic on a period's dates can't
+// overflow
+const MIN_PERIOD_DATE: NaiveDate = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+const MAX_PERIOD_DATE: NaiveDate = NaiveDate::from_ymd_opt(9999, 12, 31).unwrap();
+
+// Resolves an inclusive period of days, where `from` defaults to `default_days` days up to
+// and including `to` (but not before `MIN_PERIOD_DATE`), and `to` defaults to today (UTC)
+pub fn date_period(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    default_days: i64,
+) -> Result<(NaiveDate, NaiveDate)> {
+    for (name, date) in [("from", from), ("to", to)] {
+        if date.is_some_and(|date| !(MIN_PERIOD_DATE..=MAX_PERIOD_DATE).contains(&date)) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'{}' must be between {} and {}",
+                name, MIN_PERIOD_DATE, MAX_PERIOD_DATE
+            )));
+        }
+    }
+
+    let to = to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = from.unwrap_or_else(|| {
+        to.checked_sub_signed(chrono::Duration::days(default_days - 1))
+            .map_or(MIN_PERIOD_DATE, |from| from.max(MIN_PERIOD_DATE))
+    });
+
+    if from > to {
+        return Err(Error::QueryParamValidationError(
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+
+    Ok((from, to))
+}
+
+// Bounds of the timestamps within an inclusive period of days, as `[start, end)`. The
+// period is one returned by `date_period`, so its last day is never `NaiveDate::MAX`
+pub fn period_bounds(from: NaiveDate, to: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = from.and_time(chrono::NaiveTime::MIN).and_utc();
+    let end = to
+        .checked_add_signed(chrono::Duration::days(1))
+        .unwrap_or(NaiveDate::MAX)
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc();
+
+    (start, end)
+}
+
+// How a review turned out, based on its answers. Most criteria pass when answered
+// `TRUE`, while local admin, cloud connections and security software pass when answered
+// `FALSE`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewOutcome {
+    // Every criterion passes
+    NoConcerns,
+    // No criterion fails, but at least one was answered `NOT_SURE`
+    Unsure,
+    // At least one criterion fails
+    Concerns,
+}
+
+impl ReviewOutcome {
+    pub const ALL: [ReviewOutcome; 3] = [
+        ReviewOutcome::NoConcerns,
+        ReviewOutcome::Unsure,
+        ReviewOutcome::Concerns,
+    ];
+
+    // `answers` are in the order of `ReviewCriterion::ALL`
+    pub fn from_answers(answers: [&ReviewOptions; 9]) -> Self {
+        let mut outcome = ReviewOutcome::NoConcerns;
+
+        for (criterion, answer) in ReviewCriterion::ALL.iter().zip(answers) {
+            match (answer, criterion.passing_answer()) {
+                (ReviewOptions::NOT_SURE, _) => outcome = ReviewOutcome::Unsure,
+                (ReviewOptions::TRUE, ReviewOptions::TRUE)
+                | (ReviewOptions::FALSE, ReviewOptions::FALSE) => (),
+                _ => return ReviewOutcome::Concerns,
+            }
+        }
+
+        outcome
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewOutcome::NoConcerns => "No concerns",
+            ReviewOutcome::Unsure => "Unsure",
+            ReviewOutcome::Concerns => "Concerns",
+        }
+    }
+}
+
+impl std::str::FromStr for ReviewOutcome {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "no_concerns" => Ok(ReviewOutcome::NoConcerns),
+            "unsure" => Ok(ReviewOutcome::Unsure),
+            "concerns" => Ok(ReviewOutcome::Concerns),
+            other => Err(Error::ServerError(std::sync::Arc::new(
+                format!("unknown review outcome: {}", other).into(),
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SummaryCount {
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OutcomeCount {
+    pub outcome: ReviewOutcome,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LocalAdminSoftware {
+    pub software_name: String,
+    pub software_version: String,
+    pub developer_name: String,
+    pub td_request_id: String,
+}
+
+// Days from a software request being made to its review being created. The statistics
+// are `None` when there are no reviews in the period
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TimeToReview {
+    pub average_days: Option<f64>,
+    pub median_days: Option<f64>,
+    pub longest_days: Option<f64>,
+    // Number of reviews within each range of days, in order
+    pub ranges: Vec<SummaryCount>,
+}
+
+// Aggregates of the reviews created between `from` and `to`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SummaryReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_reviews: i64,
+    pub exported_reviews: i64,
+    pub outcomes: Vec<OutcomeCount>,
+    pub top_vendors: Vec<SummaryCount>,
+    pub local_admin_software: Vec<LocalAdminSoftware>,
+    pub reviews_per_reviewer: Vec<SummaryCount>,
+    pub time_to_review: TimeToReview,
+}
+
+// Unit Tests
+#[cfg(test)]
+mod summary_report_params_tests {
+    use chrono::NaiveDate;
+
+    use super::SummaryReportParams;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn period_defaults_to_the_days_before_to() {
+        let params = SummaryReportParams {
+            from: None,
+            to: Some(date("2024-12-31")),
+        };
+
+        assert_eq!(
+            (date("2024-12-02"), date("2024-12-31")),
+            params.period().unwrap()
+        );
+    }
+
+    #[test]
+    fn a_single_day_is_a_valid_period() {
+        let params = SummaryReportParams {
+            from: Some(date("2024-12-31")),
+            to: Some(date("2024-12-31")),
+        };
+
+        assert!(params.period().is_ok());
+    }
+
+    #[test]
+    fn from_after_to_is_invalid() {
+        let params = SummaryReportParams {
+            from: Some(date("2025-01-01")),
+            to: Some(date("2024-12-31")),
+        };
+
+        assert!(params.period().is_err());
+    }
+
+    #[test]
+    fn dates_outside_the_supported_range_are_invalid() {
+        for (from, to) in [
+            (None, Some(NaiveDate::MAX)),
+            (None, Some(NaiveDate::MIN)),
+            (Some(NaiveDate::MIN), Some(date("2024-12-31"))),
+            (Some(date("1969-12-31")), Some(date("2024-12-31"))),
+            (Some(date("2024-12-31")), Some(date("+10000-01-01"))),
+        ] {
+            let params = SummaryReportParams { from, to };
+
+            assert!(params.period().is_err(), "from: {:?}, to: {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn default_from_does_not_go_before_the_supported_range() {
+        let params = SummaryReportParams {
+            from: None,
+            to: Some(date("1970-01-05")),
+        };
+
+        assert_eq!(
+            (date("1970-01-01"), date("1970-01-05")),
+            params.period().unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod review_outcome_tests {
+    use super::ReviewOutcome;
+    use crate::api::models::ReviewOptions;
+
+    const PASSING: [ReviewOptions; 9] = [
+        ReviewOptions::TRUE,
+        ReviewOptions::TRUE,
+        ReviewOptions::TRUE,
+        ReviewOptions::TRUE,
+        ReviewOptions::FALSE,
+        ReviewOptions::FALSE,
+        ReviewOptions::FALSE,
+        ReviewOptions::FALSE,
+        ReviewOptions::TRUE,
+    ];
+
+    fn outcome(answers: &[ReviewOptions; 9]) -> ReviewOutcome {
+        ReviewOutcome::from_answers(answers.each_ref())
+    }
+
+    #[test]
+    fn passing_answers_have_no_concerns() {
+        assert_eq!(ReviewOutcome::NoConcerns, outcome(&PASSING));
+    }
+
+    #[test]
+    fn not_sure_answer_is_unsure() {
+        let mut answers = PASSING;
+        answers[3] = ReviewOptions::NOT_SURE;
+
+        assert_eq!(ReviewOutcome::Unsure, outcome(&answers));
+    }
+
+    #[test]
+    fn failing_answer_is_a_concern_even_when_unsure() {
+        let mut answers = PASSING;
+        answers[0] = ReviewOptions::NOT_SURE;
+        // Requires local admin
+        answers[4] = ReviewOptions::TRUE;
+
+        assert_eq!(ReviewOutcome::Concerns, outcome(&answers));
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+// A ticket in the ticketing system, in the shape of the `software_request` of a new
+// software review so it can pre-fill one. Details the ticket doesn't have are left empty
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Ticket {
+    pub td_request_id: String,
+    pub software: TicketSoftware,
+    pub requester: TicketRequester,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct TicketSoftware {
+    pub software_name: String,
+    pub software_version: String,
+    pub developer_name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct TicketRequester {
+    pub name: String,
+    pub email: String,
+    pub department: String,
+}
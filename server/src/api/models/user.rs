@@ -1,9 +1,9 @@
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use unicode_segmentation::UnicodeSegmentation;
-use validator::ValidateEmail;
 
-use crate::{Error, Result};
+use crate::api::utils::validation;
+use crate::{FieldErrorCode, FieldErrors, Result};
 
 #[derive(Debug, Deserialize)]
 pub struct User {
@@ -58,77 +58,65 @@ impl From<&User> for UserDTO {
 
 impl User {
     pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_name(&self.name) {
-            return Err(Error::ValidationError(format!(
-                "user payload: '{}' is an invaild name for user",
-                &self.name
-            )));
+            errors.add(
+                "name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid name", &self.name),
+            );
         }
 
         if !Self::validate_email(&self.email) {
-            return Err(Error::ValidationError(format!(
-                "user payload: '{}' is an invaild email for user",
-                &self.email
-            )));
+            errors.add(
+                "email",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid email", &self.email),
+            );
         }
 
         if !Self::validate_password(self.password.expose_secret()) {
-            return Err(Error::ValidationError(
-                "user payload: invaild password provided for user".into(),
-            ));
+            errors.add(
+                "password",
+                FieldErrorCode::InvalidValue,
+                "password does not meet the password requirements",
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     // When handling partial updates on users, their password hash is
     // parsed, resulting in a failure because of `forbidden_chars` contained within it
     pub fn parse_without_password(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
         if !Self::validate_name(&self.name) {
-            return Err(Error::ValidationError(format!(
-                "user payload: '{}' is an invaild name for user",
-                &self.name
-            )));
+            errors.add(
+                "name",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid name", &self.name),
+            );
         }
 
         if !Self::validate_email(&self.email) {
-            return Err(Error::ValidationError(format!(
-                "user payload: '{}' is an invaild email for user",
-                &self.email
-            )));
+            errors.add(
+                "email",
+                FieldErrorCode::InvalidValue,
+                format!("'{}' is an invalid email", &self.email),
+            );
         }
 
-        Ok(())
+        errors.into_result()
     }
 
     fn validate_name(name: &str) -> bool {
-        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'];
-
-        let name_is_empty_or_whitespace = name.trim().is_empty();
-
-        // A grapheme is defined by the Unicode standard as a "user-perceived"
-        // character: `å` is a single grapheme, but it is composed of two characters
-        // (`a` and `̊`)
-        //
-        // `graphemes` returns an iterator over the graphemes in the input
-        // `true` specifies that we want to use the extended grapheme definition set
-        let name_too_long = name.graphemes(true).count() > 100;
-        let name_contains_forbidden_chars = name.chars().any(|c| forbidden_chars.contains(&c));
-
-        // Return false if any of the above conditions are met
-        !(name_is_empty_or_whitespace || name_too_long || name_contains_forbidden_chars)
+        validation::validate_text(name, 100)
     }
 
-    fn validate_email(email: &String) -> bool {
-        // Check if the email contains exactly one '@' symbol and has a domain
-        let split = email.split('@').collect::<Vec<&str>>();
-
-        if split.len() != 2 || split[1].is_empty() {
-            return false;
-        }
-
-        // `ValidateEmail` validates email based on HTML5 spec
-        ValidateEmail::validate_email(&email)
+    fn validate_email(email: &str) -> bool {
+        validation::validate_email(email)
     }
 
     pub fn validate_password(password: &str) -> bool {
@@ -186,13 +174,18 @@ mod name_tests {
     }
 
     #[test]
-    fn forbidden_characters_in_name_are_invalid() {
-        for chars in &['/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-'] {
-            let name = chars.to_string();
-            assert_eq!(User::validate_name(&name), false);
+    fn real_world_names_are_valid() {
+        for name in ["Conor O'Brien", "Mary-Jane Watson", "José Núñez"] {
+            assert!(User::validate_name(name));
         }
     }
 
+    #[test]
+    fn control_characters_in_name_are_invalid() {
+        let name = "John\u{1b}Doe".to_string();
+        assert!(!User::validate_name(&name));
+    }
+
     #[test]
     fn valid_name_is_accepted() {
         let name = "John".to_string();
@@ -235,7 +228,7 @@ mod email_tests {
 
     #[test]
     fn vaild_email_is_accepted() {
-        let email = SafeEmail().fake();
+        let email: String = SafeEmail().fake();
         assert_eq!(User::validate_email(&email), true)
     }
 }
@@ -0,0 +1,298 @@
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+
+use crate::api::utils::validation;
+use crate::{Error, FieldErrorCode, FieldErrors, Result};
+
+// Review lifecycle events a webhook can subscribe to
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "review.created")]
+    ReviewCreated,
+    #[serde(rename = "review.updated")]
+    ReviewUpdated,
+    #[serde(rename = "review.exported")]
+    ReviewExported,
+    #[serde(rename = "review.deleted")]
+    ReviewDeleted,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::ReviewCreated,
+        WebhookEvent::ReviewUpdated,
+        WebhookEvent::ReviewExported,
+        WebhookEvent::ReviewDeleted,
+    ];
+
+    // Also the value stored in `webhook.event_types`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::ReviewCreated => "review.created",
+            WebhookEvent::ReviewUpdated => "review.updated",
+            WebhookEvent::ReviewExported => "review.exported",
+            WebhookEvent::ReviewDeleted => "review.deleted",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEvent {
+    type Err = Error;
+
+    fn from_str(event: &str) -> Result<Self> {
+        WebhookEvent::ALL
+            .into_iter()
+            .find(|webhook_event| webhook_event.as_str() == event)
+            .ok_or_else(|| {
+                Error::ServerError(std::sync::Arc::new(
+                    format!("'{}' is not a webhook event", event).into(),
+                ))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Webhook {
+    pub id: Option<uuid::Uuid>,
+    pub url: String,
+    pub event_types: Vec<WebhookEvent>,
+    // Shared with the receiver to verify the signature of each payload
+    pub secret: SecretString,
+    #[serde(default = "Webhook::default_active")]
+    pub active: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub version: Option<i32>,
+}
+
+// Data Transfer Object (DTO) for Webhooks. The secret is never returned
+#[derive(Debug, Serialize)]
+pub struct WebhookDTO {
+    pub id: Option<uuid::Uuid>,
+    pub url: String,
+    pub event_types: Vec<WebhookEvent>,
+    pub active: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<&Webhook> for WebhookDTO {
+    fn from(webhook: &Webhook) -> Self {
+        WebhookDTO {
+            id: webhook.id,
+            url: webhook.url.clone(),
+            event_types: webhook.event_types.clone(),
+            active: webhook.active,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+impl Webhook {
+    pub const MAX_URL_LENGTH: usize = 2048;
+    pub const MIN_SECRET_LENGTH: usize = 16;
+    pub const MAX_SECRET_LENGTH: usize = 256;
+
+    pub fn parse(&self) -> Result<()> {
+        let mut errors = FieldErrors::new();
+
+        if !Self::validate_url(&self.url) {
+            errors.add(
+                "url",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid url, use an absolute http(s) url",
+                    &self.url
+                ),
+            );
+        }
+
+        if self.event_types.is_empty() {
+            errors.add(
+                "event_types",
+                FieldErrorCode::InvalidValue,
+                "at least one event type must be provided",
+            );
+        }
+
+        if !Self::validate_secret(self.secret.expose_secret()) {
+            errors.add(
+                "secret",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "secret must be between {} and {} characters, without whitespace",
+                    Self::MIN_SECRET_LENGTH,
+                    Self::MAX_SECRET_LENGTH
+                ),
+            );
+        }
+
+        errors.into_result()
+    }
+
+    fn default_active() -> bool {
+        true
+    }
+
+    fn validate_url(url: &str) -> bool {
+        if !validation::validate_text(url, Self::MAX_URL_LENGTH) {
+            return false;
+        }
+
+        reqwest::Url::parse(url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+    }
+
+    fn validate_secret(secret: &str) -> bool {
+        (Self::MIN_SECRET_LENGTH..=Self::MAX_SECRET_LENGTH).contains(&secret.chars().count())
+            && !secret.chars().any(char::is_whitespace)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "webhook_delivery_status")]
+#[allow(non_camel_case_types)]
+pub enum WebhookDeliveryStatus {
+    PENDING,
+    DELIVERED,
+    FAILED,
+}
+
+// An event queued for a webhook, with every attempt made to deliver it
+#[derive(Debug, Serialize)]
+pub struct WebhookDelivery {
+    pub id: uuid::Uuid,
+    pub webhook_id: uuid::Uuid,
+    pub event_type: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    // Only set while the delivery is pending
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub attempt_log: Vec<WebhookDeliveryAttempt>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryAttempt {
+    pub attempt: i32,
+    // Missing when no response was received, e.g. on a timeout
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub attempted_at: chrono::DateTime<chrono::Utc>,
+}
+
+// A delivery claimed by the delivery worker, along with where and how to send it
+#[derive(Debug)]
+pub struct PendingWebhookDelivery {
+    pub id: uuid::Uuid,
+    pub url: String,
+    pub secret: SecretString,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookDeliveryParams {
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<i64>,
+}
+
+impl WebhookDeliveryParams {
+    pub const DEFAULT_LIMIT: i64 = 50;
+    pub const MAX_LIMIT: i64 = 100;
+
+    pub fn limit(&self) -> Result<i64> {
+        let limit = self.limit.unwrap_or(Self::DEFAULT_LIMIT);
+
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(Error::QueryParamValidationError(format!(
+                "'limit' must be between 1 and {}",
+                Self::MAX_LIMIT
+            )));
+        }
+
+        Ok(limit)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod webhook_tests {
+    use secrecy::SecretString;
+
+    use super::{Webhook, WebhookEvent};
+
+    fn webhook(url: &str, event_types: Vec<WebhookEvent>, secret: &str) -> Webhook {
+        Webhook {
+            id: None,
+            url: url.to_string(),
+            event_types,
+            secret: SecretString::new(secret.into()),
+            active: true,
+            created_at: None,
+            updated_at: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn valid_webhook_is_accepted() {
+        let webhook = webhook(
+            "https://automation.example.edu/hooks/k6r",
+            vec![WebhookEvent::ReviewCreated],
+            "0123456789abcdef",
+        );
+
+        assert!(webhook.parse().is_ok());
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected() {
+        for url in [
+            "",
+            "not a url",
+            "ftp://example.edu/hook",
+            "/hooks/k6r",
+            "https://",
+        ] {
+            let webhook = webhook(url, vec![WebhookEvent::ReviewCreated], "0123456789abcdef");
+
+            assert!(webhook.parse().is_err(), "'{}' should be invalid", url);
+        }
+    }
+
+    #[test]
+    fn missing_event_types_are_rejected() {
+        let webhook = webhook("https://example.edu/hook", vec![], "0123456789abcdef");
+
+        assert!(webhook.parse().is_err());
+    }
+
+    #[test]
+    fn short_or_blank_secrets_are_rejected() {
+        for secret in ["short", "0123456789 abcdef", ""] {
+            let webhook = webhook(
+                "https://example.edu/hook",
+                vec![WebhookEvent::ReviewCreated],
+                secret,
+            );
+
+            assert!(webhook.parse().is_err(), "'{}' should be invalid", secret);
+        }
+    }
+
+    #[test]
+    fn event_names_round_trip() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(event, event.as_str().parse::<WebhookEvent>().unwrap());
+        }
+
+        assert!("review.approved".parse::<WebhookEvent>().is_err());
+    }
+}
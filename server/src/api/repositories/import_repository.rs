@@ -0,0 +1,171 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use super::software_review_repository::map_insert_error;
+use crate::api::models::{ReviewOptions, SoftwareReviewPayload};
+use crate::Result;
+
+// Every query takes a connection rather than the pool, so an import runs in a single
+// transaction and can be rolled back as a whole (e.g. for a dry run)
+
+// Software is matched by name, ignoring case, so `slack` and `Slack` are not imported
+// as separate software
+#[tracing::instrument(name = "fetching software by name from database", skip(conn))]
+pub async fn fetch_software_id_by_name(
+    software_name: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<Uuid>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id
+        FROM software
+        WHERE lower(software_name) = lower($1)
+        "#,
+        software_name
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.id))
+}
+
+#[tracing::instrument(name = "fetching requester by email from database", skip(conn))]
+pub async fn fetch_requester_id_by_email(
+    email: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<Uuid>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id
+        FROM requester
+        WHERE lower(email) = lower($1)
+        "#,
+        email
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.id))
+}
+
+#[tracing::instrument(name = "fetching user by email from database", skip(conn))]
+pub async fn fetch_user_id_by_email(email: &str, conn: &mut PgConnection) -> Result<Option<Uuid>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id
+        FROM user_account
+        WHERE lower(email) = lower($1)
+        "#,
+        email
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.id))
+}
+
+// Returns the id of the software request for the ticket, and whether it has already been
+// reviewed
+#[tracing::instrument(name = "fetching software request by ticket from database", skip(conn))]
+pub async fn fetch_software_request_by_ticket(
+    td_request_id: &str,
+    conn: &mut PgConnection,
+) -> Result<Option<(Uuid, bool)>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT r.id, EXISTS (
+            SELECT 1 FROM software_review sr WHERE sr.software_request_id = r.id
+        ) AS "reviewed!"
+        FROM software_request r
+        WHERE r.td_request_id = $1
+        "#,
+        td_request_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| (row.id, row.reviewed)))
+}
+
+// Returns the ticket of the software's request. Software can only be requested once
+#[tracing::instrument(
+    name = "fetching software request by software from database",
+    skip(conn)
+)]
+pub async fn fetch_software_request_ticket_by_software_id(
+    software_id: Uuid,
+    conn: &mut PgConnection,
+) -> Result<Option<String>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT td_request_id
+        FROM software_request
+        WHERE software_id = $1
+        "#,
+        software_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| row.td_request_id))
+}
+
+// Unlike `insert_software_review`, the export status and review date are kept from the
+// imported record. `reviewed_at` defaults to now, and the review expires `validity_days`
+// after it
+#[tracing::instrument(
+    name = "importing software review into database",
+    skip(payload, validity_days, conn)
+)]
+pub async fn insert_import_software_review(
+    payload: &SoftwareReviewPayload,
+    software_request_id: Uuid,
+    reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    validity_days: i32,
+    conn: &mut PgConnection,
+) -> Result<Uuid> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO software_review (
+            software_request_id, reviewer_id,
+            is_supported, is_current_version, is_reputation_good,
+            is_installation_from_developer, is_local_admin_required,
+            is_connected_to_brockport_cloud, is_connected_to_cloud_services_or_client,
+            is_security_or_optimization_software, is_supported_by_current_os, review_notes,
+            exported, reviewed_version, created_at, updated_at, expires_at
+        )
+        SELECT
+            $1, $2,
+            $3, $4, $5,
+            $6, $7,
+            $8, $9,
+            $10, $11, $12,
+            $13, s.software_version, COALESCE($14, NOW()), COALESCE($14, NOW()),
+            COALESCE($14, NOW()) + make_interval(days => $15)
+        FROM software_request r
+        INNER JOIN software s ON r.software_id = s.id
+        WHERE r.id = $1
+        RETURNING id
+        "#,
+        software_request_id,
+        payload.reviewer_id,
+        payload.is_supported.clone() as ReviewOptions,
+        payload.is_current_version.clone() as ReviewOptions,
+        payload.is_reputation_good.clone() as ReviewOptions,
+        payload.is_installation_from_developer.clone() as ReviewOptions,
+        payload.is_local_admin_required.clone() as ReviewOptions,
+        payload.is_connected_to_brockport_cloud.clone() as ReviewOptions,
+        payload.is_connected_to_cloud_services_or_client.clone() as ReviewOptions,
+        payload.is_security_or_optimization_software.clone() as ReviewOptions,
+        payload.is_supported_by_current_os.clone() as ReviewOptions,
+        payload.review_notes,
+        payload.exported.unwrap_or(false),
+        reviewed_at,
+        validity_days
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(map_insert_error)?;
+
+    Ok(row.id)
+}
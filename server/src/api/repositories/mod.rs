@@ -1,29 +1,65 @@
 mod auth_repository;
+mod import_repository;
 mod requester_repository;
+mod review_comment_repository;
+mod search_repository;
 mod software_repository;
 mod software_request_repository;
 mod software_review_repository;
+mod stats_repository;
+mod summary_report_repository;
 mod user_repository;
 mod user_token_repository;
+mod webhook_repository;
 
 pub use auth_repository::{fetch_credentials_by_email, fetch_credentials_by_user_id};
+pub use import_repository::{
+    fetch_requester_id_by_email, fetch_software_id_by_name, fetch_software_request_by_ticket,
+    fetch_software_request_ticket_by_software_id, fetch_user_id_by_email,
+    insert_import_software_review,
+};
 pub use requester_repository::{
-    delete_requester, fetch_all_requesters, fetch_requester_by_id, insert_requester,
+    delete_requester, fetch_all_requesters, fetch_requester_by_id,
+    fetch_requesters_outside_email_domains, insert_requester, stream_all_requesters,
     update_requester,
 };
+pub use review_comment_repository::{
+    delete_review_comment, fetch_comment_mentions, fetch_review_comment_by_id,
+    fetch_review_comments, insert_review_comment, insert_review_comment_mentions,
+    update_review_comment,
+};
+pub use search_repository::search_all;
 pub use software_repository::{
-    delete_software, fetch_all_software, fetch_software_by_id, insert_software, update_software,
+    delete_software, fetch_all_software, fetch_similar_software, fetch_software_by_id,
+    insert_software, stream_all_software, update_software,
 };
 pub use software_request_repository::{
     delete_software_request, fetch_all_software_requests, fetch_software_request_by_id,
-    insert_software_request, update_software_request,
+    fetch_unreviewed_software_requests, insert_software_request, stream_all_software_requests,
+    update_software_request, update_software_request_assignee,
 };
 pub use software_review_repository::{
     delete_software_review, fetch_all_software_reviews, fetch_software_review_by_id,
-    fetch_software_review_details, insert_software_review, update_software_review,
+    fetch_software_review_details, flag_expiring_software_reviews, insert_review_requester,
+    insert_review_software, insert_review_software_request, insert_software_review,
+    reopen_expired_software_requests, stream_all_software_reviews, update_software_review,
     update_software_review_exported,
 };
+pub use stats_repository::{
+    fetch_review_answers, fetch_reviewer_workload, fetch_reviews_per_period, fetch_top_departments,
+    fetch_unreviewed_request_totals,
+};
+pub use summary_report_repository::{
+    fetch_local_admin_software, fetch_review_outcomes, fetch_review_totals,
+    fetch_reviews_per_reviewer, fetch_time_to_review, fetch_top_vendors,
+};
 pub use user_repository::{
-    delete_user, fetch_all_users, fetch_user_by_id, insert_user, update_user, update_user_password,
+    delete_user, fetch_all_users, fetch_user_by_id, fetch_users_outside_email_domains, insert_user,
+    update_user, update_user_password,
 };
 pub use user_token_repository::{fetch_valid_tokens, insert_user_token, update_user_token};
+pub use webhook_repository::{
+    claim_webhook_deliveries, delete_webhook, fetch_all_webhooks, fetch_webhook_by_id,
+    fetch_webhook_deliveries, insert_webhook, insert_webhook_deliveries,
+    insert_webhook_delivery_attempt, update_webhook,
+};
@@ -1,4 +1,6 @@
+use futures_util::TryStreamExt;
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::models::{Requester, RequesterDTO};
@@ -7,6 +9,8 @@ use crate::{Error, Result};
 
 #[derive(Debug, sqlx::FromRow)]
 struct RequesterRecordCount {
+    // Only selected for a page of records
+    #[sqlx(default)]
     count: i64,
     id: Uuid,
     name: String,
@@ -15,6 +19,18 @@ struct RequesterRecordCount {
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl From<RequesterRecordCount> for RequesterDTO {
+    fn from(record: RequesterRecordCount) -> Self {
+        RequesterDTO {
+            id: Some(record.id),
+            name: record.name,
+            email: record.email,
+            department: record.department,
+            created_at: Some(record.created_at),
+        }
+    }
+}
+
 #[tracing::instrument(
     name = "fetching all requesters from database",
     skip(
@@ -39,28 +55,12 @@ pub async fn fetch_all_requesters(
     let limit = per_page;
     let offset = (page - 1) * per_page;
 
-    let query = if let (Some(field), Some(_)) = (filter_field.as_ref(), filter_value.as_ref()) {
-        format!(
-            r#"
-            SELECT count(*) OVER(), id, name, email, department, created_at
-            FROM requester
-            WHERE (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))
-            ORDER BY {} {}, id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            field, sort_column, sort_direction, limit, offset
-        )
-    } else {
-        format!(
-            r#"
-            SELECT count(*) OVER(), id, name, email, department, created_at
-            FROM requester
-            ORDER BY {} {}, id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            sort_column, sort_direction, limit, offset
-        )
-    };
+    let query = all_requesters_query(
+        &sort_column,
+        &sort_direction,
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        Some((limit, offset)),
+    );
 
     let query = sqlx::query_as::<_, RequesterRecordCount>(&query);
 
@@ -75,22 +75,92 @@ pub async fn fetch_all_requesters(
 
     let total_records = records.first().map_or(0, |record| record.count);
 
-    let requester_records: Vec<RequesterDTO> = records
-        .into_iter()
-        .map(|record| RequesterDTO {
-            id: Some(record.id),
-            name: record.name,
-            email: record.email,
-            department: record.department,
-            created_at: Some(record.created_at),
-        })
-        .collect();
+    let requester_records: Vec<RequesterDTO> =
+        records.into_iter().map(RequesterDTO::from).collect();
 
     let metadata = Metadata::calculate_metadata(total_records, page, per_page);
 
     Ok((requester_records, metadata))
 }
 
+// Sends every requester matching the filter to `rows` as it is read, for exports
+#[tracing::instrument(
+    name = "streaming all requesters from database",
+    skip(sort_column, sort_direction, filter_field, filter_value, db_pool, rows)
+)]
+pub async fn stream_all_requesters(
+    sort_column: String,
+    sort_direction: String,
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    db_pool: &PgPool,
+    rows: mpsc::Sender<Result<RequesterDTO>>,
+) -> Result<()> {
+    let query = all_requesters_query(
+        &sort_column,
+        &sort_direction,
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        None,
+    );
+
+    let query = sqlx::query_as::<_, RequesterRecordCount>(&query);
+
+    // Bind the user-supplied value only if it exists
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let mut records = query.fetch(db_pool);
+
+    while let Some(record) = records.try_next().await? {
+        // The client has gone away, so there is no need to read the rest
+        if rows.send(Ok(record.into())).await.is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// `pagination` is the `(limit, offset)` of a page. Without it every matching record is
+// selected, and the total count is not needed
+fn all_requesters_query(
+    sort_column: &str,
+    sort_direction: &str,
+    filter_field: Option<&str>,
+    pagination: Option<(usize, usize)>,
+) -> String {
+    let count = if pagination.is_some() {
+        "count(*) OVER(), "
+    } else {
+        ""
+    };
+
+    let filter = filter_field.map_or(String::new(), |field| {
+        format!(
+            "WHERE (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))",
+            field
+        )
+    });
+
+    let limit = pagination.map_or(String::new(), |(limit, offset)| {
+        format!("LIMIT {} OFFSET {}", limit, offset)
+    });
+
+    format!(
+        r#"
+        SELECT {}id, name, email, department, created_at
+        FROM requester
+        {}
+        ORDER BY {} {}, id ASC
+        {}
+        "#,
+        count, filter, sort_column, sort_direction, limit
+    )
+}
+
 #[tracing::instrument(
     name = "fetching requester by id from database",
     skip(requester_id, db_pool)
@@ -122,6 +192,44 @@ pub async fn fetch_requester_by_id(requester_id: Uuid, db_pool: &PgPool) -> Resu
     }
 }
 
+#[tracing::instrument(
+    name = "fetching requesters outside email domains from database",
+    skip(allowed_domains, db_pool)
+)]
+pub async fn fetch_requesters_outside_email_domains(
+    allowed_domains: &[String],
+    db_pool: &PgPool,
+) -> Result<Vec<RequesterDTO>> {
+    let allowed_domains: Vec<String> = allowed_domains
+        .iter()
+        .map(|domain| domain.to_lowercase())
+        .collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, name, email, department, created_at
+        FROM requester
+        WHERE lower(split_part(email, '@', 2)) <> ALL($1)
+        ORDER BY email ASC
+        "#,
+        &allowed_domains
+    )
+    .fetch_all(db_pool)
+    .await
+    .map_err(Error::from)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RequesterDTO {
+            id: Some(row.id),
+            name: row.name,
+            email: row.email,
+            department: row.department,
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
 #[tracing::instrument(name = "inserting requester into database", skip(payload, db_pool))]
 pub async fn insert_requester(payload: &Requester, db_pool: &PgPool) -> Result<Uuid> {
     match sqlx::query!(
@@ -0,0 +1,240 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::models::{CommentAuthor, CommentMention, ReviewComment, ReviewCommentDTO};
+use crate::{Error, Result};
+
+// Comments on the review that haven't been deleted, oldest first
+#[tracing::instrument(
+    name = "fetching review comments from database",
+    skip(review_id, db_pool)
+)]
+pub async fn fetch_review_comments(
+    review_id: Uuid,
+    db_pool: &PgPool,
+) -> Result<Vec<ReviewCommentDTO>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            c.id, c.software_review_id, c.body, c.edited_at, c.created_at,
+            u.id AS author_id, u.name AS author_name, u.email AS author_email
+        FROM review_comment c
+        INNER JOIN user_account u ON c.author_id = u.id
+        WHERE c.software_review_id = $1 AND c.deleted_at IS NULL
+        ORDER BY c.created_at ASC, c.id ASC
+        "#,
+        review_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ReviewCommentDTO {
+            id: row.id,
+            software_review_id: row.software_review_id,
+            author: CommentAuthor {
+                id: row.author_id,
+                name: row.author_name,
+                email: row.author_email,
+            },
+            body: row.body,
+            edited_at: row.edited_at,
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
+// A deleted comment, or one on another review, is not found
+#[tracing::instrument(
+    name = "fetching review comment by id from database",
+    skip(review_id, comment_id, db_pool)
+)]
+pub async fn fetch_review_comment_by_id(
+    review_id: Uuid,
+    comment_id: Uuid,
+    db_pool: &PgPool,
+) -> Result<ReviewComment> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, software_review_id, author_id, body, edited_at, created_at, version
+        FROM review_comment
+        WHERE id = $1 AND software_review_id = $2 AND deleted_at IS NULL
+        "#,
+        comment_id,
+        review_id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    match row {
+        Some(row) => Ok(ReviewComment {
+            id: row.id,
+            software_review_id: row.software_review_id,
+            author_id: row.author_id,
+            body: row.body,
+            edited_at: row.edited_at,
+            created_at: row.created_at,
+            version: row.version.unwrap_or_default(),
+        }),
+        None => Err(Error::PgNotFoundError),
+    }
+}
+
+#[tracing::instrument(
+    name = "inserting review comment into database",
+    skip(review_id, author_id, body, db_pool)
+)]
+pub async fn insert_review_comment(
+    review_id: Uuid,
+    author_id: Uuid,
+    body: &str,
+    db_pool: &PgPool,
+) -> Result<Uuid> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO review_comment (software_review_id, author_id, body)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        review_id,
+        author_id,
+        body
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok(row.id)
+}
+
+#[tracing::instrument(name = "updating review comment in database", skip(comment, db_pool))]
+pub async fn update_review_comment(comment: ReviewComment, db_pool: &PgPool) -> Result<()> {
+    match sqlx::query!(
+        r#"
+        UPDATE review_comment
+        SET body = $1, edited_at = NOW(), version = version + 1
+        WHERE id = $2 AND version = $3 AND deleted_at IS NULL
+        RETURNING version
+        "#,
+        comment.body,
+        comment.id,
+        comment.version
+    )
+    .fetch_optional(db_pool)
+    .await
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Error::PgNotFoundError),
+        Err(err) => Err(Error::from(err)),
+    }
+}
+
+// The comment is kept, but is no longer listed or exported
+#[tracing::instrument(
+    name = "deleting review comment from database",
+    skip(comment_id, db_pool)
+)]
+pub async fn delete_review_comment(comment_id: Uuid, db_pool: &PgPool) -> Result<()> {
+    match sqlx::query!(
+        r#"
+        UPDATE review_comment
+        SET deleted_at = NOW(), version = version + 1
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id
+        "#,
+        comment_id
+    )
+    .fetch_optional(db_pool)
+    .await
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Error::PgNotFoundError),
+        Err(err) => Err(Error::from(err)),
+    }
+}
+
+// Records the users with one of `emails` as mentioned in the comment, leaving out its
+// author. Returns the users who hadn't already been mentioned in it
+#[tracing::instrument(
+    name = "inserting review comment mentions into database",
+    skip(comment_id, author_id, emails, db_pool)
+)]
+pub async fn insert_review_comment_mentions(
+    comment_id: Uuid,
+    author_id: Uuid,
+    emails: &[String],
+    db_pool: &PgPool,
+) -> Result<Vec<Uuid>> {
+    let rows = sqlx::query!(
+        r#"
+        INSERT INTO review_comment_mention (comment_id, user_id)
+        SELECT $1, id
+        FROM user_account
+        WHERE LOWER(email) = ANY($2) AND id <> $3
+        ON CONFLICT DO NOTHING
+        RETURNING user_id
+        "#,
+        comment_id,
+        emails,
+        author_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.user_id).collect())
+}
+
+// The most recent comments mentioning the user, newest first. Deleted comments are left
+// out
+#[tracing::instrument(
+    name = "fetching comment mentions from database",
+    skip(user_id, limit, db_pool)
+)]
+pub async fn fetch_comment_mentions(
+    user_id: Uuid,
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<CommentMention>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            c.id, c.software_review_id, c.body, c.edited_at, c.created_at,
+            u.id AS author_id, u.name AS author_name, u.email AS author_email,
+            s.software_name, r.td_request_id, m.created_at AS mentioned_at
+        FROM review_comment_mention m
+        INNER JOIN review_comment c ON m.comment_id = c.id
+        INNER JOIN user_account u ON c.author_id = u.id
+        INNER JOIN software_review rv ON c.software_review_id = rv.id
+        INNER JOIN software_request r ON rv.software_request_id = r.id
+        INNER JOIN software s ON r.software_id = s.id
+        WHERE m.user_id = $1 AND c.deleted_at IS NULL
+        ORDER BY m.created_at DESC, c.id DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CommentMention {
+            comment: ReviewCommentDTO {
+                id: row.id,
+                software_review_id: row.software_review_id,
+                author: CommentAuthor {
+                    id: row.author_id,
+                    name: row.author_name,
+                    email: row.author_email,
+                },
+                body: row.body,
+                edited_at: row.edited_at,
+                created_at: row.created_at,
+            },
+            software_name: row.software_name,
+            td_request_id: row.td_request_id,
+            mentioned_at: row.mentioned_at,
+        })
+        .collect())
+}
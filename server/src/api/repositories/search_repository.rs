@@ -0,0 +1,136 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::models::{SearchHit, SearchHitType};
+use crate::api::utils::Metadata;
+use crate::{Error, Result};
+
+#[derive(Debug, sqlx::FromRow)]
+struct SearchHitRecordCount {
+    count: i64,
+    hit_type: String,
+    id: Uuid,
+    title: String,
+    highlight: String,
+    rank: f32,
+}
+
+#[tracing::instrument(
+    name = "searching all entities in database",
+    skip(search_term, page, per_page, db_pool)
+)]
+pub async fn search_all(
+    search_term: String,
+    page: usize,
+    per_page: usize,
+    db_pool: &PgPool,
+) -> Result<(Vec<SearchHit>, Metadata)> {
+    let limit = per_page;
+    let offset = (page - 1) * per_page;
+
+    // Each branch matches against the generated `search_vector` column of its table.
+    // Reviews also match on the reviewed software so searching for a software name
+    // surfaces any existing reviews of it
+    let query = format!(
+        r#"
+        WITH search AS (
+            SELECT websearch_to_tsquery('simple', $1) AS query
+        )
+        SELECT count(*) OVER() AS count, hit.*
+        FROM (
+            SELECT
+                'software' AS hit_type,
+                s.id,
+                s.software_name AS title,
+                ts_headline(
+                    'simple',
+                    html_escape(concat_ws(' | ', s.software_name, s.developer_name, s.description)),
+                    search.query,
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2'
+                ) AS highlight,
+                ts_rank(s.search_vector, search.query) AS rank
+            FROM software s, search
+            WHERE s.search_vector @@ search.query
+
+            UNION ALL
+
+            SELECT
+                'requester' AS hit_type,
+                rq.id,
+                rq.name AS title,
+                ts_headline(
+                    'simple',
+                    html_escape(concat_ws(' | ', rq.name, rq.email, rq.department)),
+                    search.query,
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2'
+                ) AS highlight,
+                ts_rank(rq.search_vector, search.query) AS rank
+            FROM requester rq, search
+            WHERE rq.search_vector @@ search.query
+
+            UNION ALL
+
+            SELECT
+                'software_request' AS hit_type,
+                r.id,
+                r.td_request_id AS title,
+                ts_headline(
+                    'simple',
+                    html_escape(concat_ws(' | ', r.td_request_id, s.software_name)),
+                    search.query,
+                    'StartSel=<mark>, StopSel=</mark>'
+                ) AS highlight,
+                ts_rank(r.search_vector, search.query) AS rank
+            FROM software_request r
+            INNER JOIN software s ON r.software_id = s.id, search
+            WHERE r.search_vector @@ search.query
+
+            UNION ALL
+
+            SELECT
+                'software_review' AS hit_type,
+                sr.id,
+                s.software_name AS title,
+                ts_headline(
+                    'simple',
+                    html_escape(concat_ws(' | ', s.software_name, sr.review_notes)),
+                    search.query,
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2'
+                ) AS highlight,
+                ts_rank(sr.search_vector || s.search_vector, search.query) AS rank
+            FROM software_review sr
+            INNER JOIN software_request r ON sr.software_request_id = r.id
+            INNER JOIN software s ON r.software_id = s.id, search
+            WHERE sr.search_vector @@ search.query OR s.search_vector @@ search.query
+        ) hit
+        ORDER BY hit.rank DESC, hit.hit_type ASC, hit.id ASC
+        LIMIT {} OFFSET {}
+        "#,
+        limit, offset
+    );
+
+    let records = sqlx::query_as::<_, SearchHitRecordCount>(&query)
+        .bind(search_term)
+        .fetch_all(db_pool)
+        .await
+        .map_err(Error::from)?;
+
+    let total_records = records.first().map_or(0, |record| record.count);
+
+    let search_hits = records
+        .into_iter()
+        .map(|record| {
+            Ok(SearchHit {
+                hit_type: record.hit_type.parse::<SearchHitType>()?,
+                id: record.id,
+                title: record.title,
+                highlight: record.highlight,
+                rank: record.rank,
+            })
+        })
+        .collect::<Result<Vec<SearchHit>>>()?;
+
+    let metadata = Metadata::calculate_metadata(total_records, page, per_page);
+
+    Ok((search_hits, metadata))
+}
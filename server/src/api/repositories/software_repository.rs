@@ -1,12 +1,16 @@
+use futures_util::TryStreamExt;
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use crate::api::models::{Software, SoftwareDTO};
+use crate::api::models::{SimilarSoftware, Software, SoftwareDTO, SoftwareVersion};
 use crate::api::utils::Metadata;
 use crate::{Error, Result};
 
 #[derive(Debug, sqlx::FromRow)]
 struct SoftwareRecordCount {
+    // Only selected for a page of records
+    #[sqlx(default)]
     count: i64,
     id: Uuid,
     software_name: String,
@@ -16,6 +20,19 @@ struct SoftwareRecordCount {
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl From<SoftwareRecordCount> for SoftwareDTO {
+    fn from(record: SoftwareRecordCount) -> Self {
+        SoftwareDTO {
+            id: Some(record.id),
+            software_name: record.software_name,
+            software_version: record.software_version,
+            developer_name: record.developer_name,
+            description: record.description,
+            created_at: Some(record.created_at),
+        }
+    }
+}
+
 #[tracing::instrument(
     name = "fetching all software from database",
     skip(
@@ -40,28 +57,12 @@ pub async fn fetch_all_software(
     let limit = per_page;
     let offset = (page - 1) * per_page;
 
-    let query = if let (Some(field), Some(_)) = (filter_field.as_ref(), filter_value.as_ref()) {
-        format!(
-            r#"
-            SELECT count(*) OVER(), id, software_name, software_version, developer_name, description, created_at
-            FROM software
-            WHERE (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))
-            ORDER BY {} {}, id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            field, sort_column, sort_direction, limit, offset
-        )
-    } else {
-        format!(
-            r#"
-            SELECT count(*) OVER(), id, software_name, software_version, developer_name, description, created_at
-            FROM software
-            ORDER BY {} {}, id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            sort_column, sort_direction, limit, offset
-        )
-    };
+    let query = all_software_query(
+        &sort_column,
+        &sort_direction,
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        Some((limit, offset)),
+    );
 
     let query = sqlx::query_as::<_, SoftwareRecordCount>(&query);
 
@@ -76,23 +77,91 @@ pub async fn fetch_all_software(
 
     let total_records = records.first().map_or(0, |record| record.count);
 
-    let software_records: Vec<SoftwareDTO> = records
-        .into_iter()
-        .map(|record| SoftwareDTO {
-            id: Some(record.id),
-            software_name: record.software_name,
-            software_version: record.software_version,
-            developer_name: record.developer_name,
-            description: record.description,
-            created_at: Some(record.created_at),
-        })
-        .collect();
+    let software_records: Vec<SoftwareDTO> = records.into_iter().map(SoftwareDTO::from).collect();
 
     let metadata = Metadata::calculate_metadata(total_records, page, per_page);
 
     Ok((software_records, metadata))
 }
 
+// Sends every software record matching the filter to `rows` as it is read, for exports
+#[tracing::instrument(
+    name = "streaming all software from database",
+    skip(sort_column, sort_direction, filter_field, filter_value, db_pool, rows)
+)]
+pub async fn stream_all_software(
+    sort_column: String,
+    sort_direction: String,
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    db_pool: &PgPool,
+    rows: mpsc::Sender<Result<SoftwareDTO>>,
+) -> Result<()> {
+    let query = all_software_query(
+        &sort_column,
+        &sort_direction,
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        None,
+    );
+
+    let query = sqlx::query_as::<_, SoftwareRecordCount>(&query);
+
+    // Bind the user-supplied value only if it exists
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let mut records = query.fetch(db_pool);
+
+    while let Some(record) = records.try_next().await? {
+        // The client has gone away, so there is no need to read the rest
+        if rows.send(Ok(record.into())).await.is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// `pagination` is the `(limit, offset)` of a page. Without it every matching record is
+// selected, and the total count is not needed
+fn all_software_query(
+    sort_column: &str,
+    sort_direction: &str,
+    filter_field: Option<&str>,
+    pagination: Option<(usize, usize)>,
+) -> String {
+    let count = if pagination.is_some() {
+        "count(*) OVER(), "
+    } else {
+        ""
+    };
+
+    let filter = filter_field.map_or(String::new(), |field| {
+        format!(
+            "WHERE (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))",
+            field
+        )
+    });
+
+    let limit = pagination.map_or(String::new(), |(limit, offset)| {
+        format!("LIMIT {} OFFSET {}", limit, offset)
+    });
+
+    format!(
+        r#"
+        SELECT {}id, software_name, software_version, developer_name, description, created_at
+        FROM software
+        {}
+        ORDER BY {} {}, id ASC
+        {}
+        "#,
+        count, filter, sort_column, sort_direction, limit
+    )
+}
+
 #[tracing::instrument(
     name = "fetching software by id from database",
     skip(software_id, db_pool)
@@ -125,16 +194,70 @@ pub async fn fetch_software_by_id(software_id: Uuid, db_pool: &PgPool) -> Result
     }
 }
 
+#[tracing::instrument(
+    name = "fetching similar software from database",
+    skip(software_name, limit, db_pool)
+)]
+pub async fn fetch_similar_software(
+    software_name: &str,
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<SimilarSoftware>> {
+    // `%` matches on whole-string trigram similarity ("Zom" vs "Zoom") while `<%` matches
+    // when the provided name is similar to any part of an existing name ("Zoom" vs "Zoom Client")
+    let rows = sqlx::query!(
+        r#"
+        SELECT 
+            id, software_name, software_version, developer_name, description, created_at,
+            GREATEST(
+                similarity(software_name, $1),
+                word_similarity($1, software_name),
+                word_similarity(software_name, $1)
+            ) AS "similarity!"
+        FROM software
+        WHERE software_name % $1 OR $1 <% software_name OR software_name <% $1
+        ORDER BY "similarity!" DESC, software_name ASC
+        LIMIT $2
+        "#,
+        software_name,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await
+    .map_err(Error::from)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SimilarSoftware {
+            software: SoftwareDTO {
+                id: Some(row.id),
+                software_name: row.software_name,
+                software_version: row.software_version,
+                developer_name: row.developer_name,
+                description: row.description,
+                created_at: row.created_at,
+            },
+            similarity: row.similarity,
+        })
+        .collect())
+}
+
 #[tracing::instrument(name = "inserting software into database", skip(payload, db_pool))]
 pub async fn insert_software(payload: &Software, db_pool: &PgPool) -> Result<Uuid> {
+    let software_version_key = payload
+        .software_version
+        .parse::<SoftwareVersion>()?
+        .sort_key();
+
     match sqlx::query!(
         r#"
-        INSERT INTO software (software_name, software_version, developer_name, description)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO software (software_name, software_version, software_version_key, developer_name, description)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING id
         "#,
         payload.software_name,
         payload.software_version,
+        software_version_key,
         payload.developer_name,
         payload.description,
     )
@@ -181,15 +304,21 @@ pub async fn update_software(
     software_id: Uuid,
     db_pool: &PgPool,
 ) -> Result<()> {
+    let software_version_key = software
+        .software_version
+        .parse::<SoftwareVersion>()?
+        .sort_key();
+
     match sqlx::query!(
         r#"
         UPDATE software
-        SET software_name = $1, software_version = $2, developer_name = $3, description = $4, version = version + 1
-        WHERE id = $5 AND version = $6
+        SET software_name = $1, software_version = $2, software_version_key = $3, developer_name = $4, description = $5, version = version + 1
+        WHERE id = $6 AND version = $7
         RETURNING version
     "#,
         software.software_name,
         software.software_version,
+        software_version_key,
         software.developer_name,
         software.description,
         software_id,
@@ -1,12 +1,19 @@
+use futures_util::TryStreamExt;
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::utils::Metadata;
-use crate::api::{RequesterDTO, SoftwareDTO, SoftwareRequest, SoftwareRequestDTO};
+use crate::api::{
+    QueuedSoftwareRequest, RequestAssignee, RequesterDTO, SoftwareDTO, SoftwareRequest,
+    SoftwareRequestDTO,
+};
 use crate::{Error, Result};
 
 #[derive(Debug, sqlx::FromRow)]
 struct SoftwareRequestRecordCount {
+    // Only selected for a page of records
+    #[sqlx(default)]
     count: i64,
     id: Uuid,
     td_request_id: String,
@@ -24,6 +31,63 @@ struct SoftwareRequestRecordCount {
     requester_created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl From<SoftwareRequestRecordCount> for SoftwareRequestDTO {
+    fn from(record: SoftwareRequestRecordCount) -> Self {
+        SoftwareRequestDTO {
+            id: Some(record.id),
+            td_request_id: record.td_request_id,
+            software: SoftwareDTO {
+                id: Some(record.software_id),
+                software_name: record.software_name,
+                software_version: record.software_version,
+                developer_name: record.developer_name,
+                description: record.description,
+                created_at: Some(record.software_created_at),
+            },
+            requester: RequesterDTO {
+                id: Some(record.requester_id),
+                name: record.requester_name,
+                email: record.requester_email,
+                department: record.requester_department,
+                created_at: Some(record.requester_created_at),
+            },
+            created_at: Some(record.created_at),
+        }
+    }
+}
+
+// A software request without a review, along with who it is assigned to
+#[derive(Debug, sqlx::FromRow)]
+struct QueuedSoftwareRequestRecord {
+    #[sqlx(flatten)]
+    software_request: SoftwareRequestRecordCount,
+    assignee_id: Option<Uuid>,
+    assignee_name: Option<String>,
+    assignee_email: Option<String>,
+    reopened_at: Option<chrono::DateTime<chrono::Utc>>,
+    age_days: i64,
+}
+
+impl From<QueuedSoftwareRequestRecord> for QueuedSoftwareRequest {
+    fn from(record: QueuedSoftwareRequestRecord) -> Self {
+        let assignee = match (
+            record.assignee_id,
+            record.assignee_name,
+            record.assignee_email,
+        ) {
+            (Some(id), Some(name), Some(email)) => Some(RequestAssignee { id, name, email }),
+            _ => None,
+        };
+
+        QueuedSoftwareRequest {
+            software_request: record.software_request.into(),
+            assignee,
+            reopened_at: record.reopened_at,
+            age_days: record.age_days,
+        }
+    }
+}
+
 #[tracing::instrument(
     name = "fetching all software_requests from database",
     skip(page, per_page, filter_field, filter_value, db_pool)
@@ -38,23 +102,103 @@ pub async fn fetch_all_software_requests(
     let limit = per_page;
     let offset = (page - 1) * per_page;
 
-    // Mutate filter_field before query construction
-    let (field, mut column) = (filter_field.clone(), None);
+    let query = all_software_requests_query(
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        Some((limit, offset)),
+    );
 
-    if let Some(ref filter_field) = field {
-        column = match filter_field.as_str() {
-            "software_name" => Some("s.software_name"),
-            "requester_email" => Some("r.email"),
-            _ => Some("td_request_id"),
-        };
+    let query = sqlx::query_as::<_, SoftwareRequestRecordCount>(&query);
+
+    // Bind the user-supplied value only if it exists
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let records = query.fetch_all(db_pool).await.map_err(Error::from)?;
+
+    let total_records = records.first().map_or(0, |record| record.count);
+
+    let software_requests_records: Vec<SoftwareRequestDTO> =
+        records.into_iter().map(SoftwareRequestDTO::from).collect();
+
+    let metadata = Metadata::calculate_metadata(total_records, page, per_page);
+
+    Ok((software_requests_records, metadata))
+}
+
+// Sends every software request matching the filter to `rows` as it is read, for exports
+#[tracing::instrument(
+    name = "streaming all software_requests from database",
+    skip(filter_field, filter_value, db_pool, rows)
+)]
+pub async fn stream_all_software_requests(
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    db_pool: &PgPool,
+    rows: mpsc::Sender<Result<SoftwareRequestDTO>>,
+) -> Result<()> {
+    let query = all_software_requests_query(
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        None,
+    );
+
+    let query = sqlx::query_as::<_, SoftwareRequestRecordCount>(&query);
+
+    // Bind the user-supplied value only if it exists
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let mut records = query.fetch(db_pool);
+
+    while let Some(record) = records.try_next().await? {
+        // The client has gone away, so there is no need to read the rest
+        if rows.send(Ok(record.into())).await.is_err() {
+            break;
+        }
     }
 
-    // Build the query with joins to get data from related tables
-    let query = if let (Some(_), Some(_)) = (filter_field.as_ref(), filter_value.as_ref()) {
+    Ok(())
+}
+
+// Build the query with joins to get data from related tables. `pagination` is the
+// `(limit, offset)` of a page. Without it every matching record is selected, and the
+// total count is not needed
+fn all_software_requests_query(
+    filter_field: Option<&str>,
+    pagination: Option<(usize, usize)>,
+) -> String {
+    let count = if pagination.is_some() {
+        "count(*) OVER(),"
+    } else {
+        ""
+    };
+
+    let filter = filter_field.map_or(String::new(), |field| {
+        let column = match field {
+            "software_name" => "s.software_name",
+            "requester_email" => "r.email",
+            _ => "td_request_id",
+        };
+
         format!(
-            r#"
+            "WHERE (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))",
+            column
+        )
+    });
+
+    let limit = pagination.map_or(String::new(), |(limit, offset)| {
+        format!("LIMIT {} OFFSET {}", limit, offset)
+    });
+
+    format!(
+        r#"
         SELECT 
-            count(*) OVER(),
+            {}
             sr.id,
             sr.td_request_id,
             sr.created_at,
@@ -75,19 +219,117 @@ pub async fn fetch_all_software_requests(
             software s ON sr.software_id = s.id
         INNER JOIN 
             requester r ON sr.requester_id = r.id
-        WHERE 
-            (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))
+        {}
         ORDER BY 
             sr.id ASC
-        LIMIT {} OFFSET {}
+        {}
         "#,
-            column.unwrap_or_default(),
-            limit,
-            offset
-        )
+        count, filter, limit
+    )
+}
+
+// Software requests that have no review yet. When `assignee_id` is provided, only the
+// requests assigned to that user are included
+#[tracing::instrument(
+    name = "fetching unreviewed software requests from database",
+    skip(
+        oldest_first,
+        page,
+        per_page,
+        filter_field,
+        filter_value,
+        assignee_id,
+        db_pool
+    )
+)]
+pub async fn fetch_unreviewed_software_requests(
+    oldest_first: bool,
+    page: usize,
+    per_page: usize,
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    assignee_id: Option<Uuid>,
+    db_pool: &PgPool,
+) -> Result<(Vec<QueuedSoftwareRequest>, Metadata)> {
+    let limit = per_page;
+    let offset = (page - 1) * per_page;
+
+    let query = unreviewed_software_requests_query(
+        oldest_first,
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        assignee_id.is_some(),
+        (limit, offset),
+    );
+
+    let query = sqlx::query_as::<_, QueuedSoftwareRequestRecord>(&query);
+
+    // Bind the user-supplied values only if they exist, in the order they are numbered
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
     } else {
-        format!(
-            r#"
+        query
+    };
+
+    let query = if let Some(assignee_id) = assignee_id {
+        query.bind(assignee_id)
+    } else {
+        query
+    };
+
+    let records = query.fetch_all(db_pool).await.map_err(Error::from)?;
+
+    let total_records = records
+        .first()
+        .map_or(0, |record| record.software_request.count);
+
+    let software_requests: Vec<QueuedSoftwareRequest> = records
+        .into_iter()
+        .map(QueuedSoftwareRequest::from)
+        .collect();
+
+    let metadata = Metadata::calculate_metadata(total_records, page, per_page);
+
+    Ok((software_requests, metadata))
+}
+
+// Sorted by the time each request was created (or reopened)
+fn unreviewed_software_requests_query(
+    oldest_first: bool,
+    filter_field: Option<&str>,
+    filter_by_assignee: bool,
+    (limit, offset): (usize, usize),
+) -> String {
+    // Requests reopened because their review expired are waiting for a review again
+    let mut conditions = vec![
+        "(NOT EXISTS (SELECT 1 FROM software_review rv WHERE rv.software_request_id = sr.id) \
+         OR sr.reopened_at IS NOT NULL)"
+            .to_string(),
+    ];
+
+    if let Some(field) = filter_field {
+        let column = match field {
+            "software_name" => "s.software_name",
+            "requester_email" => "r.email",
+            "assignee_email" => "u.email",
+            _ => "sr.td_request_id",
+        };
+
+        conditions.push(format!(
+            "(to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))",
+            column
+        ));
+    }
+
+    if filter_by_assignee {
+        let param = if filter_field.is_some() { "$2" } else { "$1" };
+
+        conditions.push(format!("sr.assignee_id = {}", param));
+    }
+
+    let created_at_direction = if oldest_first { "ASC" } else { "DESC" };
+
+    format!(
+        r#"
         SELECT 
             count(*) OVER(),
             sr.id,
@@ -103,60 +345,32 @@ pub async fn fetch_all_software_requests(
             r.name AS requester_name,
             r.email AS requester_email,
             r.department AS requester_department,
-            r.created_at AS requester_created_at
+            r.created_at AS requester_created_at,
+            u.id AS assignee_id,
+            u.name AS assignee_name,
+            u.email AS assignee_email,
+            sr.reopened_at,
+            floor(
+                EXTRACT(EPOCH FROM now() - COALESCE(sr.reopened_at, sr.created_at)) / 86400
+            )::BIGINT AS age_days
         FROM 
             software_request sr
         INNER JOIN 
             software s ON sr.software_id = s.id
         INNER JOIN 
             requester r ON sr.requester_id = r.id
+        LEFT JOIN 
+            user_account u ON sr.assignee_id = u.id
+        WHERE {}
         ORDER BY 
-            sr.id ASC
+            COALESCE(sr.reopened_at, sr.created_at) {}, sr.id ASC
         LIMIT {} OFFSET {}
         "#,
-            limit, offset
-        )
-    };
-    let query = sqlx::query_as::<_, SoftwareRequestRecordCount>(&query);
-
-    // Bind the user-supplied value only if it exists
-    let query = if let Some(value) = filter_value {
-        query.bind(value)
-    } else {
-        query
-    };
-
-    let records = query.fetch_all(db_pool).await.map_err(Error::from)?;
-
-    let total_records = records.first().map_or(0, |record| record.count);
-
-    let software_requests_records: Vec<SoftwareRequestDTO> = records
-        .into_iter()
-        .map(|record| SoftwareRequestDTO {
-            id: Some(record.id),
-            td_request_id: record.td_request_id,
-            software: SoftwareDTO {
-                id: Some(record.software_id),
-                software_name: record.software_name,
-                software_version: record.software_version,
-                developer_name: record.developer_name,
-                description: record.description,
-                created_at: Some(record.software_created_at),
-            },
-            requester: RequesterDTO {
-                id: Some(record.requester_id),
-                name: record.requester_name,
-                email: record.requester_email,
-                department: record.requester_department,
-                created_at: Some(record.requester_created_at),
-            },
-            created_at: Some(record.created_at),
-        })
-        .collect();
-
-    let metadata = Metadata::calculate_metadata(total_records, page, per_page);
-
-    Ok((software_requests_records, metadata))
+        conditions.join(" AND "),
+        created_at_direction,
+        limit,
+        offset
+    )
 }
 
 #[tracing::instrument(
@@ -277,3 +491,36 @@ pub async fn update_software_request(
         },
     }
 }
+
+// Assigns the software request to a user for review, or unassigns it when `assignee_id`
+// is `None`
+#[tracing::instrument(
+    name = "updating software request assignee in database",
+    skip(request_id, assignee_id, db_pool)
+)]
+pub async fn update_software_request_assignee(
+    request_id: Uuid,
+    assignee_id: Option<Uuid>,
+    db_pool: &PgPool,
+) -> Result<()> {
+    match sqlx::query!(
+        r#"
+        UPDATE software_request
+        SET assignee_id = $1
+        WHERE id = $2
+        RETURNING id
+        "#,
+        assignee_id,
+        request_id
+    )
+    .fetch_optional(db_pool)
+    .await
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Error::PgNotFoundError),
+        Err(err) => match err.as_database_error().and_then(|db_err| db_err.code()) {
+            Some(code) if code == "23503" => Err(Error::PgKeyViolation),
+            _ => Err(Error::from(err)),
+        },
+    }
+}
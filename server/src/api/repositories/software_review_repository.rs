@@ -1,15 +1,19 @@
-use sqlx::PgPool;
+use futures_util::TryStreamExt;
+use sqlx::{PgConnection, PgPool};
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::models::{
     RequesterDTO, ReviewOptions, SoftwareDTO, SoftwareRequestDTO, SoftwareReview,
-    SoftwareReviewDTO, SoftwareReviewPayload, UserDTO, UserRole,
+    SoftwareReviewDTO, SoftwareReviewPayload, SoftwareVersion, UserDTO, UserRole,
 };
 use crate::api::utils::Metadata;
-use crate::{Error, Result};
+use crate::{Error, FieldError, FieldErrorCode, Result};
 
 #[derive(Debug, sqlx::FromRow)]
 struct SoftwareReviewRecordCount {
+    // Only selected for a page of records
+    #[sqlx(default)]
     count: i64,
     id: Uuid,
     software_request_id: Uuid,
@@ -42,167 +46,19 @@ struct SoftwareReviewRecordCount {
     is_supported_by_current_os: ReviewOptions,
     exported: bool,
     review_notes: String,
+    reviewed_version: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[tracing::instrument(
-    name = "fetching all software reviews from database",
-    skip(page, per_page, filter_field, filter_value, db_pool)
-)]
-pub async fn fetch_all_software_reviews(
-    page: usize,
-    per_page: usize,
-    filter_field: Option<String>,
-    filter_value: Option<String>,
-    db_pool: &PgPool,
-) -> Result<(Vec<SoftwareReviewDTO>, Metadata)> {
-    let limit = per_page;
-    let offset = (page - 1) * per_page;
-
-    // Mutate filter_field before query construction
-    let (field, mut column) = (filter_field.clone(), None);
-
-    if let Some(ref filter_field) = field {
-        column = match filter_field.as_str() {
-            "td_request_id" => Some("r.td_request_id"),
-            "reviewer_email" => Some("u.email"),
-            "requester_email" => Some("rq.email"),
-            "software_name" => Some("s.software_name"),
-            _ => Some("sr.exported"),
-        };
-    }
-
-    // Build the query with joins to get data from related tables
-    let query = if let (Some(_), Some(_)) = (filter_field.as_ref(), filter_value.as_ref()) {
-        format!(
-            r#"
-            SELECT 
-                count(*) OVER() AS count,
-                sr.id,
-                sr.software_request_id,
-                sr.reviewer_id,
-                sr.is_supported,
-                sr.is_current_version,
-                sr.is_reputation_good,
-                sr.is_installation_from_developer,
-                sr.is_local_admin_required,
-                sr.is_connected_to_brockport_cloud,
-                sr.is_connected_to_cloud_services_or_client,
-                sr.is_security_or_optimization_software,
-                sr.is_supported_by_current_os,
-                sr.exported,
-                sr.review_notes,
-                sr.created_at,
-                sr.updated_at,
-                r.td_request_id,
-                r.created_at AS software_request_created_at,
-                s.id AS software_id,
-                s.software_name,
-                s.software_version,
-                s.developer_name,
-                s.description,
-                s.created_at AS software_created_at,
-                rq.id AS requester_id,
-                rq.name AS requester_name,
-                rq.email AS requester_email,
-                rq.department AS requester_department,
-                rq.created_at AS requester_created_at,
-                u.name AS reviewer_name,
-                u.email AS reviewer_email,
-                u.role AS reviewer_role,
-                u.created_at AS reviewer_created_at
-            FROM 
-                software_review sr
-            INNER JOIN 
-                software_request r ON sr.software_request_id = r.id
-            INNER JOIN 
-                software s ON r.software_id = s.id
-            INNER JOIN 
-                requester rq ON r.requester_id = rq.id
-            INNER JOIN 
-                user_account u ON sr.reviewer_id = u.id
-            WHERE 
-                (to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))
-            ORDER BY 
-                sr.id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            column.unwrap_or_default(),
-            limit,
-            offset
-        )
-    } else {
-        format!(
-            r#"
-            SELECT 
-                count(*) OVER() AS count,
-                sr.id,
-                sr.software_request_id,
-                sr.reviewer_id,
-                sr.is_supported,
-                sr.is_current_version,
-                sr.is_reputation_good,
-                sr.is_installation_from_developer,
-                sr.is_local_admin_required,
-                sr.is_connected_to_brockport_cloud,
-                sr.is_connected_to_cloud_services_or_client,
-                sr.is_security_or_optimization_software,
-                sr.is_supported_by_current_os,
-                sr.exported,
-                sr.review_notes,
-                sr.created_at,
-                sr.updated_at,
-                r.td_request_id,
-                r.created_at AS software_request_created_at,
-                s.id AS software_id,
-                s.software_name,
-                s.software_version,
-                s.developer_name,
-                s.description,
-                s.created_at AS software_created_at,
-                rq.id AS requester_id,
-                rq.name AS requester_name,
-                rq.email AS requester_email,
-                rq.department AS requester_department,
-                rq.created_at AS requester_created_at,
-                u.name AS reviewer_name,
-                u.email AS reviewer_email,
-                u.role AS reviewer_role,
-                u.created_at AS reviewer_created_at
-            FROM 
-                software_review sr
-            INNER JOIN 
-                software_request r ON sr.software_request_id = r.id
-            INNER JOIN 
-                software s ON r.software_id = s.id
-            INNER JOIN 
-                requester rq ON r.requester_id = rq.id
-            INNER JOIN 
-                user_account u ON sr.reviewer_id = u.id
-            ORDER BY 
-                sr.id ASC
-            LIMIT {} OFFSET {}
-            "#,
-            limit, offset
-        )
-    };
-    let query = sqlx::query_as::<_, SoftwareReviewRecordCount>(&query);
-
-    // Bind the user-supplied value only if it exists
-    let query = if let Some(value) = filter_value {
-        query.bind(value)
-    } else {
-        query
-    };
-
-    let records = query.fetch_all(db_pool).await.map_err(Error::from)?;
-
-    let total_records = records.first().map_or(0, |record| record.count);
-
-    let software_reviews_records: Vec<SoftwareReviewDTO> = records
-        .into_iter()
-        .map(|record| SoftwareReviewDTO {
+impl From<SoftwareReviewRecordCount> for SoftwareReviewDTO {
+    fn from(record: SoftwareReviewRecordCount) -> Self {
+        SoftwareReviewDTO {
             id: record.id,
+            newer_version_available: SoftwareVersion::is_newer(
+                &record.software_version,
+                &record.reviewed_version,
+            ),
             software_request: SoftwareRequestDTO {
                 id: Some(record.software_request_id),
                 td_request_id: record.td_request_id,
@@ -242,15 +98,215 @@ pub async fn fetch_all_software_reviews(
             is_supported_by_current_os: record.is_supported_by_current_os,
             exported: record.exported,
             review_notes: record.review_notes,
+            reviewed_version: record.reviewed_version,
+            expires_at: record.expires_at,
             created_at: Some(record.created_at),
-        })
-        .collect();
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "fetching all software reviews from database",
+    skip(page, per_page, filter_field, filter_value, expiring_within, db_pool)
+)]
+pub async fn fetch_all_software_reviews(
+    page: usize,
+    per_page: usize,
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    expiring_within: Option<i32>,
+    db_pool: &PgPool,
+) -> Result<(Vec<SoftwareReviewDTO>, Metadata)> {
+    let limit = per_page;
+    let offset = (page - 1) * per_page;
+
+    let query = all_software_reviews_query(
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        expiring_within.is_some(),
+        Some((limit, offset)),
+    );
+
+    let query = sqlx::query_as::<_, SoftwareReviewRecordCount>(&query);
+
+    // Bind the user-supplied values only if they exist, in the order they are numbered
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let query = if let Some(days) = expiring_within {
+        query.bind(days)
+    } else {
+        query
+    };
+
+    let records = query.fetch_all(db_pool).await.map_err(Error::from)?;
+
+    let total_records = records.first().map_or(0, |record| record.count);
+
+    let software_reviews_records: Vec<SoftwareReviewDTO> =
+        records.into_iter().map(SoftwareReviewDTO::from).collect();
 
     let metadata = Metadata::calculate_metadata(total_records, page, per_page);
 
     Ok((software_reviews_records, metadata))
 }
 
+// Sends every software review matching the filter to `rows` as it is read, for exports
+#[tracing::instrument(
+    name = "streaming all software reviews from database",
+    skip(filter_field, filter_value, expiring_within, db_pool, rows)
+)]
+pub async fn stream_all_software_reviews(
+    filter_field: Option<String>,
+    filter_value: Option<String>,
+    expiring_within: Option<i32>,
+    db_pool: &PgPool,
+    rows: mpsc::Sender<Result<SoftwareReviewDTO>>,
+) -> Result<()> {
+    let query = all_software_reviews_query(
+        filter_field.as_deref().filter(|_| filter_value.is_some()),
+        expiring_within.is_some(),
+        None,
+    );
+
+    let query = sqlx::query_as::<_, SoftwareReviewRecordCount>(&query);
+
+    // Bind the user-supplied values only if they exist, in the order they are numbered
+    let query = if let Some(value) = filter_value {
+        query.bind(value)
+    } else {
+        query
+    };
+
+    let query = if let Some(days) = expiring_within {
+        query.bind(days)
+    } else {
+        query
+    };
+
+    let mut records = query.fetch(db_pool);
+
+    while let Some(record) = records.try_next().await? {
+        // The client has gone away, so there is no need to read the rest
+        if rows.send(Ok(record.into())).await.is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// Build the query with joins to get data from related tables. `pagination` is the
+// `(limit, offset)` of a page. Without it every matching record is selected, and the
+// total count is not needed. `filter_by_expiry` only selects reviews expiring within the
+// number of days bound after the filter value, including those that already expired
+fn all_software_reviews_query(
+    filter_field: Option<&str>,
+    filter_by_expiry: bool,
+    pagination: Option<(usize, usize)>,
+) -> String {
+    let count = if pagination.is_some() {
+        "count(*) OVER() AS count,"
+    } else {
+        ""
+    };
+
+    let filter = filter_field.map_or(String::new(), |field| {
+        let column = match field {
+            "td_request_id" => "r.td_request_id",
+            "reviewer_email" => "u.email",
+            "requester_email" => "rq.email",
+            "software_name" => "s.software_name",
+            _ => "sr.exported",
+        };
+
+        format!(
+            "(to_tsvector('simple', {}::TEXT) @@ plainto_tsquery('simple', $1))",
+            column
+        )
+    });
+
+    let expiry = filter_by_expiry.then(|| {
+        let param = if filter.is_empty() { "$1" } else { "$2" };
+
+        format!("sr.expires_at <= NOW() + make_interval(days => {})", param)
+    });
+
+    let conditions: Vec<String> = [(!filter.is_empty()).then_some(filter), expiry]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let filter = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let limit = pagination.map_or(String::new(), |(limit, offset)| {
+        format!("LIMIT {} OFFSET {}", limit, offset)
+    });
+
+    format!(
+        r#"
+        SELECT 
+            {}
+            sr.id,
+            sr.software_request_id,
+            sr.reviewer_id,
+            sr.is_supported,
+            sr.is_current_version,
+            sr.is_reputation_good,
+            sr.is_installation_from_developer,
+            sr.is_local_admin_required,
+            sr.is_connected_to_brockport_cloud,
+            sr.is_connected_to_cloud_services_or_client,
+            sr.is_security_or_optimization_software,
+            sr.is_supported_by_current_os,
+            sr.exported,
+            sr.review_notes,
+            sr.reviewed_version,
+            sr.expires_at,
+            sr.created_at,
+            sr.updated_at,
+            r.td_request_id,
+            r.created_at AS software_request_created_at,
+            s.id AS software_id,
+            s.software_name,
+            s.software_version,
+            s.developer_name,
+            s.description,
+            s.created_at AS software_created_at,
+            rq.id AS requester_id,
+            rq.name AS requester_name,
+            rq.email AS requester_email,
+            rq.department AS requester_department,
+            rq.created_at AS requester_created_at,
+            u.name AS reviewer_name,
+            u.email AS reviewer_email,
+            u.role AS reviewer_role,
+            u.created_at AS reviewer_created_at
+        FROM 
+            software_review sr
+        INNER JOIN 
+            software_request r ON sr.software_request_id = r.id
+        INNER JOIN 
+            software s ON r.software_id = s.id
+        INNER JOIN 
+            requester rq ON r.requester_id = rq.id
+        INNER JOIN 
+            user_account u ON sr.reviewer_id = u.id
+        {}
+        ORDER BY 
+            sr.id ASC
+        {}
+        "#,
+        count, filter, limit
+    )
+}
+
 #[tracing::instrument(
     name = "fetching software review by id from database",
     skip(review_id, db_pool)
@@ -272,7 +328,7 @@ pub async fn fetch_software_review_by_id(
             is_connected_to_cloud_services_or_client AS "is_connected_to_cloud_services_or_client: ReviewOptions", 
             is_security_or_optimization_software AS "is_security_or_optimization_software: ReviewOptions", 
             is_supported_by_current_os AS "is_supported_by_current_os: ReviewOptions", 
-            exported, review_notes, created_at, updated_at, version
+            exported, review_notes, reviewed_version, expires_at, created_at, updated_at, version
         FROM software_review
         WHERE id = $1
         "#,
@@ -298,6 +354,8 @@ pub async fn fetch_software_review_by_id(
             is_supported_by_current_os: row.is_supported_by_current_os,
             exported: row.exported,
             review_notes: row.review_notes,
+            reviewed_version: row.reviewed_version,
+            expires_at: row.expires_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
             version: row.version,
@@ -308,99 +366,111 @@ pub async fn fetch_software_review_by_id(
 
 #[tracing::instrument(
     name = "inserting software review into database",
-    skip(payload, db_pool)
+    skip(payload, validity_days, db_pool)
 )]
 pub async fn insert_software_review(
     payload: &SoftwareReviewPayload,
     reviewer_id: &Uuid,
+    validity_days: i32,
     db_pool: &PgPool,
-) -> Result<()> {
+) -> Result<Uuid> {
     let mut tx = db_pool.begin().await?;
 
-    let software_id = match sqlx::query!(
-        r#"
-        INSERT INTO software (software_name, software_version, developer_name, description)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id
-        "#,
-        payload.software_request.software.software_name,
-        payload.software_request.software.software_version,
-        payload.software_request.software.developer_name,
-        payload.software_request.software.description,
-    )
-    .fetch_optional(&mut *tx)
-    .await
-    {
-        Ok(Some(row)) => Ok(row.id),
-        Ok(None) => Err(Error::PgNotFoundError),
-        Err(err) => match err.as_database_error().and_then(|db_err| db_err.code()) {
-            Some(code) if code == "23505" => Err(Error::PgRecordExists),
-            _ => Err(Error::from(err)),
-        },
-    };
+    // Every step propagates its error immediately. Returning early drops `tx`, which
+    // rolls back any rows inserted by the previous steps
+    //
+    // An existing software request is linked directly. Otherwise the software and
+    // requester are each either linked by id or created from the inline details
+    let software_request_id = match (&payload.software_request_id, &payload.software_request) {
+        (Some(software_request_id), None) => *software_request_id,
+        (None, Some(software_request)) => {
+            let software_id = match (&software_request.software_id, &software_request.software) {
+                (Some(software_id), None) => *software_id,
+                (None, Some(software)) => insert_review_software(software, &mut tx).await?,
+                _ => {
+                    return Err(Error::ValidationError(
+                        FieldError::new(
+                            "software_request.software",
+                            FieldErrorCode::ConflictingFields,
+                            "exactly one of software_id or software must be provided",
+                        )
+                        .into(),
+                    ))
+                }
+            };
 
-    let requester_id = match sqlx::query!(
-        r#"
-        INSERT INTO requester (name, email, department)
-        VALUES ($1, $2, $3)
-        RETURNING id
-        "#,
-        payload.software_request.requester.name,
-        payload.software_request.requester.email,
-        payload.software_request.requester.department,
-    )
-    .fetch_optional(&mut *tx)
-    .await
-    {
-        Ok(Some(row)) => Ok(row.id),
-        Ok(None) => Err(Error::PgNotFoundError),
-        Err(err) => match err.as_database_error().and_then(|db_err| db_err.code()) {
-            Some(code) if code == "23505" => Err(Error::PgRecordExists),
-            _ => Err(Error::from(err)),
-        },
+            let requester_id = match (&software_request.requester_id, &software_request.requester) {
+                (Some(requester_id), None) => *requester_id,
+                (None, Some(requester)) => insert_review_requester(requester, &mut tx).await?,
+                _ => {
+                    return Err(Error::ValidationError(
+                        FieldError::new(
+                            "software_request.requester",
+                            FieldErrorCode::ConflictingFields,
+                            "exactly one of requester_id or requester must be provided",
+                        )
+                        .into(),
+                    ))
+                }
+            };
+
+            insert_review_software_request(
+                &software_request.td_request_id,
+                software_id,
+                requester_id,
+                &mut tx,
+            )
+            .await?
+        }
+        _ => {
+            return Err(Error::ValidationError(
+                FieldError::new(
+                    "software_request",
+                    FieldErrorCode::ConflictingFields,
+                    "exactly one of software_request_id or software_request must be provided",
+                )
+                .into(),
+            ))
+        }
     };
 
-    let software_request_id = match sqlx::query!(
+    // Record the software version being reviewed so a newer version requested later can
+    // be reported
+    let reviewed_version = sqlx::query!(
         r#"
-        INSERT INTO software_request (td_request_id, software_id, requester_id)
-        VALUES ($1, $2, $3)
-        RETURNING id
+        SELECT s.software_version
+        FROM software_request r
+        INNER JOIN software s ON r.software_id = s.id
+        WHERE r.id = $1
         "#,
-        payload.software_request.td_request_id,
-        software_id?,
-        requester_id?
+        software_request_id
     )
     .fetch_optional(&mut *tx)
-    .await
-    {
-        Ok(Some(row)) => Ok(row.id),
-        Ok(None) => Err(Error::PgNotFoundError),
-        Err(err) => match err.as_database_error().and_then(|db_err| db_err.code()) {
-            Some(code) if code == "23503" => Err(Error::PgKeyViolation),
-            Some(code) if code == "23505" => Err(Error::PgRecordExists),
-            _ => Err(Error::from(err)),
-        },
-    };
+    .await?
+    .ok_or(Error::PgKeyViolation)?
+    .software_version;
 
-    let _ = match sqlx::query!(
+    let row = sqlx::query!(
         r#"
         INSERT INTO software_review (
             software_request_id, reviewer_id, 
             is_supported, is_current_version, is_reputation_good, 
             is_installation_from_developer, is_local_admin_required, 
             is_connected_to_brockport_cloud, is_connected_to_cloud_services_or_client, 
-            is_security_or_optimization_software, is_supported_by_current_os, review_notes
+            is_security_or_optimization_software, is_supported_by_current_os, review_notes,
+            reviewed_version, expires_at
         )
         VALUES (
             $1, $2, 
             $3, $4, $5, 
             $6, $7, 
             $8, $9, 
-            $10, $11, $12
+            $10, $11, $12,
+            $13, NOW() + make_interval(days => $14)
         )
         RETURNING id
         "#,
-        software_request_id?,
+        software_request_id,
         reviewer_id,
         payload.is_supported.clone() as ReviewOptions,
         payload.is_current_version.clone() as ReviewOptions,
@@ -411,23 +481,113 @@ pub async fn insert_software_review(
         payload.is_connected_to_cloud_services_or_client.clone() as ReviewOptions,
         payload.is_security_or_optimization_software.clone() as ReviewOptions,
         payload.is_supported_by_current_os.clone() as ReviewOptions,
-        payload.review_notes.clone()
+        payload.review_notes.clone(),
+        reviewed_version,
+        validity_days
     )
-    .fetch_optional(&mut *tx)
+    .fetch_one(&mut *tx)
     .await
-    {
-        Ok(Some(_)) => Ok(()),
-        Ok(None) => Err(Error::PgNotFoundError),
-        Err(err) => match err.as_database_error().and_then(|db_err| db_err.code()) {
-            Some(code) if code == "23503" => Err(Error::PgKeyViolation),
-            Some(code) if code == "23505" => Err(Error::PgRecordExists),
-            _ => Err(Error::from(err)),
-        },
-    };
+    .map_err(map_insert_error)?;
 
     tx.commit().await?;
 
-    Ok(())
+    Ok(row.id)
+}
+
+pub async fn insert_review_software(
+    software: &SoftwareDTO,
+    conn: &mut PgConnection,
+) -> Result<Uuid> {
+    let software_version_key = software
+        .software_version
+        .parse::<SoftwareVersion>()?
+        .sort_key();
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO software (software_name, software_version, software_version_key, developer_name, description)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+        software.software_name,
+        software.software_version,
+        software_version_key,
+        software.developer_name,
+        software.description,
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(map_insert_error)?;
+
+    Ok(row.id)
+}
+
+pub async fn insert_review_requester(
+    requester: &RequesterDTO,
+    conn: &mut PgConnection,
+) -> Result<Uuid> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO requester (name, email, department)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        requester.name,
+        requester.email,
+        requester.department,
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(map_insert_error)?;
+
+    Ok(row.id)
+}
+
+pub async fn insert_review_software_request(
+    td_request_id: &str,
+    software_id: Uuid,
+    requester_id: Uuid,
+    conn: &mut PgConnection,
+) -> Result<Uuid> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO software_request (td_request_id, software_id, requester_id)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        td_request_id,
+        software_id,
+        requester_id
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(map_insert_error)?;
+
+    Ok(row.id)
+}
+
+// Maps an error from one of the review inserts, naming the conflicting field when a
+// UNIQUE constraint is violated so the client knows which part of the payload to fix
+pub(super) fn map_insert_error(err: sqlx::Error) -> Error {
+    let Some(db_err) = err.as_database_error() else {
+        return Error::from(err);
+    };
+
+    match db_err.code().as_deref() {
+        Some("23505") => match db_err.constraint() {
+            Some("software_software_name_key") => Error::PgFieldConflict("software name".into()),
+            Some("requester_email_key") => Error::PgFieldConflict("requester email".into()),
+            Some("software_request_td_request_id_key") => {
+                Error::PgFieldConflict("ticket number".into())
+            }
+            // Software can only be requested once, so existing software that has already
+            // been requested can't be linked to a new request
+            Some("software_request_software_id_key") => Error::PgFieldConflict("software".into()),
+            _ => Error::PgRecordExists,
+        },
+        Some("23503") => Error::PgKeyViolation,
+        _ => Error::from(err),
+    }
 }
 
 #[tracing::instrument(
@@ -457,17 +617,22 @@ pub async fn delete_software_review(review_id: Uuid, db_pool: &PgPool) -> Result
 
 #[tracing::instrument(
     name = "updating software review details in database",
-    skip(software_review, review_id, db_pool)
+    skip(software_review, review_id, validity_days, db_pool)
 )]
 pub async fn update_software_review(
     software_review: SoftwareReview,
     review_id: Uuid,
+    validity_days: i32,
     db_pool: &PgPool,
 ) -> Result<()> {
+    // Updating a review counts as reviewing it again, so it is valid for another
+    // `validity_days`, and its software request leaves the review queue if it had been
+    // reopened
     let result = sqlx::query!(
         r#"
-        UPDATE software_review
-        SET 
+        WITH updated AS (
+            UPDATE software_review
+            SET 
             is_supported = $1,
             is_current_version = $2,
             is_reputation_good = $3,
@@ -478,9 +643,17 @@ pub async fn update_software_review(
             is_security_or_optimization_software = $8,
             is_supported_by_current_os = $9,
             review_notes = $10,
+            expires_at = NOW() + make_interval(days => $13),
+            expiry_flagged_at = NULL,
             version = version + 1
-        WHERE id = $11 AND version = $12
-        RETURNING version
+            WHERE id = $11 AND version = $12
+            RETURNING software_request_id, version
+        ), reviewed AS (
+            UPDATE software_request
+            SET reopened_at = NULL
+            WHERE id IN (SELECT software_request_id FROM updated) AND reopened_at IS NOT NULL
+        )
+        SELECT version FROM updated
     "#,
         software_review.is_supported.clone() as ReviewOptions,
         software_review.is_current_version.clone() as ReviewOptions,
@@ -495,7 +668,8 @@ pub async fn update_software_review(
         software_review.is_supported_by_current_os.clone() as ReviewOptions,
         software_review.review_notes,
         review_id,
-        software_review.version
+        software_review.version,
+        validity_days
     )
     .fetch_optional(db_pool)
     .await;
@@ -626,3 +800,56 @@ pub async fn fetch_software_review_details(
         None => Err(Error::PgNotFoundError),
     }
 }
+
+// Flags the reviews expiring within `warning_days` (or already expired) that haven't been
+// flagged yet, so each is only reported once until it is reviewed again. Returns the id
+// and expiry of each review flagged
+#[tracing::instrument(
+    name = "flagging expiring software reviews in database",
+    skip(warning_days, db_pool)
+)]
+pub async fn flag_expiring_software_reviews(
+    warning_days: i32,
+    db_pool: &PgPool,
+) -> Result<Vec<(Uuid, chrono::DateTime<chrono::Utc>)>> {
+    let rows = sqlx::query!(
+        r#"
+        UPDATE software_review
+        SET expiry_flagged_at = NOW()
+        WHERE expires_at <= NOW() + make_interval(days => $1) AND expiry_flagged_at IS NULL
+        RETURNING id, expires_at
+        "#,
+        warning_days
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.id, row.expires_at))
+        .collect())
+}
+
+// Puts the software requests of expired reviews back in the review queue. Returns the id
+// of each software request reopened
+#[tracing::instrument(
+    name = "reopening expired software requests in database",
+    skip(db_pool)
+)]
+pub async fn reopen_expired_software_requests(db_pool: &PgPool) -> Result<Vec<Uuid>> {
+    let rows = sqlx::query!(
+        r#"
+        UPDATE software_request r
+        SET reopened_at = NOW()
+        FROM software_review sr
+        WHERE sr.software_request_id = r.id
+            AND sr.expires_at <= NOW()
+            AND r.reopened_at IS NULL
+        RETURNING r.id
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.id).collect())
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::api::models::{PeriodCount, ReviewerWorkload, StatsInterval, SummaryCount};
+use crate::Result;
+
+// Reviews created within each week or month overlapping `from` to `to`, including those
+// without any reviews
+#[tracing::instrument(
+    name = "fetching reviews created per period from database",
+    skip(from, to, interval, db_pool)
+)]
+pub async fn fetch_reviews_per_period(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    interval: StatsInterval,
+    db_pool: &PgPool,
+) -> Result<Vec<PeriodCount>> {
+    let records = sqlx::query_as!(
+        PeriodCount,
+        r#"
+        SELECT periods.period_start::date AS "period_start!", count(sr.id) AS "count!"
+        FROM generate_series(
+            date_trunc($3, $1 AT TIME ZONE 'UTC'),
+            date_trunc($3, ($2 AT TIME ZONE 'UTC') - interval '1 microsecond'),
+            ('1 ' || $3)::interval
+        ) AS periods(period_start)
+        LEFT JOIN software_review sr
+            ON date_trunc($3, sr.created_at AT TIME ZONE 'UTC') = periods.period_start
+            AND sr.created_at >= $1 AND sr.created_at < $2
+        GROUP BY periods.period_start
+        ORDER BY periods.period_start
+        "#,
+        from,
+        to,
+        interval.as_str()
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
+
+// Number of reviews with each answer, per criterion. Returns `(criterion, TRUE, FALSE,
+// NOT_SURE)`, with no rows when there are no reviews
+#[tracing::instrument(
+    name = "fetching review answers from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_review_answers(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<Vec<(String, i64, i64, i64)>> {
+    let records = sqlx::query!(
+        r#"
+        SELECT
+            answers.criterion AS "criterion!",
+            count(*) FILTER (WHERE answers.answer = 'TRUE') AS "true_count!",
+            count(*) FILTER (WHERE answers.answer = 'FALSE') AS "false_count!",
+            count(*) FILTER (WHERE answers.answer = 'NOT_SURE') AS "not_sure_count!"
+        FROM software_review sr
+        CROSS JOIN LATERAL (
+            VALUES
+                ('is_supported', sr.is_supported),
+                ('is_current_version', sr.is_current_version),
+                ('is_reputation_good', sr.is_reputation_good),
+                ('is_installation_from_developer', sr.is_installation_from_developer),
+                ('is_local_admin_required', sr.is_local_admin_required),
+                ('is_connected_to_brockport_cloud', sr.is_connected_to_brockport_cloud),
+                (
+                    'is_connected_to_cloud_services_or_client',
+                    sr.is_connected_to_cloud_services_or_client
+                ),
+                (
+                    'is_security_or_optimization_software',
+                    sr.is_security_or_optimization_software
+                ),
+                ('is_supported_by_current_os', sr.is_supported_by_current_os)
+        ) AS answers(criterion, answer)
+        WHERE sr.created_at >= $1 AND sr.created_at < $2
+        GROUP BY answers.criterion
+        "#,
+        from,
+        to
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            (
+                record.criterion,
+                record.true_count,
+                record.false_count,
+                record.not_sure_count,
+            )
+        })
+        .collect())
+}
+
+// Software requests made within the period without a review. Returns the count, the
+// oldest request's creation time and the average age in days
+#[tracing::instrument(
+    name = "fetching unreviewed software requests from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_unreviewed_request_totals(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<(i64, Option<DateTime<Utc>>, Option<f64>)> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            count(*) AS "count!",
+            min(r.created_at) AS oldest_created_at,
+            avg(EXTRACT(EPOCH FROM now() - r.created_at) / 86400)::float8 AS average_age_days
+        FROM software_request r
+        WHERE r.created_at >= $1 AND r.created_at < $2
+            AND NOT EXISTS (
+                SELECT 1 FROM software_review sr WHERE sr.software_request_id = r.id
+            )
+        "#,
+        from,
+        to
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok((
+        record.count,
+        record.oldest_created_at,
+        record.average_age_days,
+    ))
+}
+
+// Reviews created within the period, and every unreviewed request currently assigned
+#[tracing::instrument(
+    name = "fetching reviewer workload from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_reviewer_workload(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<Vec<ReviewerWorkload>> {
+    let records = sqlx::query_as!(
+        ReviewerWorkload,
+        r#"
+        SELECT
+            u.id AS user_id,
+            u.name,
+            u.email,
+            count(sr.id) AS "reviews!",
+            count(sr.id) FILTER (WHERE sr.exported) AS "exported_reviews!",
+            (
+                SELECT count(*)
+                FROM software_request r
+                WHERE r.assignee_id = u.id
+                    AND (
+                        NOT EXISTS (
+                            SELECT 1 FROM software_review rv WHERE rv.software_request_id = r.id
+                        )
+                        OR r.reopened_at IS NOT NULL
+                    )
+            ) AS "assigned_requests!"
+        FROM user_account u
+        LEFT JOIN software_review sr
+            ON sr.reviewer_id = u.id AND sr.created_at >= $1 AND sr.created_at < $2
+        GROUP BY u.id, u.name, u.email
+        ORDER BY count(sr.id) DESC, u.name
+        "#,
+        from,
+        to
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
+
+// Departments of the requesters with the most software requests made within the period
+#[tracing::instrument(
+    name = "fetching top requesting departments from database",
+    skip(from, to, limit, db_pool)
+)]
+pub async fn fetch_top_departments(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<SummaryCount>> {
+    let records = sqlx::query_as!(
+        SummaryCount,
+        r#"
+        SELECT rq.department AS "name!", count(*) AS "count!"
+        FROM software_request r
+        INNER JOIN requester rq ON r.requester_id = rq.id
+        WHERE r.created_at >= $1 AND r.created_at < $2
+        GROUP BY rq.department
+        ORDER BY count(*) DESC, rq.department
+        LIMIT $3
+        "#,
+        from,
+        to,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::api::models::{LocalAdminSoftware, OutcomeCount, SummaryCount, TimeToReview};
+use crate::Result;
+
+#[tracing::instrument(name = "fetching review totals from database", skip(from, to, db_pool))]
+pub async fn fetch_review_totals(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<(i64, i64)> {
+    let totals = sqlx::query!(
+        r#"
+        SELECT
+            count(*) AS "total!",
+            count(*) FILTER (WHERE exported) AS "exported!"
+        FROM software_review
+        WHERE created_at >= $1 AND created_at < $2
+        "#,
+        from,
+        to
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok((totals.total, totals.exported))
+}
+
+// Criteria that fail when answered `TRUE` are the ones about local admin, cloud
+// connections and security software (see `ReviewOutcome`)
+#[tracing::instrument(
+    name = "fetching review outcomes from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_review_outcomes(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<Vec<OutcomeCount>> {
+    let records = sqlx::query!(
+        r#"
+        SELECT outcome AS "outcome!", count(*) AS "count!"
+        FROM (
+            SELECT CASE
+                WHEN 'FALSE' IN (
+                    is_supported, is_current_version, is_reputation_good,
+                    is_installation_from_developer, is_supported_by_current_os
+                ) OR 'TRUE' IN (
+                    is_local_admin_required, is_connected_to_brockport_cloud,
+                    is_connected_to_cloud_services_or_client,
+                    is_security_or_optimization_software
+                ) THEN 'concerns'
+                WHEN 'NOT_SURE' IN (
+                    is_supported, is_current_version, is_reputation_good,
+                    is_installation_from_developer, is_supported_by_current_os,
+                    is_local_admin_required, is_connected_to_brockport_cloud,
+                    is_connected_to_cloud_services_or_client,
+                    is_security_or_optimization_software
+                ) THEN 'unsure'
+                ELSE 'no_concerns'
+            END AS outcome
+            FROM software_review
+            WHERE created_at >= $1 AND created_at < $2
+        ) reviews
+        GROUP BY outcome
+        "#,
+        from,
+        to
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    records
+        .into_iter()
+        .map(|record| {
+            Ok(OutcomeCount {
+                outcome: record.outcome.parse()?,
+                count: record.count,
+            })
+        })
+        .collect()
+}
+
+// Developers of the most reviewed software
+#[tracing::instrument(
+    name = "fetching top vendors from database",
+    skip(from, to, limit, db_pool)
+)]
+pub async fn fetch_top_vendors(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<SummaryCount>> {
+    let records = sqlx::query_as!(
+        SummaryCount,
+        r#"
+        SELECT s.developer_name AS "name!", count(*) AS "count!"
+        FROM software_review sr
+        INNER JOIN software_request r ON sr.software_request_id = r.id
+        INNER JOIN software s ON r.software_id = s.id
+        WHERE sr.created_at >= $1 AND sr.created_at < $2
+        GROUP BY s.developer_name
+        ORDER BY count(*) DESC, s.developer_name
+        LIMIT $3
+        "#,
+        from,
+        to,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
+
+#[tracing::instrument(
+    name = "fetching software requiring local admin from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_local_admin_software(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<Vec<LocalAdminSoftware>> {
+    let records = sqlx::query_as!(
+        LocalAdminSoftware,
+        r#"
+        SELECT s.software_name, s.software_version, s.developer_name, r.td_request_id
+        FROM software_review sr
+        INNER JOIN software_request r ON sr.software_request_id = r.id
+        INNER JOIN software s ON r.software_id = s.id
+        WHERE sr.created_at >= $1 AND sr.created_at < $2
+            AND sr.is_local_admin_required = 'TRUE'
+        ORDER BY s.software_name, s.software_version
+        "#,
+        from,
+        to
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
+
+#[tracing::instrument(
+    name = "fetching reviews per reviewer from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_reviews_per_reviewer(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<Vec<SummaryCount>> {
+    let records = sqlx::query_as!(
+        SummaryCount,
+        r#"
+        SELECT u.name AS "name!", count(*) AS "count!"
+        FROM software_review sr
+        INNER JOIN user_account u ON sr.reviewer_id = u.id
+        WHERE sr.created_at >= $1 AND sr.created_at < $2
+        GROUP BY u.id, u.name
+        ORDER BY count(*) DESC, u.name
+        "#,
+        from,
+        to
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(records)
+}
+
+// Days from each software request being made to its review being created
+#[tracing::instrument(
+    name = "fetching time to review from database",
+    skip(from, to, db_pool)
+)]
+pub async fn fetch_time_to_review(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    db_pool: &PgPool,
+) -> Result<TimeToReview> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            avg(days) AS average_days,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY days) AS median_days,
+            max(days) AS longest_days,
+            count(*) FILTER (WHERE days < 1) AS "under_one_day!",
+            count(*) FILTER (WHERE days >= 1 AND days < 7) AS "under_one_week!",
+            count(*) FILTER (WHERE days >= 7 AND days < 30) AS "under_thirty_days!",
+            count(*) FILTER (WHERE days >= 30) AS "thirty_days_or_more!"
+        FROM (
+            SELECT (EXTRACT(EPOCH FROM sr.created_at - r.created_at) / 86400)::float8 AS days
+            FROM software_review sr
+            INNER JOIN software_request r ON sr.software_request_id = r.id
+            WHERE sr.created_at >= $1 AND sr.created_at < $2
+                AND r.created_at IS NOT NULL
+        ) reviews
+        "#,
+        from,
+        to
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    let range = |name: &str, count: i64| SummaryCount {
+        name: name.to_string(),
+        count,
+    };
+
+    Ok(TimeToReview {
+        average_days: record.average_days,
+        median_days: record.median_days,
+        longest_days: record.longest_days,
+        ranges: vec![
+            range("Under 1 day", record.under_one_day),
+            range("1 to 6 days", record.under_one_week),
+            range("7 to 29 days", record.under_thirty_days),
+            range("30 days or more", record.thirty_days_or_more),
+        ],
+    })
+}
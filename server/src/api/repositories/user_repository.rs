@@ -149,6 +149,44 @@ pub async fn fetch_user_by_id(user_id: Uuid, db_pool: &PgPool) -> Result<User> {
     }
 }
 
+#[tracing::instrument(
+    name = "fetching users outside email domains from database",
+    skip(allowed_domains, db_pool)
+)]
+pub async fn fetch_users_outside_email_domains(
+    allowed_domains: &[String],
+    db_pool: &PgPool,
+) -> Result<Vec<UserDTO>> {
+    let allowed_domains: Vec<String> = allowed_domains
+        .iter()
+        .map(|domain| domain.to_lowercase())
+        .collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, name, email, role AS "role: UserRole", created_at
+        FROM user_account
+        WHERE lower(split_part(email, '@', 2)) <> ALL($1)
+        ORDER BY email ASC
+        "#,
+        &allowed_domains
+    )
+    .fetch_all(db_pool)
+    .await
+    .map_err(Error::from)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| UserDTO {
+            id: Some(row.id),
+            name: row.name,
+            email: row.email,
+            role: row.role.expect("BUG: role for user_account not found"),
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
 #[tracing::instrument(
     name = "inserting user into database",
     skip(payload, password_hash, db_pool)
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use secrecy::{ExposeSecret, SecretString};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::models::{
+    PendingWebhookDelivery, Webhook, WebhookDTO, WebhookDelivery, WebhookDeliveryAttempt,
+    WebhookDeliveryStatus, WebhookEvent,
+};
+use crate::{Error, Result};
+
+// Event types are stored as their names, e.g. `review.created`
+fn parse_event_types(event_types: Vec<String>) -> Result<Vec<WebhookEvent>> {
+    event_types.iter().map(|event| event.parse()).collect()
+}
+
+fn event_type_names(event_types: &[WebhookEvent]) -> Vec<String> {
+    event_types
+        .iter()
+        .map(|event| event.as_str().to_string())
+        .collect()
+}
+
+#[tracing::instrument(name = "fetching all webhooks from database", skip(db_pool))]
+pub async fn fetch_all_webhooks(db_pool: &PgPool) -> Result<Vec<WebhookDTO>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, url, event_types, active, created_at, updated_at
+        FROM webhook
+        ORDER BY created_at ASC, id ASC
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(WebhookDTO {
+                id: Some(row.id),
+                url: row.url,
+                event_types: parse_event_types(row.event_types)?,
+                active: row.active,
+                created_at: Some(row.created_at),
+                updated_at: Some(row.updated_at),
+            })
+        })
+        .collect()
+}
+
+#[tracing::instrument(
+    name = "fetching webhook by id from database",
+    skip(webhook_id, db_pool)
+)]
+pub async fn fetch_webhook_by_id(webhook_id: Uuid, db_pool: &PgPool) -> Result<Webhook> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, url, event_types, secret, active, created_at, updated_at, version
+        FROM webhook
+        WHERE id = $1
+        "#,
+        webhook_id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    match row {
+        Some(row) => Ok(Webhook {
+            id: Some(row.id),
+            url: row.url,
+            event_types: parse_event_types(row.event_types)?,
+            secret: SecretString::new(row.secret.into()),
+            active: row.active,
+            created_at: Some(row.created_at),
+            updated_at: Some(row.updated_at),
+            version: row.version,
+        }),
+        None => Err(Error::PgNotFoundError),
+    }
+}
+
+#[tracing::instrument(name = "inserting webhook into database", skip(payload, db_pool))]
+pub async fn insert_webhook(payload: &Webhook, db_pool: &PgPool) -> Result<Uuid> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO webhook (url, event_types, secret, active)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        payload.url,
+        &event_type_names(&payload.event_types),
+        payload.secret.expose_secret(),
+        payload.active
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    Ok(row.id)
+}
+
+#[tracing::instrument(
+    name = "updating webhook details in database",
+    skip(webhook, webhook_id, db_pool)
+)]
+pub async fn update_webhook(webhook: Webhook, webhook_id: Uuid, db_pool: &PgPool) -> Result<()> {
+    match sqlx::query!(
+        r#"
+        UPDATE webhook
+        SET url = $1, event_types = $2, secret = $3, active = $4, version = version + 1
+        WHERE id = $5 AND version = $6
+        RETURNING version
+        "#,
+        webhook.url,
+        &event_type_names(&webhook.event_types),
+        webhook.secret.expose_secret(),
+        webhook.active,
+        webhook_id,
+        webhook.version
+    )
+    .fetch_optional(db_pool)
+    .await
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Error::PgNotFoundError),
+        Err(err) => Err(Error::from(err)),
+    }
+}
+
+// Deliveries to the webhook, and their attempts, are deleted with it
+#[tracing::instrument(name = "deleting webhook from database", skip(webhook_id, db_pool))]
+pub async fn delete_webhook(webhook_id: Uuid, db_pool: &PgPool) -> Result<()> {
+    match sqlx::query!(
+        r#"
+        DELETE FROM webhook
+        WHERE id = $1
+        RETURNING id
+        "#,
+        webhook_id
+    )
+    .fetch_optional(db_pool)
+    .await
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(Error::PgNotFoundError),
+        Err(err) => Err(Error::from(err)),
+    }
+}
+
+// Queues `payload` for every active webhook subscribed to `event`. Returns the number of
+// deliveries queued
+#[tracing::instrument(
+    name = "inserting webhook deliveries into database",
+    skip(payload, db_pool)
+)]
+pub async fn insert_webhook_deliveries(
+    event: WebhookEvent,
+    payload: &serde_json::Value,
+    db_pool: &PgPool,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery (webhook_id, event_type, payload)
+        SELECT id, $1, $2
+        FROM webhook
+        WHERE active AND $1 = ANY(event_types)
+        "#,
+        event.as_str(),
+        payload
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Claims up to `limit` pending deliveries that are due, oldest first. A claimed delivery
+// isn't due again for `lease_seconds`, so it is only retried by another poll (or worker)
+// if its attempt is never recorded. Deliveries to inactive webhooks wait until the
+// webhook is activated again
+#[tracing::instrument(
+    name = "claiming due webhook deliveries from database",
+    skip(limit, lease_seconds, db_pool)
+)]
+pub async fn claim_webhook_deliveries(
+    limit: i64,
+    lease_seconds: f64,
+    db_pool: &PgPool,
+) -> Result<Vec<PendingWebhookDelivery>> {
+    let rows = sqlx::query!(
+        r#"
+        WITH due AS (
+            SELECT d.id
+            FROM webhook_delivery d
+            INNER JOIN webhook w ON d.webhook_id = w.id
+            WHERE d.status = 'PENDING' AND d.next_attempt_at <= NOW() AND w.active
+            ORDER BY d.next_attempt_at ASC, d.id ASC
+            LIMIT $1
+            FOR UPDATE OF d SKIP LOCKED
+        )
+        UPDATE webhook_delivery d
+        SET next_attempt_at = NOW() + make_interval(secs => $2)
+        FROM due, webhook w
+        WHERE d.id = due.id AND d.webhook_id = w.id
+        RETURNING d.id, w.url, w.secret, d.event_type, d.payload, d.attempts
+        "#,
+        limit,
+        lease_seconds
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PendingWebhookDelivery {
+            id: row.id,
+            url: row.url,
+            secret: SecretString::new(row.secret.into()),
+            event_type: row.event_type,
+            payload: row.payload,
+            attempts: row.attempts,
+        })
+        .collect())
+}
+
+// Logs an attempt and moves the delivery to `status`. A pending delivery is retried at
+// `next_attempt_at`
+#[tracing::instrument(
+    name = "recording webhook delivery attempt in database",
+    skip(delivery_id, attempt, next_attempt_at, db_pool)
+)]
+pub async fn insert_webhook_delivery_attempt(
+    delivery_id: Uuid,
+    attempt: &WebhookDeliveryAttempt,
+    status: WebhookDeliveryStatus,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+    db_pool: &PgPool,
+) -> Result<()> {
+    let mut tx = db_pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery_attempt (
+            delivery_id, attempt, status_code, error, duration_ms, attempted_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        delivery_id,
+        attempt.attempt,
+        attempt.status_code,
+        attempt.error,
+        attempt.duration_ms,
+        attempt.attempted_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE webhook_delivery
+        SET status = $1, attempts = $2, next_attempt_at = $3
+        WHERE id = $4
+        "#,
+        status as WebhookDeliveryStatus,
+        attempt.attempt,
+        next_attempt_at,
+        delivery_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+// The most recent deliveries to the webhook, newest first, each with its attempts in order
+#[tracing::instrument(
+    name = "fetching webhook deliveries from database",
+    skip(webhook_id, status, limit, db_pool)
+)]
+pub async fn fetch_webhook_deliveries(
+    webhook_id: Uuid,
+    status: Option<WebhookDeliveryStatus>,
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<WebhookDelivery>> {
+    let deliveries = sqlx::query!(
+        r#"
+        SELECT
+            id, webhook_id, event_type, status AS "status: WebhookDeliveryStatus", attempts,
+            next_attempt_at, created_at, updated_at
+        FROM webhook_delivery
+        WHERE webhook_id = $1 AND ($2::webhook_delivery_status IS NULL OR status = $2)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3
+        "#,
+        webhook_id,
+        status as Option<WebhookDeliveryStatus>,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let delivery_ids: Vec<Uuid> = deliveries.iter().map(|delivery| delivery.id).collect();
+
+    let attempts = sqlx::query!(
+        r#"
+        SELECT delivery_id, attempt, status_code, error, duration_ms, attempted_at
+        FROM webhook_delivery_attempt
+        WHERE delivery_id = ANY($1)
+        ORDER BY attempt ASC, attempted_at ASC
+        "#,
+        &delivery_ids
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut attempt_logs: HashMap<Uuid, Vec<WebhookDeliveryAttempt>> = HashMap::new();
+
+    for attempt in attempts {
+        attempt_logs
+            .entry(attempt.delivery_id)
+            .or_default()
+            .push(WebhookDeliveryAttempt {
+                attempt: attempt.attempt,
+                status_code: attempt.status_code,
+                error: attempt.error,
+                duration_ms: attempt.duration_ms,
+                attempted_at: attempt.attempted_at,
+            });
+    }
+
+    Ok(deliveries
+        .into_iter()
+        .map(|delivery| WebhookDelivery {
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: (delivery.status == WebhookDeliveryStatus::PENDING)
+                .then_some(delivery.next_attempt_at),
+            created_at: delivery.created_at,
+            updated_at: delivery.updated_at,
+            attempt_log: attempt_logs.remove(&delivery.id).unwrap_or_default(),
+        })
+        .collect())
+}
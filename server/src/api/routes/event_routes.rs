@@ -0,0 +1,10 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::api_get_events;
+use crate::server::ServerState;
+
+pub fn event_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/events` path
+    Router::new().route("/", get(api_get_events))
+}
@@ -0,0 +1,10 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::api_get_email_domain_violations;
+use crate::server::ServerState;
+
+pub fn identity_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/identity` path
+    Router::new().route("/violations", get(api_get_email_domain_violations))
+}
@@ -0,0 +1,17 @@
+use axum::extract::DefaultBodyLimit;
+use axum::routing::post;
+use axum::Router;
+
+use crate::api::controllers::api_import_records;
+use crate::server::ServerState;
+
+// Bulk imports can be much larger than the default 2MB body limit
+const IMPORT_BODY_LIMIT: usize = 20 * 1024 * 1024;
+
+pub fn import_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/import` path
+    Router::new().route(
+        "/",
+        post(api_import_records).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+    )
+}
@@ -1,15 +1,31 @@
 mod auth_routes;
+mod event_routes;
 mod health_routes;
+mod identity_routes;
+mod import_routes;
+mod report_routes;
 mod requester_routes;
+mod search_routes;
 mod software_request_routes;
 mod software_review;
 mod software_routes;
+mod stats_routes;
+mod ticket_routes;
 mod user_routes;
+mod webhook_routes;
 
 pub use auth_routes::auth_routes;
+pub use event_routes::event_routes;
 pub use health_routes::health_routes;
+pub use identity_routes::identity_routes;
+pub use import_routes::import_routes;
+pub use report_routes::report_routes;
 pub use requester_routes::requester_routes;
+pub use search_routes::search_routes;
 pub use software_request_routes::software_request_routes;
 pub use software_review::software_review_routes;
 pub use software_routes::software_routes;
+pub use stats_routes::stats_routes;
+pub use ticket_routes::ticket_routes;
 pub use user_routes::user_routes;
+pub use webhook_routes::webhook_routes;
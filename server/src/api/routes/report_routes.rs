@@ -0,0 +1,10 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::api_get_summary_report;
+use crate::server::ServerState;
+
+pub fn report_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/reports` path
+    Router::new().route("/summary", get(api_get_summary_report))
+}
@@ -0,0 +1,10 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::api_search;
+use crate::server::ServerState;
+
+pub fn search_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/search` path
+    Router::new().route("/", get(api_search))
+}
@@ -1,9 +1,10 @@
-use axum::routing::{delete, get};
+use axum::routing::{delete, get, patch};
 use axum::Router;
 
 use crate::api::controllers::{
-    api_create_software_request, api_delete_software_request, api_get_all_software_requests,
-    api_update_software_request,
+    api_assign_software_request, api_create_software_request, api_delete_software_request,
+    api_get_all_software_requests, api_get_my_software_request_queue,
+    api_get_software_request_queue, api_update_software_request,
 };
 use crate::server::ServerState;
 
@@ -14,8 +15,12 @@ pub fn software_request_routes() -> Router<ServerState> {
             "/",
             get(api_get_all_software_requests).post(api_create_software_request),
         )
+        // Software requests without a review yet
+        .route("/queue", get(api_get_software_request_queue))
+        .route("/queue/mine", get(api_get_my_software_request_queue))
         .route(
             "/:request_id",
             delete(api_delete_software_request).patch(api_update_software_request),
         )
+        .route("/:request_id/assignee", patch(api_assign_software_request))
 }
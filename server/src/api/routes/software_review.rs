@@ -1,9 +1,11 @@
-use axum::routing::{delete, get};
+use axum::routing::{delete, get, post};
 use axum::Router;
 
 use crate::api::controllers::{
-    api_create_software_review, api_delete_software_review, api_export_software_review,
-    api_get_all_software_reviews, api_update_software_review,
+    api_create_review_comment, api_create_software_review, api_delete_review_comment,
+    api_delete_software_review, api_export_software_review, api_export_software_reviews,
+    api_get_all_software_reviews, api_get_review_comments, api_post_review_to_ticket,
+    api_update_review_comment, api_update_software_review, api_verify_software_review,
 };
 use crate::server::ServerState;
 
@@ -18,5 +20,16 @@ pub fn software_review_routes() -> Router<ServerState> {
             "/:review_id",
             delete(api_delete_software_review).patch(api_update_software_review),
         )
+        .route("/export", post(api_export_software_reviews))
+        .route("/verify", post(api_verify_software_review))
         .route("/:review_id/export", get(api_export_software_review))
+        .route("/:review_id/ticket", post(api_post_review_to_ticket))
+        .route(
+            "/:review_id/comments",
+            get(api_get_review_comments).post(api_create_review_comment),
+        )
+        .route(
+            "/:review_id/comments/:comment_id",
+            delete(api_delete_review_comment).patch(api_update_review_comment),
+        )
 }
@@ -2,7 +2,8 @@ use axum::routing::{delete, get};
 use axum::Router;
 
 use crate::api::controllers::{
-    api_create_software, api_delete_software, api_get_all_software, api_update_software,
+    api_create_software, api_delete_software, api_get_all_software, api_get_similar_software,
+    api_update_software,
 };
 use crate::server::ServerState;
 
@@ -10,6 +11,7 @@ pub fn software_routes() -> Router<ServerState> {
     // All routes are under the `/api/v1/software` path
     Router::new()
         .route("/", get(api_get_all_software).post(api_create_software))
+        .route("/similar", get(api_get_similar_software))
         .route(
             "/:software_id",
             delete(api_delete_software).patch(api_update_software),
@@ -0,0 +1,19 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::{
+    api_get_answer_stats, api_get_department_stats, api_get_export_stats, api_get_review_stats,
+    api_get_reviewer_workload_stats, api_get_unreviewed_request_stats,
+};
+use crate::server::ServerState;
+
+pub fn stats_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/stats` path
+    Router::new()
+        .route("/reviews", get(api_get_review_stats))
+        .route("/answers", get(api_get_answer_stats))
+        .route("/exports", get(api_get_export_stats))
+        .route("/unreviewed", get(api_get_unreviewed_request_stats))
+        .route("/reviewers", get(api_get_reviewer_workload_stats))
+        .route("/departments", get(api_get_department_stats))
+}
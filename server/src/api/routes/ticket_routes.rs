@@ -0,0 +1,10 @@
+use axum::routing::get;
+use axum::Router;
+
+use crate::api::controllers::api_get_ticket;
+use crate::server::ServerState;
+
+pub fn ticket_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/tickets` path
+    Router::new().route("/:td_request_id", get(api_get_ticket))
+}
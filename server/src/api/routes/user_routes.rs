@@ -2,7 +2,8 @@ use axum::routing::{delete, get, post};
 use axum::Router;
 
 use crate::api::controllers::{
-    api_change_password, api_create_user, api_delete_user, api_get_all_users, api_update_user,
+    api_change_password, api_create_user, api_delete_user, api_get_all_users,
+    api_get_my_comment_mentions, api_update_user,
 };
 use crate::server::ServerState;
 
@@ -12,4 +13,6 @@ pub fn user_routes() -> Router<ServerState> {
         .route("/", get(api_get_all_users).post(api_create_user))
         .route("/:user_id", delete(api_delete_user).patch(api_update_user))
         .route("/password", post(api_change_password))
+        // Review comments mentioning the logged in user
+        .route("/mentions", get(api_get_my_comment_mentions))
 }
@@ -0,0 +1,19 @@
+use axum::routing::{delete, get};
+use axum::Router;
+
+use crate::api::controllers::{
+    api_create_webhook, api_delete_webhook, api_get_all_webhooks, api_get_webhook_deliveries,
+    api_update_webhook,
+};
+use crate::server::ServerState;
+
+pub fn webhook_routes() -> Router<ServerState> {
+    // All routes are under the `/api/v1/webhooks` path
+    Router::new()
+        .route("/", get(api_get_all_webhooks).post(api_create_webhook))
+        .route(
+            "/:webhook_id",
+            delete(api_delete_webhook).patch(api_update_webhook),
+        )
+        .route("/:webhook_id/deliveries", get(api_get_webhook_deliveries))
+}
@@ -0,0 +1,49 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use crate::api::repositories::{
+    fetch_requesters_outside_email_domains, fetch_users_outside_email_domains,
+};
+use crate::config::IdentityConfig;
+use crate::Result;
+
+// Users and requesters whose email domain is not allowed by the identity config. Records
+// created before a domain was removed from the config are only found this way
+#[tracing::instrument(name = "getting email domain violations", skip(identity, db_pool))]
+pub async fn get_email_domain_violations(
+    identity: &IdentityConfig,
+    db_pool: &PgPool,
+) -> Result<(Vec<Value>, Vec<Value>)> {
+    // An empty list allows any domain, so there is nothing to report
+    let users = if identity.staff_email_domains.is_empty() {
+        vec![]
+    } else {
+        fetch_users_outside_email_domains(&identity.staff_email_domains, db_pool).await?
+    };
+
+    let requesters = if identity.requester_email_domains.is_empty() {
+        vec![]
+    } else {
+        fetch_requesters_outside_email_domains(&identity.requester_email_domains, db_pool).await?
+    };
+
+    let wrapped_users: Vec<Value> = users
+        .into_iter()
+        .map(|user| {
+            json!({
+                "user": user
+            })
+        })
+        .collect();
+
+    let wrapped_requesters: Vec<Value> = requesters
+        .into_iter()
+        .map(|requester| {
+            json!({
+                "requester": requester
+            })
+        })
+        .collect();
+
+    Ok((wrapped_users, wrapped_requesters))
+}
@@ -0,0 +1,448 @@
+use sqlx::{Connection, PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::api::models::{
+    ImportKind, ImportRow, ImportRowResult, ImportRowStatus, RequesterDTO, RequesterImportRow,
+    SoftwareDTO, SoftwareImportRow, SoftwareRequestImportRow, SoftwareReviewImportRow,
+};
+use crate::api::repositories::{
+    fetch_requester_id_by_email, fetch_software_id_by_name, fetch_software_request_by_ticket,
+    fetch_software_request_ticket_by_software_id, fetch_user_id_by_email,
+    insert_import_software_review, insert_review_requester, insert_review_software,
+    insert_review_software_request,
+};
+use crate::config::{IdentityConfig, ReviewExpiryConfig};
+use crate::{Error, FieldError, FieldErrorCode, Result};
+
+// A CSV row and either its parsed contents, or every error found while parsing it
+type ParsedRow<T> = (u64, std::result::Result<T, Vec<FieldError>>);
+
+// The rows of each imported CSV file, parsed up front so a malformed file is rejected
+// before anything is written
+enum ParsedFile {
+    Software(Vec<ParsedRow<SoftwareImportRow>>),
+    Requesters(Vec<ParsedRow<RequesterImportRow>>),
+    Requests(Vec<ParsedRow<SoftwareRequestImportRow>>),
+    Reviews(Vec<ParsedRow<SoftwareReviewImportRow>>),
+}
+
+// Imports the CSV `files` in a single transaction, in the order of `ImportKind::ALL`.
+// Each row is imported within its own savepoint, so an invalid row is reported and
+// skipped without affecting the others. A dry run reports the same results but rolls
+// back the transaction
+#[tracing::instrument(
+    name = "importing records",
+    skip(files, importer_id, identity, review_expiry, db_pool)
+)]
+pub async fn import_records(
+    files: Vec<(ImportKind, String)>,
+    dry_run: bool,
+    importer_id: Uuid,
+    identity: &IdentityConfig,
+    review_expiry: &ReviewExpiryConfig,
+    db_pool: &PgPool,
+) -> Result<Vec<(ImportKind, Vec<ImportRowResult>)>> {
+    let mut parsed_files = vec![];
+
+    for kind in ImportKind::ALL {
+        let Some((_, csv)) = files.iter().find(|(file_kind, _)| *file_kind == kind) else {
+            continue;
+        };
+
+        let parsed_file = match kind {
+            ImportKind::Software => ParsedFile::Software(read_csv(kind, csv)?),
+            ImportKind::Requesters => ParsedFile::Requesters(read_csv(kind, csv)?),
+            ImportKind::Requests => ParsedFile::Requests(read_csv(kind, csv)?),
+            ImportKind::Reviews => ParsedFile::Reviews(read_csv(kind, csv)?),
+        };
+
+        parsed_files.push((kind, parsed_file));
+    }
+
+    let mut tx = db_pool.begin().await?;
+    let mut import_results = vec![];
+
+    for (kind, parsed_file) in parsed_files {
+        let mut results = vec![];
+
+        match parsed_file {
+            ParsedFile::Software(rows) => {
+                for (row, parsed) in rows {
+                    let software = match parsed {
+                        Ok(software) => software,
+                        Err(errors) => {
+                            results.push(ImportRowResult::rejected(row, errors));
+                            continue;
+                        }
+                    };
+
+                    let mut savepoint = tx.begin().await?;
+                    let result = import_software(&software.to_dto(), &mut savepoint).await;
+                    results.push(finish_row(row, result, savepoint).await?);
+                }
+            }
+            ParsedFile::Requesters(rows) => {
+                for (row, parsed) in rows {
+                    let requester = match parsed {
+                        Ok(requester) => requester,
+                        Err(errors) => {
+                            results.push(ImportRowResult::rejected(row, errors));
+                            continue;
+                        }
+                    };
+
+                    let mut savepoint = tx.begin().await?;
+                    let result =
+                        import_requester(&requester.to_dto(), "email", identity, &mut savepoint)
+                            .await;
+                    results.push(finish_row(row, result, savepoint).await?);
+                }
+            }
+            ParsedFile::Requests(rows) => {
+                for (row, parsed) in rows {
+                    let software_request = match parsed {
+                        Ok(software_request) => software_request,
+                        Err(errors) => {
+                            results.push(ImportRowResult::rejected(row, errors));
+                            continue;
+                        }
+                    };
+
+                    let mut savepoint = tx.begin().await?;
+                    let result = import_software_request(&software_request, &mut savepoint).await;
+                    results.push(finish_row(row, result, savepoint).await?);
+                }
+            }
+            ParsedFile::Reviews(rows) => {
+                for (row, parsed) in rows {
+                    let software_review = match parsed {
+                        Ok(software_review) => software_review,
+                        Err(errors) => {
+                            results.push(ImportRowResult::rejected(row, errors));
+                            continue;
+                        }
+                    };
+
+                    let mut savepoint = tx.begin().await?;
+                    let result = import_software_review(
+                        &software_review,
+                        importer_id,
+                        identity,
+                        review_expiry,
+                        &mut savepoint,
+                    )
+                    .await;
+                    results.push(finish_row(row, result, savepoint).await?);
+                }
+            }
+        }
+
+        import_results.push((kind, results));
+    }
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    Ok(import_results)
+}
+
+// Parses every row of `csv`, which must have a header row containing the columns
+// required for `kind`. Column order does not matter and values are trimmed
+fn read_csv<T: ImportRow>(kind: ImportKind, csv: &str) -> Result<Vec<ParsedRow<T>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|err| file_error(kind, FieldErrorCode::MalformedPayload, err.to_string()))?
+        .clone();
+
+    let missing_columns: Vec<&str> = kind
+        .required_columns()
+        .iter()
+        .copied()
+        .filter(|column| !headers.iter().any(|header| header == *column))
+        .collect();
+
+    if !missing_columns.is_empty() {
+        return Err(file_error(
+            kind,
+            FieldErrorCode::MissingField,
+            format!("missing required columns: {}", missing_columns.join(", ")),
+        ));
+    }
+
+    let mut rows = vec![];
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                let row = err.position().map_or(0, |position| position.line());
+                rows.push((
+                    row,
+                    Err(vec![FieldError::new(
+                        "",
+                        FieldErrorCode::MalformedPayload,
+                        err.to_string(),
+                    )]),
+                ));
+                continue;
+            }
+        };
+
+        let row = record.position().map_or(0, |position| position.line());
+
+        let parsed = match record.deserialize::<T>(Some(&headers)) {
+            Ok(parsed) => match parsed.parse() {
+                Ok(()) => Ok(parsed),
+                Err(Error::ValidationError(errors)) => Err(errors.to_vec()),
+                Err(err) => return Err(err),
+            },
+            Err(err) => Err(vec![deserialize_error(&headers, err)]),
+        };
+
+        rows.push((row, parsed));
+    }
+
+    Ok(rows)
+}
+
+// Names the column a value could not be deserialized from when the CSV reader knows it.
+// Errors from the value itself (e.g. `NOPE` for a review option) only have a message
+fn deserialize_error(headers: &csv::StringRecord, err: csv::Error) -> FieldError {
+    if let csv::ErrorKind::Deserialize { err, .. } = err.kind() {
+        let column = err
+            .field()
+            .and_then(|index| headers.get(index as usize))
+            .unwrap_or_default();
+
+        return FieldError::new(column, FieldErrorCode::InvalidValue, err.kind().to_string());
+    }
+
+    FieldError::new("", FieldErrorCode::MalformedPayload, err.to_string())
+}
+
+fn file_error(kind: ImportKind, code: FieldErrorCode, message: String) -> Error {
+    Error::ValidationError(FieldError::new(kind.as_str(), code, message).into())
+}
+
+// Releases the row's savepoint if it was imported, otherwise rolls back anything the row
+// wrote and reports why it was rejected
+async fn finish_row(
+    row: u64,
+    result: Result<(ImportRowStatus, Uuid)>,
+    savepoint: Transaction<'_, Postgres>,
+) -> Result<ImportRowResult> {
+    match result {
+        Ok((status, id)) => {
+            savepoint.commit().await?;
+            Ok(ImportRowResult::imported(row, status, id))
+        }
+        Err(err) => {
+            savepoint.rollback().await?;
+            Ok(ImportRowResult::rejected(row, row_errors(err)?))
+        }
+    }
+}
+
+// Errors caused by the row's contents are reported against the row. Anything else (e.g.
+// the database being unavailable) fails the whole import
+fn row_errors(err: Error) -> Result<Vec<FieldError>> {
+    match err {
+        Error::ValidationError(errors) => Ok(errors.to_vec()),
+        Error::PgFieldConflict(field) => Ok(vec![FieldError::new(
+            "",
+            FieldErrorCode::InvalidValue,
+            format!("a record with the same {} already exists", field),
+        )]),
+        Error::PgRecordExists | Error::PgKeyViolation => Ok(vec![FieldError::new(
+            "",
+            FieldErrorCode::InvalidValue,
+            err.to_string(),
+        )]),
+        err => Err(err),
+    }
+}
+
+fn row_error(field: &str, message: String) -> Error {
+    Error::ValidationError(FieldError::new(field, FieldErrorCode::InvalidValue, message).into())
+}
+
+async fn import_software(
+    software: &SoftwareDTO,
+    conn: &mut PgConnection,
+) -> Result<(ImportRowStatus, Uuid)> {
+    match fetch_software_id_by_name(&software.software_name, conn).await? {
+        Some(software_id) => Ok((ImportRowStatus::Matched, software_id)),
+        None => {
+            let software_id = insert_review_software(software, conn).await?;
+            Ok((ImportRowStatus::Created, software_id))
+        }
+    }
+}
+
+// The requester email domain is only checked for new requesters, existing requesters
+// are reported by the identity violations endpoint instead
+async fn import_requester(
+    requester: &RequesterDTO,
+    email_field: &str,
+    identity: &IdentityConfig,
+    conn: &mut PgConnection,
+) -> Result<(ImportRowStatus, Uuid)> {
+    match fetch_requester_id_by_email(&requester.email, conn).await? {
+        Some(requester_id) => Ok((ImportRowStatus::Matched, requester_id)),
+        None => {
+            identity.validate_requester_email(email_field, &requester.email)?;
+
+            let requester_id = insert_review_requester(requester, conn).await?;
+            Ok((ImportRowStatus::Created, requester_id))
+        }
+    }
+}
+
+async fn import_software_request(
+    software_request: &SoftwareRequestImportRow,
+    conn: &mut PgConnection,
+) -> Result<(ImportRowStatus, Uuid)> {
+    if let Some((software_request_id, _)) =
+        fetch_software_request_by_ticket(&software_request.td_request_id, conn).await?
+    {
+        return Ok((ImportRowStatus::Matched, software_request_id));
+    }
+
+    let software_id = fetch_software_id_by_name(&software_request.software_name, conn)
+        .await?
+        .ok_or_else(|| {
+            row_error(
+                "software_name",
+                format!(
+                    "no software named '{}' exists",
+                    software_request.software_name
+                ),
+            )
+        })?;
+
+    let requester_id = fetch_requester_id_by_email(&software_request.requester_email, conn)
+        .await?
+        .ok_or_else(|| {
+            row_error(
+                "requester_email",
+                format!(
+                    "no requester with the email '{}' exists",
+                    software_request.requester_email
+                ),
+            )
+        })?;
+
+    let software_request_id = insert_software_request(
+        &software_request.td_request_id,
+        &software_request.software_name,
+        software_id,
+        requester_id,
+        conn,
+    )
+    .await?;
+
+    Ok((ImportRowStatus::Created, software_request_id))
+}
+
+// Software can only be requested once, so a request for software that already has one
+// is rejected with the ticket it was requested on
+async fn insert_software_request(
+    td_request_id: &str,
+    software_name: &str,
+    software_id: Uuid,
+    requester_id: Uuid,
+    conn: &mut PgConnection,
+) -> Result<Uuid> {
+    if let Some(requested_on) =
+        fetch_software_request_ticket_by_software_id(software_id, conn).await?
+    {
+        return Err(row_error(
+            "software_name",
+            format!(
+                "software '{}' has already been requested on ticket '{}'",
+                software_name, requested_on
+            ),
+        ));
+    }
+
+    insert_review_software_request(td_request_id, software_id, requester_id, conn).await
+}
+
+async fn import_software_review(
+    software_review: &SoftwareReviewImportRow,
+    importer_id: Uuid,
+    identity: &IdentityConfig,
+    review_expiry: &ReviewExpiryConfig,
+    conn: &mut PgConnection,
+) -> Result<(ImportRowStatus, Uuid)> {
+    let reviewer_id = match &software_review.reviewer_email {
+        Some(reviewer_email) => fetch_user_id_by_email(reviewer_email, conn)
+            .await?
+            .ok_or_else(|| {
+                row_error(
+                    "reviewer_email",
+                    format!("no user with the email '{}' exists", reviewer_email),
+                )
+            })?,
+        None => importer_id,
+    };
+
+    // A review for an existing ticket is linked to its software request. Otherwise the
+    // software and requester are matched or created like the other files
+    let software_request_id =
+        match fetch_software_request_by_ticket(&software_review.td_request_id, conn).await? {
+            Some((_, true)) => {
+                return Err(row_error(
+                    "td_request_id",
+                    format!(
+                        "ticket '{}' has already been reviewed",
+                        software_review.td_request_id
+                    ),
+                ))
+            }
+            Some((software_request_id, false)) => software_request_id,
+            None => {
+                let (_, software_id) = import_software(&software_review.software(), conn).await?;
+                let (_, requester_id) = import_requester(
+                    &software_review.requester(),
+                    "requester_email",
+                    identity,
+                    conn,
+                )
+                .await?;
+
+                insert_software_request(
+                    &software_review.td_request_id,
+                    &software_review.software_name,
+                    software_id,
+                    requester_id,
+                    conn,
+                )
+                .await?
+            }
+        };
+
+    let reviewed_at = software_review
+        .reviewed_at
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc());
+
+    let payload = software_review.to_payload(reviewer_id);
+
+    let software_review_id = insert_import_software_review(
+        &payload,
+        software_request_id,
+        reviewed_at,
+        review_expiry.validity_days(payload.outcome()),
+        conn,
+    )
+    .await?;
+
+    Ok((ImportRowStatus::Created, software_review_id))
+}
@@ -1,28 +1,58 @@
 mod auth_service;
+mod identity_service;
+mod import_service;
 mod requester_service;
+mod review_comment_service;
+mod search_service;
 mod software_request_service;
 mod software_review_service;
 mod software_service;
+mod stats_service;
+mod summary_report_service;
+mod ticketing_service;
 mod user_service;
 mod user_token_service;
+mod webhook_service;
 
 pub use auth_service::{compute_password_hash, validate_credentials, verify_password_hash};
+pub use identity_service::get_email_domain_violations;
+pub use import_service::import_records;
 pub use requester_service::{
-    create_requester, get_all_requesters, remove_requester, update_requester_details,
+    create_requester, export_all_requesters, get_all_requesters, remove_requester,
+    update_requester_details,
 };
+pub use review_comment_service::{
+    create_review_comment, get_comment_mentions, get_review_comments, remove_review_comment,
+    update_review_comment_details,
+};
+pub use search_service::search_entities;
 pub use software_request_service::{
-    create_software_request, get_all_software_requests, remove_software_request,
+    assign_software_request, create_software_request, export_all_software_requests,
+    get_all_software_requests, get_unreviewed_software_requests, remove_software_request,
     update_software_request_details,
 };
 pub use software_review_service::{
-    create_software_review, get_all_software_reviews, get_software_review, remove_software_review,
-    update_review_exported, update_software_review_details,
+    create_software_review, export_all_software_reviews, export_software_reviews,
+    flag_expiring_reviews, get_all_software_reviews, get_review_export_ids, get_software_review,
+    remove_software_review, reopen_expired_requests, update_review_exported,
+    update_software_review_details, verify_software_review,
 };
 pub use software_service::{
-    create_software, get_all_software, remove_software, update_software_details,
+    create_software, export_all_software, get_all_software, get_similar_software, remove_software,
+    update_software_details,
+};
+pub use stats_service::{
+    get_answer_stats, get_department_stats, get_export_stats, get_review_stats,
+    get_reviewer_workload_stats, get_unreviewed_request_stats,
 };
+pub use summary_report_service::get_summary_report;
+pub use ticketing_service::{get_ticket, post_review_to_ticket};
 pub use user_service::{
     change_user_password, create_user, get_all_users, get_user_by_id, remove_user,
     update_user_details,
 };
 pub use user_token_service::{get_valid_tokens, revoke_user_token, save_user_token};
+pub use webhook_service::{
+    create_webhook, deliver_pending_webhooks, get_all_webhooks, get_webhook_deliveries,
+    queue_webhook_event, remove_webhook, update_webhook_details,
+};
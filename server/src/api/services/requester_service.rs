@@ -1,14 +1,15 @@
 use serde_json::{json, Value};
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::controllers::UpdateRequesterPayload;
-use crate::api::models::Requester;
+use crate::api::models::{ChangeAction, ChangeEntity, ChangeEvent, Requester, RequesterDTO};
 use crate::api::repositories::{
     delete_requester, fetch_all_requesters, fetch_requester_by_id, insert_requester,
-    update_requester,
+    stream_all_requesters, update_requester,
 };
-use crate::api::utils::{Metadata, QueryParams};
+use crate::api::utils::{spawn_export, EventHub, Metadata, QueryParams};
 use crate::{Error, Result};
 
 #[tracing::instrument(name = "getting all requesters", skip(query_params, db_pool))]
@@ -16,6 +17,62 @@ pub async fn get_all_requesters(
     query_params: QueryParams,
     db_pool: &PgPool,
 ) -> Result<(Vec<Value>, Metadata)> {
+    let (sort_column, sort_direction, filter_field, filter_value) =
+        parse_requester_query_params(&query_params)?;
+
+    let page = query_params.page.unwrap_or(1);
+    let per_page = query_params.per_page.unwrap_or(10);
+
+    let (requesters, metadata) = fetch_all_requesters(
+        sort_column,
+        sort_direction,
+        page,
+        per_page,
+        filter_field,
+        filter_value,
+        db_pool,
+    )
+    .await?;
+
+    let wrapped_requesters: Vec<Value> = requesters
+        .into_iter()
+        .map(|requester| {
+            json!({
+                "requester": requester
+            })
+        })
+        .collect();
+
+    Ok((wrapped_requesters, metadata))
+}
+
+// Exports every requester matching the same sort and filter as `get_all_requesters`,
+// ignoring pagination
+#[tracing::instrument(name = "exporting all requesters", skip(query_params, db_pool))]
+pub fn export_all_requesters(
+    query_params: QueryParams,
+    db_pool: PgPool,
+) -> Result<mpsc::Receiver<Result<RequesterDTO>>> {
+    let (sort_column, sort_direction, filter_field, filter_value) =
+        parse_requester_query_params(&query_params)?;
+
+    Ok(spawn_export(move |rows| async move {
+        stream_all_requesters(
+            sort_column,
+            sort_direction,
+            filter_field,
+            filter_value,
+            &db_pool,
+            rows,
+        )
+        .await
+    }))
+}
+
+// Returns the sort column and direction, and the filter field and value
+fn parse_requester_query_params(
+    query_params: &QueryParams,
+) -> Result<(String, String, Option<String>, Option<String>)> {
     let sort_safe_list = [
         "name".to_string(),
         "email".to_string(),
@@ -33,11 +90,9 @@ pub async fn get_all_requesters(
 
     query_params.parse(&sort_safe_list, &filter_safe_list)?;
 
-    let page = query_params.page.unwrap_or(1);
-    let per_page = query_params.per_page.unwrap_or(10);
-
     let (sort_column, sort_direction) = match query_params
         .sort
+        .clone()
         .unwrap_or("id".to_string())
         .strip_prefix("-")
     {
@@ -48,7 +103,7 @@ pub async fn get_all_requesters(
     let mut filter_field = None;
     let mut filter_value = None;
 
-    if let Some(filter_str) = query_params.filter {
+    if let Some(filter_str) = &query_params.filter {
         let parts: Vec<&str> = filter_str.split(':').collect();
 
         if parts.len() == 2 {
@@ -60,43 +115,51 @@ pub async fn get_all_requesters(
         }
     }
 
-    let (requesters, metadata) = fetch_all_requesters(
-        sort_column,
-        sort_direction,
-        page,
-        per_page,
-        filter_field,
-        filter_value,
-        db_pool,
-    )
-    .await?;
+    Ok((sort_column, sort_direction, filter_field, filter_value))
+}
 
-    let wrapped_requesters: Vec<Value> = requesters
-        .into_iter()
-        .map(|requester| {
-            json!({
-                "requester": requester
-            })
-        })
-        .collect();
+#[tracing::instrument(name = "creating requester", skip(payload, events, db_pool))]
+pub async fn create_requester(
+    payload: &Requester,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<Uuid> {
+    let requester_id = insert_requester(payload, db_pool).await?;
 
-    Ok((wrapped_requesters, metadata))
-}
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Requester,
+        ChangeAction::Created,
+        requester_id,
+    ));
 
-#[tracing::instrument(name = "creating requester", skip(payload, db_pool))]
-pub async fn create_requester(payload: &Requester, db_pool: &PgPool) -> Result<Uuid> {
-    insert_requester(payload, db_pool).await
+    Ok(requester_id)
 }
 
-#[tracing::instrument(name = "removing requester", skip(requester_id, db_pool))]
-pub async fn remove_requester(requester_id: Uuid, db_pool: &PgPool) -> Result<()> {
-    delete_requester(requester_id, db_pool).await
+#[tracing::instrument(name = "removing requester", skip(requester_id, events, db_pool))]
+pub async fn remove_requester(
+    requester_id: Uuid,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    delete_requester(requester_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Requester,
+        ChangeAction::Deleted,
+        requester_id,
+    ));
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "updating requester", skip(payload, requester_id, db_pool))]
+#[tracing::instrument(
+    name = "updating requester",
+    skip(payload, requester_id, events, db_pool)
+)]
 pub async fn update_requester_details(
     payload: UpdateRequesterPayload,
     requester_id: Uuid,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
     // Fetch requester from database if a record exists
@@ -127,5 +190,13 @@ pub async fn update_requester_details(
 
     requester.parse()?;
 
-    update_requester(requester, requester_id, db_pool).await
+    update_requester(requester, requester_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Requester,
+        ChangeAction::Updated,
+        requester_id,
+    ));
+
+    Ok(())
 }
@@ -0,0 +1,164 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::models::{
+    ChangeAction, ChangeEvent, CommentMention, CommentMentionParams, ReviewCommentDTO,
+    ReviewCommentPayload, UserRole,
+};
+use crate::api::repositories::{
+    delete_review_comment, fetch_comment_mentions, fetch_review_comment_by_id,
+    fetch_review_comments, fetch_software_review_by_id, insert_review_comment,
+    insert_review_comment_mentions, update_review_comment,
+};
+use crate::api::utils::EventHub;
+use crate::config::CommentsConfig;
+use crate::{Error, Result};
+
+#[tracing::instrument(name = "getting review comments", skip(review_id, db_pool))]
+pub async fn get_review_comments(
+    review_id: Uuid,
+    db_pool: &PgPool,
+) -> Result<Vec<ReviewCommentDTO>> {
+    // Reports a missing review rather than an empty thread
+    fetch_software_review_by_id(review_id, db_pool).await?;
+
+    fetch_review_comments(review_id, db_pool).await
+}
+
+#[tracing::instrument(
+    name = "creating review comment",
+    skip(review_id, payload, author_id, events, db_pool)
+)]
+pub async fn create_review_comment(
+    review_id: Uuid,
+    payload: &ReviewCommentPayload,
+    author_id: Uuid,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<Uuid> {
+    fetch_software_review_by_id(review_id, db_pool).await?;
+
+    let comment_id = insert_review_comment(review_id, author_id, &payload.body, db_pool).await?;
+
+    events.publish(ChangeEvent::comment(
+        ChangeAction::Created,
+        comment_id,
+        review_id,
+    ));
+
+    notify_mentioned_users(payload, comment_id, review_id, author_id, events, db_pool).await;
+
+    Ok(comment_id)
+}
+
+// Only the author can edit a comment, and only within the edit window
+#[tracing::instrument(
+    name = "updating review comment",
+    skip(payload, review_id, comment_id, editor_id, config, events, db_pool)
+)]
+pub async fn update_review_comment_details(
+    payload: &ReviewCommentPayload,
+    review_id: Uuid,
+    comment_id: Uuid,
+    editor_id: Uuid,
+    config: &CommentsConfig,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    let mut comment = fetch_review_comment_by_id(review_id, comment_id, db_pool).await?;
+
+    if comment.author_id != editor_id {
+        return Err(Error::AuthInvalidRoleError);
+    }
+
+    if !comment.is_editable(config.edit_window_minutes, chrono::Utc::now()) {
+        return Err(Error::CommentEditWindowClosedError);
+    }
+
+    comment.body.clone_from(&payload.body);
+
+    update_review_comment(comment, db_pool).await?;
+
+    events.publish(ChangeEvent::comment(
+        ChangeAction::Updated,
+        comment_id,
+        review_id,
+    ));
+
+    // Users added by the edit are notified, those already mentioned aren't notified again
+    notify_mentioned_users(payload, comment_id, review_id, editor_id, events, db_pool).await;
+
+    Ok(())
+}
+
+// Authors can delete their own comments, and admins can delete any comment
+#[tracing::instrument(
+    name = "removing review comment",
+    skip(review_id, comment_id, user_id, role, events, db_pool)
+)]
+pub async fn remove_review_comment(
+    review_id: Uuid,
+    comment_id: Uuid,
+    user_id: Uuid,
+    role: &UserRole,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    let comment = fetch_review_comment_by_id(review_id, comment_id, db_pool).await?;
+
+    if comment.author_id != user_id && !matches!(role, UserRole::ADMIN) {
+        return Err(Error::AuthInvalidRoleError);
+    }
+
+    delete_review_comment(comment_id, db_pool).await?;
+
+    events.publish(ChangeEvent::comment(
+        ChangeAction::Deleted,
+        comment_id,
+        review_id,
+    ));
+
+    Ok(())
+}
+
+#[tracing::instrument(name = "getting comment mentions", skip(user_id, params, db_pool))]
+pub async fn get_comment_mentions(
+    user_id: Uuid,
+    params: CommentMentionParams,
+    db_pool: &PgPool,
+) -> Result<Vec<CommentMention>> {
+    let limit = params.limit()?;
+
+    fetch_comment_mentions(user_id, limit, db_pool).await
+}
+
+// Records the users mentioned in the comment and tells each of them over `/events`. The
+// comment has already been saved, so a failure is logged rather than returned
+async fn notify_mentioned_users(
+    payload: &ReviewCommentPayload,
+    comment_id: Uuid,
+    review_id: Uuid,
+    author_id: Uuid,
+    events: &EventHub,
+    db_pool: &PgPool,
+) {
+    let emails = payload.mentioned_emails();
+
+    if emails.is_empty() {
+        return;
+    }
+
+    match insert_review_comment_mentions(comment_id, author_id, &emails, db_pool).await {
+        Ok(mentioned_ids) => {
+            for user_id in mentioned_ids {
+                events.publish(ChangeEvent::mention(comment_id, review_id, user_id));
+            }
+        }
+        Err(err) => tracing::error!(
+            error.cause_chain = ?err,
+            error.message = %err,
+            "failed to record mentions in review comment {}",
+            comment_id
+        ),
+    }
+}
@@ -0,0 +1,32 @@
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use crate::api::models::SearchParams;
+use crate::api::repositories::search_all;
+use crate::api::utils::Metadata;
+use crate::Result;
+
+#[tracing::instrument(name = "searching all entities", skip(search_params, db_pool))]
+pub async fn search_entities(
+    search_params: SearchParams,
+    db_pool: &PgPool,
+) -> Result<(Vec<Value>, Metadata)> {
+    search_params.parse()?;
+
+    let page = search_params.page.unwrap_or(1);
+    let per_page = search_params.per_page.unwrap_or(10);
+
+    let (search_hits, metadata) =
+        search_all(search_params.q.trim().to_string(), page, per_page, db_pool).await?;
+
+    let wrapped_search_hits: Vec<Value> = search_hits
+        .into_iter()
+        .map(|search_hit| {
+            json!({
+                "search_result": search_hit
+            })
+        })
+        .collect();
+
+    Ok((wrapped_search_hits, metadata))
+}
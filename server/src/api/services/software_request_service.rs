@@ -1,14 +1,19 @@
 use serde_json::{json, Value};
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::controllers::UpdateSoftwareRequestPayload;
 use crate::api::repositories::{
     delete_software_request, fetch_all_software_requests, fetch_software_request_by_id,
-    insert_software_request, update_software_request,
+    fetch_unreviewed_software_requests, insert_software_request, stream_all_software_requests,
+    update_software_request, update_software_request_assignee,
+};
+use crate::api::utils::{spawn_export, EventHub, Metadata, QueryParams};
+use crate::api::{
+    ChangeAction, ChangeEntity, ChangeEvent, QueuedSoftwareRequest, SoftwareRequest,
+    SoftwareRequestDTO,
 };
-use crate::api::utils::{Metadata, QueryParams};
-use crate::api::SoftwareRequest;
 use crate::{Error, Result};
 
 #[tracing::instrument(name = "getting all software requests", skip(query_params, db_pool))]
@@ -16,23 +21,107 @@ pub async fn get_all_software_requests(
     query_params: QueryParams,
     db_pool: &PgPool,
 ) -> Result<(Vec<Value>, Metadata)> {
-    let sort_safe_list = [];
+    let (filter_field, filter_value) = parse_software_request_query_params(&query_params)?;
+
+    let page = query_params.page.unwrap_or(1);
+    let per_page = query_params.per_page.unwrap_or(10);
+
+    let (software_requests, metadata) =
+        fetch_all_software_requests(page, per_page, filter_field, filter_value, db_pool).await?;
+
+    let wrapped_software_requests: Vec<Value> = software_requests
+        .into_iter()
+        .map(|software_request| {
+            json!({
+                "software_request": software_request
+            })
+        })
+        .collect();
+
+    Ok((wrapped_software_requests, metadata))
+}
+
+// Software requests waiting for a review, oldest first by default. `assignee_id` limits
+// them to the requests assigned to that user
+#[tracing::instrument(
+    name = "getting unreviewed software requests",
+    skip(query_params, assignee_id, db_pool)
+)]
+pub async fn get_unreviewed_software_requests(
+    query_params: QueryParams,
+    assignee_id: Option<Uuid>,
+    db_pool: &PgPool,
+) -> Result<(Vec<QueuedSoftwareRequest>, Metadata)> {
+    let sort_safe_list = ["age".to_string(), "-age".to_string()];
 
     let filter_safe_list = [
         "td_request_id".to_string(),
         "software_name".to_string(),
         "requester_email".to_string(),
+        "assignee_email".to_string(),
     ];
 
     query_params.parse(&sort_safe_list, &filter_safe_list)?;
 
+    // `age` lists the youngest requests first, and `-age` (the default) the oldest
+    let oldest_first = query_params.sort.as_deref() != Some("age");
+
+    let (filter_field, filter_value) = match query_params
+        .filter
+        .as_deref()
+        .and_then(|filter_str| filter_str.split_once(':'))
+    {
+        Some((field, value)) => (Some(field.to_string()), Some(value.to_string())),
+        None => (None, None),
+    };
+
     let page = query_params.page.unwrap_or(1);
     let per_page = query_params.per_page.unwrap_or(10);
 
+    fetch_unreviewed_software_requests(
+        oldest_first,
+        page,
+        per_page,
+        filter_field,
+        filter_value,
+        assignee_id,
+        db_pool,
+    )
+    .await
+}
+
+// Exports every software request matching the same filter as
+// `get_all_software_requests`, ignoring pagination
+#[tracing::instrument(name = "exporting all software requests", skip(query_params, db_pool))]
+pub fn export_all_software_requests(
+    query_params: QueryParams,
+    db_pool: PgPool,
+) -> Result<mpsc::Receiver<Result<SoftwareRequestDTO>>> {
+    let (filter_field, filter_value) = parse_software_request_query_params(&query_params)?;
+
+    Ok(spawn_export(move |rows| async move {
+        stream_all_software_requests(filter_field, filter_value, &db_pool, rows).await
+    }))
+}
+
+// Returns the filter field and value
+fn parse_software_request_query_params(
+    query_params: &QueryParams,
+) -> Result<(Option<String>, Option<String>)> {
+    let sort_safe_list = [];
+
+    let filter_safe_list = [
+        "td_request_id".to_string(),
+        "software_name".to_string(),
+        "requester_email".to_string(),
+    ];
+
+    query_params.parse(&sort_safe_list, &filter_safe_list)?;
+
     let mut filter_field = None;
     let mut filter_value = None;
 
-    if let Some(filter_str) = query_params.filter {
+    if let Some(filter_str) = &query_params.filter {
         let parts: Vec<&str> = filter_str.split(':').collect();
 
         if parts.len() == 2 {
@@ -44,35 +133,72 @@ pub async fn get_all_software_requests(
         }
     }
 
-    let (software_requests, metadata) =
-        fetch_all_software_requests(page, per_page, filter_field, filter_value, db_pool).await?;
+    Ok((filter_field, filter_value))
+}
 
-    let wrapped_software_requests: Vec<Value> = software_requests
-        .into_iter()
-        .map(|software_request| {
-            json!({
-                "software_request": software_request
-            })
-        })
-        .collect();
+#[tracing::instrument(name = "creating software request", skip(payload, events, db_pool))]
+pub async fn create_software_request(
+    payload: &SoftwareRequest,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<Uuid> {
+    let request_id = insert_software_request(payload, db_pool).await?;
 
-    Ok((wrapped_software_requests, metadata))
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareRequest,
+        ChangeAction::Created,
+        request_id,
+    ));
+
+    Ok(request_id)
 }
 
-#[tracing::instrument(name = "creating software request", skip(payload, db_pool))]
-pub async fn create_software_request(payload: &SoftwareRequest, db_pool: &PgPool) -> Result<Uuid> {
-    insert_software_request(payload, db_pool).await
+#[tracing::instrument(
+    name = "assigning software request",
+    skip(request_id, assignee_id, events, db_pool)
+)]
+pub async fn assign_software_request(
+    request_id: Uuid,
+    assignee_id: Option<Uuid>,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    update_software_request_assignee(request_id, assignee_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareRequest,
+        ChangeAction::Updated,
+        request_id,
+    ));
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "removing software reqeust", skip(request_id, db_pool))]
-pub async fn remove_software_request(request_id: uuid::Uuid, db_pool: &PgPool) -> Result<()> {
-    delete_software_request(request_id, db_pool).await
+#[tracing::instrument(name = "removing software reqeust", skip(request_id, events, db_pool))]
+pub async fn remove_software_request(
+    request_id: uuid::Uuid,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    delete_software_request(request_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareRequest,
+        ChangeAction::Deleted,
+        request_id,
+    ));
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "updating software request", skip(payload, request_id, db_pool))]
+#[tracing::instrument(
+    name = "updating software request",
+    skip(payload, request_id, events, db_pool)
+)]
 pub async fn update_software_request_details(
     payload: UpdateSoftwareRequestPayload,
     request_id: uuid::Uuid,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
     // Fetch software request from database if a record exists
@@ -93,5 +219,13 @@ pub async fn update_software_request_details(
 
     software_request.parse()?;
 
-    update_software_request(software_request, request_id, db_pool).await
+    update_software_request(software_request, request_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareRequest,
+        ChangeAction::Updated,
+        request_id,
+    ));
+
+    Ok(())
 }
@@ -1,23 +1,84 @@
+use std::collections::HashSet;
+
 use serde_json::{json, Value};
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::controllers::UpdateSoftwareReviewPayload;
-use crate::api::models::SoftwareReviewPayload;
+use crate::api::models::{
+    ChangeAction, ChangeEntity, ChangeEvent, ReviewExportManifest, ReviewExportPayload,
+    ReviewExportResult, ReviewVerification, ReviewVerificationStatus, SoftwareReviewPayload,
+    WebhookEvent,
+};
 use crate::api::repositories::{
     delete_software_review, fetch_all_software_reviews, fetch_software_review_by_id,
-    fetch_software_review_details, insert_software_review, update_software_review,
+    fetch_software_review_details, flag_expiring_software_reviews, insert_software_review,
+    reopen_expired_software_requests, stream_all_software_reviews, update_software_review,
     update_software_review_exported,
 };
-use crate::api::utils::{Metadata, QueryParams};
+use crate::api::services::queue_webhook_event;
+use crate::api::utils::{
+    content_hash, render_pdf, sanitize_markdown, spawn_export, EventHub, Metadata, QueryParams,
+    ReportIntegrity, ReportTemplate, ZipArchive,
+};
 use crate::api::SoftwareReviewDTO;
-use crate::{Error, Result};
+use crate::config::ReviewExpiryConfig;
+use crate::{Error, FieldError, FieldErrorCode, Result};
 
 #[tracing::instrument(name = "getting all software reviews", skip(query_params, db_pool))]
 pub async fn get_all_software_reviews(
     query_params: QueryParams,
     db_pool: &PgPool,
 ) -> Result<(Vec<Value>, Metadata)> {
+    let (filter_field, filter_value, expiring_within) =
+        parse_software_review_query_params(&query_params)?;
+
+    let page = query_params.page.unwrap_or(1);
+    let per_page = query_params.per_page.unwrap_or(10);
+
+    let (software_reviews, metadata) = fetch_all_software_reviews(
+        page,
+        per_page,
+        filter_field,
+        filter_value,
+        expiring_within,
+        db_pool,
+    )
+    .await?;
+
+    let wrapped_software_reviews: Vec<Value> = software_reviews
+        .into_iter()
+        .map(|software_review| {
+            json!({
+                "software_review": software_review
+            })
+        })
+        .collect();
+
+    Ok((wrapped_software_reviews, metadata))
+}
+
+// Exports every software review matching the same filter as `get_all_software_reviews`,
+// ignoring pagination
+#[tracing::instrument(name = "exporting all software reviews", skip(query_params, db_pool))]
+pub fn export_all_software_reviews(
+    query_params: QueryParams,
+    db_pool: PgPool,
+) -> Result<mpsc::Receiver<Result<SoftwareReviewDTO>>> {
+    let (filter_field, filter_value, expiring_within) =
+        parse_software_review_query_params(&query_params)?;
+
+    Ok(spawn_export(move |rows| async move {
+        stream_all_software_reviews(filter_field, filter_value, expiring_within, &db_pool, rows)
+            .await
+    }))
+}
+
+// Returns the filter field and value, and the number of days from `expiring_within`
+fn parse_software_review_query_params(
+    query_params: &QueryParams,
+) -> Result<(Option<String>, Option<String>, Option<i32>)> {
     let sort_safe_list = [];
 
     let filter_safe_list = [
@@ -30,13 +91,10 @@ pub async fn get_all_software_reviews(
 
     query_params.parse(&sort_safe_list, &filter_safe_list)?;
 
-    let page = query_params.page.unwrap_or(1);
-    let per_page = query_params.per_page.unwrap_or(10);
-
     let mut filter_field = None;
     let mut filter_value = None;
 
-    if let Some(filter_str) = query_params.filter {
+    if let Some(filter_str) = &query_params.filter {
         let parts: Vec<&str> = filter_str.split(':').collect();
 
         if parts.len() == 2 {
@@ -48,42 +106,153 @@ pub async fn get_all_software_reviews(
         }
     }
 
-    let (software_reviews, metadata) =
-        fetch_all_software_reviews(page, per_page, filter_field, filter_value, db_pool).await?;
+    let expiring_within = query_params
+        .expiring_within
+        .as_deref()
+        .map(parse_expiring_within)
+        .transpose()?;
 
-    let wrapped_software_reviews: Vec<Value> = software_reviews
-        .into_iter()
-        .map(|software_review| {
-            json!({
-                "software_review": software_review
-            })
-        })
-        .collect();
+    Ok((filter_field, filter_value, expiring_within))
+}
 
-    Ok((wrapped_software_reviews, metadata))
+// Parses a number of days written as `<days>d`, e.g. `30d`
+fn parse_expiring_within(expiring_within: &str) -> Result<i32> {
+    expiring_within
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<i32>().ok())
+        .filter(|days| (1..=3650).contains(days))
+        .ok_or_else(|| {
+            Error::QueryParamValidationError(format!(
+                "'{}' is an invalid expiring_within query param. Use a number of days \
+                 between 1 and 3650, e.g. 'expiring_within=30d'",
+                expiring_within
+            ))
+        })
 }
 
-#[tracing::instrument(name = "creating software review", skip(payload, db_pool))]
+#[tracing::instrument(
+    name = "creating software review",
+    skip(payload, review_expiry, events, db_pool)
+)]
 pub async fn create_software_review(
     payload: &SoftwareReviewPayload,
     reviewer_id: &Uuid,
+    review_expiry: &ReviewExpiryConfig,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
-    insert_software_review(payload, reviewer_id, db_pool).await
+    let validity_days = review_expiry.validity_days(payload.outcome());
+
+    let review_id = insert_software_review(payload, reviewer_id, validity_days, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareReview,
+        ChangeAction::Created,
+        review_id,
+    ));
+
+    let Some(software_review_dto) =
+        read_review_for_webhooks(WebhookEvent::ReviewCreated, review_id, db_pool).await
+    else {
+        return Ok(());
+    };
+
+    // Records created from the inline details of the software request are new to clients
+    // as well
+    if let Some(software_request) = &payload.software_request {
+        let created = &software_review_dto.software_request;
+
+        let created_records = [
+            (ChangeEntity::SoftwareRequest, created.id),
+            (
+                ChangeEntity::Software,
+                created
+                    .software
+                    .id
+                    .filter(|_| software_request.software.is_some()),
+            ),
+            (
+                ChangeEntity::Requester,
+                created
+                    .requester
+                    .id
+                    .filter(|_| software_request.requester.is_some()),
+            ),
+        ];
+
+        for (entity, id) in created_records {
+            if let Some(id) = id {
+                events.publish(ChangeEvent::new(entity, ChangeAction::Created, id));
+            }
+        }
+    }
+
+    queue_webhook_event(
+        WebhookEvent::ReviewCreated,
+        json!({ "software_review": software_review_dto }),
+        db_pool,
+    )
+    .await;
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "removing software review", skip(review_id, db_pool))]
-pub async fn remove_software_review(review_id: uuid::Uuid, db_pool: &PgPool) -> Result<()> {
-    delete_software_review(review_id, db_pool).await
+// Returns the id and expiry of the reviews that started expiring within `warning_days`
+// since the last check
+#[tracing::instrument(
+    name = "flagging expiring software reviews",
+    skip(warning_days, db_pool)
+)]
+pub async fn flag_expiring_reviews(
+    warning_days: u16,
+    db_pool: &PgPool,
+) -> Result<Vec<(Uuid, chrono::DateTime<chrono::Utc>)>> {
+    flag_expiring_software_reviews(i32::from(warning_days), db_pool).await
+}
+
+#[tracing::instrument(name = "reopening expired software requests", skip(db_pool))]
+pub async fn reopen_expired_requests(db_pool: &PgPool) -> Result<Vec<Uuid>> {
+    reopen_expired_software_requests(db_pool).await
+}
+
+#[tracing::instrument(name = "removing software review", skip(review_id, events, db_pool))]
+pub async fn remove_software_review(
+    review_id: uuid::Uuid,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    // The review is sent with the event, so it is read before it is deleted
+    let software_review = get_software_review(review_id, db_pool).await.ok();
+
+    delete_software_review(review_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareReview,
+        ChangeAction::Deleted,
+        review_id,
+    ));
+
+    if let Some((software_review_dto, _)) = software_review {
+        queue_webhook_event(
+            WebhookEvent::ReviewDeleted,
+            json!({ "software_review": software_review_dto }),
+            db_pool,
+        )
+        .await;
+    }
+
+    Ok(())
 }
 
 #[tracing::instrument(
     name = "updating software review details",
-    skip(payload, review_id, db_pool)
+    skip(payload, review_id, review_expiry, events, db_pool)
 )]
 pub async fn update_software_review_details(
     payload: UpdateSoftwareReviewPayload,
     review_id: Uuid,
+    review_expiry: &ReviewExpiryConfig,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
     // Fetch the existing review details from the database
@@ -142,7 +311,7 @@ pub async fn update_software_review_details(
     }
 
     if let Some(review_notes) = payload.review_notes {
-        software_review.review_notes = review_notes;
+        software_review.review_notes = sanitize_markdown(&review_notes);
         fields_updated = true;
     }
 
@@ -153,7 +322,20 @@ pub async fn update_software_review_details(
 
     software_review.parse()?;
 
-    update_software_review(software_review, review_id, db_pool).await
+    // The review is valid from now, for the outcome of the updated answers
+    let validity_days = review_expiry.validity_days(software_review.outcome());
+
+    update_software_review(software_review, review_id, validity_days, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareReview,
+        ChangeAction::Updated,
+        review_id,
+    ));
+
+    notify_review_webhooks(WebhookEvent::ReviewUpdated, review_id, db_pool).await;
+
+    Ok(())
 }
 
 #[tracing::instrument(name = "get software review by id", skip(review_id, db_pool))]
@@ -175,12 +357,281 @@ pub async fn get_software_review(
 
 #[tracing::instrument(
     name = "update software review",
-    skip(review_id, review_version, db_pool)
+    skip(review_id, review_version, events, db_pool)
 )]
 pub async fn update_review_exported(
     review_id: &Uuid,
     review_version: i32,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
-    update_software_review_exported(review_id, review_version, db_pool).await
+    update_software_review_exported(review_id, review_version, db_pool).await?;
+
+    // Marking the review exported bumps its version
+    events.publish(ChangeEvent::new(
+        ChangeEntity::SoftwareReview,
+        ChangeAction::Updated,
+        *review_id,
+    ));
+
+    notify_review_webhooks(WebhookEvent::ReviewExported, *review_id, db_pool).await;
+
+    Ok(())
+}
+
+// Queues `event` for webhooks with the review as it is now
+async fn notify_review_webhooks(event: WebhookEvent, review_id: Uuid, db_pool: &PgPool) {
+    if let Some(software_review_dto) = read_review_for_webhooks(event, review_id, db_pool).await {
+        queue_webhook_event(
+            event,
+            json!({ "software_review": software_review_dto }),
+            db_pool,
+        )
+        .await
+    }
+}
+
+// The change has already been saved, so a failure to read the review back is logged
+// rather than returned
+async fn read_review_for_webhooks(
+    event: WebhookEvent,
+    review_id: Uuid,
+    db_pool: &PgPool,
+) -> Option<SoftwareReviewDTO> {
+    match get_software_review(review_id, db_pool).await {
+        Ok((software_review_dto, _)) => Some(software_review_dto),
+        Err(err) => {
+            tracing::error!(
+                error.cause_chain = ?err,
+                error.message = %err,
+                "failed to read software review {} for '{}' webhook event",
+                review_id,
+                event.as_str()
+            );
+
+            None
+        }
+    }
+}
+
+// Returns the ids of the reviews to include in a batch export, in the order they were
+// provided
+#[tracing::instrument(name = "getting software reviews to export", skip(payload, db_pool))]
+pub async fn get_review_export_ids(
+    payload: &ReviewExportPayload,
+    db_pool: &PgPool,
+) -> Result<Vec<Uuid>> {
+    payload.parse()?;
+
+    if let Some(review_ids) = &payload.review_ids {
+        let mut included = HashSet::new();
+
+        return Ok(review_ids
+            .iter()
+            .copied()
+            .filter(|review_id| included.insert(*review_id))
+            .collect());
+    }
+
+    let query_params = QueryParams {
+        page: None,
+        per_page: None,
+        sort: None,
+        filter: payload.filter.clone(),
+        format: None,
+        expiring_within: None,
+    };
+
+    // The filter is part of the payload, so an invalid filter is reported as a field error
+    let (filter_field, filter_value, _) = parse_software_review_query_params(&query_params)
+        .map_err(|err| match err {
+            Error::QueryParamValidationError(message) => Error::ValidationError(
+                FieldError::new("filter", FieldErrorCode::InvalidValue, message).into(),
+            ),
+            err => err,
+        })?;
+
+    let (software_reviews, metadata) = fetch_all_software_reviews(
+        1,
+        ReviewExportPayload::MAX_REVIEWS,
+        filter_field,
+        filter_value,
+        None,
+        db_pool,
+    )
+    .await?;
+
+    if software_reviews.is_empty()
+        || metadata.total_records > ReviewExportPayload::MAX_REVIEWS as i64
+    {
+        return Err(Error::ValidationError(
+            FieldError::new(
+                "filter",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "filter matches {} reviews, between 1 and {} can be exported",
+                    metadata.total_records,
+                    ReviewExportPayload::MAX_REVIEWS
+                ),
+            )
+            .into(),
+        ));
+    }
+
+    Ok(software_reviews
+        .into_iter()
+        .map(|software_review| software_review.id)
+        .collect())
+}
+
+// Adds a PDF of each review to `archive` and marks the review exported, followed by a
+// `manifest.json` with the outcome of every review. A review that fails is reported in
+// the manifest rather than ending the export
+#[tracing::instrument(
+    name = "exporting software reviews",
+    skip(
+        review_ids,
+        archive,
+        report_template,
+        report_integrity,
+        events,
+        db_pool
+    )
+)]
+pub async fn export_software_reviews(
+    review_ids: Vec<Uuid>,
+    mut archive: ZipArchive,
+    report_template: std::sync::Arc<ReportTemplate>,
+    report_integrity: std::sync::Arc<ReportIntegrity>,
+    events: EventHub,
+    db_pool: PgPool,
+) -> Result<()> {
+    let mut results = Vec::with_capacity(review_ids.len());
+
+    for review_id in review_ids {
+        let (software_review_dto, software_review_version, pdf) =
+            match render_software_review(review_id, &report_template, &report_integrity, &db_pool)
+                .await
+            {
+                Ok(rendered) => rendered,
+                Err(err) => {
+                    results.push(ReviewExportResult::failed(
+                        review_id,
+                        None,
+                        review_export_error(review_id, err),
+                    ));
+                    continue;
+                }
+            };
+
+        let software_request = &software_review_dto.software_request;
+
+        // Only fails if the client has gone away, which ends the export
+        let file = archive
+            .add_file(
+                &format!(
+                    "{} - {}.pdf",
+                    software_request.td_request_id, software_request.software.software_name
+                ),
+                &pdf,
+            )
+            .await?;
+
+        // The review is marked exported once its PDF is in the archive
+        let result =
+            match update_review_exported(&review_id, software_review_version, &events, &db_pool)
+                .await
+            {
+                Ok(()) => ReviewExportResult::exported(review_id, file),
+                Err(err) => ReviewExportResult::failed(
+                    review_id,
+                    Some(file),
+                    review_export_error(review_id, err),
+                ),
+            };
+
+        results.push(result);
+    }
+
+    let manifest = serde_json::to_vec_pretty(&ReviewExportManifest::from(results))
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+    archive.add_file("manifest.json", &manifest).await?;
+
+    archive.finish().await
+}
+
+async fn render_software_review(
+    review_id: Uuid,
+    report_template: &ReportTemplate,
+    report_integrity: &ReportIntegrity,
+    db_pool: &PgPool,
+) -> Result<(SoftwareReviewDTO, i32, Vec<u8>)> {
+    let (software_review_dto, software_review_version) =
+        get_software_review(review_id, db_pool).await?;
+
+    let pdf = render_pdf(
+        &software_review_dto,
+        &[],
+        software_review_version,
+        report_template,
+        report_integrity,
+    )
+    .await?;
+
+    Ok((software_review_dto, software_review_version, pdf))
+}
+
+// Checks an uploaded report against the review it was rendered from. The report matches
+// when the review's content is unchanged, even if the review's version has since been
+// bumped by marking it exported
+#[tracing::instrument(
+    name = "verifying software review report",
+    skip(pdf, report_integrity, db_pool)
+)]
+pub async fn verify_software_review(
+    pdf: &[u8],
+    report_integrity: &ReportIntegrity,
+    db_pool: &PgPool,
+) -> Result<ReviewVerification> {
+    let inspection = report_integrity.inspect(pdf)?;
+    let stamp = inspection.stamp;
+
+    let (software_review_dto, current_version) =
+        get_software_review(stamp.review_id, db_pool).await?;
+
+    let content_matches = content_hash(&software_review_dto) == stamp.content_hash;
+
+    let status = if inspection.signature_valid == Some(false) {
+        ReviewVerificationStatus::Tampered
+    } else if content_matches && stamp.review_version <= current_version {
+        if inspection.signed {
+            ReviewVerificationStatus::Valid
+        } else {
+            ReviewVerificationStatus::MetadataOnly
+        }
+    } else if stamp.review_version < current_version {
+        ReviewVerificationStatus::Outdated
+    } else {
+        // The report claims to be the current version, or a version that doesn't exist
+        // yet, but doesn't match it
+        ReviewVerificationStatus::Tampered
+    };
+
+    Ok(ReviewVerification {
+        review_id: stamp.review_id,
+        status,
+        report_version: stamp.review_version,
+        current_version,
+        content_hash: stamp.content_hash,
+        signed: inspection.signed,
+        signature_valid: inspection.signature_valid,
+    })
+}
+
+// The manifest is read by the client, so it gets the same message as an error response
+fn review_export_error(review_id: Uuid, err: Error) -> String {
+    tracing::error!("failed to export software review {}: {:?}", review_id, err);
+
+    err.client_status_and_error().1.to_string()
 }
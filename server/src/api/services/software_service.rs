@@ -1,13 +1,18 @@
 use serde_json::{json, Value};
 use sqlx::PgPool;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::api::controllers::UpdateSoftwarePayload;
-use crate::api::models::Software;
+use crate::api::models::{
+    ChangeAction, ChangeEntity, ChangeEvent, SimilarSoftware, SimilarSoftwareParams, Software,
+    SoftwareDTO,
+};
 use crate::api::repositories::{
-    delete_software, fetch_all_software, fetch_software_by_id, insert_software, update_software,
+    delete_software, fetch_all_software, fetch_similar_software, fetch_software_by_id,
+    insert_software, stream_all_software, update_software,
 };
-use crate::api::utils::{Metadata, QueryParams};
+use crate::api::utils::{spawn_export, EventHub, Metadata, QueryParams};
 use crate::{Error, Result};
 
 #[tracing::instrument(name = "getting all software", skip(query_params, db_pool))]
@@ -15,10 +20,68 @@ pub async fn get_all_software(
     query_params: QueryParams,
     db_pool: &PgPool,
 ) -> Result<(Vec<Value>, Metadata)> {
+    let (sort_column, sort_direction, filter_field, filter_value) =
+        parse_software_query_params(&query_params)?;
+
+    let page = query_params.page.unwrap_or(1);
+    let per_page = query_params.per_page.unwrap_or(10);
+
+    let (software, metadata) = fetch_all_software(
+        sort_column,
+        sort_direction,
+        page,
+        per_page,
+        filter_field,
+        filter_value,
+        db_pool,
+    )
+    .await?;
+
+    let wrapped_software: Vec<Value> = software
+        .into_iter()
+        .map(|software| {
+            json!({
+                "software": software
+            })
+        })
+        .collect();
+
+    Ok((wrapped_software, metadata))
+}
+
+// Exports every software matching the same sort and filter as `get_all_software`,
+// ignoring pagination
+#[tracing::instrument(name = "exporting all software", skip(query_params, db_pool))]
+pub fn export_all_software(
+    query_params: QueryParams,
+    db_pool: PgPool,
+) -> Result<mpsc::Receiver<Result<SoftwareDTO>>> {
+    let (sort_column, sort_direction, filter_field, filter_value) =
+        parse_software_query_params(&query_params)?;
+
+    Ok(spawn_export(move |rows| async move {
+        stream_all_software(
+            sort_column,
+            sort_direction,
+            filter_field,
+            filter_value,
+            &db_pool,
+            rows,
+        )
+        .await
+    }))
+}
+
+// Returns the sort column and direction, and the filter field and value
+fn parse_software_query_params(
+    query_params: &QueryParams,
+) -> Result<(String, String, Option<String>, Option<String>)> {
     let sort_safe_list = [
         "software_name".to_string(),
+        "software_version".to_string(),
         "developer_name".to_string(),
         "-software_name".to_string(),
+        "-software_version".to_string(),
         "-developer_name".to_string(),
     ];
 
@@ -26,22 +89,24 @@ pub async fn get_all_software(
 
     query_params.parse(&sort_safe_list, &filter_safe_list)?;
 
-    let page = query_params.page.unwrap_or(1);
-    let per_page = query_params.per_page.unwrap_or(10);
+    let sort = query_params.sort.clone().unwrap_or("id".to_string());
 
-    let (sort_column, sort_direction) = match query_params
-        .sort
-        .unwrap_or("id".to_string())
-        .strip_prefix("-")
-    {
+    let (sort_column, sort_direction) = match sort.strip_prefix("-") {
         Some(sort_column) => (sort_column.to_string(), "DESC".to_string()),
-        None => ("id".to_string(), "ASC".to_string()),
+        None => (sort, "ASC".to_string()),
+    };
+
+    // Versions are sorted by precedence using the key stored alongside them, since
+    // sorting the version text would place "10.0" before "9.0"
+    let sort_column = match sort_column.as_str() {
+        "software_version" => "software_version_key".to_string(),
+        _ => sort_column,
     };
 
     let mut filter_field = None;
     let mut filter_value = None;
 
-    if let Some(filter_str) = query_params.filter {
+    if let Some(filter_str) = &query_params.filter {
         let parts: Vec<&str> = filter_str.split(':').collect();
 
         if parts.len() == 2 {
@@ -53,43 +118,59 @@ pub async fn get_all_software(
         }
     }
 
-    let (software, metadata) = fetch_all_software(
-        sort_column,
-        sort_direction,
-        page,
-        per_page,
-        filter_field,
-        filter_value,
-        db_pool,
-    )
-    .await?;
+    Ok((sort_column, sort_direction, filter_field, filter_value))
+}
 
-    let wrapped_software: Vec<Value> = software
-        .into_iter()
-        .map(|software| {
-            json!({
-                "software": software
-            })
-        })
-        .collect();
+#[tracing::instrument(name = "getting similar software", skip(params, db_pool))]
+pub async fn get_similar_software(
+    params: SimilarSoftwareParams,
+    db_pool: &PgPool,
+) -> Result<Vec<SimilarSoftware>> {
+    params.parse()?;
 
-    Ok((wrapped_software, metadata))
+    let limit = params.limit.unwrap_or(5);
+
+    fetch_similar_software(params.name.trim(), limit, db_pool).await
 }
 
-#[tracing::instrument(name = "creating software", skip(payload, db_pool))]
-pub async fn create_software(payload: &Software, db_pool: &PgPool) -> Result<Uuid> {
-    insert_software(payload, db_pool).await
+#[tracing::instrument(name = "creating software", skip(payload, events, db_pool))]
+pub async fn create_software(
+    payload: &Software,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<Uuid> {
+    let software_id = insert_software(payload, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Software,
+        ChangeAction::Created,
+        software_id,
+    ));
+
+    Ok(software_id)
 }
 
-#[tracing::instrument(name = "removing software", skip(software_id, db_pool))]
-pub async fn remove_software(software_id: Uuid, db_pool: &PgPool) -> Result<()> {
-    delete_software(software_id, db_pool).await
+#[tracing::instrument(name = "removing software", skip(software_id, events, db_pool))]
+pub async fn remove_software(software_id: Uuid, events: &EventHub, db_pool: &PgPool) -> Result<()> {
+    delete_software(software_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Software,
+        ChangeAction::Deleted,
+        software_id,
+    ));
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "updating software", skip(payload, software_id, db_pool))]
+#[tracing::instrument(
+    name = "updating software",
+    skip(payload, software_id, events, db_pool)
+)]
 pub async fn update_software_details(
     payload: UpdateSoftwarePayload,
     software_id: Uuid,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
     // Fetch software from database if a record exists
@@ -125,5 +206,13 @@ pub async fn update_software_details(
 
     software.parse()?;
 
-    update_software(software, software_id, db_pool).await
+    update_software(software, software_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::Software,
+        ChangeAction::Updated,
+        software_id,
+    ));
+
+    Ok(())
 }
@@ -0,0 +1,191 @@
+use std::future::Future;
+
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::api::models::{
+    period_bounds, AnswerStats, CriterionAnswers, DepartmentStats, ExportStats, ReviewStats,
+    ReviewerWorkloadStats, StatsParams, UnreviewedRequestStats,
+};
+use crate::api::repositories::{
+    fetch_review_answers, fetch_review_totals, fetch_reviewer_workload, fetch_reviews_per_period,
+    fetch_top_departments, fetch_unreviewed_request_totals,
+};
+use crate::api::utils::{ReviewCriterion, StatsCache};
+use crate::{Error, Result};
+
+#[tracing::instrument(name = "getting review stats", skip(params, stats_cache, db_pool))]
+pub async fn get_review_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let interval = params.interval();
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("reviews:{}:{}:{}", from, to, interval.as_str());
+
+    cached(stats_cache, key, async {
+        Ok(ReviewStats {
+            from,
+            to,
+            interval,
+            periods: fetch_reviews_per_period(start, end, interval, db_pool).await?,
+        })
+    })
+    .await
+}
+
+#[tracing::instrument(name = "getting answer stats", skip(params, stats_cache, db_pool))]
+pub async fn get_answer_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("answers:{}:{}", from, to);
+
+    cached(stats_cache, key, async {
+        let answers = fetch_review_answers(start, end, db_pool).await?;
+
+        // Every criterion is listed, in the same order, even when there are no reviews
+        let criteria = ReviewCriterion::ALL
+            .into_iter()
+            .map(|criterion| {
+                let (true_count, false_count, not_sure_count) = answers
+                    .iter()
+                    .find(|(name, ..)| name == criterion.name())
+                    .map_or((0, 0, 0), |(_, true_count, false_count, not_sure_count)| {
+                        (*true_count, *false_count, *not_sure_count)
+                    });
+
+                CriterionAnswers {
+                    criterion,
+                    true_count,
+                    false_count,
+                    not_sure_count,
+                }
+            })
+            .collect();
+
+        Ok(AnswerStats { from, to, criteria })
+    })
+    .await
+}
+
+#[tracing::instrument(name = "getting export stats", skip(params, stats_cache, db_pool))]
+pub async fn get_export_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("exports:{}:{}", from, to);
+
+    cached(stats_cache, key, async {
+        let (total, exported) = fetch_review_totals(start, end, db_pool).await?;
+
+        Ok(ExportStats {
+            from,
+            to,
+            exported,
+            not_exported: total - exported,
+        })
+    })
+    .await
+}
+
+#[tracing::instrument(
+    name = "getting unreviewed request stats",
+    skip(params, stats_cache, db_pool)
+)]
+pub async fn get_unreviewed_request_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("unreviewed:{}:{}", from, to);
+
+    cached(stats_cache, key, async {
+        let (count, oldest_created_at, average_age_days) =
+            fetch_unreviewed_request_totals(start, end, db_pool).await?;
+
+        Ok(UnreviewedRequestStats {
+            from,
+            to,
+            count,
+            oldest_created_at,
+            average_age_days,
+        })
+    })
+    .await
+}
+
+#[tracing::instrument(
+    name = "getting reviewer workload stats",
+    skip(params, stats_cache, db_pool)
+)]
+pub async fn get_reviewer_workload_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("reviewers:{}:{}", from, to);
+
+    cached(stats_cache, key, async {
+        Ok(ReviewerWorkloadStats {
+            from,
+            to,
+            reviewers: fetch_reviewer_workload(start, end, db_pool).await?,
+        })
+    })
+    .await
+}
+
+#[tracing::instrument(name = "getting department stats", skip(params, stats_cache, db_pool))]
+pub async fn get_department_stats(
+    params: StatsParams,
+    stats_cache: &StatsCache,
+    db_pool: &PgPool,
+) -> Result<serde_json::Value> {
+    let (from, to) = params.period()?;
+    let limit = params.limit()?;
+    let (start, end) = period_bounds(from, to);
+
+    let key = format!("departments:{}:{}:{}", from, to, limit);
+
+    cached(stats_cache, key, async {
+        Ok(DepartmentStats {
+            from,
+            to,
+            departments: fetch_top_departments(start, end, limit, db_pool).await?,
+        })
+    })
+    .await
+}
+
+// Serializes the statistics computed by `compute`, reusing the cached value for `key` when
+// there is one
+async fn cached<T: Serialize>(
+    stats_cache: &StatsCache,
+    key: String,
+    compute: impl Future<Output = Result<T>>,
+) -> Result<serde_json::Value> {
+    stats_cache
+        .get_or_compute(key, async {
+            serde_json::to_value(compute.await?)
+                .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+        })
+        .await
+}
@@ -0,0 +1,51 @@
+use sqlx::PgPool;
+
+use crate::api::models::{
+    period_bounds, OutcomeCount, ReviewOutcome, SummaryReport, SummaryReportParams,
+};
+use crate::api::repositories::{
+    fetch_local_admin_software, fetch_review_outcomes, fetch_review_totals,
+    fetch_reviews_per_reviewer, fetch_time_to_review, fetch_top_vendors,
+};
+use crate::Result;
+
+// Number of vendors listed in the summary
+const TOP_VENDORS: i64 = 10;
+
+#[tracing::instrument(name = "getting summary report", skip(params, db_pool))]
+pub async fn get_summary_report(
+    params: SummaryReportParams,
+    db_pool: &PgPool,
+) -> Result<SummaryReport> {
+    let (from, to) = params.period()?;
+
+    // Reviews created on any day from `from` up to and including `to`
+    let (start, end) = period_bounds(from, to);
+
+    let (total_reviews, exported_reviews) = fetch_review_totals(start, end, db_pool).await?;
+    let outcome_counts = fetch_review_outcomes(start, end, db_pool).await?;
+
+    // Every outcome is listed, in the same order, even when no review had it
+    let outcomes = ReviewOutcome::ALL
+        .into_iter()
+        .map(|outcome| OutcomeCount {
+            outcome,
+            count: outcome_counts
+                .iter()
+                .find(|outcome_count| outcome_count.outcome == outcome)
+                .map_or(0, |outcome_count| outcome_count.count),
+        })
+        .collect();
+
+    Ok(SummaryReport {
+        from,
+        to,
+        total_reviews,
+        exported_reviews,
+        outcomes,
+        top_vendors: fetch_top_vendors(start, end, TOP_VENDORS, db_pool).await?,
+        local_admin_software: fetch_local_admin_software(start, end, db_pool).await?,
+        reviews_per_reviewer: fetch_reviews_per_reviewer(start, end, db_pool).await?,
+        time_to_review: fetch_time_to_review(start, end, db_pool).await?,
+    })
+}
@@ -0,0 +1,92 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::models::{SoftwareRequestDTO, SoftwareReviewDTO, Ticket};
+use crate::api::services::{get_software_review, update_review_exported};
+use crate::api::utils::{render_pdf, EventHub, ReportIntegrity, ReportTemplate, TicketingClient};
+use crate::{Error, FieldError, FieldErrorCode, Result};
+
+#[tracing::instrument(name = "getting ticket", skip(client))]
+pub async fn get_ticket(client: &impl TicketingClient, td_request_id: &str) -> Result<Ticket> {
+    if !SoftwareRequestDTO::validate_td_request_id(td_request_id) {
+        return Err(Error::ValidationError(
+            FieldError::new(
+                "td_request_id",
+                FieldErrorCode::InvalidValue,
+                format!(
+                    "'{}' is an invalid ticket number, expected exactly 8 digits",
+                    td_request_id
+                ),
+            )
+            .into(),
+        ));
+    }
+
+    client.fetch_ticket(td_request_id).await
+}
+
+// Attaches the review's PDF to the ticket of its software request and comments with the
+// outcome. The review is then marked exported, the same as downloading its PDF
+#[tracing::instrument(
+    name = "posting software review to ticket",
+    skip(client, report_template, report_integrity, events, db_pool)
+)]
+pub async fn post_review_to_ticket(
+    client: &impl TicketingClient,
+    review_id: Uuid,
+    report_template: &ReportTemplate,
+    report_integrity: &ReportIntegrity,
+    events: &EventHub,
+    db_pool: &PgPool,
+) -> Result<()> {
+    let (software_review_dto, software_review_version) =
+        get_software_review(review_id, db_pool).await?;
+
+    let pdf = render_pdf(
+        &software_review_dto,
+        &[],
+        software_review_version,
+        report_template,
+        report_integrity,
+    )
+    .await?;
+
+    let software_request = &software_review_dto.software_request;
+    let file_name = format!(
+        "{} - {}.pdf",
+        software_request.td_request_id, software_request.software.software_name
+    );
+
+    client
+        .attach_file(
+            &software_request.td_request_id,
+            &file_name,
+            "application/pdf",
+            pdf,
+        )
+        .await?;
+
+    client
+        .post_comment(
+            &software_request.td_request_id,
+            &review_comment(&software_review_dto, &file_name),
+        )
+        .await?;
+
+    update_review_exported(&review_id, software_review_version, events, db_pool).await
+}
+
+fn review_comment(software_review: &SoftwareReviewDTO, file_name: &str) -> String {
+    let software = &software_review.software_request.software;
+
+    format!(
+        "Software review of {} {} completed by {}. Outcome: {}. The review is valid until {} \
+         and its report is attached as \"{}\".",
+        software.software_name,
+        software_review.reviewed_version,
+        software_review.reviewer.name,
+        software_review.outcome().label(),
+        software_review.expires_at.format("%Y-%m-%d"),
+        file_name
+    )
+}
@@ -4,16 +4,16 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::api::controllers::UpdateUserPayload;
-use crate::api::models::User;
+use crate::api::models::{ChangeAction, ChangeEntity, ChangeEvent, User};
 use crate::api::repositories::{
     delete_user, fetch_all_users, fetch_credentials_by_user_id, fetch_user_by_id, insert_user,
     update_user, update_user_password,
 };
 use crate::api::services::{compute_password_hash, verify_password_hash};
-use crate::api::utils::{Metadata, QueryParams};
+use crate::api::utils::{EventHub, Metadata, QueryParams};
 use crate::api::UserDTO;
 use crate::log::spawn_blocking_with_tracing;
-use crate::{Error, Result};
+use crate::{Error, FieldError, FieldErrorCode, Result};
 
 #[tracing::instrument(
     name = "changing user password",
@@ -27,13 +27,23 @@ pub async fn change_user_password(
 ) -> Result<()> {
     if new_password.expose_secret() == current_password.expose_secret() {
         return Err(Error::ValidationError(
-            "change password payload: passwords provided should not match".into(),
+            FieldError::new(
+                "new_password",
+                FieldErrorCode::InvalidValue,
+                "new password must differ from the current password",
+            )
+            .into(),
         ));
     }
 
     if !User::validate_password(new_password.expose_secret()) {
         return Err(Error::ValidationError(
-            "change password payload: invaild password provided by user".into(),
+            FieldError::new(
+                "new_password",
+                FieldErrorCode::InvalidValue,
+                "new password does not meet the password requirements",
+            )
+            .into(),
         ));
     }
 
@@ -73,6 +83,12 @@ pub async fn get_all_users(
 
     query_params.parse(&sort_safe_list, &filter_safe_list)?;
 
+    if query_params.format.is_some() {
+        return Err(Error::QueryParamValidationError(
+            "users cannot be exported".to_string(),
+        ));
+    }
+
     let page = query_params.page.unwrap_or(1);
     let per_page = query_params.per_page.unwrap_or(10);
 
@@ -130,22 +146,39 @@ pub async fn get_user_by_id(user_id: Uuid, db_pool: &PgPool) -> Result<UserDTO>
     Ok(UserDTO::from(&user))
 }
 
-#[tracing::instrument(name = "creating user", skip(payload, db_pool))]
-pub async fn create_user(payload: &User, db_pool: &PgPool) -> Result<Uuid> {
+#[tracing::instrument(name = "creating user", skip(payload, events, db_pool))]
+pub async fn create_user(payload: &User, events: &EventHub, db_pool: &PgPool) -> Result<Uuid> {
     let password_hash = compute_password_hash(&payload.password)?;
 
-    insert_user(payload, password_hash, db_pool).await
+    let user_id = insert_user(payload, password_hash, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::User,
+        ChangeAction::Created,
+        user_id,
+    ));
+
+    Ok(user_id)
 }
 
-#[tracing::instrument(name = "removing user", skip(user_id, db_pool))]
-pub async fn remove_user(user_id: Uuid, db_pool: &PgPool) -> Result<()> {
-    delete_user(user_id, db_pool).await
+#[tracing::instrument(name = "removing user", skip(user_id, events, db_pool))]
+pub async fn remove_user(user_id: Uuid, events: &EventHub, db_pool: &PgPool) -> Result<()> {
+    delete_user(user_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::User,
+        ChangeAction::Deleted,
+        user_id,
+    ));
+
+    Ok(())
 }
 
-#[tracing::instrument(name = "updating user", skip(payload, user_id, db_pool))]
+#[tracing::instrument(name = "updating user", skip(payload, user_id, events, db_pool))]
 pub async fn update_user_details(
     payload: UpdateUserPayload,
     user_id: Uuid,
+    events: &EventHub,
     db_pool: &PgPool,
 ) -> Result<()> {
     // Fetch user from database if a record exists
@@ -176,5 +209,13 @@ pub async fn update_user_details(
 
     user.parse_without_password()?;
 
-    update_user(user, user_id, db_pool).await
+    update_user(user, user_id, db_pool).await?;
+
+    events.publish(ChangeEvent::new(
+        ChangeEntity::User,
+        ChangeAction::Updated,
+        user_id,
+    ));
+
+    Ok(())
 }
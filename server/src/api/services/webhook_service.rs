@@ -0,0 +1,259 @@
+use std::time::Instant;
+
+use secrecy::ExposeSecret;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::controllers::UpdateWebhookPayload;
+use crate::api::models::{
+    PendingWebhookDelivery, Webhook, WebhookDTO, WebhookDelivery, WebhookDeliveryAttempt,
+    WebhookDeliveryParams, WebhookDeliveryStatus, WebhookEvent,
+};
+use crate::api::repositories::{
+    claim_webhook_deliveries, delete_webhook, fetch_all_webhooks, fetch_webhook_by_id,
+    fetch_webhook_deliveries, insert_webhook, insert_webhook_deliveries,
+    insert_webhook_delivery_attempt, update_webhook,
+};
+use crate::api::utils::{
+    sign_webhook_payload, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER,
+    WEBHOOK_TIMESTAMP_HEADER,
+};
+use crate::config::WebhookConfig;
+use crate::{Error, Result};
+
+#[tracing::instrument(name = "getting all webhooks", skip(db_pool))]
+pub async fn get_all_webhooks(db_pool: &PgPool) -> Result<Vec<Value>> {
+    let webhooks = fetch_all_webhooks(db_pool).await?;
+
+    Ok(webhooks
+        .into_iter()
+        .map(|webhook| json!({ "webhook": webhook }))
+        .collect())
+}
+
+#[tracing::instrument(name = "creating webhook", skip(payload, db_pool))]
+pub async fn create_webhook(payload: &Webhook, db_pool: &PgPool) -> Result<Uuid> {
+    insert_webhook(payload, db_pool).await
+}
+
+#[tracing::instrument(name = "removing webhook", skip(webhook_id, db_pool))]
+pub async fn remove_webhook(webhook_id: Uuid, db_pool: &PgPool) -> Result<()> {
+    delete_webhook(webhook_id, db_pool).await
+}
+
+#[tracing::instrument(name = "updating webhook", skip(payload, webhook_id, db_pool))]
+pub async fn update_webhook_details(
+    payload: UpdateWebhookPayload,
+    webhook_id: Uuid,
+    db_pool: &PgPool,
+) -> Result<WebhookDTO> {
+    let mut webhook = fetch_webhook_by_id(webhook_id, db_pool).await?;
+
+    let mut fields_updated = false;
+
+    if let Some(url) = payload.url {
+        webhook.url = url;
+        fields_updated = true;
+    }
+
+    if let Some(event_types) = payload.event_types {
+        webhook.event_types = event_types;
+        fields_updated = true;
+    }
+
+    if let Some(secret) = payload.secret {
+        webhook.secret = secret;
+        fields_updated = true;
+    }
+
+    if let Some(active) = payload.active {
+        webhook.active = active;
+        fields_updated = true;
+    }
+
+    if !fields_updated {
+        return Err(Error::NoUpdatesProvidedError);
+    }
+
+    webhook.parse()?;
+
+    let webhook_dto = WebhookDTO::from(&webhook);
+
+    update_webhook(webhook, webhook_id, db_pool).await?;
+
+    Ok(webhook_dto)
+}
+
+#[tracing::instrument(name = "getting webhook deliveries", skip(webhook_id, params, db_pool))]
+pub async fn get_webhook_deliveries(
+    webhook_id: Uuid,
+    params: WebhookDeliveryParams,
+    db_pool: &PgPool,
+) -> Result<Vec<WebhookDelivery>> {
+    let limit = params.limit()?;
+
+    // Reports a missing webhook rather than an empty log
+    fetch_webhook_by_id(webhook_id, db_pool).await?;
+
+    fetch_webhook_deliveries(webhook_id, params.status, limit, db_pool).await
+}
+
+// Queues `data` for the webhooks subscribed to `event`. The change that caused the event
+// has already been saved, so a failure is logged rather than returned
+#[tracing::instrument(name = "queueing webhook event", skip(data, db_pool))]
+pub async fn queue_webhook_event(event: WebhookEvent, data: Value, db_pool: &PgPool) {
+    let payload = json!({
+        "event": event,
+        "occurred_at": chrono::Utc::now(),
+        "data": data
+    });
+
+    if let Err(err) = insert_webhook_deliveries(event, &payload, db_pool).await {
+        tracing::error!(
+            error.cause_chain = ?err,
+            error.message = %err,
+            "failed to queue '{}' webhook event",
+            event.as_str()
+        );
+    }
+}
+
+// Sends the deliveries that are due, recording each attempt. Returns the number of
+// deliveries attempted
+#[tracing::instrument(name = "delivering pending webhooks", skip(config, client, db_pool))]
+pub async fn deliver_pending_webhooks(
+    config: &WebhookConfig,
+    client: &reqwest::Client,
+    db_pool: &PgPool,
+) -> Result<usize> {
+    // A claimed delivery is left alone for longer than its request can take
+    let lease_seconds = (config.timeout_seconds * 2 + 30) as f64;
+
+    let deliveries = claim_webhook_deliveries(config.batch_size, lease_seconds, db_pool).await?;
+    let delivered = deliveries.len();
+
+    for delivery in deliveries {
+        let delivery_id = delivery.id;
+        let attempt = send_webhook(client, delivery).await?;
+
+        let (status, next_attempt_at) = if attempt.error.is_none() {
+            (WebhookDeliveryStatus::DELIVERED, attempt.attempted_at)
+        } else if attempt.attempt >= config.max_attempts {
+            (WebhookDeliveryStatus::FAILED, attempt.attempted_at)
+        } else {
+            (
+                WebhookDeliveryStatus::PENDING,
+                chrono::Utc::now() + retry_delay(config, attempt.attempt),
+            )
+        };
+
+        if status == WebhookDeliveryStatus::FAILED {
+            tracing::warn!(
+                delivery_id = %delivery_id,
+                "webhook delivery failed after {} attempts",
+                attempt.attempt
+            );
+        }
+
+        insert_webhook_delivery_attempt(delivery_id, &attempt, status, next_attempt_at, db_pool)
+            .await?;
+    }
+
+    Ok(delivered)
+}
+
+// Any 2xx response counts as delivered. Redirects are not followed
+async fn send_webhook(
+    client: &reqwest::Client,
+    delivery: PendingWebhookDelivery,
+) -> Result<WebhookDeliveryAttempt> {
+    let body = delivery.payload.to_string();
+    let attempted_at = chrono::Utc::now();
+    let timestamp = attempted_at.timestamp();
+    let signature = sign_webhook_payload(delivery.secret.expose_secret(), timestamp, &body)?;
+
+    let started = Instant::now();
+
+    let response = client
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+        .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+        .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+        .header(WEBHOOK_SIGNATURE_HEADER, signature)
+        .body(body)
+        .send()
+        .await;
+
+    let duration_ms = started.elapsed().as_millis() as i64;
+
+    let (status_code, error) = match response {
+        Ok(response) if response.status().is_success() => {
+            (Some(i32::from(response.status().as_u16())), None)
+        }
+        Ok(response) => (
+            Some(i32::from(response.status().as_u16())),
+            Some(format!("receiver responded with {}", response.status())),
+        ),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    Ok(WebhookDeliveryAttempt {
+        attempt: delivery.attempts + 1,
+        status_code,
+        error,
+        duration_ms,
+        attempted_at,
+    })
+}
+
+// `retry_base_seconds` after the first attempt, doubling after every attempt after that,
+// up to `retry_max_seconds`
+fn retry_delay(config: &WebhookConfig, attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 31) as u32;
+
+    let seconds = config
+        .retry_base_seconds
+        .saturating_mul(2_u64.saturating_pow(exponent))
+        .min(config.retry_max_seconds);
+
+    i64::try_from(seconds)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .unwrap_or_else(chrono::Duration::max_value)
+}
+
+// Unit Tests
+#[cfg(test)]
+mod retry_delay_tests {
+    use chrono::Duration;
+
+    use super::retry_delay;
+    use crate::config::WebhookConfig;
+
+    #[test]
+    fn delay_doubles_after_every_attempt() {
+        let config = WebhookConfig {
+            retry_base_seconds: 30,
+            retry_max_seconds: 3600,
+            ..Default::default()
+        };
+
+        assert_eq!(Duration::seconds(30), retry_delay(&config, 1));
+        assert_eq!(Duration::seconds(60), retry_delay(&config, 2));
+        assert_eq!(Duration::seconds(120), retry_delay(&config, 3));
+    }
+
+    #[test]
+    fn delay_is_capped() {
+        let config = WebhookConfig {
+            retry_base_seconds: 30,
+            retry_max_seconds: 3600,
+            ..Default::default()
+        };
+
+        assert_eq!(Duration::seconds(3600), retry_delay(&config, 8));
+        assert_eq!(Duration::seconds(3600), retry_delay(&config, i32::MAX));
+    }
+}
@@ -0,0 +1,142 @@
+// Broadcasts changes made through the API to every open `/events` stream, so clients can
+// refresh their lists instead of finding out about a change through a version conflict.
+// Services publish a change once it is saved, and each stream only forwards the changes
+// its user's role may see
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::response::sse::Event;
+use futures_util::stream;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::api::models::ChangeEvent;
+use crate::api::utils::{Claims, TokenCache};
+
+#[derive(Clone, Debug)]
+pub struct EventHub {
+    sender: broadcast::Sender<ChangeEvent>,
+}
+
+struct EventStream {
+    receiver: broadcast::Receiver<ChangeEvent>,
+    claims: Claims,
+    token_cache: TokenCache,
+    token_check: tokio::time::Interval,
+}
+
+impl EventHub {
+    // A stream that falls more than `capacity` events behind misses the oldest ones, and
+    // is sent a `resync` event instead
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+
+        EventHub { sender }
+    }
+
+    // Having nobody listening isn't an error
+    pub fn publish(&self, event: ChangeEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    // SSE events for the user of `claims`, named after each change (e.g.
+    // `software.updated`). The stream ends once the token expires or is revoked, which is
+    // checked every `token_check_interval` and before each event is sent
+    pub fn stream(
+        &self,
+        claims: Claims,
+        token_cache: TokenCache,
+        token_check_interval: Duration,
+    ) -> impl futures_util::Stream<Item = Result<Event, Infallible>> {
+        let state = EventStream {
+            receiver: self.subscribe(),
+            claims,
+            token_cache,
+            token_check: tokio::time::interval(token_check_interval),
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                tokio::select! {
+                    received = state.receiver.recv() => match received {
+                        Ok(event) if event.is_visible_to(&state.claims.sub, &state.claims.role) => {
+                            if !state.is_token_valid().await {
+                                return None;
+                            }
+
+                            if let Ok(sse_event) = Event::default().event(event.name()).json_data(&event) {
+                                return Some((Ok(sse_event), state));
+                            }
+                        }
+                        Ok(_) => (),
+                        // Clients reload their lists, since they can't tell what changed
+                        Err(RecvError::Lagged(missed)) => {
+                            let sse_event = Event::default().event("resync").data(missed.to_string());
+                            return Some((Ok(sse_event), state));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    },
+                    _ = state.token_check.tick() => {
+                        if !state.is_token_valid().await {
+                            return None;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl EventStream {
+    async fn is_token_valid(&self) -> bool {
+        let now = usize::try_from(chrono::Utc::now().timestamp()).unwrap_or_default();
+
+        self.claims.exp > now
+            && self
+                .token_cache
+                .is_token_valid(self.claims.jti, self.claims.sub)
+                .await
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod event_hub_tests {
+    use uuid::Uuid;
+
+    use super::EventHub;
+    use crate::api::models::{ChangeAction, ChangeEntity, ChangeEvent};
+
+    #[tokio::test]
+    async fn published_events_reach_every_subscriber() {
+        let hub = EventHub::new(8);
+        let mut first = hub.subscribe();
+        let mut second = hub.subscribe();
+
+        let event = ChangeEvent::new(
+            ChangeEntity::Software,
+            ChangeAction::Created,
+            Uuid::new_v4(),
+        );
+        hub.publish(event.clone());
+
+        assert_eq!(event, first.recv().await.unwrap());
+        assert_eq!(event, second.recv().await.unwrap());
+    }
+
+    #[test]
+    fn publishing_without_subscribers_is_ignored() {
+        let hub = EventHub::new(8);
+
+        hub.publish(ChangeEvent::new(
+            ChangeEntity::Requester,
+            ChangeAction::Deleted,
+            Uuid::new_v4(),
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use std::future::Future;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::Response;
+use futures_util::stream;
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use super::validation::encode_content_disposition;
+use crate::{Error, Result};
+
+// Rows buffered between the database and the response. A slow client fills the buffer,
+// which pauses reading from the database rather than holding the export in memory
+const EXPORT_BUFFER_ROWS: usize = 256;
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+// Formats the list endpoints can be exported as, instead of paginated JSON
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+    JsonLines,
+}
+
+impl ExportFormat {
+    // The `format` query param takes precedence over the `Accept` header. Returns `None`
+    // when neither asks for an export, in which case the JSON list is returned
+    pub fn from_request(format: Option<&str>, headers: &HeaderMap) -> Result<Option<Self>> {
+        if let Some(format) = format {
+            return format.parse().map(Some);
+        }
+
+        let accept = headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        // The first supported media type is used, ignoring any quality values
+        Ok(accept
+            .split(',')
+            .filter_map(|media_range| media_range.split(';').next())
+            .find_map(|media_type| Self::from_media_type(media_type.trim())))
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "text/csv" => Some(ExportFormat::Csv),
+            XLSX_CONTENT_TYPE => Some(ExportFormat::Xlsx),
+            "application/x-ndjson" | "application/jsonl" => Some(ExportFormat::JsonLines),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => XLSX_CONTENT_TYPE,
+            ExportFormat::JsonLines => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "xlsx" => Ok(ExportFormat::Xlsx),
+            "jsonl" => Ok(ExportFormat::JsonLines),
+            other => Err(Error::QueryParamValidationError(format!(
+                "'{}' is an invaild format query param",
+                other
+            ))),
+        }
+    }
+}
+
+// A record that can be exported. JSON Lines writes the serialized record, while CSV and
+// XLSX write `values` under a header row of `COLUMNS`
+pub trait ExportRow: Serialize + Send + 'static {
+    const COLUMNS: &'static [&'static str];
+
+    fn values(&self) -> Vec<String>;
+}
+
+// Runs `export` in its own task, which sends each row to the returned channel as it is
+// read from the database. An error ends the export and is sent as the last row
+pub fn spawn_export<T, F, Fut>(export: F) -> mpsc::Receiver<Result<T>>
+where
+    T: Send + 'static,
+    F: FnOnce(mpsc::Sender<Result<T>>) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_ROWS);
+    let errors = sender.clone();
+    let export = export(sender);
+
+    tokio::spawn(async move {
+        if let Err(err) = export.await {
+            let _ = errors.send(Err(err)).await;
+        }
+    });
+
+    receiver
+}
+
+// Builds a download of `rows` named `<name>-<date>.<extension>`. CSV and JSON Lines are
+// streamed as rows arrive. XLSX is a zip archive, so the workbook is built before it is
+// sent
+pub async fn export_response<T: ExportRow>(
+    format: ExportFormat,
+    name: &str,
+    mut rows: mpsc::Receiver<Result<T>>,
+) -> Result<Response> {
+    let filename = format!(
+        "{}-{}.{}",
+        name,
+        chrono::Utc::now().format("%Y-%m-%d"),
+        format.extension()
+    );
+
+    let body = match format {
+        ExportFormat::Xlsx => Body::from(write_xlsx(&mut rows).await?),
+        ExportFormat::Csv | ExportFormat::JsonLines => Body::from_stream(stream_rows(format, rows)),
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            encode_content_disposition(&filename),
+        )
+        .body(body)
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+}
+
+enum StreamState<T> {
+    Header(mpsc::Receiver<Result<T>>),
+    Rows(mpsc::Receiver<Result<T>>),
+    Done,
+}
+
+// Encodes each row as it is received. The status has already been sent by the time a
+// row fails, so the error ends the body early and the client sees an incomplete download
+fn stream_rows<T: ExportRow>(
+    format: ExportFormat,
+    rows: mpsc::Receiver<Result<T>>,
+) -> impl futures_util::Stream<Item = Result<Bytes>> {
+    stream::unfold(StreamState::Header(rows), move |state| async move {
+        match state {
+            StreamState::Header(rows) if format == ExportFormat::Csv => {
+                let header = encode_csv_record(T::COLUMNS.iter().map(|column| column.to_string()));
+                Some((header, StreamState::Rows(rows)))
+            }
+            StreamState::Header(mut rows) | StreamState::Rows(mut rows) => {
+                match rows.recv().await {
+                    Some(Ok(row)) => {
+                        let chunk = match format {
+                            ExportFormat::Csv => encode_csv_record(row.values().into_iter()),
+                            _ => encode_json_line(&row),
+                        };
+                        Some((chunk, StreamState::Rows(rows)))
+                    }
+                    Some(Err(err)) => {
+                        tracing::error!("export ended early: {:?}", err);
+                        Some((Err(err), StreamState::Done))
+                    }
+                    None => None,
+                }
+            }
+            StreamState::Done => None,
+        }
+    })
+}
+
+fn encode_csv_record(values: impl Iterator<Item = String>) -> Result<Bytes> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    writer
+        .write_record(values.map(escape_formula))
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+    let record = writer
+        .into_inner()
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.to_string().into())))?;
+
+    Ok(Bytes::from(record))
+}
+
+fn encode_json_line<T: Serialize>(row: &T) -> Result<Bytes> {
+    let mut line = serde_json::to_vec(row)
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+    line.push(b'\n');
+
+    Ok(Bytes::from(line))
+}
+
+// Spreadsheet apps evaluate a CSV cell starting with one of these characters as a
+// formula, so the value is prefixed with `'` to be shown as text instead
+fn escape_formula(value: String) -> String {
+    match value.chars().next() {
+        Some('=' | '+' | '-' | '@' | '\t' | '\r') => format!("'{}", value),
+        _ => value,
+    }
+}
+
+async fn write_xlsx<T: ExportRow>(rows: &mut mpsc::Receiver<Result<T>>) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    let header_format = Format::new().set_bold();
+
+    for (col, column) in T::COLUMNS.iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *column, &header_format)
+            .map_err(xlsx_error)?;
+    }
+    worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+
+    let mut row_index = 1;
+
+    while let Some(row) = rows.recv().await {
+        // Values are written as text, so they are never evaluated as formulas
+        for (col, value) in row?.values().iter().enumerate() {
+            worksheet
+                .write_string(row_index, col as u16, value)
+                .map_err(xlsx_error)?;
+        }
+        row_index += 1;
+    }
+
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+fn xlsx_error(err: XlsxError) -> Error {
+    Error::ServerError(std::sync::Arc::new(err.into()))
+}
+
+// Unit Tests
+#[cfg(test)]
+mod export_format_tests {
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    use super::ExportFormat;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn format_param_takes_precedence_over_accept() {
+        let format = ExportFormat::from_request(Some("xlsx"), &accept("text/csv")).unwrap();
+        assert_eq!(Some(ExportFormat::Xlsx), format);
+    }
+
+    #[test]
+    fn accept_header_selects_export_format() {
+        let format =
+            ExportFormat::from_request(None, &accept("text/CSV;q=0.9, application/json")).unwrap();
+        assert_eq!(Some(ExportFormat::Csv), format);
+    }
+
+    #[test]
+    fn browser_accept_header_returns_json() {
+        let format = ExportFormat::from_request(
+            None,
+            &accept("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+        )
+        .unwrap();
+        assert_eq!(None, format);
+    }
+
+    #[test]
+    fn unknown_format_param_is_invalid() {
+        assert!(ExportFormat::from_request(Some("pdf"), &HeaderMap::new()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod escape_formula_tests {
+    use super::escape_formula;
+
+    #[test]
+    fn formula_values_are_escaped() {
+        for value in ["=HYPERLINK(\"http://evil\")", "+1", "-2+3", "@SUM(A1)"] {
+            assert_eq!(format!("'{}", value), escape_formula(value.to_string()));
+        }
+    }
+
+    #[test]
+    fn plain_values_are_unchanged() {
+        assert_eq!("Slack", escape_formula("Slack".to_string()));
+        assert_eq!("1.0.0", escape_formula("1.0.0".to_string()));
+    }
+}
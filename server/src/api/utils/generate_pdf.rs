@@ -1,328 +1,903 @@
+use std::io::Cursor;
+
 use axum::http::StatusCode;
 use axum::response::Response;
+use owned_ttf_parser::Face;
 use printpdf::*;
-
-use crate::api::models::SoftwareReviewDTO;
-use crate::api::ReviewOptions;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::api::models::{ReviewCommentDTO, SoftwareReviewDTO};
+use crate::api::utils::validation::encode_content_disposition;
+use crate::api::utils::{
+    MarkdownBlock, MarkdownBlockKind, ReportFonts, ReportIntegrity, ReportTemplate, ReviewReport,
+    ReviewReportContent, MM_PER_PT,
+};
 use crate::{Error, Result};
 
-#[tracing::instrument(name = "generating pdf for software review", skip(software_review))]
-pub async fn generate_pdf(software_review: &SoftwareReviewDTO) -> Result<Response> {
-    let (doc, page, layer) = PdfDocument::new(
+// Spacing added after every character, in points
+pub(super) const CHARACTER_SPACING: f32 = 1.0;
+// Thickness of the outline that makes titles and headings bold
+pub(super) const BOLD_OUTLINE: f32 = 1.2;
+// Size of the verification small print, relative to the text
+const SMALL_PRINT_SCALE: f32 = 0.7;
+// Indent of each level of a nested list in the notes
+const LIST_INDENT_MM: f32 = 5.0;
+
+#[tracing::instrument(
+    name = "generating pdf for software review",
+    skip(software_review, review_version, template, integrity)
+)]
+pub async fn generate_pdf(
+    software_review: &SoftwareReviewDTO,
+    review_version: i32,
+    template: &ReportTemplate,
+    integrity: &ReportIntegrity,
+) -> Result<Response> {
+    let buffer = render_pdf(software_review, &[], review_version, template, integrity).await?;
+
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/pdf")
+        .header(
+            "Content-Disposition",
+            encode_content_disposition(&format!(
+                "{}.pdf",
+                software_review.software_request.software.software_name
+            )),
+        )
+        .body(buffer.into())
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+    Ok(response)
+}
+
+// Renders version `review_version` of the review, with `comments`, as a PDF document laid
+// out by `template`, returning the bytes of the file once it is stamped and signed by
+// `integrity`. The stamp only covers the review, so comments don't affect verification
+#[tracing::instrument(
+    name = "rendering pdf for software review",
+    skip(software_review, comments, review_version, template, integrity)
+)]
+pub async fn render_pdf(
+    software_review: &SoftwareReviewDTO,
+    comments: &[ReviewCommentDTO],
+    review_version: i32,
+    template: &ReportTemplate,
+    integrity: &ReportIntegrity,
+) -> Result<Vec<u8>> {
+    let pages = layout_pages(software_review, comments, template)?;
+    let stamp = integrity.stamp(software_review, review_version);
+
+    let page = &template.page;
+    let fonts = &template.fonts;
+
+    let (doc, first_page, first_layer) = PdfDocument::new(
         software_review
             .software_request
             .software
             .software_name
             .clone(),
-        Mm(210.0), // Width (A4 size)
-        Mm(297.0), // Height (A4 size)
+        Mm(page.width_mm),
+        Mm(page.height_mm),
         "Layer 1",
     );
 
-    let current_layer = doc.get_page(page).get_layer(layer);
+    let font = doc
+        .add_external_font(Cursor::new(&template.assets.regular_font))
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+    let font_symbol = doc
+        .add_external_font(Cursor::new(&template.assets.symbol_font))
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
 
-    let image_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join("img")
-        .join("logo.bmp");
+    let metrics = FontMetrics::new(&template.assets.regular_font)?;
 
-    let mut image_file = std::fs::File::open(image_path)
+    let line_height_mm = fonts.line_height * MM_PER_PT;
+    let content_top_mm = page.height_mm - page.content_top_mm - page.margin_mm;
+    let default_color = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    for (page_number, lines) in pages.iter().enumerate() {
+        let current_layer = if page_number == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page_index, layer) =
+                doc.add_page(Mm(page.width_mm), Mm(page.height_mm), "Layer 1");
+            doc.get_page(page_index).get_layer(layer)
+        };
+
+        add_logo(&current_layer, template)?;
+
+        for (line_number, line) in lines.iter().enumerate() {
+            let y = content_top_mm - line_number as f32 * line_height_mm;
+
+            current_layer.begin_text_section();
+            current_layer.set_text_cursor(Mm(page.margin_mm + line.indent_mm), Mm(y));
+            current_layer.set_word_spacing(0.0);
+            current_layer.set_character_spacing(CHARACTER_SPACING);
+            current_layer.set_text_rendering_mode(TextRenderingMode::FillStroke);
+
+            for span in &line.spans {
+                let (span_font, size, outline) = match span.style {
+                    SpanStyle::Title => (&font, fonts.title_size, BOLD_OUTLINE),
+                    SpanStyle::Heading => (&font, fonts.heading_size, BOLD_OUTLINE),
+                    SpanStyle::Text => (&font, fonts.text_size, 0.0),
+                    SpanStyle::Bold => (&font, fonts.text_size, BOLD_OUTLINE),
+                    SpanStyle::Symbol(_) => (&font_symbol, fonts.symbol_size, 0.0),
+                };
+
+                current_layer.set_font(span_font, size);
+                current_layer.set_outline_thickness(outline);
+                current_layer.set_fill_color(match &span.style {
+                    SpanStyle::Symbol(color) => color.clone(),
+                    _ => default_color.clone(),
+                });
+                current_layer.write_text(&span.text, span_font);
+            }
+
+            current_layer.end_text_section();
+        }
+
+        let write_centered = |text: &str, size: f32, y_mm: f32| {
+            let width_mm = metrics.text_width_mm(text, size);
+
+            current_layer.begin_text_section();
+            current_layer.set_text_cursor(
+                Mm(((page.width_mm - width_mm) / 2.0).max(page.margin_mm)),
+                Mm(y_mm),
+            );
+            current_layer.set_character_spacing(CHARACTER_SPACING);
+            current_layer.set_text_rendering_mode(TextRenderingMode::Fill);
+            current_layer.set_fill_color(default_color.clone());
+            current_layer.set_font(&font, size);
+            current_layer.write_text(text, &font);
+            current_layer.end_text_section();
+        };
+
+        let fill_footer = |text: &str| {
+            template.fill_footer(text, software_review, &stamp, page_number + 1, pages.len())
+        };
+
+        write_centered(
+            &fill_footer(&template.footer.text),
+            fonts.text_size,
+            page.margin_mm,
+        );
+
+        // The small print is below the footer, in the bottom margin
+        let small_size = fonts.text_size * SMALL_PRINT_SCALE;
+        let small_line_height_mm = small_size * 1.5 * MM_PER_PT;
+        let small_print = wrap_text(
+            &fill_footer(&template.footer.verification),
+            page.width_mm - page.margin_mm * 2.0,
+            |line| metrics.text_width_mm(line, small_size),
+        );
+
+        for (line_number, line) in small_print.iter().enumerate() {
+            let y_mm = page.margin_mm - (line_number + 1) as f32 * small_line_height_mm;
+
+            if y_mm > 0.0 {
+                write_centered(line, small_size, y_mm);
+            }
+        }
+    }
+
+    let mut buffer = Vec::new();
+    doc.save(&mut std::io::BufWriter::new(&mut buffer))
         .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
 
+    integrity.seal(buffer, &stamp)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SpanStyle {
+    Title,
+    Heading,
+    Text,
+    // Text drawn with the outline of a heading. There is no italic font, so italic text
+    // is drawn as `Text`
+    Bold,
+    Symbol(Color),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Span {
+    text: String,
+    style: SpanStyle,
+}
+
+// A single line of the report. `indent_mm` is the distance from the left margin
+#[derive(Clone, Debug, Default)]
+struct Line {
+    spans: Vec<Span>,
+    indent_mm: f32,
+}
+
+impl Span {
+    fn new(text: String, style: SpanStyle) -> Self {
+        Span { text, style }
+    }
+}
+
+impl Line {
+    fn new(text: String, style: SpanStyle) -> Self {
+        Line {
+            spans: vec![Span::new(text, style)],
+            indent_mm: 0.0,
+        }
+    }
+
+    fn blank() -> Self {
+        Line::default()
+    }
+}
+
+// Draws the template's logo, if it has one, in the top left corner of the page
+pub(super) fn add_logo(layer: &PdfLayerReference, template: &ReportTemplate) -> Result<()> {
+    let (Some(logo), Some(logo_bytes)) = (&template.logo, &template.assets.logo) else {
+        return Ok(());
+    };
+
+    let page = &template.page;
+
     let image = Image::try_from(
-        image_crate::codecs::bmp::BmpDecoder::new(&mut image_file)
+        image_crate::codecs::bmp::BmpDecoder::new(Cursor::new(logo_bytes))
             .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?,
     )
     .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
 
-    let padding = Mm(15.0);
-    let default_color = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
-
     image.add_to_layer(
-        current_layer.clone(),
+        layer.clone(),
         ImageTransform {
-            translate_x: Some(padding),
-            translate_y: Some(Mm(297.0 - 20.0) - padding),
-            scale_x: Some(0.2),
-            scale_y: Some(0.2),
+            translate_x: Some(Mm(page.margin_mm)),
+            translate_y: Some(Mm(page.height_mm - logo.height_mm - page.margin_mm)),
+            scale_x: Some(logo.scale),
+            scale_y: Some(logo.scale),
             rotate: None,
             dpi: None,
         },
     );
 
-    let font_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join("font")
-        .join("NotoSans-Regular.ttf");
+    Ok(())
+}
 
-    let font_symbol_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join("font")
-        .join("NotoSansSymbols2-Regular.ttf");
+// Advance widths of the glyphs in a font, used to measure text before it is written
+pub(super) struct FontMetrics<'a> {
+    face: Face<'a>,
+}
 
-    let font = doc
-        .add_external_font(
-            std::fs::File::open(font_path)
-                .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?,
-        )
-        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+impl<'a> FontMetrics<'a> {
+    pub(super) fn new(font: &'a [u8]) -> Result<Self> {
+        let face = Face::parse(font, 0)
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
 
-    let font_symbol = doc
-        .add_external_font(
-            std::fs::File::open(font_symbol_path)
-                .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?,
-        )
-        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+        Ok(FontMetrics { face })
+    }
 
-    let est_offset = chrono::FixedOffset::west_opt(5 * 3600).unwrap(); // UTC-5
+    // Width of `text` written at `font_size` points, including the character spacing
+    pub(super) fn text_width_mm(&self, text: &str, font_size: f32) -> f32 {
+        let units_per_em = self.face.units_per_em() as f32;
 
-    // ----------------------------------------------------------------------------
-    current_layer.begin_text_section();
+        let width_pt: f32 = text
+            .chars()
+            .map(|c| {
+                // Characters missing from the font are drawn as the `.notdef` glyph
+                let glyph = self.face.glyph_index(c).unwrap_or_default();
+                let advance = self.face.glyph_hor_advance(glyph).unwrap_or_default() as f32;
 
-    current_layer.set_font(&font, 16.0);
-    current_layer.set_outline_thickness(1.2);
-    current_layer.set_text_cursor(padding, Mm(280.0 - 20.0) - padding);
-    current_layer.set_line_height(33.0);
-    current_layer.set_word_spacing(0.0);
-    current_layer.set_character_spacing(1.0);
-    current_layer.set_text_rendering_mode(TextRenderingMode::FillStroke);
+                advance / units_per_em * font_size + CHARACTER_SPACING
+            })
+            .sum();
 
-    current_layer.write_text("BITS Application Security Review", &font);
-    current_layer.add_line_break();
+        width_pt * MM_PER_PT
+    }
+}
 
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_outline_thickness(0.0);
-    current_layer.write_text(
-        software_review
-            .software_request
-            .software
-            .software_name
-            .clone(),
-        &font,
-    );
-    current_layer.add_line_break();
-    current_layer.write_text(
-        format!(
-            "Request #{}",
-            software_review.software_request.td_request_id
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-    current_layer.write_text(
-        format!(
-            "Date: {}",
-            software_review
-                .created_at
-                .unwrap_or_default()
-                .with_timezone(&est_offset)
-                .format("%m/%d/%Y")
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-    current_layer.write_text(
-        format!("Reviewer Name: {}", software_review.reviewer.name.clone(),),
-        &font,
-    );
+// Splits the content of the report into pages. The title is repeated at the top of every
+// page, and a section that continues onto a new page repeats its heading
+fn layout_pages(
+    software_review: &SoftwareReviewDTO,
+    comments: &[ReviewCommentDTO],
+    template: &ReportTemplate,
+) -> Result<Vec<Vec<Line>>> {
+    let report = ReviewReport::new(software_review, comments, template);
 
-    current_layer.add_line_break();
-    current_layer.add_line_break();
-    // ----------------------------------------------------------------------------
-    current_layer.set_font(&font, 14.0);
-    current_layer.set_outline_thickness(1.2);
-    current_layer.write_text("Installation Criteria", &font);
-    current_layer.add_line_break();
-
-    current_layer.set_outline_thickness(0.0);
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) = convert_response_to_glyph(software_review.is_supported.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(format!(" | {}", "Still supported by developer"), &font);
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) = convert_response_to_glyph(software_review.is_current_version.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(format!(" | {}", "Current version is requested"), &font);
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) = convert_response_to_glyph(software_review.is_reputation_good.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(format!(" | {}", "Developer reputation is good"), &font);
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) =
-        convert_response_to_glyph(software_review.is_installation_from_developer.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(
-            " | {}",
-            "Installation package received from developer/vendor"
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) = convert_response_to_glyph(software_review.is_local_admin_required.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(" | {}", "Local administrator not required for daily use"),
-        &font,
-    );
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) =
-        convert_response_to_glyph(software_review.is_connected_to_brockport_cloud.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(" | {}", "Doesn't connect to SUNY Brockport cloud accounts"),
-        &font,
-    );
-    current_layer.add_line_break();
+    let fonts = &template.fonts;
+    let metrics = FontMetrics::new(&template.assets.regular_font)?;
+    let symbol_metrics = FontMetrics::new(&template.assets.symbol_font)?;
 
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) = convert_response_to_glyph(
-        software_review
-            .is_connected_to_cloud_services_or_client
-            .clone(),
-    );
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(
-            " | {}",
-            "Doesn't connect to any other cloud services or serve as a client for cloud services"
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) =
-        convert_response_to_glyph(software_review.is_security_or_optimization_software.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(
-            " | {}",
-            "Isn't computer security software or optimization software"
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-
-    current_layer.set_font(&font_symbol, 15.0);
-    let (glyph, color) =
-        convert_response_to_glyph(software_review.is_supported_by_current_os.clone());
-    current_layer.set_fill_color(color);
-    current_layer.write_text(glyph, &font_symbol);
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_fill_color(default_color.clone());
-    current_layer.write_text(
-        format!(
-            " | {}",
-            "Supports the current operating systems deployed on campus"
-        ),
-        &font,
-    );
-    current_layer.add_line_break();
-    current_layer.add_line_break();
-    // ----------------------------------------------------------------------------
-    current_layer.set_font(&font, 14.0);
-    current_layer.set_outline_thickness(1.2);
-    current_layer.write_text("Notes", &font);
-    current_layer.add_line_break();
+    let width_mm = template.page.width_mm - template.page.margin_mm * 2.0;
+
+    let styled_lines = |text: &str, style: SpanStyle, size: f32| -> Vec<Line> {
+        wrap_text(text, width_mm, |line| metrics.text_width_mm(line, size))
+            .into_iter()
+            .map(|line| Line::new(line, style.clone()))
+            .collect()
+    };
 
-    current_layer.set_font(&font, 11.0);
-    current_layer.set_outline_thickness(0.0);
+    let title = styled_lines(&report.title, SpanStyle::Title, fonts.title_size);
 
-    let review_notes = software_review.review_notes.clone();
-    let lines = split_into_lines(&review_notes, 80);
+    let mut pages = Pages::new(template.lines_per_page(), title.clone());
 
-    for line in lines {
-        current_layer.write_text(&line, &font);
-        current_layer.add_line_break();
+    for line in title {
+        pages.push(line);
     }
 
-    current_layer.end_text_section();
-    // ----------------------------------------------------------------------------
+    for line in &report.header_lines {
+        for line in styled_lines(line, SpanStyle::Text, fonts.text_size) {
+            pages.push(line);
+        }
+    }
 
-    let mut buffer = Vec::new();
-    doc.save(&mut std::io::BufWriter::new(&mut buffer))
-        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+    for section in &report.sections {
+        let heading = styled_lines(&section.title, SpanStyle::Heading, fonts.heading_size);
+
+        let mut content = vec![];
+
+        match &section.content {
+            ReviewReportContent::Criteria(criteria) => {
+                for criterion in criteria {
+                    let glyph = criterion.answer.glyph;
+                    let (red, green, blue) = criterion.answer.color;
+                    let color = Color::Rgb(Rgb::new(
+                        f32::from(red) / 255.0,
+                        f32::from(green) / 255.0,
+                        f32::from(blue) / 255.0,
+                        None,
+                    ));
+
+                    // The label wraps to the right of the glyph
+                    let indent_mm = symbol_metrics.text_width_mm(glyph, fonts.symbol_size)
+                        + metrics.text_width_mm(" | ", fonts.text_size);
+
+                    let label_lines = wrap_text(&criterion.label, width_mm - indent_mm, |line| {
+                        metrics.text_width_mm(line, fonts.text_size)
+                    });
+
+                    for (index, label_line) in label_lines.into_iter().enumerate() {
+                        if index == 0 {
+                            content.push(Line {
+                                spans: vec![
+                                    Span {
+                                        text: glyph.to_string(),
+                                        style: SpanStyle::Symbol(color.clone()),
+                                    },
+                                    Span {
+                                        text: format!(" | {}", label_line),
+                                        style: SpanStyle::Text,
+                                    },
+                                ],
+                                indent_mm: 0.0,
+                            });
+                        } else {
+                            content.push(Line {
+                                indent_mm,
+                                ..Line::new(label_line, SpanStyle::Text)
+                            });
+                        }
+                    }
+                }
+            }
+            ReviewReportContent::Text(lines) => {
+                for line in lines {
+                    content.extend(styled_lines(line, SpanStyle::Text, fonts.text_size));
+                }
+            }
+            ReviewReportContent::Markdown(blocks) => {
+                content.extend(markdown_lines(blocks, width_mm, &metrics, fonts));
+            }
+        }
 
-    let response = Response::builder()
-        .status(StatusCode::OK)
-        .header("Content-Type", "application/pdf")
-        .header(
-            "Content-Disposition",
-            format!(
-                "attachment; filename=\"{}.pdf\"",
-                software_review.software_request.software.software_name
-            ),
-        )
-        .body(buffer.into())
-        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+        pages.push_section(heading, content);
+    }
 
-    Ok(response)
+    Ok(pages.finish())
 }
 
-fn convert_response_to_glyph(review_response: ReviewOptions) -> (String, Color) {
-    match review_response {
-        ReviewOptions::TRUE => ("✓".into(), Color::Rgb(Rgb::new(0.0, 255.0, 0.0, None))),
-        ReviewOptions::FALSE => ("✖".into(), Color::Rgb(Rgb::new(255.0, 0.0, 0.0, None))),
-        // `?` won't render colors other than black or white when targeting fill
-        _ => ("❓".into(), Color::Rgb(Rgb::new(5.0, 5.0, 5.0, None))),
+// Lays out formatted text. Headings are bold, and list items are indented by their depth,
+// with the text wrapping to the right of the bullet or number. Blocks are set apart by a
+// blank line, except items of the same list
+fn markdown_lines(
+    blocks: &[MarkdownBlock],
+    width_mm: f32,
+    metrics: &FontMetrics,
+    fonts: &ReportFonts,
+) -> Vec<Line> {
+    let text_width = |text: &str, _: &SpanStyle| metrics.text_width_mm(text, fonts.text_size);
+    let mut lines = vec![];
+
+    for (index, block) in blocks.iter().enumerate() {
+        let is_item = |kind: &MarkdownBlockKind| matches!(kind, MarkdownBlockKind::ListItem { .. });
+
+        if index > 0 && !(is_item(&block.kind) && is_item(&blocks[index - 1].kind)) {
+            lines.push(Line::blank());
+        }
+
+        let spans: Vec<Span> = block
+            .spans
+            .iter()
+            .map(|span| Span {
+                text: span.text.clone(),
+                style: match block.kind {
+                    MarkdownBlockKind::Heading(_) => SpanStyle::Bold,
+                    _ if span.bold => SpanStyle::Bold,
+                    _ => SpanStyle::Text,
+                },
+            })
+            .collect();
+
+        let MarkdownBlockKind::ListItem { depth, number } = block.kind else {
+            lines.extend(
+                wrap_spans(&spans, width_mm, text_width)
+                    .into_iter()
+                    .map(|spans| Line {
+                        spans,
+                        indent_mm: 0.0,
+                    }),
+            );
+            continue;
+        };
+
+        let marker = match number {
+            Some(number) => format!("{}. ", number),
+            None => "• ".to_string(),
+        };
+        let indent_mm = depth as f32 * LIST_INDENT_MM;
+        let marker_width_mm = metrics.text_width_mm(&marker, fonts.text_size);
+
+        let item_lines = wrap_spans(&spans, width_mm - indent_mm - marker_width_mm, text_width);
+
+        for (index, mut spans) in item_lines.into_iter().enumerate() {
+            if index == 0 {
+                spans.insert(0, Span::new(marker.clone(), SpanStyle::Text));
+                lines.push(Line { spans, indent_mm });
+            } else {
+                lines.push(Line {
+                    spans,
+                    indent_mm: indent_mm + marker_width_mm,
+                });
+            }
+        }
     }
+
+    lines
 }
 
-fn split_into_lines(text: &str, max_line_length: usize) -> Vec<String> {
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
+// Fills pages with lines, starting a new page when the current one is full
+struct Pages {
+    lines_per_page: usize,
+    // Lines at the top of every page after the first
+    running_header: Vec<Line>,
+    pages: Vec<Vec<Line>>,
+    current: Vec<Line>,
+}
+
+impl Pages {
+    fn new(lines_per_page: usize, running_header: Vec<Line>) -> Self {
+        Pages {
+            lines_per_page,
+            running_header,
+            pages: vec![],
+            current: vec![],
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.lines_per_page.saturating_sub(self.current.len())
+    }
 
-    for word in text.split_whitespace() {
-        if current_line.len() + word.len() + 1 > max_line_length {
-            lines.push(current_line.clone());
-            current_line = word.to_string();
+    fn push(&mut self, line: Line) {
+        if self.remaining() == 0 {
+            self.new_page(&[]);
+        }
+
+        self.current.push(line);
+    }
+
+    // Adds a section below a blank line. The heading is kept with the first line of the
+    // content, and repeated if the content continues onto another page
+    fn push_section(&mut self, heading: Vec<Line>, content: Vec<Line>) {
+        let first_line = content.len().min(1);
+
+        if self.remaining() < 1 + heading.len() + first_line {
+            self.new_page(&[]);
         } else {
+            self.current.push(Line::blank());
+        }
+
+        for line in heading.iter().cloned() {
+            self.push(line);
+        }
+
+        for line in content {
+            if self.remaining() == 0 {
+                self.new_page(&heading);
+            }
+
+            self.current.push(line);
+        }
+    }
+
+    fn new_page(&mut self, heading: &[Line]) {
+        let page = std::mem::take(&mut self.current);
+        self.pages.push(page);
+
+        self.current.extend(self.running_header.iter().cloned());
+        self.current.push(Line::blank());
+        self.current.extend(heading.iter().cloned());
+    }
+
+    fn finish(mut self) -> Vec<Vec<Line>> {
+        self.pages.push(self.current);
+
+        self.pages
+    }
+}
+
+// Wraps `text` into lines no wider than `max_width_mm`, as measured by `width_mm`. Line
+// breaks in the text are kept, and a word too wide for a line of its own is split
+// between graphemes
+pub(super) fn wrap_text(
+    text: &str,
+    max_width_mm: f32,
+    width_mm: impl Fn(&str) -> f32,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut current_line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let candidate = if current_line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current_line, word)
+            };
+
+            if width_mm(&candidate) <= max_width_mm {
+                current_line = candidate;
+                continue;
+            }
+
             if !current_line.is_empty() {
-                current_line.push(' ');
+                lines.push(std::mem::take(&mut current_line));
+            }
+
+            for grapheme in word.graphemes(true) {
+                if !current_line.is_empty()
+                    && width_mm(&(current_line.clone() + grapheme)) > max_width_mm
+                {
+                    lines.push(std::mem::take(&mut current_line));
+                }
+
+                current_line.push_str(grapheme);
+            }
+        }
+
+        // Blank lines in the text are kept as blank lines in the report
+        lines.push(current_line);
+    }
+
+    // Trailing blank lines would only take up room on the page
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    lines
+}
+
+// Wraps styled text like `wrap_text`, keeping the style of every part of it. A word can
+// change style part way through, and the space before a word takes its style
+fn wrap_spans(
+    spans: &[Span],
+    max_width_mm: f32,
+    width_mm: impl Fn(&str, &SpanStyle) -> f32,
+) -> Vec<Vec<Span>> {
+    // The words of each line of the text, as the styled parts of each word
+    let mut paragraphs: Vec<Vec<Vec<Span>>> = vec![vec![]];
+    let mut in_word = false;
+
+    for span in spans {
+        for c in span.text.chars() {
+            if c.is_whitespace() {
+                in_word = false;
+
+                if c == '\n' {
+                    paragraphs.push(vec![]);
+                }
+                continue;
+            }
+
+            let Some(words) = paragraphs.last_mut() else {
+                continue;
+            };
+
+            if !in_word {
+                words.push(vec![]);
+                in_word = true;
+            }
+
+            if let Some(word) = words.last_mut() {
+                push_text(word, &c.to_string(), &span.style);
             }
-            current_line.push_str(word);
         }
     }
 
-    if !current_line.is_empty() {
+    let mut lines = Vec::new();
+
+    for words in paragraphs {
+        let mut current_line: Vec<Span> = vec![];
+        let mut current_width = 0.0;
+
+        for word in words {
+            let word_width: f32 = word
+                .iter()
+                .map(|part| width_mm(&part.text, &part.style))
+                .sum();
+            let space_width = width_mm(" ", &word[0].style);
+
+            if current_line.is_empty() && word_width <= max_width_mm {
+                current_width = word_width;
+                current_line.extend(word);
+                continue;
+            }
+
+            if !current_line.is_empty() && current_width + space_width + word_width <= max_width_mm
+            {
+                push_text(&mut current_line, " ", &word[0].style);
+                current_width += space_width + word_width;
+                for part in word {
+                    push_text(&mut current_line, &part.text, &part.style);
+                }
+                continue;
+            }
+
+            if !current_line.is_empty() {
+                lines.push(std::mem::take(&mut current_line));
+                current_width = 0.0;
+            }
+
+            for part in word {
+                for grapheme in part.text.graphemes(true) {
+                    let grapheme_width = width_mm(grapheme, &part.style);
+
+                    if !current_line.is_empty() && current_width + grapheme_width > max_width_mm {
+                        lines.push(std::mem::take(&mut current_line));
+                        current_width = 0.0;
+                    }
+
+                    push_text(&mut current_line, grapheme, &part.style);
+                    current_width += grapheme_width;
+                }
+            }
+        }
+
+        // Blank lines in the text are kept as blank lines in the report
         lines.push(current_line);
     }
 
+    // Trailing blank lines would only take up room on the page
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
     lines
 }
+
+// Adds `text` to the last span when it has the same style
+fn push_text(spans: &mut Vec<Span>, text: &str, style: &SpanStyle) {
+    match spans.last_mut() {
+        Some(last) if last.style == *style => last.text.push_str(text),
+        _ => spans.push(Span::new(text.to_string(), style.clone())),
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod generate_pdf_tests {
+    use uuid::Uuid;
+
+    use super::{
+        layout_pages, markdown_lines, render_pdf, wrap_spans, wrap_text, FontMetrics, Line, Span,
+        SpanStyle,
+    };
+    use crate::api::models::{
+        RequesterDTO, ReviewOptions, SoftwareDTO, SoftwareRequestDTO, SoftwareReviewDTO, UserDTO,
+        UserRole,
+    };
+    use crate::api::utils::{parse_markdown, ReportIntegrity, ReportTemplate};
+
+    fn software_review(review_notes: &str) -> SoftwareReviewDTO {
+        SoftwareReviewDTO {
+            id: Uuid::new_v4(),
+            software_request: SoftwareRequestDTO {
+                id: None,
+                td_request_id: "12345678".to_string(),
+                software: SoftwareDTO {
+                    id: None,
+                    software_name: "Zürich Übersetzer".to_string(),
+                    software_version: "1.0.0".to_string(),
+                    developer_name: "Test Developer".to_string(),
+                    description: "A test software application".to_string(),
+                    created_at: None,
+                },
+                requester: RequesterDTO {
+                    id: None,
+                    name: "John".to_string(),
+                    email: "john@gmail.com".to_string(),
+                    department: "IT".to_string(),
+                    created_at: None,
+                },
+                created_at: None,
+            },
+            reviewer: UserDTO {
+                id: None,
+                name: "Jane".to_string(),
+                email: "jane@gmail.com".to_string(),
+                role: UserRole::REVIEWER,
+                created_at: None,
+            },
+            is_supported: ReviewOptions::TRUE,
+            is_current_version: ReviewOptions::TRUE,
+            is_reputation_good: ReviewOptions::TRUE,
+            is_installation_from_developer: ReviewOptions::NOT_SURE,
+            is_local_admin_required: ReviewOptions::FALSE,
+            is_connected_to_brockport_cloud: ReviewOptions::FALSE,
+            is_connected_to_cloud_services_or_client: ReviewOptions::FALSE,
+            is_security_or_optimization_software: ReviewOptions::FALSE,
+            is_supported_by_current_os: ReviewOptions::TRUE,
+            exported: false,
+            review_notes: review_notes.to_string(),
+            reviewed_version: "1.0.0".to_string(),
+            newer_version_available: false,
+            expires_at: chrono::Utc::now(),
+            created_at: Some(chrono::Utc::now()),
+        }
+    }
+
+    fn integrity() -> ReportIntegrity {
+        ReportIntegrity::new("https://localhost/reviews/verify".to_string(), None)
+    }
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    // Every character is 1mm wide
+    fn char_width(text: &str) -> f32 {
+        text.chars().count() as f32
+    }
+
+    #[test]
+    fn wrap_text_measures_characters_not_bytes() {
+        assert_eq!(
+            vec!["ääää ööö", "üü"],
+            wrap_text("ääää ööö üü", 8.0, char_width)
+        );
+    }
+
+    #[test]
+    fn wrap_text_splits_words_wider_than_a_line() {
+        assert_eq!(
+            vec!["日本語のソフトウ", "ェア ok"],
+            wrap_text("日本語のソフトウェア ok", 8.0, char_width)
+        );
+    }
+
+    #[test]
+    fn wrap_text_keeps_line_breaks() {
+        assert_eq!(
+            vec!["first", "", "second line", "wraps"],
+            wrap_text("first\n\nsecond line wraps\n\n", 11.0, char_width)
+        );
+    }
+
+    #[test]
+    fn wrap_spans_keeps_styles_within_words() {
+        let spans = [
+            Span::new("aaa b".to_string(), SpanStyle::Text),
+            Span::new("bb".to_string(), SpanStyle::Bold),
+            Span::new("b ccc".to_string(), SpanStyle::Text),
+        ];
+
+        assert_eq!(
+            vec![
+                vec![Span::new("aaa".to_string(), SpanStyle::Text)],
+                vec![
+                    Span::new("b".to_string(), SpanStyle::Text),
+                    Span::new("bb".to_string(), SpanStyle::Bold),
+                    Span::new("b".to_string(), SpanStyle::Text),
+                ],
+                vec![Span::new("ccc".to_string(), SpanStyle::Text)],
+            ],
+            wrap_spans(&spans, 5.0, |text, _| char_width(text))
+        );
+    }
+
+    #[test]
+    fn markdown_notes_are_formatted() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+        let blocks = parse_markdown(
+            "# Findings\n\nThe installer is **signed**.\n\n- Runs as a user\n  1. No drivers",
+        );
+        let metrics = FontMetrics::new(&template.assets.regular_font).unwrap();
+
+        let lines = markdown_lines(&blocks, 150.0, &metrics, &template.fonts);
+
+        assert_eq!(
+            vec![
+                "Findings",
+                "",
+                "The installer is signed.",
+                "",
+                "• Runs as a user",
+                "1. No drivers"
+            ],
+            lines.iter().map(text).collect::<Vec<_>>()
+        );
+        assert_eq!(SpanStyle::Bold, lines[0].spans[0].style);
+        assert_eq!(
+            vec![SpanStyle::Text, SpanStyle::Bold, SpanStyle::Text],
+            lines[2]
+                .spans
+                .iter()
+                .map(|span| span.style.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(lines[5].indent_mm > lines[4].indent_mm);
+    }
+
+    #[tokio::test]
+    async fn short_notes_render_on_one_page() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+        let software_review = software_review("All conditions satisfied.");
+
+        let pdf = render_pdf(&software_review, &[], 1, &template, &integrity())
+            .await
+            .unwrap();
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+
+        assert_eq!(1, document.get_pages().len());
+    }
+
+    #[tokio::test]
+    async fn long_unicode_notes_continue_on_another_page() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+        let notes = "Überprüfung der Größe – naïve façade, 日本語のテキスト, ✓ Ωmega. ".repeat(12);
+        let software_review = software_review(notes.trim());
+
+        let pages = layout_pages(&software_review, &[], &template).unwrap();
+        assert_eq!(2, pages.len());
+
+        // The title and the heading of the notes are repeated above the rest of the notes
+        let second_page: Vec<String> = pages[1].iter().map(text).collect();
+        assert_eq!("BITS Application Security Review", second_page[0]);
+        assert_eq!("", second_page[1]);
+        assert_eq!("Notes", second_page[2]);
+        assert!(second_page[3].starts_with(|c: char| !c.is_whitespace()));
+        assert!(pages
+            .iter()
+            .all(|page| page.len() <= template.lines_per_page()));
+
+        // No words are lost when the notes are wrapped across pages
+        let notes_lines: Vec<String> = pages
+            .iter()
+            .flat_map(|page| {
+                let heading = page.iter().position(|line| text(line) == "Notes");
+                page.iter()
+                    .skip(heading.map_or(page.len(), |heading| heading + 1))
+            })
+            .map(text)
+            .collect();
+        assert_eq!(
+            notes.split_whitespace().collect::<Vec<_>>(),
+            notes_lines.join(" ").split_whitespace().collect::<Vec<_>>()
+        );
+
+        let pdf = render_pdf(&software_review, &[], 1, &template, &integrity())
+            .await
+            .unwrap();
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+
+        assert_eq!(2, document.get_pages().len());
+    }
+}
@@ -0,0 +1,535 @@
+use std::io::Cursor;
+
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+use printpdf::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::generate_pdf::{add_logo, wrap_text, FontMetrics, BOLD_OUTLINE, CHARACTER_SPACING};
+use crate::api::models::SummaryReport;
+use crate::api::utils::validation::encode_content_disposition;
+use crate::api::utils::{ReportTemplate, MM_PER_PT};
+use crate::{Error, Result};
+
+const SUMMARY_TITLE: &str = "Software Review Summary";
+
+// Height of a table row or chart bar, relative to the text size
+const ROW_HEIGHT_SCALE: f32 = 1.8;
+// Share of the content width taken by the labels of a bar chart
+const BAR_LABEL_WIDTH: f32 = 0.35;
+// Room kept to the right of the longest bar for its count
+const BAR_COUNT_WIDTH_MM: f32 = 15.0;
+const BAR_COLOR: (f32, f32, f32) = (0.26, 0.45, 0.71);
+const RULE_THICKNESS: f32 = 0.5;
+
+#[tracing::instrument(name = "generating summary pdf", skip(report, template))]
+pub async fn generate_summary_pdf(
+    report: &SummaryReport,
+    template: &ReportTemplate,
+) -> Result<Response> {
+    let buffer = render_summary_pdf(report, template).await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            encode_content_disposition(&format!(
+                "software-review-summary-{}-to-{}.pdf",
+                report.from, report.to
+            )),
+        )
+        .body(buffer.into())
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+}
+
+// Renders the summary as a PDF with the page layout, fonts and logo of the review
+// report template
+#[tracing::instrument(name = "rendering summary pdf", skip(report, template))]
+pub async fn render_summary_pdf(
+    report: &SummaryReport,
+    template: &ReportTemplate,
+) -> Result<Vec<u8>> {
+    let mut writer = SummaryWriter::new(template)?;
+
+    writer.text(&format!(
+        "Reviews created from {} to {}",
+        template.format_day(report.from),
+        template.format_day(report.to)
+    ));
+    writer.text(&format!(
+        "Generated {}",
+        template.format_date(chrono::Utc::now())
+    ));
+
+    writer.heading("Overview");
+    writer.table(
+        &[("", 0.7), ("Reviews", 0.3)],
+        &[
+            vec!["Created".to_string(), report.total_reviews.to_string()],
+            vec!["Exported".to_string(), report.exported_reviews.to_string()],
+            vec![
+                "Not exported".to_string(),
+                (report.total_reviews - report.exported_reviews).to_string(),
+            ],
+        ],
+    );
+
+    writer.heading("Reviews by Outcome");
+    writer.bar_chart(
+        &report
+            .outcomes
+            .iter()
+            .map(|outcome| (outcome.outcome.label().to_string(), outcome.count))
+            .collect::<Vec<_>>(),
+    );
+    writer.text(
+        "Concerns: at least one criterion was not met. Unsure: no criterion was failed, but \
+         at least one was answered not sure.",
+    );
+
+    writer.heading("Top Vendors");
+    writer.bar_chart(
+        &report
+            .top_vendors
+            .iter()
+            .map(|vendor| (vendor.name.clone(), vendor.count))
+            .collect::<Vec<_>>(),
+    );
+
+    writer.heading("Software Requiring Local Admin");
+    if report.local_admin_software.is_empty() {
+        writer.text("No reviewed software requires local admin.");
+    } else {
+        writer.table(
+            &[
+                ("Software", 0.4),
+                ("Version", 0.15),
+                ("Developer", 0.3),
+                ("Request", 0.15),
+            ],
+            &report
+                .local_admin_software
+                .iter()
+                .map(|software| {
+                    vec![
+                        software.software_name.clone(),
+                        software.software_version.clone(),
+                        software.developer_name.clone(),
+                        software.td_request_id.clone(),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    writer.heading("Reviews per Reviewer");
+    writer.bar_chart(
+        &report
+            .reviews_per_reviewer
+            .iter()
+            .map(|reviewer| (reviewer.name.clone(), reviewer.count))
+            .collect::<Vec<_>>(),
+    );
+
+    let time_to_review = &report.time_to_review;
+    let days = |days: Option<f64>| days.map_or("-".to_string(), |days| format!("{:.1}", days));
+
+    writer.heading("Time to Review");
+    writer.text("Days from a software request being made to its review.");
+    writer.table(
+        &[("", 0.7), ("Days", 0.3)],
+        &[
+            vec!["Average".to_string(), days(time_to_review.average_days)],
+            vec!["Median".to_string(), days(time_to_review.median_days)],
+            vec!["Longest".to_string(), days(time_to_review.longest_days)],
+        ],
+    );
+    writer.bar_chart(
+        &time_to_review
+            .ranges
+            .iter()
+            .map(|range| (range.name.clone(), range.count))
+            .collect::<Vec<_>>(),
+    );
+
+    writer.finish()
+}
+
+// Writes rows of text, tables and charts down the page, starting a new page when the
+// current one is full. The logo and title are repeated at the top of every page
+struct SummaryWriter<'a> {
+    template: &'a ReportTemplate,
+    doc: PdfDocumentReference,
+    font: IndirectFontRef,
+    metrics: FontMetrics<'a>,
+    pages: Vec<PdfLayerReference>,
+    // Top of the next row, from the bottom of the page
+    y_mm: f32,
+}
+
+impl<'a> SummaryWriter<'a> {
+    fn new(template: &'a ReportTemplate) -> Result<Self> {
+        let page = &template.page;
+
+        let (doc, first_page, first_layer) = PdfDocument::new(
+            SUMMARY_TITLE,
+            Mm(page.width_mm),
+            Mm(page.height_mm),
+            "Layer 1",
+        );
+
+        let font = doc
+            .add_external_font(Cursor::new(&template.assets.regular_font))
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+        let layer = doc.get_page(first_page).get_layer(first_layer);
+
+        let mut writer = SummaryWriter {
+            template,
+            doc,
+            font,
+            metrics: FontMetrics::new(&template.assets.regular_font)?,
+            pages: vec![],
+            y_mm: 0.0,
+        };
+
+        writer.start_page(layer)?;
+
+        Ok(writer)
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        // There is always at least the first page
+        self.pages.last().unwrap()
+    }
+
+    fn content_width_mm(&self) -> f32 {
+        self.template.page.width_mm - self.template.page.margin_mm * 2.0
+    }
+
+    fn row_height_mm(&self) -> f32 {
+        self.template.fonts.text_size * ROW_HEIGHT_SCALE * MM_PER_PT
+    }
+
+    fn start_page(&mut self, layer: PdfLayerReference) -> Result<()> {
+        add_logo(&layer, self.template)?;
+        self.pages.push(layer);
+
+        let page = &self.template.page;
+        let title_size = self.template.fonts.title_size;
+
+        self.y_mm = page.height_mm - page.margin_mm - page.content_top_mm;
+        self.write(SUMMARY_TITLE, page.margin_mm, self.y_mm, title_size, true);
+        self.y_mm -= title_size * ROW_HEIGHT_SCALE * MM_PER_PT;
+
+        Ok(())
+    }
+
+    fn new_page(&mut self) {
+        let page = &self.template.page;
+        let (page_index, layer) =
+            self.doc
+                .add_page(Mm(page.width_mm), Mm(page.height_mm), "Layer 1");
+        let layer = self.doc.get_page(page_index).get_layer(layer);
+
+        // The logo was already read when the first page was started
+        let _ = self.start_page(layer);
+    }
+
+    // Starts a new page unless `height_mm` fits above the footer
+    fn ensure_room(&mut self, height_mm: f32) -> bool {
+        let bottom_mm = self.template.page.margin_mm + self.row_height_mm();
+
+        if self.y_mm - height_mm < bottom_mm {
+            self.new_page();
+            return true;
+        }
+
+        false
+    }
+
+    fn write(&self, text: &str, x_mm: f32, y_mm: f32, size: f32, bold: bool) {
+        let layer = self.layer();
+
+        layer.begin_text_section();
+        layer.set_text_cursor(Mm(x_mm), Mm(y_mm));
+        layer.set_character_spacing(CHARACTER_SPACING);
+        layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        layer.set_outline_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        if bold {
+            layer.set_text_rendering_mode(TextRenderingMode::FillStroke);
+            layer.set_outline_thickness(BOLD_OUTLINE);
+        } else {
+            layer.set_text_rendering_mode(TextRenderingMode::Fill);
+        }
+        layer.set_font(&self.font, size);
+        layer.write_text(text, &self.font);
+        layer.end_text_section();
+    }
+
+    // Shortens `text` with an ellipsis so it is no wider than `width_mm`
+    fn fit(&self, text: &str, width_mm: f32) -> String {
+        let size = self.template.fonts.text_size;
+
+        if self.metrics.text_width_mm(text, size) <= width_mm {
+            return text.to_string();
+        }
+
+        let mut fitted = String::new();
+
+        for grapheme in text.graphemes(true) {
+            if self
+                .metrics
+                .text_width_mm(&format!("{}{}…", fitted, grapheme), size)
+                > width_mm
+            {
+                break;
+            }
+
+            fitted.push_str(grapheme);
+        }
+
+        fitted + "…"
+    }
+
+    // The heading is kept on the same page as at least two rows of its content
+    fn heading(&mut self, title: &str) {
+        let heading_size = self.template.fonts.heading_size;
+        let heading_height_mm = heading_size * ROW_HEIGHT_SCALE * MM_PER_PT;
+
+        if !self.ensure_room(heading_height_mm * 1.5 + self.row_height_mm() * 2.0) {
+            self.y_mm -= heading_height_mm * 0.5;
+        }
+
+        let margin_mm = self.template.page.margin_mm;
+        self.write(title, margin_mm, self.y_mm, heading_size, true);
+        self.y_mm -= heading_height_mm;
+    }
+
+    fn text(&mut self, text: &str) {
+        let size = self.template.fonts.text_size;
+        let margin_mm = self.template.page.margin_mm;
+
+        for line in wrap_text(text, self.content_width_mm(), |line| {
+            self.metrics.text_width_mm(line, size)
+        }) {
+            self.ensure_room(self.row_height_mm());
+            self.write(&line, margin_mm, self.y_mm, size, false);
+            self.y_mm -= self.row_height_mm();
+        }
+    }
+
+    // Draws a horizontal rule across the content, just below the current row
+    fn rule(&self) {
+        let margin_mm = self.template.page.margin_mm;
+        let y_mm = self.y_mm + self.row_height_mm() * 0.6;
+        let layer = self.layer();
+
+        layer.set_outline_color(Color::Rgb(Rgb::new(0.6, 0.6, 0.6, None)));
+        layer.set_outline_thickness(RULE_THICKNESS);
+        layer.add_line(printpdf::Line {
+            points: vec![
+                (Point::new(Mm(margin_mm), Mm(y_mm)), false),
+                (
+                    Point::new(Mm(margin_mm + self.content_width_mm()), Mm(y_mm)),
+                    false,
+                ),
+            ],
+            is_closed: false,
+        });
+    }
+
+    // `columns` are the header of each column and its share of the content width. The
+    // header is repeated when the table continues onto another page
+    fn table(&mut self, columns: &[(&str, f32)], rows: &[Vec<String>]) {
+        let size = self.template.fonts.text_size;
+        let margin_mm = self.template.page.margin_mm;
+        let width_mm = self.content_width_mm();
+
+        let write_row = |writer: &mut Self, cells: &[String], bold: bool| {
+            let mut x_mm = margin_mm;
+
+            for (cell, (_, share)) in cells.iter().zip(columns) {
+                // A gap is kept between columns
+                let column_width_mm = width_mm * share;
+                let text = writer.fit(cell, column_width_mm - 2.0);
+
+                writer.write(&text, x_mm, writer.y_mm, size, bold);
+                x_mm += column_width_mm;
+            }
+
+            writer.y_mm -= writer.row_height_mm();
+        };
+
+        let header: Vec<String> = columns.iter().map(|(name, _)| name.to_string()).collect();
+
+        self.ensure_room(self.row_height_mm() * 2.0);
+        write_row(self, &header, true);
+        self.rule();
+
+        for row in rows {
+            if self.ensure_room(self.row_height_mm()) {
+                write_row(self, &header, true);
+                self.rule();
+            }
+
+            write_row(self, row, false);
+        }
+    }
+
+    // A horizontal bar for each label, scaled to the largest count
+    fn bar_chart(&mut self, bars: &[(String, i64)]) {
+        if bars.is_empty() {
+            self.text("No reviews in this period.");
+            return;
+        }
+
+        let size = self.template.fonts.text_size;
+        let margin_mm = self.template.page.margin_mm;
+        let label_width_mm = self.content_width_mm() * BAR_LABEL_WIDTH;
+        let bar_width_mm = self.content_width_mm() - label_width_mm - BAR_COUNT_WIDTH_MM;
+        let bar_height_mm = self.row_height_mm() * 0.6;
+
+        let largest = bars
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        for (label, count) in bars {
+            self.ensure_room(self.row_height_mm());
+
+            let label = self.fit(label, label_width_mm - 2.0);
+            self.write(&label, margin_mm, self.y_mm, size, false);
+
+            let bar_start_mm = margin_mm + label_width_mm;
+            let bar_length_mm = bar_width_mm * (*count as f32 / largest as f32);
+
+            // Text sits on its baseline, so the bar is lowered to line up with it
+            let bar_bottom_mm = self.y_mm - bar_height_mm * 0.25;
+
+            if bar_length_mm > 0.0 {
+                let layer = self.layer();
+                let (red, green, blue) = BAR_COLOR;
+
+                layer.set_fill_color(Color::Rgb(Rgb::new(red, green, blue, None)));
+                layer.add_rect(Rect::new(
+                    Mm(bar_start_mm),
+                    Mm(bar_bottom_mm),
+                    Mm(bar_start_mm + bar_length_mm),
+                    Mm(bar_bottom_mm + bar_height_mm),
+                ));
+            }
+
+            self.write(
+                &count.to_string(),
+                bar_start_mm + bar_length_mm + 2.0,
+                self.y_mm,
+                size,
+                false,
+            );
+            self.y_mm -= self.row_height_mm();
+        }
+    }
+
+    // Adds the page numbers, then saves the document
+    fn finish(self) -> Result<Vec<u8>> {
+        let page = &self.template.page;
+        let size = self.template.fonts.text_size;
+
+        for (page_number, layer) in self.pages.iter().enumerate() {
+            let footer = format!("Page {} of {}", page_number + 1, self.pages.len());
+            let width_mm = self.metrics.text_width_mm(&footer, size);
+
+            layer.begin_text_section();
+            layer.set_text_cursor(
+                Mm(((page.width_mm - width_mm) / 2.0).max(page.margin_mm)),
+                Mm(page.margin_mm),
+            );
+            layer.set_character_spacing(CHARACTER_SPACING);
+            layer.set_text_rendering_mode(TextRenderingMode::Fill);
+            layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+            layer.set_font(&self.font, size);
+            layer.write_text(&footer, &self.font);
+            layer.end_text_section();
+        }
+
+        let mut buffer = Vec::new();
+        self.doc
+            .save(&mut std::io::BufWriter::new(&mut buffer))
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+        Ok(buffer)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod generate_summary_pdf_tests {
+    use super::render_summary_pdf;
+    use crate::api::models::{
+        LocalAdminSoftware, OutcomeCount, ReviewOutcome, SummaryCount, SummaryReport, TimeToReview,
+    };
+    use crate::api::utils::ReportTemplate;
+
+    fn summary_report(local_admin_software: usize) -> SummaryReport {
+        SummaryReport {
+            from: "2024-12-01".parse().unwrap(),
+            to: "2024-12-31".parse().unwrap(),
+            total_reviews: 3,
+            exported_reviews: 1,
+            outcomes: ReviewOutcome::ALL
+                .into_iter()
+                .map(|outcome| OutcomeCount { outcome, count: 1 })
+                .collect(),
+            top_vendors: vec![SummaryCount {
+                name: "A developer with a name far too long to fit beside its bar".to_string(),
+                count: 3,
+            }],
+            local_admin_software: (0..local_admin_software)
+                .map(|index| LocalAdminSoftware {
+                    software_name: format!("Software {}", index),
+                    software_version: "1.0.0".to_string(),
+                    developer_name: "Test Developer".to_string(),
+                    td_request_id: format!("{:08}", index),
+                })
+                .collect(),
+            reviews_per_reviewer: vec![],
+            time_to_review: TimeToReview {
+                average_days: Some(2.5),
+                median_days: Some(1.0),
+                longest_days: Some(6.5),
+                ranges: vec![SummaryCount {
+                    name: "Under 1 day".to_string(),
+                    count: 0,
+                }],
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn short_summary_renders_on_one_page() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+
+        let pdf = render_summary_pdf(&summary_report(0), &template)
+            .await
+            .unwrap();
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+
+        assert_eq!(1, document.get_pages().len());
+    }
+
+    #[tokio::test]
+    async fn long_tables_continue_on_other_pages() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+
+        let pdf = render_summary_pdf(&summary_report(100), &template)
+            .await
+            .unwrap();
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+
+        assert!(document.get_pages().len() > 2);
+    }
+}
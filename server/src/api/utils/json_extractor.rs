@@ -4,7 +4,7 @@ use axum::extract::{FromRequest, Request};
 use axum::response::IntoResponse;
 use serde::Serialize;
 
-use crate::Error;
+use crate::{Error, FieldError, FieldErrorCode};
 
 // Define our own `Json` extractor that customizes the error from `axum::Json`
 #[derive(Debug)]
@@ -25,13 +25,7 @@ where
 
         match axum::Json::<T>::from_request(req, state).await {
             Ok(value) => Ok(Self(value.0)),
-            Err(rejection) => {
-                let json_error = JsonError {
-                    message: rejection.body_text(),
-                };
-
-                Err(Error::PayloadExtractorError(json_error))
-            }
+            Err(rejection) => Err(Error::PayloadExtractorError(JsonError::from(rejection))),
         }
     }
 }
@@ -46,6 +40,67 @@ impl<T: Serialize> IntoResponse for Json<T> {
 #[derive(Clone, Debug, Serialize)]
 pub struct JsonError {
     message: String,
+    field: String,
+    code: FieldErrorCode,
+}
+
+impl JsonError {
+    pub fn field_error(&self) -> FieldError {
+        FieldError::new(self.field.clone(), self.code, self.message.clone())
+    }
+}
+
+impl From<JsonRejection> for JsonError {
+    fn from(rejection: JsonRejection) -> Self {
+        let message = rejection.body_text();
+
+        // `axum::Json` deserializes through `serde_path_to_error`, so data errors carry
+        // the path to the field that could not be deserialized
+        let path_error = match &rejection {
+            JsonRejection::JsonDataError(err) => std::error::Error::source(err)
+                .and_then(|err| err.source())
+                .and_then(|err| {
+                    err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>()
+                }),
+            _ => None,
+        };
+
+        let Some(path_error) = path_error else {
+            return JsonError {
+                message,
+                field: String::new(),
+                code: FieldErrorCode::MalformedPayload,
+            };
+        };
+
+        let path = match path_error.path().to_string().as_str() {
+            "." => String::new(),
+            path => path.to_string(),
+        };
+        let inner_message = path_error.inner().to_string();
+
+        // Missing field errors are reported against the parent object, so append the
+        // name of the missing field to the path
+        match inner_message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next())
+        {
+            Some(missing_field) => JsonError {
+                message,
+                field: if path.is_empty() {
+                    missing_field.to_string()
+                } else {
+                    format!("{}.{}", path, missing_field)
+                },
+                code: FieldErrorCode::MissingField,
+            },
+            None => JsonError {
+                message,
+                field: path,
+                code: FieldErrorCode::InvalidType,
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for JsonError {
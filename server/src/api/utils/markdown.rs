@@ -0,0 +1,528 @@
+use pulldown_cmark::{Event, LinkType, Parser, Tag, TagEnd};
+
+// Review notes are written in Markdown, limited to the formatting every report format can
+// show: headings, paragraphs, bullet and numbered lists, bold and italic text, and line
+// breaks. Anything else is reduced to its text (code, quotes, links and images) or left
+// out (HTML and rules), so notes can't add markup of their own to a report
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownBlock {
+    pub kind: MarkdownBlockKind,
+    pub spans: Vec<MarkdownSpan>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkdownBlockKind {
+    // From 1 to 6
+    Heading(u8),
+    Paragraph,
+    // An item of a list `depth` lists deep, where a top level list is 0. `number` is
+    // `None` for bullets
+    ListItem { depth: usize, number: Option<u64> },
+}
+
+// A run of text with the same formatting. Line breaks within a block are kept as `\n`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkdownSpan {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+// Rewrites `text` with only the allowed formatting. The result parses to the same blocks
+// as `text`, so sanitizing it again leaves it unchanged
+pub fn sanitize_markdown(text: &str) -> String {
+    write_markdown(&parse_markdown(text), 0)
+}
+
+// The allowed blocks of `text`, without any blocks left empty once disallowed content is
+// removed
+pub fn parse_markdown(text: &str) -> Vec<MarkdownBlock> {
+    let mut builder = BlockBuilder::default();
+
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::Paragraph | Tag::CodeBlock(_)) => builder.open(None),
+            Event::Start(Tag::Heading { level, .. }) => {
+                builder.open(Some(MarkdownBlockKind::Heading(level as u8)))
+            }
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock) => {
+                builder.close()
+            }
+            Event::Start(Tag::List(start)) => {
+                builder.close();
+                builder.lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                builder.close();
+                builder.lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                builder.close();
+
+                let depth = builder.lists.len().saturating_sub(1);
+                let number = builder.lists.last_mut().and_then(|start| {
+                    let number = *start;
+                    *start = number.map(|number| number.saturating_add(1));
+                    number
+                });
+
+                builder.item = Some(MarkdownBlockKind::ListItem { depth, number });
+            }
+            Event::End(TagEnd::Item) => {
+                builder.close();
+                builder.item = None;
+            }
+            Event::Start(Tag::Strong) => builder.bold += 1,
+            Event::End(TagEnd::Strong) => builder.bold = builder.bold.saturating_sub(1),
+            Event::Start(Tag::Emphasis) => builder.italic += 1,
+            Event::End(TagEnd::Emphasis) => builder.italic = builder.italic.saturating_sub(1),
+            // The address of a link is kept after its text, unless it is the text
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => builder.links.push(
+                (!matches!(link_type, LinkType::Autolink | LinkType::Email))
+                    .then(|| dest_url.to_string()),
+            ),
+            Event::End(TagEnd::Link) => {
+                if let Some(Some(url)) = builder.links.pop() {
+                    builder.push(&format!(" ({})", url));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => builder.push(&text),
+            Event::SoftBreak => builder.push(" "),
+            Event::HardBreak => builder.push("\n"),
+            // Quotes and images are replaced by their text, while HTML, rules and the
+            // extensions that aren't enabled are left out
+            _ => (),
+        }
+    }
+
+    builder.close();
+
+    builder.blocks
+}
+
+// Writes `blocks` as CommonMark, with headings `heading_offset` levels lower than they
+// were written, e.g. to nest them below the heading of a report section
+pub fn write_markdown(blocks: &[MarkdownBlock], heading_offset: u8) -> String {
+    let mut markdown = String::new();
+    // The column the content of the last item at each depth starts at, and whether its
+    // list is numbered
+    let mut items: Vec<(usize, bool)> = vec![];
+
+    for (index, block) in blocks.iter().enumerate() {
+        // Items of the same list, or the first item of a list nested in the one before, are
+        // on the next line. Any other list is set apart by a blank line, as it couldn't
+        // always interrupt the item before
+        let continues_list = match block.kind {
+            MarkdownBlockKind::ListItem { depth, number } => {
+                items
+                    .get(depth)
+                    .is_some_and(|(_, numbered)| *numbered == number.is_some())
+                    || (depth > 0
+                        && depth == items.len()
+                        && number.is_none_or(|number| number == 1))
+            }
+            _ => false,
+        };
+
+        if continues_list {
+            markdown.push('\n');
+        } else if index > 0 {
+            markdown.push_str("\n\n");
+        }
+
+        match block.kind {
+            MarkdownBlockKind::Heading(level) => {
+                let level = level.saturating_add(heading_offset).min(6) as usize;
+                // A heading is a single line
+                let spans: Vec<MarkdownSpan> = block
+                    .spans
+                    .iter()
+                    .map(|span| MarkdownSpan {
+                        text: span.text.replace('\n', " "),
+                        ..span.clone()
+                    })
+                    .collect();
+
+                markdown.push_str(&"#".repeat(level));
+                markdown.push(' ');
+                markdown.push_str(&write_spans(&spans, 0));
+                items.clear();
+            }
+            MarkdownBlockKind::Paragraph => {
+                markdown.push_str(&write_spans(&block.spans, 0));
+                items.clear();
+            }
+            MarkdownBlockKind::ListItem { depth, number } => {
+                // Nested items are indented to the content of their parent
+                items.truncate(depth);
+                let indent = items.last().map_or(0, |(column, _)| *column);
+
+                let marker = match number {
+                    Some(number) => format!("{}. ", number),
+                    None => "- ".to_string(),
+                };
+                items.push((indent + marker.len(), number.is_some()));
+
+                markdown.push_str(&" ".repeat(indent));
+                markdown.push_str(&marker);
+                markdown.push_str(&write_spans(&block.spans, indent + marker.len()));
+            }
+        }
+    }
+
+    markdown
+}
+
+// Escapes a single line of text, so Markdown shows it as written
+pub fn escape_markdown(line: &str) -> String {
+    let escaped = escape_inline(line);
+
+    let leading_spaces = escaped.len() - escaped.trim_start().len();
+    let (indent, rest) = escaped.split_at(leading_spaces);
+
+    format!("{}{}", indent, escape_line_start(rest))
+}
+
+// Escapes the characters that start formatting wherever they are in a line
+fn escape_inline(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!' | '&' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() && c != '\t' => (),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Lines starting like a list item or a heading underline would change the block
+fn escape_line_start(escaped: &str) -> String {
+    let digits = escaped.chars().take_while(char::is_ascii_digit).count();
+
+    match escaped[digits..].chars().next() {
+        Some('-' | '+' | '=') if digits == 0 => format!("\\{}", escaped),
+        Some('.' | ')') if digits > 0 => {
+            format!("{}\\{}", &escaped[..digits], &escaped[digits..])
+        }
+        _ => escaped.to_string(),
+    }
+}
+
+// Writes the text of a block, continuing lines after a line break at `indent`. Bold is
+// opened outside of italic, and whitespace is kept outside of both, so every marker can
+// open or close where it is written
+fn write_spans(spans: &[MarkdownSpan], indent: usize) -> String {
+    let mut markdown = String::new();
+    let (mut bold, mut italic) = (false, false);
+    let mut at_line_start = true;
+    let mut whitespace = String::new();
+
+    let write_text = |markdown: &mut String, text: &str, at_line_start: &mut bool| {
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                markdown.push_str("\\\n");
+                markdown.push_str(&" ".repeat(indent));
+                *at_line_start = true;
+            }
+
+            let line = if *at_line_start {
+                line.trim_start()
+            } else {
+                line
+            };
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let escaped = escape_inline(line);
+
+            if *at_line_start {
+                markdown.push_str(&escape_line_start(&escaped));
+            } else {
+                markdown.push_str(&escaped);
+            }
+
+            *at_line_start = false;
+        }
+    };
+
+    for span in spans {
+        let text = span.text.trim();
+
+        if text.is_empty() {
+            whitespace.push_str(&span.text);
+            continue;
+        }
+
+        whitespace.push_str(&span.text[..span.text.len() - span.text.trim_start().len()]);
+
+        // Italic is closed to close or open bold around it
+        if italic && (!span.italic || bold != span.bold) {
+            markdown.push('*');
+            italic = false;
+        }
+        if bold && !span.bold {
+            markdown.push_str("**");
+            bold = false;
+        }
+
+        if !whitespace.is_empty() {
+            if whitespace.contains('\n') {
+                write_text(&mut markdown, "\n", &mut at_line_start);
+            } else if !at_line_start {
+                markdown.push(' ');
+            }
+            whitespace.clear();
+        }
+
+        if span.bold && !bold {
+            markdown.push_str("**");
+            bold = true;
+            at_line_start = false;
+        }
+        if span.italic && !italic {
+            markdown.push('*');
+            italic = true;
+            at_line_start = false;
+        }
+
+        write_text(&mut markdown, text, &mut at_line_start);
+
+        whitespace.push_str(&span.text[span.text.trim_end().len()..]);
+    }
+
+    if italic {
+        markdown.push('*');
+    }
+    if bold {
+        markdown.push_str("**");
+    }
+
+    markdown
+}
+
+// Collects the text of the allowed blocks from the events of a parser
+#[derive(Default)]
+struct BlockBuilder {
+    blocks: Vec<MarkdownBlock>,
+    current: Option<MarkdownBlock>,
+    // The next number of each open list, or `None` for bullets
+    lists: Vec<Option<u64>>,
+    // The list item whose first block hasn't been opened yet
+    item: Option<MarkdownBlockKind>,
+    // The address to show after each open link
+    links: Vec<Option<String>>,
+    bold: usize,
+    italic: usize,
+}
+
+impl BlockBuilder {
+    // Starts a block of `kind`, or of the pending list item or a paragraph when `None`
+    fn open(&mut self, kind: Option<MarkdownBlockKind>) {
+        self.close();
+
+        let kind = kind
+            .or_else(|| self.item.take())
+            .unwrap_or(MarkdownBlockKind::Paragraph);
+
+        self.current = Some(MarkdownBlock {
+            kind,
+            spans: vec![],
+        });
+    }
+
+    fn close(&mut self) {
+        let Some(mut block) = self.current.take() else {
+            return;
+        };
+
+        if let Some(first) = block.spans.first_mut() {
+            first.text = first.text.trim_start().to_string();
+        }
+        if let Some(last) = block.spans.last_mut() {
+            last.text = last.text.trim_end().to_string();
+        }
+        block.spans.retain(|span| !span.text.is_empty());
+
+        if !block.spans.is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        if self.current.is_none() {
+            self.open(None);
+        }
+
+        let (bold, italic) = (self.bold > 0, self.italic > 0);
+        let Some(block) = self.current.as_mut() else {
+            return;
+        };
+
+        // Control characters other than line breaks and tabs can't be shown
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+            .collect();
+
+        match block.spans.last_mut() {
+            Some(last) if last.bold == bold && last.italic == italic => last.text.push_str(&text),
+            _ => block.spans.push(MarkdownSpan { text, bold, italic }),
+        }
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod markdown_tests {
+    use super::{
+        escape_markdown, parse_markdown, sanitize_markdown, write_markdown, MarkdownBlockKind,
+        MarkdownSpan,
+    };
+
+    fn span(text: &str, bold: bool, italic: bool) -> MarkdownSpan {
+        MarkdownSpan {
+            text: text.to_string(),
+            bold,
+            italic,
+        }
+    }
+
+    #[test]
+    fn allowed_formatting_is_kept() {
+        let notes = "# Findings\n\nThe installer is **signed** and *current*.\n\n\
+                     - Runs as a user\n  - No drivers\n- No telemetry\n\n\
+                     3. Install\n4. Reboot";
+
+        assert_eq!(notes, sanitize_markdown(notes));
+
+        let blocks = parse_markdown(notes);
+        assert_eq!(MarkdownBlockKind::Heading(1), blocks[0].kind);
+        assert_eq!(
+            vec![
+                span("The installer is ", false, false),
+                span("signed", true, false),
+                span(" and ", false, false),
+                span("current", false, true),
+                span(".", false, false),
+            ],
+            blocks[1].spans
+        );
+        assert_eq!(
+            MarkdownBlockKind::ListItem {
+                depth: 1,
+                number: None
+            },
+            blocks[3].kind
+        );
+        assert_eq!(
+            MarkdownBlockKind::ListItem {
+                depth: 0,
+                number: Some(4)
+            },
+            blocks[6].kind
+        );
+    }
+
+    #[test]
+    fn html_is_removed() {
+        assert_eq!(
+            "Safe text",
+            sanitize_markdown("<script>alert(1)</script>\n\nSafe <b>text</b>")
+        );
+        assert_eq!(
+            "hidden",
+            sanitize_markdown("<div onclick=\"alert(1)\">\n\nhidden\n\n</div>")
+        );
+    }
+
+    #[test]
+    fn other_formatting_is_reduced_to_text() {
+        assert_eq!(
+            "See the vendor (https://example.com/x) page, or click (javascript:alert(1))",
+            sanitize_markdown(
+                "See the [vendor](https://example.com/x) page, or [click](javascript:alert(1))"
+            )
+        );
+        assert_eq!(
+            "Checked https://example.com and the installer\n\n\\> quoted\n\nrun\\\nsetup.exe",
+            sanitize_markdown(
+                "Checked <https://example.com> and ![the installer](x.png)\n\n\
+                 > \\> quoted\n\n```\nrun\nsetup.exe\n```\n\n---"
+            )
+        );
+    }
+
+    #[test]
+    fn sanitized_markdown_is_unchanged_when_sanitized_again() {
+        for notes in [
+            "**bold *both* bold** *italic **both***",
+            "Line one  \nline two\\\n- not a list",
+            "1) first\n2) second\n\n* nested\n    1. deep\n\n   continued",
+            "a**\"quoted\"**b and `code *with* stars`",
+            "#not a heading #1 & &amp; \\*",
+            "Setext heading\n===\n\nText\n***",
+        ] {
+            let sanitized = sanitize_markdown(notes);
+
+            assert_eq!(
+                sanitized,
+                sanitize_markdown(&sanitized),
+                "{:?} changed when sanitized again",
+                notes
+            );
+        }
+    }
+
+    // As escaped by the `convert_review_notes_to_markdown` migration
+    #[test]
+    fn plain_text_notes_migrated_to_markdown_are_kept_as_written() {
+        let migrated = "Vendor-signed (v2.1).\\\n\\- \\*No\\* admin rights\n\n1\\. ok";
+
+        assert_eq!(migrated, sanitize_markdown(migrated));
+
+        let blocks = parse_markdown(migrated);
+        assert_eq!(
+            vec![
+                vec![span(
+                    "Vendor-signed (v2.1).\n- *No* admin rights",
+                    false,
+                    false
+                )],
+                vec![span("1. ok", false, false)],
+            ],
+            blocks
+                .into_iter()
+                .map(|block| block.spans)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn headings_can_be_moved_down() {
+        let blocks = parse_markdown("# Findings\n\n###### Detail");
+
+        assert_eq!("### Findings\n\n###### Detail", write_markdown(&blocks, 2));
+    }
+
+    #[test]
+    fn escape_markdown_keeps_text_literal() {
+        assert_eq!("\\*not bold\\*", escape_markdown("*not bold*"));
+        assert_eq!("\\- not a list", escape_markdown("- not a list"));
+        assert_eq!("  1\\. not a list", escape_markdown("  1. not a list"));
+        assert_eq!("\\<script\\>", escape_markdown("<script>"));
+        assert_eq!("plain text 1.0", escape_markdown("plain text 1.0"));
+    }
+}
@@ -1,15 +1,62 @@
 mod cookie;
+mod event_hub;
+mod export;
 mod generate_pdf;
+mod generate_summary_pdf;
 mod json_extractor;
 mod jwt;
 mod log_cleanup_worker;
+mod markdown;
+mod multipart_extractor;
 mod path_extractor;
+mod pdf_signature;
 mod query_extractor;
+mod report_formats;
+mod report_integrity;
+mod report_template;
+mod review_expiry_worker;
+mod review_report;
+mod stats_cache;
+mod teamdynamix_client;
+mod ticketing_client;
+pub mod validation;
+mod webhook_signature;
+mod webhook_worker;
+mod zip_archive;
 
 pub use cookie::{Cookie, SameSite};
-pub use generate_pdf::generate_pdf;
+pub use event_hub::EventHub;
+pub use export::{export_response, spawn_export, ExportFormat, ExportRow};
+pub use generate_pdf::{generate_pdf, render_pdf};
+pub use generate_summary_pdf::{generate_summary_pdf, render_summary_pdf};
 pub use json_extractor::{Json, JsonError};
 pub use jwt::*;
 pub use log_cleanup_worker::log_cleanup_task;
+pub use markdown::{
+    escape_markdown, parse_markdown, sanitize_markdown, write_markdown, MarkdownBlock,
+    MarkdownBlockKind, MarkdownSpan,
+};
+pub use multipart_extractor::Multipart;
 pub use path_extractor::{Path, PathError};
-pub use query_extractor::{Metadata, QueryExtractor, QueryParams};
+pub use pdf_signature::PdfSigner;
+pub use query_extractor::{Metadata, Query, QueryExtractor, QueryParams};
+pub use report_formats::{generate_report, render_docx, render_html, render_markdown};
+pub use report_integrity::{content_hash, ReportInspection, ReportIntegrity, ReportStamp};
+pub use report_template::{
+    PageLayout, ReportAssets, ReportCriterion, ReportDate, ReportFonts, ReportFooter, ReportHeader,
+    ReportLogo, ReportSection, ReportTemplate, ReviewCriterion, MM_PER_PT,
+};
+pub use review_expiry_worker::{check_review_expiry, review_expiry_task};
+pub use review_report::{
+    convert_response_to_glyph, ReviewAnswer, ReviewReport, ReviewReportContent,
+    ReviewReportCriterion, ReviewReportSection,
+};
+pub use stats_cache::StatsCache;
+pub use teamdynamix_client::TeamDynamixClient;
+pub use ticketing_client::TicketingClient;
+pub use webhook_signature::{
+    sign_webhook_payload, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER,
+    WEBHOOK_TIMESTAMP_HEADER,
+};
+pub use webhook_worker::{deliver_webhooks, webhook_client, webhook_delivery_task};
+pub use zip_archive::{zip_archive_response, ZipArchive};
@@ -0,0 +1,79 @@
+use axum::async_trait;
+use axum::extract::multipart::MultipartError;
+use axum::extract::{FromRequest, Request};
+
+use crate::{Error, FieldError, FieldErrorCode};
+
+// Define our own `Multipart` extractor that customizes the error from `axum::Multipart`
+#[derive(Debug)]
+pub struct Multipart(pub axum::extract::Multipart);
+
+#[async_trait]
+impl<S> FromRequest<S> for Multipart
+where
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Multipart::from_request(req, state).await {
+            Ok(value) => Ok(Self(value)),
+            Err(rejection) => Err(malformed_payload_error(rejection.body_text())),
+        }
+    }
+}
+
+impl Multipart {
+    // Reads every part of the form as `(name, text)` pairs, in the order they were sent
+    pub async fn text_parts(mut self) -> crate::Result<Vec<(String, String)>> {
+        let mut parts = vec![];
+
+        while let Some(field) = self.0.next_field().await.map_err(multipart_error)? {
+            let name = field.name().unwrap_or_default().to_string();
+            let text = field.text().await.map_err(multipart_error)?;
+
+            parts.push((name, text));
+        }
+
+        Ok(parts)
+    }
+
+    // Reads the contents of the part named `name`, which must be the only part of the form
+    pub async fn file(mut self, name: &str) -> crate::Result<axum::body::Bytes> {
+        let mut file = None;
+
+        while let Some(field) = self.0.next_field().await.map_err(multipart_error)? {
+            if field.name() != Some(name) || file.is_some() {
+                return Err(Error::ValidationError(
+                    FieldError::new(
+                        field.name().unwrap_or_default(),
+                        FieldErrorCode::InvalidValue,
+                        format!("only a single '{}' part may be provided", name),
+                    )
+                    .into(),
+                ));
+            }
+
+            file = Some(field.bytes().await.map_err(multipart_error)?);
+        }
+
+        file.ok_or_else(|| {
+            Error::ValidationError(
+                FieldError::new(
+                    name,
+                    FieldErrorCode::MissingField,
+                    format!("'{}' must be provided", name),
+                )
+                .into(),
+            )
+        })
+    }
+}
+
+fn multipart_error(err: MultipartError) -> Error {
+    malformed_payload_error(err.body_text())
+}
+
+fn malformed_payload_error(message: String) -> Error {
+    Error::ValidationError(FieldError::new("", FieldErrorCode::MalformedPayload, message).into())
+}
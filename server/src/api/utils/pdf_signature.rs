@@ -0,0 +1,273 @@
+use std::path::Path;
+
+use lopdf::{dictionary, Document, Object, StringFormat};
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::X509;
+
+use crate::{Error, Result};
+
+// Space reserved in the file for the DER encoded signature, which is written as hex, so
+// it takes up twice as many bytes
+const SIGNATURE_BYTES: usize = 8192;
+
+// Written in place of the byte range, which isn't known until the document is saved. Each
+// offset is as wide as any offset that replaces it
+const BYTE_RANGE_PLACEHOLDER: i64 = 9_999_999_999;
+
+// Signs PDFs with a detached PKCS#7 signature (`adbe.pkcs7.detached`) using the configured
+// certificate and private key. The signature covers every byte of the file except the
+// signature itself
+pub struct PdfSigner {
+    certificate: X509,
+    private_key: PKey<Private>,
+}
+
+impl PdfSigner {
+    // Reads a PEM encoded certificate and the private key it was issued for
+    pub fn load(certificate_path: &Path, private_key_path: &Path) -> Result<PdfSigner> {
+        let certificate = std::fs::read(certificate_path)
+            .map_err(|err| key_error(format!("failed to read certificate: {}", err)))
+            .and_then(|pem| {
+                X509::from_pem(&pem)
+                    .map_err(|err| key_error(format!("invalid certificate: {}", err)))
+            })?;
+
+        let private_key = std::fs::read(private_key_path)
+            .map_err(|err| key_error(format!("failed to read private key: {}", err)))
+            .and_then(|pem| {
+                PKey::private_key_from_pem(&pem)
+                    .map_err(|err| key_error(format!("invalid private key: {}", err)))
+            })?;
+
+        let key_matches = certificate
+            .public_key()
+            .map(|public_key| public_key.public_eq(&private_key))
+            .unwrap_or(false);
+
+        if !key_matches {
+            return Err(key_error("private key does not belong to the certificate"));
+        }
+
+        Ok(PdfSigner {
+            certificate,
+            private_key,
+        })
+    }
+
+    // Adds an empty signature to `doc`, filled in by `sign` once the document is saved
+    pub fn add_signature_field(&self, doc: &mut Document) -> Result<()> {
+        let first_page = *doc
+            .get_pages()
+            .values()
+            .next()
+            .ok_or_else(|| signing_error("document has no pages"))?;
+
+        let signature = doc.add_object(dictionary! {
+            "Type" => "Sig",
+            "Filter" => "Adobe.PPKLite",
+            "SubFilter" => "adbe.pkcs7.detached",
+            "ByteRange" => vec![
+                0.into(),
+                BYTE_RANGE_PLACEHOLDER.into(),
+                BYTE_RANGE_PLACEHOLDER.into(),
+                BYTE_RANGE_PLACEHOLDER.into(),
+            ],
+            "Contents" => Object::String(vec![0; SIGNATURE_BYTES], StringFormat::Hexadecimal),
+            "M" => Object::string_literal(chrono::Utc::now().format("D:%Y%m%d%H%M%SZ").to_string()),
+            "Reason" => Object::string_literal("Software review export"),
+        });
+
+        // An invisible widget on the first page holds the signature
+        let field = doc.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Widget",
+            "FT" => "Sig",
+            "T" => Object::string_literal("Signature"),
+            "V" => signature,
+            "Rect" => vec![0.into(), 0.into(), 0.into(), 0.into()],
+            "F" => 132,
+            "P" => first_page,
+        });
+
+        doc.get_dictionary_mut(first_page)
+            .map_err(pdf_error)?
+            .set("Annots", vec![field.into()]);
+
+        doc.catalog_mut().map_err(pdf_error)?.set(
+            "AcroForm",
+            dictionary! {
+                "Fields" => vec![field.into()],
+                "SigFlags" => 3,
+            },
+        );
+
+        Ok(())
+    }
+
+    // Signs a saved document that was prepared by `add_signature_field`
+    pub fn sign(&self, mut pdf: Vec<u8>) -> Result<Vec<u8>> {
+        let byte_range_placeholder = format!("[0 {0} {0} {0}]", BYTE_RANGE_PLACEHOLDER);
+
+        let mut placeholder = vec![b'0'; SIGNATURE_BYTES * 2 + 2];
+        placeholder[0] = b'<';
+        placeholder[SIGNATURE_BYTES * 2 + 1] = b'>';
+
+        let contents_start = find(&pdf, &placeholder)
+            .ok_or_else(|| signing_error("document has no signature contents"))?;
+        let contents_end = contents_start + placeholder.len();
+
+        let byte_range = format!(
+            "[0 {} {} {}",
+            contents_start,
+            contents_end,
+            pdf.len() - contents_end
+        );
+        // Padded with spaces, so no offsets change
+        let byte_range = format!(
+            "{:width$}]",
+            byte_range,
+            width = byte_range_placeholder.len() - 1
+        );
+
+        let byte_range_start = find(&pdf, byte_range_placeholder.as_bytes())
+            .ok_or_else(|| signing_error("document has no signature byte range"))?;
+        pdf[byte_range_start..byte_range_start + byte_range.len()]
+            .copy_from_slice(byte_range.as_bytes());
+
+        let mut signed_data = pdf[..contents_start].to_vec();
+        signed_data.extend_from_slice(&pdf[contents_end..]);
+
+        let certificate_chain: Stack<X509> = Stack::new().map_err(openssl_error)?;
+
+        let signature = Pkcs7::sign(
+            &self.certificate,
+            &self.private_key,
+            &certificate_chain,
+            &signed_data,
+            Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY,
+        )
+        .and_then(|signature| signature.to_der())
+        .map_err(openssl_error)?;
+
+        if signature.len() > SIGNATURE_BYTES {
+            return Err(signing_error("signature is too large for the document"));
+        }
+
+        let signature: String = signature
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        pdf[contents_start + 1..contents_start + 1 + signature.len()]
+            .copy_from_slice(signature.as_bytes());
+
+        Ok(pdf)
+    }
+
+    // Returns true if `pdf` has a single signature, made with the configured certificate,
+    // that covers the whole file. `doc` is `pdf` once parsed
+    pub fn verify(&self, pdf: &[u8], doc: &Document) -> bool {
+        let signatures = signature_dictionaries(doc);
+
+        let [signature] = signatures.as_slice() else {
+            return false;
+        };
+
+        let byte_range: Vec<usize> = match signature.get(b"ByteRange").and_then(Object::as_array) {
+            Ok(byte_range) => byte_range
+                .iter()
+                .filter_map(|offset| offset.as_i64().ok())
+                .filter_map(|offset| usize::try_from(offset).ok())
+                .collect(),
+            Err(_) => return false,
+        };
+
+        let Ok(contents) = signature.get(b"Contents").and_then(Object::as_str) else {
+            return false;
+        };
+
+        // Only the signature itself may be left out, and nothing may be added after the
+        // signed bytes
+        let [0, contents_start, contents_end, rest] = byte_range[..] else {
+            return false;
+        };
+
+        if contents_start >= contents_end
+            || contents_end.checked_add(rest) != Some(pdf.len())
+            || pdf[contents_start] != b'<'
+            || pdf[contents_end - 1] != b'>'
+            || !pdf[contents_start + 1..contents_end - 1]
+                .iter()
+                .all(u8::is_ascii_hexdigit)
+        {
+            return false;
+        }
+
+        let mut signed_data = pdf[..contents_start].to_vec();
+        signed_data.extend_from_slice(&pdf[contents_end..]);
+
+        let verified = || -> std::result::Result<(), openssl::error::ErrorStack> {
+            let signature = Pkcs7::from_der(contents)?;
+
+            let mut certificates = Stack::new()?;
+            certificates.push(self.certificate.clone())?;
+
+            // Only the configured certificate is trusted, so the certificates in the
+            // signature and their chain are ignored
+            signature.verify(
+                &certificates,
+                &X509StoreBuilder::new()?.build(),
+                Some(&signed_data),
+                None,
+                Pkcs7Flags::NOINTERN | Pkcs7Flags::NOVERIFY | Pkcs7Flags::BINARY,
+            )
+        };
+
+        verified().is_ok()
+    }
+}
+
+// Returns true if `doc` has any signature, whether or not it is valid
+pub fn is_signed(doc: &Document) -> bool {
+    !signature_dictionaries(doc).is_empty()
+}
+
+fn signature_dictionaries(doc: &Document) -> Vec<&lopdf::Dictionary> {
+    doc.objects
+        .values()
+        .filter_map(|object| object.as_dict().ok())
+        .filter(|dict| {
+            dict.get(b"Type")
+                .and_then(Object::as_name)
+                .is_ok_and(|name| name == b"Sig")
+        })
+        .collect()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn signing_error(problem: impl std::fmt::Display) -> Error {
+    Error::ServerError(std::sync::Arc::new(
+        format!("failed to sign report: {}", problem).into(),
+    ))
+}
+
+fn key_error(problem: impl std::fmt::Display) -> Error {
+    Error::ServerError(std::sync::Arc::new(
+        format!("invalid report signing key: {}", problem).into(),
+    ))
+}
+
+fn openssl_error(err: openssl::error::ErrorStack) -> Error {
+    Error::ServerError(std::sync::Arc::new(err.into()))
+}
+
+fn pdf_error(err: lopdf::Error) -> Error {
+    Error::ServerError(std::sync::Arc::new(err.into()))
+}
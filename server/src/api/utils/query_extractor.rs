@@ -1,9 +1,10 @@
 use axum::async_trait;
-use axum::extract::{FromRequestParts, Query};
+use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use unicode_segmentation::UnicodeSegmentation;
 
+use super::{validation, ExportFormat};
 use crate::Error;
 
 #[derive(Debug, Deserialize)]
@@ -12,6 +13,10 @@ pub struct QueryParams {
     pub per_page: Option<usize>,
     pub sort: Option<String>,
     pub filter: Option<String>,
+    // Export format for endpoints that support exports (see `ExportFormat`)
+    pub format: Option<String>,
+    // Only used by software reviews, e.g. `30d` (see `parse_expiring_within`)
+    pub expiring_within: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -80,16 +85,9 @@ impl QueryParams {
                 )));
             }
 
-            let forbidden_chars = [
-                '/', '(', ')', '"', '<', '>', '\\', '{', '}', '$', '\'', '-', ';', '%',
-            ];
-
-            if value.trim().is_empty()
-                || value.graphemes(true).count() > 100
-                || value.chars().any(|c| forbidden_chars.contains(&c))
-                || value.contains("--")
-                || value.contains("/*")
-            {
+            // The value is always bound as a query parameter, so only its length and
+            // content are checked here
+            if !validation::validate_text(&value, 100) {
                 return Err(Error::QueryParamValidationError(format!(
                     "'{}' is an invalid filter value",
                     field
@@ -97,11 +95,18 @@ impl QueryParams {
             }
         }
 
+        // Check if `format` is valid
+        if let Some(format) = &self.format {
+            format.parse::<ExportFormat>()?;
+        }
+
         Ok(QueryParams {
             page: self.page,
             per_page: self.per_page,
             sort: self.sort.clone(),
             filter: self.filter.clone(),
+            format: self.format.clone(),
+            expiring_within: self.expiring_within.clone(),
         })
     }
 }
@@ -119,7 +124,7 @@ impl Metadata {
 }
 
 #[derive(Debug)]
-pub struct QueryExtractor(pub Query<QueryParams>);
+pub struct QueryExtractor(pub axum::extract::Query<QueryParams>);
 
 #[async_trait]
 impl<S> FromRequestParts<S> for QueryExtractor
@@ -130,19 +135,27 @@ where
 
     async fn from_request_parts(req: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         // Extract query parameters using `Query` extractor
-        let query_params = Query::<QueryParams>::try_from_uri(&req.uri).map_err(|_| {
-            Error::QueryParamValidationError(format!(
-                "invalid query parameters provided for uri: '{}'",
-                req.uri
-            ))
-        })?;
+        let query_params =
+            axum::extract::Query::<QueryParams>::try_from_uri(&req.uri).map_err(|_| {
+                Error::QueryParamValidationError(format!(
+                    "invalid query parameters provided for uri: '{}'",
+                    req.uri
+                ))
+            })?;
 
         // If there are no query parameters, return QueryExtractor
         if req.uri.query().is_none() {
             return Ok(QueryExtractor(query_params));
         }
 
-        let expected_params: [&str; 4] = ["page", "per_page", "sort", "filter"];
+        let expected_params: [&str; 6] = [
+            "page",
+            "per_page",
+            "sort",
+            "filter",
+            "format",
+            "expiring_within",
+        ];
 
         let actual_query = req.uri.query().unwrap_or("");
 
@@ -163,3 +176,24 @@ where
         Ok(QueryExtractor(query_params))
     }
 }
+
+// Define our own `Query` extractor that customizes the error from `axum::Query`
+// Used for endpoints that accept query parameters other than `QueryParams`
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(req: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::try_from_uri(&req.uri) {
+            Ok(value) => Ok(Self(value.0)),
+            Err(rejection) => Err(Error::QueryParamValidationError(rejection.body_text())),
+        }
+    }
+}
@@ -0,0 +1,746 @@
+use async_zip::tokio::write::ZipFileWriter;
+use async_zip::{Compression, ZipDateTime, ZipEntryBuilder};
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+
+use crate::api::models::{ReviewCommentDTO, ReviewReportFormat, SoftwareReviewDTO};
+use crate::api::utils::validation::encode_content_disposition;
+use crate::api::utils::{
+    escape_markdown, render_pdf, write_markdown, MarkdownBlock, MarkdownBlockKind, MarkdownSpan,
+    ReportIntegrity, ReportTemplate, ReviewReport, ReviewReportContent,
+};
+use crate::{Error, Result};
+
+// Twentieths of a point, the unit of page sizes in a DOCX document
+const TWIPS_PER_MM: f32 = 1440.0 / 25.4;
+// Indent of each level of a nested list in the notes
+const DOCX_LIST_INDENT_MM: f32 = 6.35;
+
+#[tracing::instrument(
+    name = "generating report for software review",
+    skip(software_review, comments, review_version, template, integrity)
+)]
+pub async fn generate_report(
+    software_review: &SoftwareReviewDTO,
+    comments: &[ReviewCommentDTO],
+    review_version: i32,
+    format: ReviewReportFormat,
+    template: &ReportTemplate,
+    integrity: &ReportIntegrity,
+) -> Result<Response> {
+    let buffer = match format {
+        ReviewReportFormat::Pdf => {
+            render_pdf(
+                software_review,
+                comments,
+                review_version,
+                template,
+                integrity,
+            )
+            .await?
+        }
+        ReviewReportFormat::Html => {
+            render_html(&ReviewReport::new(software_review, comments, template)).into_bytes()
+        }
+        ReviewReportFormat::Markdown => {
+            render_markdown(&ReviewReport::new(software_review, comments, template)).into_bytes()
+        }
+        ReviewReportFormat::Docx => {
+            render_docx(
+                &ReviewReport::new(software_review, comments, template),
+                template,
+            )
+            .await?
+        }
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            encode_content_disposition(&format!(
+                "{}.{}",
+                software_review.software_request.software.software_name,
+                format.extension()
+            )),
+        )
+        .body(buffer.into())
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+}
+
+// Renders the report as a standalone HTML page
+pub fn render_html(report: &ReviewReport) -> String {
+    let mut body = format!("<h1>{}</h1>\n", escape_xml(&report.title));
+
+    if !report.header_lines.is_empty() {
+        let header_lines: Vec<String> = report
+            .header_lines
+            .iter()
+            .map(|line| escape_xml(line))
+            .collect();
+        body.push_str(&format!("<p>{}</p>\n", header_lines.join("<br>\n")));
+    }
+
+    for section in &report.sections {
+        body.push_str(&format!("<h2>{}</h2>\n", escape_xml(&section.title)));
+
+        match &section.content {
+            ReviewReportContent::Criteria(criteria) => {
+                body.push_str("<ul class=\"criteria\">\n");
+
+                for criterion in criteria {
+                    let (red, green, blue) = criterion.answer.color;
+
+                    body.push_str(&format!(
+                        "<li><span class=\"answer\" style=\"color: #{:02x}{:02x}{:02x}\" \
+                         title=\"{}\">{}</span> {}</li>\n",
+                        red,
+                        green,
+                        blue,
+                        criterion.answer.text,
+                        criterion.answer.glyph,
+                        escape_xml(&criterion.label)
+                    ));
+                }
+
+                body.push_str("</ul>\n");
+            }
+            ReviewReportContent::Text(texts) => {
+                for paragraph in texts.iter().flat_map(|text| paragraphs(text)) {
+                    let lines: Vec<String> =
+                        paragraph.iter().map(|line| escape_xml(line)).collect();
+                    body.push_str(&format!("<p>{}</p>\n", lines.join("<br>\n")));
+                }
+            }
+            ReviewReportContent::Markdown(blocks) => body.push_str(&markdown_html(blocks)),
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{}</title>\n\
+         <style>body {{ font-family: sans-serif; max-width: 48em; margin: 2em auto; }} \
+         ul.criteria {{ list-style: none; padding-left: 0; }}</style>\n\
+         </head>\n\
+         <body>\n\
+         {}\
+         </body>\n\
+         </html>\n",
+        escape_xml(&report.title),
+        body
+    )
+}
+
+// Renders the report as CommonMark. Text from the review is escaped, so it can't add
+// formatting of its own, except the notes, which are written with the formatting they
+// were sanitized to
+pub fn render_markdown(report: &ReviewReport) -> String {
+    let mut markdown = format!("# {}\n", escape_markdown(&report.title));
+
+    if !report.header_lines.is_empty() {
+        let header_lines: Vec<String> = report
+            .header_lines
+            .iter()
+            .map(|line| escape_markdown(line))
+            .collect();
+        markdown.push_str(&format!("\n{}\n", header_lines.join("\\\n")));
+    }
+
+    for section in &report.sections {
+        markdown.push_str(&format!("\n## {}\n\n", escape_markdown(&section.title)));
+
+        match &section.content {
+            ReviewReportContent::Criteria(criteria) => {
+                for criterion in criteria {
+                    markdown.push_str(&format!(
+                        "- {} {}\n",
+                        criterion.answer.glyph,
+                        escape_markdown(&criterion.label)
+                    ));
+                }
+            }
+            ReviewReportContent::Text(texts) => {
+                let paragraphs: Vec<String> = texts
+                    .iter()
+                    .flat_map(|text| paragraphs(text))
+                    .map(|paragraph| {
+                        let lines: Vec<String> =
+                            paragraph.iter().map(|line| escape_markdown(line)).collect();
+                        lines.join("\\\n")
+                    })
+                    .collect();
+
+                markdown.push_str(&paragraphs.join("\n\n"));
+                markdown.push('\n');
+            }
+            // Headings in the notes are nested below the heading of the section
+            ReviewReportContent::Markdown(blocks) => {
+                markdown.push_str(&write_markdown(blocks, 2));
+                markdown.push('\n');
+            }
+        }
+    }
+
+    markdown
+}
+
+// Renders the report as a Word document, using the page size and font sizes of the
+// template. The document only has the parts Word requires, so it is written directly
+// rather than through a template document
+pub async fn render_docx(report: &ReviewReport, template: &ReportTemplate) -> Result<Vec<u8>> {
+    let fonts = &template.fonts;
+
+    let mut body = docx_paragraph(&[DocxRun::bold(&report.title, fonts.title_size)]);
+
+    if !report.header_lines.is_empty() {
+        let runs: Vec<DocxRun> = report
+            .header_lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| DocxRun {
+                line_break: index > 0,
+                ..DocxRun::text(line, fonts.text_size)
+            })
+            .collect();
+        body.push_str(&docx_paragraph(&runs));
+    }
+
+    for section in &report.sections {
+        body.push_str(&docx_paragraph(&[DocxRun::bold(
+            &section.title,
+            fonts.heading_size,
+        )]));
+
+        match &section.content {
+            ReviewReportContent::Criteria(criteria) => {
+                for criterion in criteria {
+                    body.push_str(&docx_paragraph(&[
+                        DocxRun {
+                            color: Some(criterion.answer.color),
+                            ..DocxRun::text(criterion.answer.glyph, fonts.symbol_size)
+                        },
+                        DocxRun::text(&format!(" | {}", criterion.label), fonts.text_size),
+                    ]));
+                }
+            }
+            ReviewReportContent::Text(texts) => {
+                for paragraph in texts.iter().flat_map(|text| paragraphs(text)) {
+                    let runs: Vec<DocxRun> = paragraph
+                        .iter()
+                        .enumerate()
+                        .map(|(index, line)| DocxRun {
+                            line_break: index > 0,
+                            ..DocxRun::text(line, fonts.text_size)
+                        })
+                        .collect();
+                    body.push_str(&docx_paragraph(&runs));
+                }
+            }
+            ReviewReportContent::Markdown(blocks) => {
+                for block in blocks {
+                    body.push_str(&docx_markdown_paragraph(block, fonts.text_size));
+                }
+            }
+        }
+    }
+
+    let page = &template.page;
+    let twips = |mm: f32| (mm * TWIPS_PER_MM).round() as i64;
+
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+         <w:body>{}<w:sectPr><w:pgSz w:w=\"{}\" w:h=\"{}\"/>\
+         <w:pgMar w:top=\"{margin}\" w:right=\"{margin}\" w:bottom=\"{margin}\" \
+         w:left=\"{margin}\" w:header=\"0\" w:footer=\"0\" w:gutter=\"0\"/>\
+         </w:sectPr></w:body></w:document>",
+        body,
+        twips(page.width_mm),
+        twips(page.height_mm),
+        margin = twips(page.margin_mm),
+    );
+
+    let content_types = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+        <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+        <Default Extension=\"rels\" \
+        ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+        <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+        <Override PartName=\"/word/document.xml\" \
+        ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>\
+        </Types>";
+
+    let relationships = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+        <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+        <Relationship Id=\"rId1\" \
+        Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" \
+        Target=\"word/document.xml\"/>\
+        </Relationships>";
+
+    let mut writer = ZipFileWriter::with_tokio(Vec::new());
+
+    for (name, data) in [
+        ("[Content_Types].xml", content_types.as_bytes()),
+        ("_rels/.rels", relationships.as_bytes()),
+        ("word/document.xml", document.as_bytes()),
+    ] {
+        let entry = ZipEntryBuilder::new(name.to_string().into(), Compression::Deflate)
+            .last_modification_date(ZipDateTime::from_chrono(&chrono::Utc::now()));
+
+        writer
+            .write_entry_whole(entry, data)
+            .await
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+    }
+
+    let buffer = writer
+        .close()
+        .await
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+    Ok(buffer.into_inner())
+}
+
+// A run of text in a DOCX paragraph, with its size in points
+struct DocxRun<'a> {
+    text: &'a str,
+    size: f32,
+    bold: bool,
+    italic: bool,
+    color: Option<(u8, u8, u8)>,
+    // Starts the run on a new line, within the same paragraph
+    line_break: bool,
+}
+
+impl<'a> DocxRun<'a> {
+    fn text(text: &'a str, size: f32) -> Self {
+        DocxRun {
+            text,
+            size,
+            bold: false,
+            italic: false,
+            color: None,
+            line_break: false,
+        }
+    }
+
+    fn bold(text: &'a str, size: f32) -> Self {
+        DocxRun {
+            bold: true,
+            ..DocxRun::text(text, size)
+        }
+    }
+}
+
+fn docx_paragraph(runs: &[DocxRun]) -> String {
+    docx_paragraph_with_properties("", runs)
+}
+
+// A block of formatted text as a paragraph. Headings are bold, and list items are indented
+// by their depth, with the text wrapping to the right of the bullet or number
+fn docx_markdown_paragraph(block: &MarkdownBlock, size: f32) -> String {
+    let mut runs = vec![];
+    let mut line_break = false;
+
+    for span in &block.spans {
+        for (index, line) in span.text.split('\n').enumerate() {
+            line_break |= index > 0;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            runs.push(DocxRun {
+                bold: span.bold || matches!(block.kind, MarkdownBlockKind::Heading(_)),
+                italic: span.italic,
+                line_break: std::mem::take(&mut line_break),
+                ..DocxRun::text(line, size)
+            });
+        }
+    }
+
+    let MarkdownBlockKind::ListItem { depth, number } = block.kind else {
+        return docx_paragraph(&runs);
+    };
+
+    let marker = match number {
+        Some(number) => format!("{}.\t", number),
+        None => "•\t".to_string(),
+    };
+    runs.insert(0, DocxRun::text(&marker, size));
+
+    let twips = |mm: f32| (mm * TWIPS_PER_MM).round() as i64;
+
+    docx_paragraph_with_properties(
+        &format!(
+            "<w:pPr><w:ind w:left=\"{}\" w:hanging=\"{}\"/></w:pPr>",
+            twips(DOCX_LIST_INDENT_MM * (depth + 1) as f32),
+            twips(DOCX_LIST_INDENT_MM)
+        ),
+        &runs,
+    )
+}
+
+// `properties` are the paragraph's `<w:pPr>`, if it has any
+fn docx_paragraph_with_properties(properties: &str, runs: &[DocxRun]) -> String {
+    let mut paragraph = format!("<w:p>{}", properties);
+
+    for run in runs {
+        let mut properties = String::new();
+
+        if run.bold {
+            properties.push_str("<w:b/>");
+        }
+        if run.italic {
+            properties.push_str("<w:i/>");
+        }
+        if let Some((red, green, blue)) = run.color {
+            properties.push_str(&format!(
+                "<w:color w:val=\"{:02X}{:02X}{:02X}\"/>",
+                red, green, blue
+            ));
+        }
+        // Sizes are in half points
+        properties.push_str(&format!("<w:sz w:val=\"{}\"/>", (run.size * 2.0).round()));
+
+        let line_break = if run.line_break { "<w:br/>" } else { "" };
+
+        paragraph.push_str(&format!(
+            "<w:r><w:rPr>{}</w:rPr>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>",
+            properties,
+            line_break,
+            escape_xml(run.text)
+        ));
+    }
+
+    paragraph.push_str("</w:p>");
+
+    paragraph
+}
+
+// Renders formatted text as HTML, with headings nested below the `h2` of the section. The
+// `li` of every open list is left open, so nested lists are within an item
+fn markdown_html(blocks: &[MarkdownBlock]) -> String {
+    let mut html = String::new();
+    // The tag of each open list, outermost first
+    let mut lists: Vec<&str> = vec![];
+
+    for block in blocks {
+        let MarkdownBlockKind::ListItem { depth, number } = block.kind else {
+            while let Some(tag) = lists.pop() {
+                html.push_str(&format!("</li>\n</{}>\n", tag));
+            }
+
+            let spans = markdown_spans_html(&block.spans);
+
+            match block.kind {
+                MarkdownBlockKind::Heading(level) => {
+                    let level = level.saturating_add(2).min(6);
+                    html.push_str(&format!("<h{}>{}</h{}>\n", level, spans, level));
+                }
+                _ => html.push_str(&format!("<p>{}</p>\n", spans)),
+            }
+            continue;
+        };
+
+        let tag = if number.is_some() { "ol" } else { "ul" };
+
+        // Lists deeper than the item, or of another kind at its depth, end before it
+        while lists.len() > depth + 1 || (lists.len() == depth + 1 && lists[depth] != tag) {
+            if let Some(tag) = lists.pop() {
+                html.push_str(&format!("</li>\n</{}>\n", tag));
+            }
+        }
+
+        if lists.len() == depth + 1 {
+            html.push_str("</li>\n");
+        }
+
+        while lists.len() <= depth {
+            if lists.len() < depth {
+                // A level without an item of its own, e.g. after a paragraph of an item
+                html.push_str("<ul>\n<li>");
+                lists.push("ul");
+            } else {
+                match number {
+                    Some(start) if start != 1 => {
+                        html.push_str(&format!("<ol start=\"{}\">\n", start))
+                    }
+                    _ => html.push_str(&format!("<{}>\n", tag)),
+                }
+                lists.push(tag);
+            }
+        }
+
+        html.push_str(&format!("<li>{}", markdown_spans_html(&block.spans)));
+    }
+
+    while let Some(tag) = lists.pop() {
+        html.push_str(&format!("</li>\n</{}>\n", tag));
+    }
+
+    html
+}
+
+fn markdown_spans_html(spans: &[MarkdownSpan]) -> String {
+    spans
+        .iter()
+        .map(|span| {
+            let lines: Vec<String> = span.text.split('\n').map(escape_xml).collect();
+            let mut text = lines.join("<br>\n");
+
+            if span.italic {
+                text = format!("<em>{}</em>", text);
+            }
+            if span.bold {
+                text = format!("<strong>{}</strong>", text);
+            }
+
+            text
+        })
+        .collect()
+}
+
+// Splits text into paragraphs at blank lines, keeping the line breaks within each
+// paragraph
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = vec![];
+    let mut current = vec![];
+
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+// Escapes text for HTML and XML. Control characters aren't allowed in XML, so they are
+// left out
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            '\t' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Unit Tests
+#[cfg(test)]
+mod report_formats_tests {
+    use uuid::Uuid;
+
+    use super::{paragraphs, render_docx, render_html, render_markdown};
+    use crate::api::models::{
+        CommentAuthor, RequesterDTO, ReviewCommentDTO, ReviewOptions, SoftwareDTO,
+        SoftwareRequestDTO, SoftwareReviewDTO, UserDTO, UserRole,
+    };
+    use crate::api::utils::{escape_markdown, ReportTemplate, ReviewReport};
+
+    fn software_review(review_notes: &str) -> SoftwareReviewDTO {
+        SoftwareReviewDTO {
+            id: Uuid::new_v4(),
+            software_request: SoftwareRequestDTO {
+                id: None,
+                td_request_id: "12345678".to_string(),
+                software: SoftwareDTO {
+                    id: None,
+                    software_name: "<Test> & Software".to_string(),
+                    software_version: "1.0.0".to_string(),
+                    developer_name: "Test Developer".to_string(),
+                    description: "A test software application".to_string(),
+                    created_at: None,
+                },
+                requester: RequesterDTO {
+                    id: None,
+                    name: "John".to_string(),
+                    email: "john@gmail.com".to_string(),
+                    department: "IT".to_string(),
+                    created_at: None,
+                },
+                created_at: None,
+            },
+            reviewer: UserDTO {
+                id: None,
+                name: "Jane".to_string(),
+                email: "jane@gmail.com".to_string(),
+                role: UserRole::REVIEWER,
+                created_at: None,
+            },
+            is_supported: ReviewOptions::TRUE,
+            is_current_version: ReviewOptions::TRUE,
+            is_reputation_good: ReviewOptions::TRUE,
+            is_installation_from_developer: ReviewOptions::NOT_SURE,
+            is_local_admin_required: ReviewOptions::FALSE,
+            is_connected_to_brockport_cloud: ReviewOptions::FALSE,
+            is_connected_to_cloud_services_or_client: ReviewOptions::FALSE,
+            is_security_or_optimization_software: ReviewOptions::FALSE,
+            is_supported_by_current_os: ReviewOptions::TRUE,
+            exported: false,
+            review_notes: review_notes.to_string(),
+            reviewed_version: "1.0.0".to_string(),
+            newer_version_available: false,
+            expires_at: chrono::Utc::now(),
+            created_at: Some(chrono::Utc::now()),
+        }
+    }
+
+    fn report(review_notes: &str) -> ReviewReport {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+
+        ReviewReport::new(&software_review(review_notes), &[], &template)
+    }
+
+    #[test]
+    fn paragraphs_split_at_blank_lines() {
+        assert_eq!(
+            vec![vec!["first", "still first"], vec!["second"]],
+            paragraphs("first\nstill first\n\n\nsecond\n")
+        );
+    }
+
+    #[test]
+    fn html_escapes_review_text() {
+        let html = render_html(&report(
+            "<b>bold</b> & **strong**\n\nsecond paragraph\n\n- one\n  1. nested\n- two",
+        ));
+
+        assert!(html.contains("<p>&lt;Test&gt; &amp; Software"));
+        // HTML in the notes is left out, while their formatting is kept
+        assert!(html.contains("<p>bold &amp; <strong>strong</strong></p>"));
+        assert!(html.contains("<p>second paragraph</p>"));
+        assert!(html
+            .contains("<ul>\n<li>one<ol>\n<li>nested</li>\n</ol>\n</li>\n<li>two</li>\n</ul>\n"));
+        assert!(!html.contains("<b>"));
+        // The answers are shown with the same glyphs as the PDF
+        assert!(html.contains("title=\"Yes\">✓</span>"));
+        assert!(html.contains("title=\"No\">✖</span>"));
+        assert!(html.contains("title=\"Not sure\">❓</span>"));
+    }
+
+    #[test]
+    fn markdown_nests_note_headings_below_the_section() {
+        let markdown = render_markdown(&report("# Findings\n\n**Signed** by the vendor"));
+
+        assert!(markdown.contains("\n## Notes\n\n### Findings\n\n**Signed** by the vendor\n"));
+    }
+
+    #[test]
+    fn markdown_lists_every_criterion() {
+        let markdown = render_markdown(&report("All conditions satisfied."));
+
+        assert!(markdown.starts_with("# BITS Application Security Review\n"));
+        assert_eq!(
+            9,
+            markdown
+                .lines()
+                .filter(|line| line.starts_with("- ✓ ")
+                    || line.starts_with("- ✖ ")
+                    || line.starts_with("- ❓ "))
+                .count()
+        );
+        assert!(markdown.contains("\nAll conditions satisfied.\n"));
+    }
+
+    #[test]
+    fn comments_are_only_included_when_given() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+        let software_review = software_review("All conditions satisfied.");
+
+        let comments =
+            ["Checked with the vendor", "Approved by *security*"].map(|body| ReviewCommentDTO {
+                id: Uuid::new_v4(),
+                software_review_id: software_review.id,
+                author: CommentAuthor {
+                    id: Uuid::new_v4(),
+                    name: "Jane".to_string(),
+                    email: "jane@gmail.com".to_string(),
+                },
+                body: body.to_string(),
+                edited_at: None,
+                created_at: chrono::Utc::now(),
+            });
+
+        let markdown = render_markdown(&ReviewReport::new(&software_review, &[], &template));
+        assert!(!markdown.contains("## Comments"));
+
+        let markdown = render_markdown(&ReviewReport::new(&software_review, &comments, &template));
+        assert!(markdown.contains("\n## Comments\n"));
+
+        // Each comment is a paragraph below its author and date
+        let date = template.format_date(comments[0].created_at);
+        assert!(markdown.contains(&format!(
+            "Jane, {}:\\\nChecked with the vendor\n\nJane, {}:\\\nApproved by \\*security\\*\n",
+            escape_markdown(&date),
+            escape_markdown(&date)
+        )));
+    }
+
+    #[tokio::test]
+    async fn docx_is_a_zip_with_a_document() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+        let report = ReviewReport::new(
+            &software_review("Fish & **chips**\n\n- *vinegar*"),
+            &[],
+            &template,
+        );
+
+        let docx = render_docx(&report, &template).await.unwrap();
+
+        assert!(docx.starts_with(b"PK"));
+
+        let reader = async_zip::base::read::mem::ZipFileReader::new(docx)
+            .await
+            .unwrap();
+        let names: Vec<String> = reader
+            .file()
+            .entries()
+            .iter()
+            .map(|entry| entry.filename().as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            vec!["[Content_Types].xml", "_rels/.rels", "word/document.xml"],
+            names
+        );
+
+        let mut document = String::new();
+        reader
+            .reader_with_entry(2)
+            .await
+            .unwrap()
+            .read_to_string_checked(&mut document)
+            .await
+            .unwrap();
+
+        // The notes keep their formatting, and list items are indented
+        assert!(document.contains(
+            "<w:rPr><w:b/><w:sz w:val=\"22\"/></w:rPr><w:t xml:space=\"preserve\">chips</w:t>"
+        ));
+        assert!(document.contains("<w:p><w:pPr><w:ind w:left=\"360\" w:hanging=\"360\"/></w:pPr>"));
+        assert!(document
+            .contains("<w:i/><w:sz w:val=\"22\"/></w:rPr><w:t xml:space=\"preserve\">vinegar"));
+    }
+}
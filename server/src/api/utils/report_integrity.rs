@@ -0,0 +1,192 @@
+use lopdf::{Dictionary, Document, Object};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::pdf_signature::{is_signed, PdfSigner};
+use crate::api::models::SoftwareReviewDTO;
+use crate::{Error, FieldError, FieldErrorCode, Result};
+
+// Keys added to the document information dictionary of every report
+const REVIEW_ID_KEY: &[u8] = b"ReviewId";
+const REVIEW_VERSION_KEY: &[u8] = b"ReviewVersion";
+const CONTENT_HASH_KEY: &[u8] = b"ContentHash";
+const VERIFICATION_URL_KEY: &[u8] = b"VerificationURL";
+
+// Identifies the review a report was rendered from, so the report can later be checked
+// against the review
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportStamp {
+    pub review_id: Uuid,
+    pub review_version: i32,
+    // See `content_hash`
+    pub content_hash: String,
+    pub verification_url: String,
+}
+
+// What `ReportIntegrity::inspect` read from a report
+#[derive(Debug)]
+pub struct ReportInspection {
+    pub stamp: ReportStamp,
+    pub signed: bool,
+    // Only checked when the report is signed and a signing key is configured, as there is
+    // no certificate to check the signature against otherwise
+    pub signature_valid: Option<bool>,
+}
+
+// Stamps reports with the review they were rendered from and, when a signing key is
+// configured, signs them
+pub struct ReportIntegrity {
+    // Printed on every report, followed by the id of the review
+    verification_url: String,
+    signer: Option<PdfSigner>,
+}
+
+impl ReportIntegrity {
+    pub fn new(verification_url: String, signer: Option<PdfSigner>) -> Self {
+        ReportIntegrity {
+            verification_url,
+            signer,
+        }
+    }
+
+    pub fn stamp(&self, software_review: &SoftwareReviewDTO, review_version: i32) -> ReportStamp {
+        ReportStamp {
+            review_id: software_review.id,
+            review_version,
+            content_hash: content_hash(software_review),
+            verification_url: format!("{}?review_id={}", self.verification_url, software_review.id),
+        }
+    }
+
+    // Adds `stamp` to the document information of a rendered report, then signs it
+    pub fn seal(&self, pdf: Vec<u8>, stamp: &ReportStamp) -> Result<Vec<u8>> {
+        let mut doc = Document::load_mem(&pdf).map_err(pdf_error)?;
+
+        let info = match doc.trailer.get(b"Info").and_then(Object::as_reference) {
+            Ok(info) => info,
+            Err(_) => {
+                let info = doc.add_object(Dictionary::new());
+                doc.trailer.set("Info", info);
+                info
+            }
+        };
+
+        let info = doc.get_dictionary_mut(info).map_err(pdf_error)?;
+        info.set(
+            REVIEW_ID_KEY,
+            Object::string_literal(stamp.review_id.to_string()),
+        );
+        info.set(REVIEW_VERSION_KEY, i64::from(stamp.review_version));
+        info.set(
+            CONTENT_HASH_KEY,
+            Object::string_literal(stamp.content_hash.clone()),
+        );
+        info.set(
+            VERIFICATION_URL_KEY,
+            Object::string_literal(stamp.verification_url.clone()),
+        );
+
+        if let Some(signer) = &self.signer {
+            signer.add_signature_field(&mut doc)?;
+        }
+
+        let mut buffer = Vec::new();
+        doc.save_to(&mut buffer)
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+        match &self.signer {
+            Some(signer) => signer.sign(buffer),
+            None => Ok(buffer),
+        }
+    }
+
+    // Reads the stamp and checks the signature of an uploaded report. Fails if the file
+    // isn't a PDF or wasn't stamped by `seal`
+    pub fn inspect(&self, pdf: &[u8]) -> Result<ReportInspection> {
+        let not_a_report = || {
+            Error::ValidationError(
+                FieldError::new(
+                    "file",
+                    FieldErrorCode::InvalidValue,
+                    "file is not a software review report",
+                )
+                .into(),
+            )
+        };
+
+        let doc = Document::load_mem(pdf).map_err(|_| not_a_report())?;
+
+        let info = doc
+            .trailer
+            .get(b"Info")
+            .and_then(Object::as_reference)
+            .and_then(|info| doc.get_dictionary(info))
+            .map_err(|_| not_a_report())?;
+
+        let text = |key: &[u8]| {
+            info.get(key)
+                .and_then(Object::as_str)
+                .ok()
+                .and_then(|value| std::str::from_utf8(value).ok())
+                .map(str::to_string)
+        };
+
+        let stamp = (|| {
+            Some(ReportStamp {
+                review_id: text(REVIEW_ID_KEY)?.parse().ok()?,
+                review_version: info
+                    .get(REVIEW_VERSION_KEY)
+                    .and_then(Object::as_i64)
+                    .ok()?
+                    .try_into()
+                    .ok()?,
+                content_hash: text(CONTENT_HASH_KEY)?,
+                verification_url: text(VERIFICATION_URL_KEY)?,
+            })
+        })()
+        .ok_or_else(not_a_report)?;
+
+        let signed = is_signed(&doc);
+        let signature_valid = self
+            .signer
+            .as_ref()
+            .filter(|_| signed)
+            .map(|signer| signer.verify(pdf, &doc));
+
+        Ok(ReportInspection {
+            stamp,
+            signed,
+            signature_valid,
+        })
+    }
+}
+
+// SHA-256 of the parts of a review shown in its report that belong to the review itself:
+// the answers, the notes, and who reviewed which version of the software. Marking a review
+// exported doesn't change it
+pub fn content_hash(software_review: &SoftwareReviewDTO) -> String {
+    let content = serde_json::json!({
+        "id": software_review.id,
+        "software_request_id": software_review.software_request.id,
+        "reviewer_id": software_review.reviewer.id,
+        "is_supported": software_review.is_supported,
+        "is_current_version": software_review.is_current_version,
+        "is_reputation_good": software_review.is_reputation_good,
+        "is_installation_from_developer": software_review.is_installation_from_developer,
+        "is_local_admin_required": software_review.is_local_admin_required,
+        "is_connected_to_brockport_cloud": software_review.is_connected_to_brockport_cloud,
+        "is_connected_to_cloud_services_or_client":
+            software_review.is_connected_to_cloud_services_or_client,
+        "is_security_or_optimization_software":
+            software_review.is_security_or_optimization_software,
+        "is_supported_by_current_os": software_review.is_supported_by_current_os,
+        "review_notes": software_review.review_notes,
+        "reviewed_version": software_review.reviewed_version,
+    });
+
+    format!("{:x}", Sha256::digest(content.to_string().as_bytes()))
+}
+
+fn pdf_error(err: lopdf::Error) -> Error {
+    Error::ServerError(std::sync::Arc::new(err.into()))
+}
@@ -0,0 +1,715 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use config::FileFormat;
+use printpdf::{Image, PdfDocument};
+use serde::{Deserialize, Serialize};
+
+use super::ReportStamp;
+use crate::api::models::{ReviewOptions, SoftwareReviewDTO};
+use crate::{Error, Result};
+
+// Placeholders that can be used in any text of the template, replaced with the details
+// of the review being rendered
+const PLACEHOLDERS: [&str; 9] = [
+    "software_name",
+    "software_version",
+    "developer_name",
+    "td_request_id",
+    "requester_name",
+    "requester_department",
+    "reviewer_name",
+    "reviewed_version",
+    "review_date",
+];
+
+// Placeholders that can only be used in the footer, as they aren't known until the
+// report is rendered
+const FOOTER_PLACEHOLDERS: [&str; 6] = [
+    "page",
+    "pages",
+    "review_id",
+    "review_version",
+    "content_hash",
+    "verification_url",
+];
+
+// Font sizes and line heights are in points, the rest of the layout is in millimeters
+pub const MM_PER_PT: f32 = 25.4 / 72.0;
+
+// A page with less room than this can't fit the title and a section heading along with
+// its content
+const MIN_LINES_PER_PAGE: usize = 5;
+
+// Layout, wording and branding of the software review PDF, read from a TOML or JSON file
+// (see `assets/templates/review_report.toml`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportTemplate {
+    pub page: PageLayout,
+    pub fonts: ReportFonts,
+    pub logo: Option<ReportLogo>,
+    pub date: ReportDate,
+    pub header: ReportHeader,
+    #[serde(default)]
+    pub footer: ReportFooter,
+    pub sections: Vec<ReportSection>,
+    // Contents of the files referenced by the template, read when it is loaded
+    #[serde(skip)]
+    pub assets: ReportAssets,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PageLayout {
+    pub width_mm: f32,
+    pub height_mm: f32,
+    pub margin_mm: f32,
+    pub content_top_mm: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportFonts {
+    pub regular: PathBuf,
+    pub symbols: PathBuf,
+    pub title_size: f32,
+    pub heading_size: f32,
+    pub text_size: f32,
+    pub symbol_size: f32,
+    pub line_height: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportLogo {
+    pub path: PathBuf,
+    pub scale: f32,
+    pub height_mm: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportDate {
+    // A `chrono` format string, e.g. `%m/%d/%Y`
+    pub format: String,
+    pub utc_offset_hours: i32,
+}
+
+// The logo and title are repeated at the top of every page, the other lines are only on
+// the first page
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportHeader {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+// Printed on every page. Besides the other placeholders, the footer can use
+// `FOOTER_PLACEHOLDERS`
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportFooter {
+    // Centered below the content
+    pub text: String,
+    // Small print in the bottom margin, identifying the review the report was rendered
+    // from. Empty to leave it out
+    pub verification: String,
+}
+
+// Sections are rendered in order below the header
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ReportSection {
+    // Each criterion's answer as a glyph, followed by its label
+    Criteria {
+        title: String,
+        criteria: Vec<ReportCriterion>,
+    },
+    // The review notes, wrapped to the width of the page
+    Notes {
+        title: String,
+    },
+    // Fixed lines of text, such as a disclaimer
+    Text {
+        title: String,
+        lines: Vec<String>,
+    },
+    // The review's comments, when a report is exported with them. Left out otherwise
+    Comments {
+        title: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportCriterion {
+    pub field: ReviewCriterion,
+    pub label: String,
+}
+
+// The review fields a criterion can show
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewCriterion {
+    IsSupported,
+    IsCurrentVersion,
+    IsReputationGood,
+    IsInstallationFromDeveloper,
+    IsLocalAdminRequired,
+    IsConnectedToBrockportCloud,
+    IsConnectedToCloudServicesOrClient,
+    IsSecurityOrOptimizationSoftware,
+    IsSupportedByCurrentOs,
+}
+
+#[derive(Debug, Default)]
+pub struct ReportAssets {
+    pub regular_font: Vec<u8>,
+    pub symbol_font: Vec<u8>,
+    pub logo: Option<Vec<u8>>,
+}
+
+impl Default for ReportFooter {
+    fn default() -> Self {
+        ReportFooter {
+            text: "Page {page} of {pages}".to_string(),
+            verification: "Review {review_id}, version {review_version}. Verify at \
+                           {verification_url}"
+                .to_string(),
+        }
+    }
+}
+
+impl ReviewCriterion {
+    pub const ALL: [ReviewCriterion; 9] = [
+        ReviewCriterion::IsSupported,
+        ReviewCriterion::IsCurrentVersion,
+        ReviewCriterion::IsReputationGood,
+        ReviewCriterion::IsInstallationFromDeveloper,
+        ReviewCriterion::IsLocalAdminRequired,
+        ReviewCriterion::IsConnectedToBrockportCloud,
+        ReviewCriterion::IsConnectedToCloudServicesOrClient,
+        ReviewCriterion::IsSecurityOrOptimizationSoftware,
+        ReviewCriterion::IsSupportedByCurrentOs,
+    ];
+
+    // The answer that raises no concern. Local admin, cloud connections and security
+    // software are only a concern when they apply
+    pub fn passing_answer(&self) -> ReviewOptions {
+        match self {
+            ReviewCriterion::IsLocalAdminRequired
+            | ReviewCriterion::IsConnectedToBrockportCloud
+            | ReviewCriterion::IsConnectedToCloudServicesOrClient
+            | ReviewCriterion::IsSecurityOrOptimizationSoftware => ReviewOptions::FALSE,
+            _ => ReviewOptions::TRUE,
+        }
+    }
+
+    // Name of the review field, which is also its column in `software_review`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReviewCriterion::IsSupported => "is_supported",
+            ReviewCriterion::IsCurrentVersion => "is_current_version",
+            ReviewCriterion::IsReputationGood => "is_reputation_good",
+            ReviewCriterion::IsInstallationFromDeveloper => "is_installation_from_developer",
+            ReviewCriterion::IsLocalAdminRequired => "is_local_admin_required",
+            ReviewCriterion::IsConnectedToBrockportCloud => "is_connected_to_brockport_cloud",
+            ReviewCriterion::IsConnectedToCloudServicesOrClient => {
+                "is_connected_to_cloud_services_or_client"
+            }
+            ReviewCriterion::IsSecurityOrOptimizationSoftware => {
+                "is_security_or_optimization_software"
+            }
+            ReviewCriterion::IsSupportedByCurrentOs => "is_supported_by_current_os",
+        }
+    }
+
+    pub fn value<'a>(&self, software_review: &'a SoftwareReviewDTO) -> &'a ReviewOptions {
+        match self {
+            ReviewCriterion::IsSupported => &software_review.is_supported,
+            ReviewCriterion::IsCurrentVersion => &software_review.is_current_version,
+            ReviewCriterion::IsReputationGood => &software_review.is_reputation_good,
+            ReviewCriterion::IsInstallationFromDeveloper => {
+                &software_review.is_installation_from_developer
+            }
+            ReviewCriterion::IsLocalAdminRequired => &software_review.is_local_admin_required,
+            ReviewCriterion::IsConnectedToBrockportCloud => {
+                &software_review.is_connected_to_brockport_cloud
+            }
+            ReviewCriterion::IsConnectedToCloudServicesOrClient => {
+                &software_review.is_connected_to_cloud_services_or_client
+            }
+            ReviewCriterion::IsSecurityOrOptimizationSoftware => {
+                &software_review.is_security_or_optimization_software
+            }
+            ReviewCriterion::IsSupportedByCurrentOs => &software_review.is_supported_by_current_os,
+        }
+    }
+}
+
+impl ReportTemplate {
+    // The template shipped with the server
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("assets")
+            .join("templates")
+            .join("review_report.toml")
+    }
+
+    // Reads and validates the template at `path`, along with the fonts and logo it
+    // references. Called when the server starts, so a broken template is found before
+    // any review is exported
+    pub fn load(path: &Path) -> Result<ReportTemplate> {
+        let format = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => FileFormat::Json,
+            _ => FileFormat::Toml,
+        };
+
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| template_error(path, format!("failed to read template: {}", err)))?;
+
+        let mut template =
+            Self::parse(&source, format).map_err(|err| template_error(path, err.to_string()))?;
+
+        // Paths in the template are relative to the template itself
+        let template_dir = path.parent().unwrap_or(Path::new("."));
+
+        let regular_font = read_asset(path, &template_dir.join(&template.fonts.regular))?;
+        let symbol_font = read_asset(path, &template_dir.join(&template.fonts.symbols))?;
+
+        // The logo is only branding, so a missing file leaves it out of reports instead of
+        // stopping the server
+        let logo = match &template.logo {
+            Some(logo) if !template_dir.join(&logo.path).exists() => {
+                tracing::warn!(
+                    "report logo '{}' does not exist, reports will not include a logo",
+                    template_dir.join(&logo.path).display()
+                );
+                None
+            }
+            Some(logo) => Some(read_asset(path, &template_dir.join(&logo.path))?),
+            None => None,
+        };
+
+        check_assets(&regular_font, &symbol_font, logo.as_deref())
+            .map_err(|err| template_error(path, err))?;
+
+        template.assets = ReportAssets {
+            regular_font,
+            symbol_font,
+            logo,
+        };
+
+        Ok(template)
+    }
+
+    // Parses the template and checks its values, without reading any of the files it
+    // references. Every problem found is included in the error
+    pub fn parse(source: &str, format: FileFormat) -> Result<ReportTemplate> {
+        let template: ReportTemplate = config::Config::builder()
+            .add_source(config::File::from_str(source, format))
+            .build()
+            .and_then(|template| template.try_deserialize())
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+        let problems = template.validate();
+
+        if !problems.is_empty() {
+            return Err(Error::ServerError(std::sync::Arc::new(
+                problems.join("; ").into(),
+            )));
+        }
+
+        Ok(template)
+    }
+
+    // Replaces the placeholders in `text` with the details of the review
+    pub fn fill(&self, text: &str, software_review: &SoftwareReviewDTO) -> String {
+        // Placeholders were checked when the template was loaded
+        replace_placeholders(text, |placeholder| {
+            self.placeholder_value(placeholder, software_review)
+        })
+        .unwrap_or_else(|_| text.to_string())
+    }
+
+    // Replaces the placeholders in `text`, part of the footer of page `page` (counting
+    // from 1) of a report with `pages` pages
+    pub fn fill_footer(
+        &self,
+        text: &str,
+        software_review: &SoftwareReviewDTO,
+        stamp: &ReportStamp,
+        page: usize,
+        pages: usize,
+    ) -> String {
+        replace_placeholders(text, |placeholder| match placeholder {
+            "page" => Some(page.to_string()),
+            "pages" => Some(pages.to_string()),
+            "review_id" => Some(stamp.review_id.to_string()),
+            "review_version" => Some(stamp.review_version.to_string()),
+            "content_hash" => Some(stamp.content_hash.clone()),
+            "verification_url" => Some(stamp.verification_url.clone()),
+            _ => self.placeholder_value(placeholder, software_review),
+        })
+        .unwrap_or_else(|_| text.to_string())
+    }
+
+    // Number of lines that fit between the top of the content and the footer
+    pub fn lines_per_page(&self) -> usize {
+        let line_height_mm = self.fonts.line_height * MM_PER_PT;
+        let content_height_mm = self.page.height_mm
+            - self.page.content_top_mm
+            - self.page.margin_mm * 2.0
+            - line_height_mm;
+
+        if content_height_mm < 0.0 || line_height_mm <= 0.0 {
+            return 0;
+        }
+
+        (content_height_mm / line_height_mm) as usize + 1
+    }
+
+    fn placeholder_value(
+        &self,
+        placeholder: &str,
+        software_review: &SoftwareReviewDTO,
+    ) -> Option<String> {
+        let software_request = &software_review.software_request;
+
+        let value = match placeholder {
+            "software_name" => software_request.software.software_name.clone(),
+            "software_version" => software_request.software.software_version.clone(),
+            "developer_name" => software_request.software.developer_name.clone(),
+            "td_request_id" => software_request.td_request_id.clone(),
+            "requester_name" => software_request.requester.name.clone(),
+            "requester_department" => software_request.requester.department.clone(),
+            "reviewer_name" => software_review.reviewer.name.clone(),
+            "reviewed_version" => software_review.reviewed_version.clone(),
+            "review_date" => self.format_date(software_review.created_at.unwrap_or_default()),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    // Formats `date` in the template's date format and UTC offset
+    pub fn format_date(&self, date: chrono::DateTime<chrono::Utc>) -> String {
+        date.with_timezone(&self.utc_offset())
+            .format(&self.date.format)
+            .to_string()
+    }
+
+    // Formats a calendar day in the template's date format, as the start of that day at
+    // the template's UTC offset
+    pub fn format_day(&self, day: chrono::NaiveDate) -> String {
+        let offset = self.utc_offset();
+
+        day.and_time(chrono::NaiveTime::MIN)
+            .and_local_timezone(offset)
+            .single()
+            .map(|date| date.format(&self.date.format).to_string())
+            .unwrap_or_else(|| day.to_string())
+    }
+
+    fn utc_offset(&self) -> chrono::FixedOffset {
+        chrono::FixedOffset::east_opt(self.date.utc_offset_hours * 3600)
+            .unwrap_or_else(|| chrono::FixedOffset::east_opt(0).unwrap())
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        let mut check = |valid: bool, problem: &str| {
+            if !valid {
+                problems.push(problem.to_string());
+            }
+        };
+
+        let page = &self.page;
+        check(
+            page.width_mm > 0.0 && page.height_mm > 0.0,
+            "page width_mm and height_mm must be greater than 0",
+        );
+        check(
+            page.margin_mm >= 0.0
+                && page.margin_mm * 2.0 < page.width_mm
+                && page.margin_mm * 2.0 < page.height_mm,
+            "page margin_mm must leave room for content on the page",
+        );
+        check(
+            page.content_top_mm >= 0.0 && page.content_top_mm < page.height_mm,
+            "page content_top_mm must be on the page",
+        );
+
+        let fonts = &self.fonts;
+        check(
+            [
+                fonts.title_size,
+                fonts.heading_size,
+                fonts.text_size,
+                fonts.symbol_size,
+                fonts.line_height,
+            ]
+            .iter()
+            .all(|size| *size > 0.0),
+            "font sizes and line_height must be greater than 0",
+        );
+
+        if let Some(logo) = &self.logo {
+            check(
+                logo.scale > 0.0 && logo.height_mm >= 0.0,
+                "logo scale must be greater than 0",
+            );
+        }
+
+        check(
+            !chrono::format::StrftimeItems::new(&self.date.format)
+                .any(|item| item == chrono::format::Item::Error),
+            "date format is not a valid chrono format string",
+        );
+        check(
+            chrono::FixedOffset::east_opt(self.date.utc_offset_hours * 3600).is_some(),
+            "date utc_offset_hours must be between -23 and 23",
+        );
+
+        check(
+            self.lines_per_page() >= MIN_LINES_PER_PAGE,
+            &format!(
+                "page must have room for at least {} lines of text",
+                MIN_LINES_PER_PAGE
+            ),
+        );
+
+        check(
+            !self.header.title.trim().is_empty(),
+            "header title must not be empty",
+        );
+        check(
+            !self.sections.is_empty(),
+            "at least one section is required",
+        );
+
+        let mut texts = vec![&self.header.title];
+        texts.extend(&self.header.lines);
+
+        for (index, section) in self.sections.iter().enumerate() {
+            let title = match section {
+                ReportSection::Criteria { title, criteria } => {
+                    if criteria.is_empty() {
+                        problems.push(format!("sections[{}] must have criteria", index));
+                    }
+
+                    for criterion in criteria {
+                        if criterion.label.trim().is_empty() {
+                            problems.push(format!(
+                                "sections[{}] criterion {:?} must have a label",
+                                index, criterion.field
+                            ));
+                        }
+                        texts.push(&criterion.label);
+                    }
+
+                    title
+                }
+                ReportSection::Notes { title } | ReportSection::Comments { title } => title,
+                ReportSection::Text { title, lines } => {
+                    texts.extend(lines);
+                    title
+                }
+            };
+
+            if title.trim().is_empty() {
+                problems.push(format!("sections[{}] title must not be empty", index));
+            }
+            texts.push(title);
+        }
+
+        for text in texts {
+            if let Err(problem) = replace_placeholders(text, |placeholder| {
+                PLACEHOLDERS.contains(&placeholder).then(String::new)
+            }) {
+                problems.push(problem);
+            }
+        }
+
+        for text in [&self.footer.text, &self.footer.verification] {
+            if let Err(problem) = replace_placeholders(text, |placeholder| {
+                (PLACEHOLDERS.contains(&placeholder) || FOOTER_PLACEHOLDERS.contains(&placeholder))
+                    .then(String::new)
+            }) {
+                problems.push(problem);
+            }
+        }
+
+        problems
+    }
+}
+
+// Replaces each `{placeholder}` in `text` with its value, returning an error if a
+// placeholder has no value or is not closed
+fn replace_placeholders(
+    text: &str,
+    mut value: impl FnMut(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find('}') else {
+            return Err(format!("'{}' has an unclosed placeholder", text));
+        };
+
+        let placeholder = &rest[start + 1..start + end];
+
+        match value(placeholder) {
+            Some(value) => filled.push_str(&value),
+            None => {
+                return Err(format!(
+                    "'{{{}}}' in '{}' is not a known placeholder",
+                    placeholder, text
+                ))
+            }
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    filled.push_str(rest);
+
+    Ok(filled)
+}
+
+fn read_asset(template_path: &Path, asset_path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(asset_path).map_err(|err| {
+        template_error(
+            template_path,
+            format!("failed to read '{}': {}", asset_path.display(), err),
+        )
+    })
+}
+
+// Adds the fonts and logo to an empty document, which fails if they can't be used in a
+// report
+fn check_assets(
+    regular_font: &[u8],
+    symbol_font: &[u8],
+    logo: Option<&[u8]>,
+) -> std::result::Result<(), String> {
+    let doc = PdfDocument::empty("template");
+
+    for font in [regular_font, symbol_font] {
+        doc.add_external_font(Cursor::new(font))
+            .map_err(|err| format!("invalid font: {}", err))?;
+    }
+
+    if let Some(logo) = logo {
+        let decoder = printpdf::image_crate::codecs::bmp::BmpDecoder::new(Cursor::new(logo))
+            .map_err(|err| format!("invalid logo: {}", err))?;
+
+        Image::try_from(decoder).map_err(|err| format!("invalid logo: {}", err))?;
+    }
+
+    Ok(())
+}
+
+fn template_error(path: &Path, problem: impl std::fmt::Display) -> Error {
+    Error::ServerError(std::sync::Arc::new(
+        format!("invalid report template '{}': {}", path.display(), problem).into(),
+    ))
+}
+
+// Unit Tests
+#[cfg(test)]
+mod report_template_tests {
+    use config::FileFormat;
+
+    use super::ReportTemplate;
+
+    fn default_template() -> String {
+        std::fs::read_to_string(ReportTemplate::default_path()).unwrap()
+    }
+
+    fn parse_error(source: &str) -> String {
+        format!(
+            "{:?}",
+            ReportTemplate::parse(source, FileFormat::Toml).unwrap_err()
+        )
+    }
+
+    #[test]
+    fn default_template_is_valid() {
+        let template = ReportTemplate::load(&ReportTemplate::default_path()).unwrap();
+
+        assert_eq!("BITS Application Security Review", template.header.title);
+        assert!(!template.assets.regular_font.is_empty());
+    }
+
+    #[test]
+    fn unknown_placeholder_is_invalid() {
+        let source = default_template().replace("{reviewer_name}", "{reviewer}");
+        assert!(parse_error(&source).contains("'{reviewer}'"));
+    }
+
+    #[test]
+    fn unknown_criterion_field_is_invalid() {
+        let source = default_template().replace("\"is_supported\"", "\"is_free\"");
+        assert!(parse_error(&source).contains("is_free"));
+    }
+
+    #[test]
+    fn unknown_setting_is_invalid() {
+        let source =
+            default_template().replace("title = \"Notes\"", "title = \"Notes\"\nwidth = 1");
+        assert!(parse_error(&source).contains("width"));
+    }
+
+    #[test]
+    fn invalid_date_format_is_invalid() {
+        let source = default_template().replace("%m/%d/%Y", "%m/%Q/%Y");
+        assert!(parse_error(&source).contains("date format"));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let source = default_template()
+            .replace("line_height = 33.0", "line_height = 300.0")
+            .replace("title = \"Notes\"", "title = \"\"");
+
+        let error = parse_error(&source);
+        assert!(error.contains("room for at least"), "{}", error);
+        assert!(error.contains("title must not be empty"), "{}", error);
+    }
+
+    #[test]
+    fn json_template_is_valid() {
+        let source = r#"{
+            "page": { "width_mm": 216, "height_mm": 279, "margin_mm": 20, "content_top_mm": 10 },
+            "fonts": {
+                "regular": "a.ttf", "symbols": "b.ttf", "title_size": 16, "heading_size": 14,
+                "text_size": 11, "symbol_size": 15, "line_height": 30
+            },
+            "date": { "format": "%Y-%m-%d", "utc_offset_hours": 0 },
+            "header": { "title": "Review of {software_name}", "lines": [] },
+            "sections": [{ "kind": "notes", "title": "Notes" }]
+        }"#;
+
+        let template = ReportTemplate::parse(source, FileFormat::Json).unwrap();
+        assert!(template.logo.is_none());
+        assert_eq!("Page {page} of {pages}", template.footer.text);
+    }
+
+    #[test]
+    fn footer_placeholders_are_only_valid_in_footer() {
+        let source = default_template().replace("Date: {review_date}", "Page {page}");
+        assert!(parse_error(&source).contains("'{page}'"));
+
+        let source = default_template().replace("{page} of {pages}", "{page}/{pages} {software}");
+        assert!(parse_error(&source).contains("'{software}'"));
+
+        let source = default_template().replace("{verification_url}", "{url}");
+        assert!(parse_error(&source).contains("'{url}'"));
+    }
+}
@@ -0,0 +1,52 @@
+use sqlx::PgPool;
+
+use crate::api::services::{flag_expiring_reviews, reopen_expired_requests};
+use crate::config::{DatabaseConfig, ReviewExpiryConfig};
+use crate::server::get_db_pool;
+use crate::Result;
+
+pub async fn review_expiry_task(
+    config: ReviewExpiryConfig,
+    db_config: DatabaseConfig,
+) -> Result<()> {
+    let db_pool = get_db_pool(&db_config)?;
+
+    let interval = tokio::time::Duration::from_secs(config.check_interval_seconds);
+
+    loop {
+        // A failed check is retried on the next interval rather than stopping the worker
+        if let Err(err) = check_review_expiry(&config, &db_pool).await {
+            tracing::error!(
+                error.cause_chain = ?err,
+                error.message = %err,
+                "review expiry check failed"
+            );
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+// Flags the reviews nearing expiry and, when `reopen_expired` is enabled, puts the software
+// requests of expired reviews back in the review queue
+#[tracing::instrument(name = "review expiry check running", skip(config, db_pool))]
+pub async fn check_review_expiry(config: &ReviewExpiryConfig, db_pool: &PgPool) -> Result<()> {
+    for (review_id, expires_at) in flag_expiring_reviews(config.warning_days, db_pool).await? {
+        tracing::warn!(
+            review_id = %review_id,
+            expires_at = %expires_at,
+            "software review is expiring and should be reviewed again"
+        );
+    }
+
+    if config.reopen_expired {
+        for request_id in reopen_expired_requests(db_pool).await? {
+            tracing::info!(
+                software_request_id = %request_id,
+                "reopened software request with an expired review"
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use crate::api::models::{ReviewCommentDTO, ReviewOptions, SoftwareReviewDTO};
+use crate::api::utils::{parse_markdown, MarkdownBlock, ReportSection, ReportTemplate};
+
+// The content of a software review report, with the template's placeholders filled in.
+// Every export format is rendered from this, so wording and answers are only worked out
+// once
+#[derive(Clone, Debug)]
+pub struct ReviewReport {
+    pub title: String,
+    // Shown below the title, on the first page of a PDF
+    pub header_lines: Vec<String>,
+    pub sections: Vec<ReviewReportSection>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewReportSection {
+    pub title: String,
+    pub content: ReviewReportContent,
+}
+
+#[derive(Clone, Debug)]
+pub enum ReviewReportContent {
+    Criteria(Vec<ReviewReportCriterion>),
+    // Text that may contain line breaks, such as comments
+    Text(Vec<String>),
+    // Text formatted with Markdown, such as the review notes
+    Markdown(Vec<MarkdownBlock>),
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewReportCriterion {
+    pub answer: ReviewAnswer,
+    pub label: String,
+}
+
+// How the answer to a criterion is shown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReviewAnswer {
+    pub glyph: &'static str,
+    // Used where the glyph can't be shown, e.g. as alt text
+    pub text: &'static str,
+    // RGB, from 0 to 255
+    pub color: (u8, u8, u8),
+}
+
+impl ReviewReport {
+    // `comments` are shown in the template's `comments` section, which is left out when
+    // there are none
+    pub fn new(
+        software_review: &SoftwareReviewDTO,
+        comments: &[ReviewCommentDTO],
+        template: &ReportTemplate,
+    ) -> Self {
+        let fill = |text: &str| template.fill(text, software_review);
+
+        let sections = template
+            .sections
+            .iter()
+            .filter(|section| {
+                !matches!(section, ReportSection::Comments { .. }) || !comments.is_empty()
+            })
+            .map(|section| match section {
+                ReportSection::Criteria { title, criteria } => ReviewReportSection {
+                    title: fill(title),
+                    content: ReviewReportContent::Criteria(
+                        criteria
+                            .iter()
+                            .map(|criterion| ReviewReportCriterion {
+                                answer: convert_response_to_glyph(
+                                    criterion.field.value(software_review),
+                                ),
+                                label: fill(&criterion.label),
+                            })
+                            .collect(),
+                    ),
+                },
+                ReportSection::Notes { title } => ReviewReportSection {
+                    title: fill(title),
+                    content: ReviewReportContent::Markdown(parse_markdown(
+                        &software_review.review_notes,
+                    )),
+                },
+                ReportSection::Text { title, lines } => ReviewReportSection {
+                    title: fill(title),
+                    content: ReviewReportContent::Text(
+                        lines.iter().map(|line| fill(line)).collect(),
+                    ),
+                },
+                ReportSection::Comments { title } => ReviewReportSection {
+                    title: fill(title),
+                    content: ReviewReportContent::Text(vec![comments_text(comments, template)]),
+                },
+            })
+            .collect();
+
+        ReviewReport {
+            title: fill(&template.header.title),
+            header_lines: template
+                .header
+                .lines
+                .iter()
+                .map(|line| fill(line))
+                .collect(),
+            sections,
+        }
+    }
+}
+
+// Each comment is a paragraph, headed by its author and the date it was posted
+fn comments_text(comments: &[ReviewCommentDTO], template: &ReportTemplate) -> String {
+    comments
+        .iter()
+        .map(|comment| {
+            format!(
+                "{}, {}:\n{}",
+                comment.author.name,
+                template.format_date(comment.created_at),
+                comment.body
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+pub fn convert_response_to_glyph(review_response: &ReviewOptions) -> ReviewAnswer {
+    match review_response {
+        ReviewOptions::TRUE => ReviewAnswer {
+            glyph: "✓",
+            text: "Yes",
+            color: (0, 255, 0),
+        },
+        ReviewOptions::FALSE => ReviewAnswer {
+            glyph: "✖",
+            text: "No",
+            color: (255, 0, 0),
+        },
+        // `?` won't render colors other than black or white when targeting fill
+        _ => ReviewAnswer {
+            glyph: "❓",
+            text: "Not sure",
+            color: (5, 5, 5),
+        },
+    }
+}
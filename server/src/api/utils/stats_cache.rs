@@ -0,0 +1,134 @@
+// Keeps computed statistics in-memory for a short time, so a dashboard polling the
+// `/stats` endpoints doesn't run the same aggregate queries on every request
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::Result;
+
+#[derive(Clone, Debug)]
+pub struct StatsCache {
+    ttl: Duration,
+    entries: std::sync::Arc<tokio::sync::RwLock<HashMap<String, (Instant, serde_json::Value)>>>,
+}
+
+impl StatsCache {
+    // A `ttl` of zero disables caching
+    pub fn new(ttl: Duration) -> Self {
+        StatsCache {
+            ttl,
+            entries: std::sync::Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+        }
+    }
+
+    // Returns the value cached for `key`, or computes and caches it when it is missing or
+    // older than the `ttl`
+    pub async fn get_or_compute<F>(&self, key: String, compute: F) -> Result<serde_json::Value>
+    where
+        F: Future<Output = Result<serde_json::Value>>,
+    {
+        if self.ttl.is_zero() {
+            return compute.await;
+        }
+
+        {
+            let cache = self.entries.read().await;
+
+            if let Some((computed_at, value)) = cache.get(&key) {
+                if computed_at.elapsed() < self.ttl {
+                    return Ok(value.clone());
+                }
+            }
+        }
+
+        let value = compute.await?;
+
+        let mut cache = self.entries.write().await;
+
+        // Expired entries are dropped here, since every period requested gets its own key
+        cache.retain(|_, (computed_at, _)| computed_at.elapsed() < self.ttl);
+        cache.insert(key, (Instant::now(), value.clone()));
+
+        Ok(value)
+    }
+}
+
+// Unit Tests
+#[cfg(test)]
+mod stats_cache_tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::StatsCache;
+
+    #[tokio::test]
+    async fn cached_value_is_reused_within_ttl() {
+        let cache = StatsCache::new(Duration::from_secs(60));
+
+        let first = cache
+            .get_or_compute("key".to_string(), async { Ok(json!(1)) })
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_compute("key".to_string(), async { Ok(json!(2)) })
+            .await
+            .unwrap();
+
+        assert_eq!(json!(1), first);
+        assert_eq!(json!(1), second);
+    }
+
+    #[tokio::test]
+    async fn values_are_cached_per_key() {
+        let cache = StatsCache::new(Duration::from_secs(60));
+
+        cache
+            .get_or_compute("first".to_string(), async { Ok(json!(1)) })
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_compute("second".to_string(), async { Ok(json!(2)) })
+            .await
+            .unwrap();
+
+        assert_eq!(json!(2), second);
+    }
+
+    #[tokio::test]
+    async fn zero_ttl_disables_caching() {
+        let cache = StatsCache::new(Duration::ZERO);
+
+        cache
+            .get_or_compute("key".to_string(), async { Ok(json!(1)) })
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_compute("key".to_string(), async { Ok(json!(2)) })
+            .await
+            .unwrap();
+
+        assert_eq!(json!(2), second);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let cache = StatsCache::new(Duration::from_secs(60));
+
+        let failed = cache
+            .get_or_compute("key".to_string(), async {
+                Err(crate::Error::QueryParamValidationError(
+                    "invalid".to_string(),
+                ))
+            })
+            .await;
+        let second = cache
+            .get_or_compute("key".to_string(), async { Ok(json!(2)) })
+            .await
+            .unwrap();
+
+        assert!(failed.is_err());
+        assert_eq!(json!(2), second);
+    }
+}
@@ -0,0 +1,348 @@
+use std::time::{Duration, Instant};
+
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::models::{Ticket, TicketRequester, TicketSoftware};
+use crate::api::utils::TicketingClient;
+use crate::config::TeamDynamixConfig;
+use crate::{Error, Result};
+
+// Client for the TeamDynamix (TDX) Web API. It signs in as an administrative service
+// account (`/api/auth/loginadmin`) and reuses the bearer token until shortly before it
+// expires
+pub struct TeamDynamixClient {
+    client: reqwest::Client,
+    config: TeamDynamixConfig,
+    token: tokio::sync::Mutex<Option<(SecretString, Instant)>>,
+}
+
+// The fields of a TDX ticket that a software request is pre-filled from
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TdxTicket {
+    title: String,
+    description: Option<String>,
+    requestor_name: Option<String>,
+    requestor_email: Option<String>,
+    account_name: Option<String>,
+    #[serde(default)]
+    attributes: Vec<TdxAttribute>,
+}
+
+// A custom attribute of a ticket. `ValueText` holds the selected choice of choice based
+// attributes, and is the same as `Value` otherwise
+#[derive(Debug, Deserialize)]
+struct TdxAttribute {
+    #[serde(rename = "ID")]
+    id: i32,
+    #[serde(rename = "Value")]
+    value: Option<String>,
+    #[serde(rename = "ValueText")]
+    value_text: Option<String>,
+}
+
+impl TeamDynamixClient {
+    // TDX tokens are valid for 24 hours
+    const TOKEN_LIFETIME: Duration = Duration::from_secs(23 * 60 * 60);
+
+    pub fn new(config: TeamDynamixConfig) -> Result<TeamDynamixClient> {
+        reqwest::Url::parse(&config.base_url).map_err(|err| {
+            Error::ServerError(std::sync::Arc::new(
+                format!("invalid TeamDynamix base_url: {}", err).into(),
+            ))
+        })?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))?;
+
+        Ok(TeamDynamixClient {
+            client,
+            config,
+            token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/api/{}",
+            self.config.base_url.trim_end_matches('/'),
+            path
+        )
+    }
+
+    fn ticket_url(&self, td_request_id: &str, path: &str) -> String {
+        self.url(&format!(
+            "{}/tickets/{}{}",
+            self.config.app_id, td_request_id, path
+        ))
+    }
+
+    async fn token(&self) -> Result<SecretString> {
+        let mut token = self.token.lock().await;
+
+        if let Some((token, issued_at)) = token.as_ref() {
+            if issued_at.elapsed() < Self::TOKEN_LIFETIME {
+                return Ok(token.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .post(self.url("auth/loginadmin"))
+            .json(&json!({
+                "BEID": self.config.beid.expose_secret(),
+                "WebServicesKey": self.config.web_services_key.expose_secret()
+            }))
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        if !response.status().is_success() {
+            return Err(Error::TicketingError(format!(
+                "sign in responded with {}",
+                response.status()
+            )));
+        }
+
+        let issued = SecretString::new(response.text().await.map_err(request_error)?.trim().into());
+
+        *token = Some((issued.clone(), Instant::now()));
+
+        Ok(issued)
+    }
+
+    // Sends an authenticated request. A rejected token is dropped, so the next request
+    // signs in again
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let token = self.token().await?;
+
+        let response = request
+            .bearer_auth(token.expose_secret())
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            reqwest::StatusCode::NOT_FOUND => Err(Error::TicketNotFoundError),
+            status => {
+                if status == reqwest::StatusCode::UNAUTHORIZED {
+                    *self.token.lock().await = None;
+                }
+
+                Err(Error::TicketingError(format!(
+                    "{} responded with {}",
+                    response.url().path(),
+                    status
+                )))
+            }
+        }
+    }
+
+    fn ticket_from(&self, td_request_id: &str, ticket: TdxTicket) -> Ticket {
+        let attribute = |attribute_id: Option<i32>| {
+            attribute_id
+                .and_then(|attribute_id| {
+                    ticket
+                        .attributes
+                        .iter()
+                        .find(|attribute| attribute.id == attribute_id)
+                })
+                .and_then(|attribute| {
+                    attribute
+                        .value_text
+                        .as_deref()
+                        .or(attribute.value.as_deref())
+                })
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Ticket {
+            td_request_id: td_request_id.to_string(),
+            software: TicketSoftware {
+                software_name: attribute(self.config.software_name_attribute_id)
+                    .unwrap_or_else(|| ticket.title.trim().to_string()),
+                software_version: attribute(self.config.software_version_attribute_id)
+                    .unwrap_or_default(),
+                developer_name: attribute(self.config.developer_attribute_id).unwrap_or_default(),
+                description: trimmed(ticket.description),
+            },
+            requester: TicketRequester {
+                name: trimmed(ticket.requestor_name),
+                email: trimmed(ticket.requestor_email),
+                department: trimmed(ticket.account_name),
+            },
+        }
+    }
+}
+
+impl TicketingClient for TeamDynamixClient {
+    #[tracing::instrument(name = "fetching TeamDynamix ticket", skip(self))]
+    async fn fetch_ticket(&self, td_request_id: &str) -> Result<Ticket> {
+        let response = self
+            .send(self.client.get(self.ticket_url(td_request_id, "")))
+            .await?;
+
+        let ticket: TdxTicket = response.json().await.map_err(request_error)?;
+
+        Ok(self.ticket_from(td_request_id, ticket))
+    }
+
+    #[tracing::instrument(
+        name = "attaching file to TeamDynamix ticket",
+        skip(self, content_type, content)
+    )]
+    async fn attach_file(
+        &self,
+        td_request_id: &str,
+        file_name: &str,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<()> {
+        let part = reqwest::multipart::Part::bytes(content)
+            .file_name(file_name.to_string())
+            .mime_str(content_type)
+            .map_err(request_error)?;
+
+        self.send(
+            self.client
+                .post(self.ticket_url(td_request_id, "/attachments"))
+                .multipart(reqwest::multipart::Form::new().part("file", part)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "commenting on TeamDynamix ticket", skip(self, comment))]
+    async fn post_comment(&self, td_request_id: &str, comment: &str) -> Result<()> {
+        // A status of `0` leaves the ticket's status unchanged
+        self.send(
+            self.client
+                .post(self.ticket_url(td_request_id, "/feed"))
+                .json(&json!({
+                    "NewStatusID": self.config.review_status_id.unwrap_or(0),
+                    "Comments": comment,
+                    "IsPrivate": false,
+                    "IsRichHtml": false
+                })),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn trimmed(value: Option<String>) -> String {
+    value
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn request_error(err: reqwest::Error) -> Error {
+    Error::TicketingError(err.without_url().to_string())
+}
+
+// Unit Tests
+#[cfg(test)]
+mod teamdynamix_client_tests {
+    use secrecy::SecretString;
+    use serde_json::json;
+
+    use super::{TdxTicket, TeamDynamixClient};
+    use crate::api::models::{Ticket, TicketRequester, TicketSoftware};
+    use crate::config::TeamDynamixConfig;
+
+    fn client(attribute_ids: Option<(i32, i32, i32)>) -> TeamDynamixClient {
+        TeamDynamixClient::new(TeamDynamixConfig {
+            base_url: "https://tdx.example.edu/TDWebApi".to_string(),
+            app_id: 31,
+            beid: SecretString::new("beid".into()),
+            web_services_key: SecretString::new("key".into()),
+            timeout_seconds: 30,
+            software_name_attribute_id: attribute_ids.map(|ids| ids.0),
+            software_version_attribute_id: attribute_ids.map(|ids| ids.1),
+            developer_attribute_id: attribute_ids.map(|ids| ids.2),
+            review_status_id: None,
+        })
+        .unwrap()
+    }
+
+    fn tdx_ticket() -> TdxTicket {
+        serde_json::from_value(json!({
+            "ID": 12345678,
+            "Title": " Install Zoom ",
+            "Description": "Needed for remote lectures\n",
+            "RequestorName": "John Doe",
+            "RequestorEmail": "jdoe@brockport.edu",
+            "AccountName": "Computer Science",
+            "Attributes": [
+                { "ID": 101, "Value": "Zoom Workplace", "ValueText": "Zoom Workplace" },
+                { "ID": 102, "Value": "6.2.0", "ValueText": "6.2.0" },
+                { "ID": 103, "Value": "417", "ValueText": "Zoom Video Communications" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ticket_is_mapped_from_custom_attributes() {
+        let ticket = client(Some((101, 102, 103))).ticket_from("12345678", tdx_ticket());
+
+        assert_eq!(
+            Ticket {
+                td_request_id: "12345678".to_string(),
+                software: TicketSoftware {
+                    software_name: "Zoom Workplace".to_string(),
+                    software_version: "6.2.0".to_string(),
+                    developer_name: "Zoom Video Communications".to_string(),
+                    description: "Needed for remote lectures".to_string(),
+                },
+                requester: TicketRequester {
+                    name: "John Doe".to_string(),
+                    email: "jdoe@brockport.edu".to_string(),
+                    department: "Computer Science".to_string(),
+                },
+            },
+            ticket
+        );
+    }
+
+    #[test]
+    fn software_name_falls_back_to_ticket_title() {
+        let ticket = client(None).ticket_from("12345678", tdx_ticket());
+
+        assert_eq!("Install Zoom", ticket.software.software_name);
+        assert_eq!("", ticket.software.software_version);
+        assert_eq!("", ticket.software.developer_name);
+    }
+
+    #[test]
+    fn missing_details_are_left_empty() {
+        let tdx_ticket: TdxTicket = serde_json::from_value(json!({
+            "ID": 12345678,
+            "Title": "Software request"
+        }))
+        .unwrap();
+
+        let ticket = client(Some((101, 102, 103))).ticket_from("12345678", tdx_ticket);
+
+        assert_eq!("Software request", ticket.software.software_name);
+        assert_eq!(TicketRequester::default(), ticket.requester);
+    }
+
+    #[test]
+    fn ticket_urls_include_the_application() {
+        let client = client(None);
+
+        assert_eq!(
+            "https://tdx.example.edu/TDWebApi/api/31/tickets/12345678/feed",
+            client.ticket_url("12345678", "/feed")
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use std::future::Future;
+
+use crate::api::models::Ticket;
+use crate::Result;
+
+// A ticketing system that software requests are raised in, e.g. TeamDynamix. Tickets are
+// identified by the `td_request_id` of their software request
+pub trait TicketingClient {
+    // Reads the requester and requested software from the ticket. Fails with
+    // `TicketNotFoundError` for an unknown ticket
+    fn fetch_ticket(&self, td_request_id: &str) -> impl Future<Output = Result<Ticket>> + Send;
+
+    fn attach_file(
+        &self,
+        td_request_id: &str,
+        file_name: &str,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    // Adds a comment to the ticket, updating its status where the client is configured to
+    fn post_comment(
+        &self,
+        td_request_id: &str,
+        comment: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
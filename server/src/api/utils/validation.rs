@@ -0,0 +1,228 @@
+use unicode_segmentation::UnicodeSegmentation;
+use validator::ValidateEmail;
+
+// Validation rules shared by the models and query params
+//
+// Free text is never interpolated into SQL (every query binds its values), so
+// punctuation such as `'`, `-`, `(` or `/` is allowed. Text is instead encoded for the
+// format it is written to when it leaves the API (see `encode_content_disposition`)
+
+// Single-line text such as names: must not be empty or whitespace, must be at most
+// `max_graphemes` long, and must not contain control characters
+pub fn validate_text(text: &str, max_graphemes: usize) -> bool {
+    let text_is_empty_or_whitespace = text.trim().is_empty();
+
+    // A grapheme is defined by the Unicode standard as a "user-perceived"
+    // character: `å` is a single grapheme, but it is composed of two characters
+    // (`a` and `̊`)
+    let text_too_long = text.graphemes(true).count() > max_graphemes;
+    let text_contains_control_chars = text.chars().any(char::is_control);
+
+    // Return false if any of the above conditions are met
+    !(text_is_empty_or_whitespace || text_too_long || text_contains_control_chars)
+}
+
+// Multi-line text such as review notes: the same rules as `validate_text`, except line
+// breaks and tabs are allowed
+pub fn validate_multiline_text(text: &str, max_graphemes: usize) -> bool {
+    let text_is_empty_or_whitespace = text.trim().is_empty();
+
+    let text_too_long = text.graphemes(true).count() > max_graphemes;
+    let text_contains_control_chars = text
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'));
+
+    // Return false if any of the above conditions are met
+    !(text_is_empty_or_whitespace || text_too_long || text_contains_control_chars)
+}
+
+pub fn validate_email(email: &str) -> bool {
+    // Check if the email contains exactly one '@' symbol and has a domain
+    let split = email.split('@').collect::<Vec<&str>>();
+
+    if split.len() != 2 || split[1].is_empty() {
+        return false;
+    }
+
+    // `ValidateEmail` validates email based on HTML5 spec
+    email.validate_email()
+}
+
+// Returns true if the domain of `email` is one of `allowed_domains` (case-insensitive).
+// An empty `allowed_domains` accepts any domain
+pub fn validate_email_domain(email: &str, allowed_domains: &[String]) -> bool {
+    if allowed_domains.is_empty() {
+        return true;
+    }
+
+    match email.rsplit_once('@') {
+        Some((_, domain)) => allowed_domains
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(domain)),
+        None => false,
+    }
+}
+
+// Builds a `Content-Disposition: attachment` header value for `filename`. Quotes,
+// backslashes and non-ASCII characters are replaced in the plain `filename` parameter,
+// while `filename*` carries the exact name percent-encoded as UTF-8 (RFC 6266)
+pub fn encode_content_disposition(filename: &str) -> String {
+    let ascii_filename: String = filename
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect();
+
+    let encoded_filename: String = filename
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        ascii_filename, encoded_filename
+    )
+}
+
+// Unit Tests
+#[cfg(test)]
+mod text_tests {
+    use super::validate_text;
+
+    // Returns true if field is valid, false if invalid
+
+    #[test]
+    fn real_world_names_are_valid() {
+        let names = [
+            "O'Brien",
+            "Mary-Jane Smith",
+            "Wi-Fi driver",
+            "Adobe Acrobat (64 bit)",
+            "AT&T Connect",
+            "Microsoft Visual C++ 2015-2022 Redistributable",
+            "Zoom/Teams plugin",
+            "\"Quoted\" Software",
+            "Résumé Builder",
+            "José Núñez",
+            "田中 太郎",
+            "$100 Budget App",
+            "Notepad++ <Portable>",
+        ];
+
+        for name in names {
+            assert!(validate_text(name, 100), "'{}' should be valid", name);
+        }
+    }
+
+    #[test]
+    fn a_100_grapheme_text_is_valid() {
+        let text = "å".repeat(100);
+        assert!(validate_text(&text, 100));
+    }
+
+    #[test]
+    fn a_101_grapheme_text_is_invalid() {
+        let text = "å".repeat(101);
+        assert!(!validate_text(&text, 100));
+    }
+
+    #[test]
+    fn empty_text_is_invalid() {
+        assert!(!validate_text("", 100));
+    }
+
+    #[test]
+    fn whitespace_only_text_is_invalid() {
+        assert!(!validate_text(" \t ", 100));
+    }
+
+    #[test]
+    fn control_characters_are_invalid() {
+        for text in ["Line\nBreak", "Null\0Byte", "Escape\u{1b}[31m", "Tab\tbed"] {
+            assert!(!validate_text(text, 100), "{:?} should be invalid", text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod multiline_text_tests {
+    use super::validate_multiline_text;
+
+    #[test]
+    fn realistic_review_notes_are_valid() {
+        let notes = "Installer is signed by the vendor (verified 2024-11-02).\n\
+                     - Requires local admin\n\
+                     - Doesn't phone home; see https://example.com/privacy";
+        assert!(validate_multiline_text(notes, 255));
+    }
+
+    #[test]
+    fn control_characters_other_than_line_breaks_are_invalid() {
+        assert!(!validate_multiline_text("Null\0Byte", 255));
+    }
+
+    #[test]
+    fn whitespace_only_text_is_invalid() {
+        assert!(!validate_multiline_text("\n\r\n\t", 255));
+    }
+}
+
+#[cfg(test)]
+mod email_domain_tests {
+    use super::validate_email_domain;
+
+    #[test]
+    fn any_domain_is_valid_without_allowed_domains() {
+        assert!(validate_email_domain("john@gmail.com", &[]));
+    }
+
+    #[test]
+    fn allowed_domain_is_matched_case_insensitively() {
+        let allowed = ["brockport.edu".to_string()];
+        assert!(validate_email_domain("jsmith@brockport.edu", &allowed));
+        assert!(validate_email_domain("JSmith@Brockport.EDU", &allowed));
+    }
+
+    #[test]
+    fn other_domains_and_subdomains_are_invalid() {
+        let allowed = ["brockport.edu".to_string()];
+        for email in [
+            "john@gmail.com",
+            "john@mail.brockport.edu",
+            "john@brockport.edu.evil.com",
+            "brockport.edu",
+        ] {
+            assert!(!validate_email_domain(email, &allowed), "{}", email);
+        }
+    }
+}
+
+#[cfg(test)]
+mod content_disposition_tests {
+    use super::encode_content_disposition;
+
+    #[test]
+    fn plain_filename_is_unchanged() {
+        assert_eq!(
+            encode_content_disposition("Slack.pdf"),
+            "attachment; filename=\"Slack.pdf\"; filename*=UTF-8''Slack.pdf"
+        );
+    }
+
+    #[test]
+    fn quotes_and_non_ascii_characters_are_encoded() {
+        assert_eq!(
+            encode_content_disposition("\"Résumé\" (64 bit).pdf"),
+            "attachment; filename=\"_R_sum__ (64 bit).pdf\"; \
+             filename*=UTF-8''%22R%C3%A9sum%C3%A9%22%20%2864%20bit%29.pdf"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use sqlx::PgPool;
+
+use crate::api::services::deliver_pending_webhooks;
+use crate::config::{DatabaseConfig, WebhookConfig};
+use crate::server::get_db_pool;
+use crate::{Error, Result};
+
+pub async fn webhook_delivery_task(config: WebhookConfig, db_config: DatabaseConfig) -> Result<()> {
+    let db_pool = get_db_pool(&db_config)?;
+    let client = webhook_client(&config)?;
+
+    let interval = tokio::time::Duration::from_secs(config.poll_interval_seconds);
+
+    loop {
+        // Deliveries are sent until none are due, then checked again after the interval.
+        // A failed poll is retried on the next interval rather than stopping the worker
+        match deliver_webhooks(&config, &client, &db_pool).await {
+            Ok(0) => tokio::time::sleep(interval).await,
+            Ok(_) => (),
+            Err(err) => {
+                tracing::error!(
+                    error.cause_chain = ?err,
+                    error.message = %err,
+                    "webhook delivery failed"
+                );
+
+                tokio::time::sleep(interval).await;
+            }
+        }
+    }
+}
+
+// Redirects are not followed, so a payload is only ever sent to the configured url
+pub fn webhook_client(config: &WebhookConfig) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(config.timeout_seconds))
+        .redirect(reqwest::redirect::Policy::none())
+        .user_agent(concat!("k6r-webhooks/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+}
+
+// Sends up to `batch_size` deliveries that are due. Returns the number attempted
+#[tracing::instrument(name = "webhook delivery running", skip(config, client, db_pool))]
+pub async fn deliver_webhooks(
+    config: &WebhookConfig,
+    client: &reqwest::Client,
+    db_pool: &PgPool,
+) -> Result<usize> {
+    deliver_pending_webhooks(config, client, db_pool).await
+}
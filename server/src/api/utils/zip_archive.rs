@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::future::Future;
+
+use async_zip::error::ZipError;
+use async_zip::tokio::write::ZipFileWriter;
+use async_zip::{Compression, ZipDateTime, ZipEntryBuilder};
+use axum::body::{Body, Bytes};
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+use futures_util::stream;
+use tokio::io::{AsyncReadExt, DuplexStream};
+
+use super::validation::encode_content_disposition;
+use crate::{Error, Result};
+
+// Bytes of the archive buffered before they are sent. A slow client fills the buffer,
+// which pauses writing the archive
+const ARCHIVE_BUFFER_BYTES: usize = 64 * 1024;
+
+// Writes files into a ZIP archive that is streamed to the client as it is written
+pub struct ZipArchive {
+    writer: ZipFileWriter<DuplexStream>,
+    file_names: HashSet<String>,
+}
+
+impl ZipArchive {
+    // Adds a file to the archive, returning the name it was stored as. The name is made
+    // safe to extract (no directories or control characters), and a suffix is added if
+    // another file already has the name (e.g. `Slack (2).pdf`)
+    pub async fn add_file(&mut self, file_name: &str, data: &[u8]) -> Result<String> {
+        let file_name = self.unique_file_name(file_name);
+
+        let entry = ZipEntryBuilder::new(file_name.clone().into(), Compression::Deflate)
+            .last_modification_date(ZipDateTime::from_chrono(&chrono::Utc::now()));
+
+        self.writer
+            .write_entry_whole(entry, data)
+            .await
+            .map_err(zip_error)?;
+
+        Ok(file_name)
+    }
+
+    // Writes the central directory, which completes the archive
+    pub async fn finish(self) -> Result<()> {
+        self.writer.close().await.map_err(zip_error)?;
+
+        Ok(())
+    }
+
+    fn unique_file_name(&mut self, file_name: &str) -> String {
+        let file_name = sanitize_file_name(file_name);
+
+        let (stem, extension) = match file_name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+            _ => (file_name.as_str(), String::new()),
+        };
+
+        let mut unique_name = file_name.clone();
+        let mut copy = 1;
+
+        while !self.file_names.insert(unique_name.to_lowercase()) {
+            copy += 1;
+            unique_name = format!("{} ({}){}", stem, copy, extension);
+        }
+
+        unique_name
+    }
+}
+
+// Runs `write` in its own task and streams the archive it writes as a download named
+// `filename`. The status has already been sent by the time `write` fails, so an error
+// ends the body early and the client sees an incomplete archive
+pub fn zip_archive_response<F, Fut>(filename: &str, write: F) -> Result<Response>
+where
+    F: FnOnce(ZipArchive) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let (writer, reader) = tokio::io::duplex(ARCHIVE_BUFFER_BYTES);
+
+    let write = write(ZipArchive {
+        writer: ZipFileWriter::with_tokio(writer),
+        file_names: HashSet::new(),
+    });
+
+    tokio::spawn(async move {
+        if let Err(err) = write.await {
+            tracing::error!("archive ended early: {:?}", err);
+        }
+    });
+
+    let body = stream::unfold(reader, |mut reader| async move {
+        let mut chunk = vec![0; ARCHIVE_BUFFER_BYTES];
+
+        match reader.read(&mut chunk).await {
+            Ok(0) => None,
+            Ok(read) => {
+                chunk.truncate(read);
+                Some((Ok(Bytes::from(chunk)), reader))
+            }
+            Err(err) => Some((Err(err), reader)),
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            encode_content_disposition(filename),
+        )
+        .body(Body::from_stream(body))
+        .map_err(|err| Error::ServerError(std::sync::Arc::new(err.into())))
+}
+
+// Replaces path separators and control characters, so a file name can't be extracted
+// outside of the archive's directory
+fn sanitize_file_name(file_name: &str) -> String {
+    let file_name: String = file_name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let file_name = file_name.trim().trim_start_matches('.');
+
+    if file_name.is_empty() {
+        "file".to_string()
+    } else {
+        file_name.to_string()
+    }
+}
+
+fn zip_error(err: ZipError) -> Error {
+    Error::ServerError(std::sync::Arc::new(err.into()))
+}
+
+// Unit Tests
+#[cfg(test)]
+mod sanitize_file_name_tests {
+    use super::sanitize_file_name;
+
+    #[test]
+    fn plain_file_name_is_unchanged() {
+        assert_eq!("1234 - Slack.pdf", sanitize_file_name("1234 - Slack.pdf"));
+    }
+
+    #[test]
+    fn path_components_are_removed() {
+        assert_eq!("_.._etc_passwd", sanitize_file_name("/../etc/passwd"));
+        assert_eq!("C__Windows_x.pdf", sanitize_file_name("C:\\Windows\\x.pdf"));
+        assert_eq!("hidden", sanitize_file_name("..hidden"));
+    }
+
+    #[test]
+    fn empty_file_name_is_replaced() {
+        assert_eq!("file", sanitize_file_name(" .. "));
+    }
+}
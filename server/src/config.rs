@@ -1,14 +1,34 @@
 use std::convert::{TryFrom, TryInto};
+use std::path::PathBuf;
 
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use sqlx::postgres::{PgConnectOptions, PgSslMode};
 
+use crate::api::{validation, ReportTemplate, ReviewOutcome};
+use crate::{Error, FieldError, FieldErrorCode};
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
     pub log: LogConfig,
     pub database: DatabaseConfig,
+    #[serde(default)]
+    pub identity: IdentityConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
+    #[serde(default)]
+    pub stats: StatsConfig,
+    #[serde(default)]
+    pub review_expiry: ReviewExpiryConfig,
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub comments: CommentsConfig,
+    // The integration is disabled when omitted
+    pub teamdynamix: Option<TeamDynamixConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -35,6 +55,228 @@ pub struct DatabaseConfig {
     pub require_ssl: bool,
 }
 
+// Email domains accepted for staff accounts (users) and requesters, e.g. `brockport.edu`.
+// Domains are matched exactly and case-insensitively. An empty list accepts any domain
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct IdentityConfig {
+    #[serde(default)]
+    pub staff_email_domains: Vec<String>,
+    #[serde(default)]
+    pub requester_email_domains: Vec<String>,
+}
+
+// The template used to render software review PDFs (see `ReportTemplate`). Defaults to
+// the template in `assets/templates`
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ReportConfig {
+    pub template_path: Option<PathBuf>,
+    // Printed on every report, followed by `?review_id=<id>`. Defaults to the client's
+    // `/dashboard/reviews/verify` page
+    pub verification_url: Option<String>,
+    // Reports are only signed when a certificate and private key are configured
+    pub signing: Option<ReportSigningConfig>,
+}
+
+// How long computed statistics are reused before being queried again. `0` disables
+// caching
+#[derive(Clone, Debug, Deserialize)]
+pub struct StatsConfig {
+    pub cache_seconds: u64,
+}
+
+// How long a review counts as a current approval, by its outcome (see `ReviewOutcome`),
+// and how the expiry worker handles reviews nearing or past expiry
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ReviewExpiryConfig {
+    pub no_concerns_days: u16,
+    pub unsure_days: u16,
+    pub concerns_days: u16,
+    // Reviews expiring within this many days are flagged
+    pub warning_days: u16,
+    // Puts the software request of an expired review back in the review queue
+    pub reopen_expired: bool,
+    pub check_interval_seconds: u64,
+}
+
+// Delivery of queued webhook events. A failed delivery is retried after
+// `retry_base_seconds`, doubling after every attempt up to `retry_max_seconds`, until
+// `max_attempts` have been made
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub poll_interval_seconds: u64,
+    // Deliveries sent per poll
+    pub batch_size: i64,
+    pub timeout_seconds: u64,
+    pub max_attempts: i32,
+    pub retry_base_seconds: u64,
+    pub retry_max_seconds: u64,
+}
+
+// Live change events sent on `/events`. A stream that falls more than `capacity` events
+// behind is told to reload. Open streams check their token every `token_check_seconds`
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct EventsConfig {
+    pub capacity: usize,
+    pub keep_alive_seconds: u64,
+    pub token_check_seconds: u64,
+}
+
+// Review comments can only be edited by their author, for `edit_window_minutes` after
+// they are posted
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CommentsConfig {
+    pub edit_window_minutes: i64,
+}
+
+// Connection to the TeamDynamix (TDX) Web API, authenticated as an administrative service
+// account, and where the details of a software request are found on a ticket
+#[derive(Clone, Debug, Deserialize)]
+pub struct TeamDynamixConfig {
+    // e.g. `https://brockport.teamdynamix.com/TDWebApi`
+    pub base_url: String,
+    // The ticketing application software requests are raised in
+    pub app_id: u32,
+    pub beid: SecretString,
+    pub web_services_key: SecretString,
+    #[serde(default = "TeamDynamixConfig::default_timeout_seconds")]
+    pub timeout_seconds: u64,
+    // Custom ticket attributes holding the requested software. The software name falls
+    // back to the ticket title
+    pub software_name_attribute_id: Option<i32>,
+    pub software_version_attribute_id: Option<i32>,
+    pub developer_attribute_id: Option<i32>,
+    // Status a ticket is moved to when a review is posted onto it. Omit to leave the
+    // status unchanged
+    pub review_status_id: Option<i32>,
+}
+
+// A PEM encoded certificate and the private key it was issued for
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReportSigningConfig {
+    pub certificate_path: PathBuf,
+    pub private_key_path: PathBuf,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        StatsConfig { cache_seconds: 60 }
+    }
+}
+
+impl Default for ReviewExpiryConfig {
+    fn default() -> Self {
+        ReviewExpiryConfig {
+            no_concerns_days: 730,
+            unsure_days: 365,
+            concerns_days: 180,
+            warning_days: 30,
+            reopen_expired: false,
+            check_interval_seconds: 3600,
+        }
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            poll_interval_seconds: 5,
+            batch_size: 20,
+            timeout_seconds: 10,
+            max_attempts: 8,
+            retry_base_seconds: 30,
+            retry_max_seconds: 21600,
+        }
+    }
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        EventsConfig {
+            capacity: 256,
+            keep_alive_seconds: 15,
+            token_check_seconds: 60,
+        }
+    }
+}
+
+impl Default for CommentsConfig {
+    fn default() -> Self {
+        CommentsConfig {
+            edit_window_minutes: 15,
+        }
+    }
+}
+
+impl TeamDynamixConfig {
+    fn default_timeout_seconds() -> u64 {
+        30
+    }
+}
+
+impl ReviewExpiryConfig {
+    // Days a review with `outcome` stays valid
+    pub fn validity_days(&self, outcome: ReviewOutcome) -> i32 {
+        let days = match outcome {
+            ReviewOutcome::NoConcerns => self.no_concerns_days,
+            ReviewOutcome::Unsure => self.unsure_days,
+            ReviewOutcome::Concerns => self.concerns_days,
+        };
+
+        i32::from(days)
+    }
+}
+
+impl ReportConfig {
+    pub fn template_path(&self) -> PathBuf {
+        self.template_path
+            .clone()
+            .unwrap_or_else(ReportTemplate::default_path)
+    }
+
+    pub fn verification_url(&self, origin: &str) -> String {
+        self.verification_url
+            .clone()
+            .unwrap_or_else(|| format!("https://{}/dashboard/reviews/verify", origin))
+    }
+}
+
+impl IdentityConfig {
+    pub fn validate_staff_email(&self, field: &str, email: &str) -> crate::Result<()> {
+        Self::validate_email_domain(field, email, &self.staff_email_domains)
+    }
+
+    pub fn validate_requester_email(&self, field: &str, email: &str) -> crate::Result<()> {
+        Self::validate_email_domain(field, email, &self.requester_email_domains)
+    }
+
+    fn validate_email_domain(
+        field: &str,
+        email: &str,
+        allowed_domains: &[String],
+    ) -> crate::Result<()> {
+        if validation::validate_email_domain(email, allowed_domains) {
+            return Ok(());
+        }
+
+        Err(Error::ValidationError(
+            FieldError::new(
+                field,
+                FieldErrorCode::DomainNotAllowed,
+                format!(
+                    "'{}' must use one of the allowed email domains: {}",
+                    email,
+                    allowed_domains.join(", ")
+                ),
+            )
+            .into(),
+        ))
+    }
+}
+
 impl DatabaseConfig {
     pub fn connect_options(&self) -> PgConnectOptions {
         let ssl_mode = if self.require_ssl {
@@ -22,8 +22,8 @@ pub enum Error {
     AuthInvalidRoleError,
 
     // -- validation
-    #[error("validation error occured while parsing {0}")]
-    ValidationError(String),
+    #[error("validation error occured while parsing payload: {0}")]
+    ValidationError(FieldErrors),
     #[error("validation error occured while parsing query parameters: {0}")]
     QueryParamValidationError(String),
 
@@ -32,11 +32,25 @@ pub enum Error {
     PgNotFoundError,
     #[error("database record already exists")]
     PgRecordExists,
+    #[error("database record with the same {0} already exists")]
+    PgFieldConflict(String),
     #[error("database key violation occured")]
     PgKeyViolation,
     #[error("database records depend on the referenced record")]
     PgDependencyViolation,
 
+    // -- ticketing
+    #[error("ticketing integration is not configured")]
+    TicketingNotConfiguredError,
+    #[error("ticket could not be found in the ticketing system")]
+    TicketNotFoundError,
+    #[error("ticketing system request failed: {0}")]
+    TicketingError(String),
+
+    // -- comments
+    #[error("comment can no longer be edited")]
+    CommentEditWindowClosedError,
+
     // -- other
     #[error("no details provided to update resource")]
     NoUpdatesProvidedError,
@@ -96,55 +110,73 @@ impl IntoResponse for Error {
 }
 
 impl Error {
-    pub fn client_status_and_error(&self) -> (StatusCode, String) {
+    // Field-level details for payload errors, so clients can point the user at the
+    // offending input. Other errors have no field details
+    pub fn client_field_errors(&self) -> Option<Vec<FieldError>> {
         match self {
-            Self::AuthEmailNotFoundError | Self::AuthInvalidPasswordError => (
-                StatusCode::UNAUTHORIZED,
-                ClientError::InvalidCredentials.to_string(),
-            ),
+            Self::ValidationError(errors) => Some(errors.to_vec()),
+            Self::PayloadExtractorError(json_error) => Some(vec![json_error.field_error()]),
+            Self::NoUpdatesProvidedError => Some(vec![FieldError::new(
+                "",
+                FieldErrorCode::NoUpdates,
+                "no details were provided to update the resource",
+            )]),
+            _ => None,
+        }
+    }
 
-            Self::AuthInvalidTokenError => (
-                StatusCode::UNAUTHORIZED,
-                ClientError::InvalidToken.to_string(),
-            ),
+    pub fn client_status_and_error(&self) -> (StatusCode, ClientError) {
+        match self {
+            Self::AuthEmailNotFoundError | Self::AuthInvalidPasswordError => {
+                (StatusCode::UNAUTHORIZED, ClientError::InvalidCredentials)
+            }
 
-            Self::AuthMissingTokenError => (
-                StatusCode::UNAUTHORIZED,
-                ClientError::MissingToken.to_string(),
-            ),
+            Self::AuthInvalidTokenError => (StatusCode::UNAUTHORIZED, ClientError::InvalidToken),
 
-            Self::AuthInvalidRoleError => {
-                (StatusCode::FORBIDDEN, ClientError::InvalidRole.to_string())
+            Self::AuthMissingTokenError => (StatusCode::UNAUTHORIZED, ClientError::MissingToken),
+
+            Self::AuthInvalidRoleError => (StatusCode::FORBIDDEN, ClientError::InvalidRole),
+
+            Self::CommentEditWindowClosedError => {
+                (StatusCode::FORBIDDEN, ClientError::EditWindowClosed)
             }
 
-            Self::QueryParamValidationError(..) | Self::PathExtractorError(..) => (
-                StatusCode::BAD_REQUEST,
-                ClientError::InvalidParams.to_string(),
-            ),
+            Self::QueryParamValidationError(..) | Self::PathExtractorError(..) => {
+                (StatusCode::BAD_REQUEST, ClientError::InvalidParams)
+            }
 
             Self::PayloadExtractorError(..)
             | Self::ValidationError(..)
-            | Self::NoUpdatesProvidedError => (
-                StatusCode::BAD_REQUEST,
-                ClientError::InvalidPayload.to_string(),
-            ),
+            | Self::NoUpdatesProvidedError => {
+                (StatusCode::BAD_REQUEST, ClientError::InvalidPayload)
+            }
 
-            Self::PgKeyViolation => (StatusCode::CONFLICT, ClientError::Conflict.to_string()),
+            Self::PgKeyViolation => (StatusCode::CONFLICT, ClientError::Conflict),
 
-            Self::PgRecordExists => (StatusCode::CONFLICT, ClientError::RecordExists.to_string()),
+            Self::PgRecordExists => (StatusCode::CONFLICT, ClientError::RecordExists),
 
-            Self::PgDependencyViolation => (
+            Self::PgFieldConflict(field) => (
                 StatusCode::CONFLICT,
-                ClientError::DependencyViolation.to_string(),
+                ClientError::FieldConflict(field.clone()),
             ),
 
-            Self::PgNotFoundError => (StatusCode::NOT_FOUND, ClientError::NotFound.to_string()),
+            Self::PgDependencyViolation => (StatusCode::CONFLICT, ClientError::DependencyViolation),
 
-            // -- Fallback
-            _ => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                ClientError::ServiceError.to_string(),
+            Self::PgNotFoundError | Self::TicketNotFoundError => {
+                (StatusCode::NOT_FOUND, ClientError::NotFound)
+            }
+
+            Self::TicketingNotConfiguredError => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ClientError::TicketingUnavailable,
             ),
+
+            Self::TicketingError(..) => {
+                (StatusCode::BAD_GATEWAY, ClientError::TicketingUnavailable)
+            }
+
+            // -- Fallback
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, ClientError::ServiceError),
         }
     }
 }
@@ -159,11 +191,57 @@ pub enum ClientError {
     MissingToken,
     NotFound,
     RecordExists,
+    FieldConflict(String),
     Conflict,
     DependencyViolation,
+    TicketingUnavailable,
+    EditWindowClosed,
     ServiceError,
 }
 
+impl ClientError {
+    // Identifies the problem type in `application/problem+json` responses (RFC 9457)
+    pub fn problem_type(&self) -> String {
+        let slug = match self {
+            ClientError::InvalidCredentials => "invalid-credentials",
+            ClientError::InvalidPayload => "invalid-payload",
+            ClientError::InvalidParams => "invalid-params",
+            ClientError::InvalidRole => "invalid-role",
+            ClientError::InvalidToken => "invalid-token",
+            ClientError::MissingToken => "missing-token",
+            ClientError::NotFound => "not-found",
+            ClientError::RecordExists => "record-exists",
+            ClientError::FieldConflict(..) => "field-conflict",
+            ClientError::Conflict => "conflict",
+            ClientError::DependencyViolation => "dependency-violation",
+            ClientError::TicketingUnavailable => "ticketing-unavailable",
+            ClientError::EditWindowClosed => "edit-window-closed",
+            ClientError::ServiceError => "service-error",
+        };
+
+        format!("urn:k6r:problem:{}", slug)
+    }
+
+    // Short summary of the problem type, which does not change between occurrences
+    pub fn title(&self) -> &'static str {
+        match self {
+            ClientError::InvalidCredentials => "Invalid credentials",
+            ClientError::InvalidPayload => "Invalid payload",
+            ClientError::InvalidParams => "Invalid parameters",
+            ClientError::InvalidRole => "Insufficient permissions",
+            ClientError::InvalidToken => "Invalid token",
+            ClientError::MissingToken => "Missing token",
+            ClientError::NotFound => "Resource not found",
+            ClientError::RecordExists | ClientError::FieldConflict(..) => "Record already exists",
+            ClientError::Conflict => "Conflict",
+            ClientError::DependencyViolation => "Dependent records exist",
+            ClientError::TicketingUnavailable => "Ticketing system unavailable",
+            ClientError::EditWindowClosed => "Edit window closed",
+            ClientError::ServiceError => "Internal server error",
+        }
+    }
+}
+
 impl std::fmt::Display for ClientError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let error_message = match self {
@@ -182,10 +260,125 @@ impl std::fmt::Display for ClientError {
             ClientError::DependencyViolation => {
                 "This record cannot be deleted because other records depend on it"
             }
+            ClientError::TicketingUnavailable => {
+                "The ticketing system could not complete the request. Please try again later"
+            }
+            ClientError::EditWindowClosed => {
+                "This comment can no longer be edited, as its edit window has closed"
+            }
             ClientError::RecordExists => "A record with the specified details already exists",
+            ClientError::FieldConflict(field) => {
+                return write!(f, "A record with the specified {} already exists", field)
+            }
             _ => "An internal server error has occurred. Please try again later",
         };
 
         write!(f, "{}", error_message)
     }
 }
+
+// A single field that failed validation. `field` is the path to the field within the
+// payload (e.g. `software_request.requester.email`), or empty when the error applies to
+// the payload as a whole
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FieldError {
+    pub code: FieldErrorCode,
+    pub field: String,
+    pub message: String,
+}
+
+// Stable, machine-readable reason a field failed validation
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldErrorCode {
+    InvalidValue,
+    InvalidType,
+    MissingField,
+    ConflictingFields,
+    MalformedPayload,
+    NoUpdates,
+    DomainNotAllowed,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: FieldErrorCode, message: impl Into<String>) -> Self {
+        FieldError {
+            code,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+// Collects every field error found while parsing a payload, rather than stopping at
+// the first one
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        code: FieldErrorCode,
+        message: impl Into<String>,
+    ) {
+        self.0.push(FieldError::new(field, code, message));
+    }
+
+    // Merges the errors from parsing a nested payload, prefixing each field with `prefix`
+    pub fn nest(&mut self, prefix: &str, result: Result<()>) -> Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::ValidationError(errors)) => {
+                self.0.extend(errors.0.into_iter().map(|mut error| {
+                    error.field = if error.field.is_empty() {
+                        prefix.to_string()
+                    } else {
+                        format!("{}.{}", prefix, error.field)
+                    };
+                    error
+                }));
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn to_vec(&self) -> Vec<FieldError> {
+        self.0.clone()
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(self))
+        }
+    }
+}
+
+impl From<FieldError> for FieldErrors {
+    fn from(error: FieldError) -> Self {
+        FieldErrors(vec![error])
+    }
+}
+
+impl std::fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors = self
+            .0
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", errors.join("; "))
+    }
+}
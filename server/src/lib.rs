@@ -5,5 +5,5 @@ pub mod log;
 pub mod server;
 
 pub use config::{get_config, Config};
-pub use error::{Error, Result};
+pub use error::{Error, FieldError, FieldErrorCode, FieldErrors, Result};
 pub use server::Server;
@@ -1,4 +1,7 @@
-use k6r::api::{log_cleanup_task, poll_and_update_token_cache, TokenCache};
+use k6r::api::{
+    log_cleanup_task, poll_and_update_token_cache, review_expiry_task, webhook_delivery_task,
+    TokenCache,
+};
 use k6r::config::get_config;
 use k6r::log::{get_subscriber, init_subscriber};
 use k6r::Server;
@@ -26,15 +29,25 @@ async fn main() -> k6r::Result<()> {
     let log_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("logs");
     let log_retention_days = config.log.retention_days;
 
-    // Spawn three new asynchronous tasks using `tokio::spawn`
+    // Spawn five new asynchronous tasks using `tokio::spawn`
     let server_task = tokio::spawn(server.run(tcp_listener));
-    let worker_task = tokio::spawn(poll_and_update_token_cache(token_cache, config.database));
+    let worker_task = tokio::spawn(poll_and_update_token_cache(
+        token_cache,
+        config.database.clone(),
+    ));
     let log_cleanup_task = tokio::spawn(log_cleanup_task(log_dir, log_retention_days));
+    let review_expiry_task = tokio::spawn(review_expiry_task(
+        config.review_expiry,
+        config.database.clone(),
+    ));
+    let webhook_task = tokio::spawn(webhook_delivery_task(config.webhooks, config.database));
 
     tokio::select! {
         t = server_task => report_exit("SERVER", t),
         t = worker_task => report_exit("WORKER", t),
-        t = log_cleanup_task => report_exit("LOG CLEANUP", Ok(t))
+        t = log_cleanup_task => report_exit("LOG CLEANUP", Ok(t)),
+        t = review_expiry_task => report_exit("REVIEW EXPIRY", t),
+        t = webhook_task => report_exit("WEBHOOKS", t)
     }
 
     Ok(())
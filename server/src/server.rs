@@ -13,14 +13,22 @@ use tower::ServiceBuilder;
 use tower_http::classify::StatusInRangeAsFailures;
 use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
+use tower_http::request_id::{
+    MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer,
+};
 use tower_http::trace::TraceLayer;
 use tower_service::Service;
 
 use crate::api::{
-    auth_routes, health_routes, main_response_mapper, requester_routes, software_request_routes,
-    software_review_routes, software_routes, user_routes, TokenCache,
+    auth_routes, event_routes, health_routes, identity_routes, import_routes, main_response_mapper,
+    report_routes, requester_routes, search_routes, software_request_routes,
+    software_review_routes, software_routes, stats_routes, ticket_routes, user_routes,
+    webhook_routes, EventHub, PdfSigner, ReportIntegrity, ReportTemplate, StatsCache,
+    TeamDynamixClient, TokenCache,
+};
+use crate::config::{
+    CommentsConfig, Config, DatabaseConfig, EventsConfig, IdentityConfig, ReviewExpiryConfig,
 };
-use crate::config::{Config, DatabaseConfig};
 use crate::Result;
 
 #[derive(Debug)]
@@ -52,14 +60,47 @@ impl Server {
 
         let environment = config.server.environment;
 
-        let instance = setup_server(
+        // Fails to start with an invalid template or signing key, rather than failing every
+        // export
+        let report_template = ReportTemplate::load(&config.report.template_path())?;
+
+        let report_signer = match &config.report.signing {
+            Some(signing) => Some(PdfSigner::load(
+                &signing.certificate_path,
+                &signing.private_key_path,
+            )?),
+            None => None,
+        };
+
+        let report_integrity = ReportIntegrity::new(
+            config.report.verification_url(&config.server.origin),
+            report_signer,
+        );
+
+        let ticketing = match config.teamdynamix {
+            Some(teamdynamix) => Some(std::sync::Arc::new(TeamDynamixClient::new(teamdynamix)?)),
+            None => None,
+        };
+
+        let state = ServerState {
             db_pool,
-            config.server.jwt_secret,
+            jwt_secret: config.server.jwt_secret,
             token_cache,
-            environment.clone(),
-            config.server.origin,
-        )
-        .await?;
+            environment: environment.clone(),
+            identity: config.identity,
+            report_template: std::sync::Arc::new(report_template),
+            report_integrity: std::sync::Arc::new(report_integrity),
+            stats_cache: StatsCache::new(std::time::Duration::from_secs(
+                config.stats.cache_seconds,
+            )),
+            review_expiry: config.review_expiry,
+            ticketing,
+            events: EventHub::new(config.events.capacity),
+            events_config: config.events,
+            comments: config.comments,
+        };
+
+        let instance = setup_server(state, config.server.origin).await?;
 
         tracing::info!(
             "{}",
@@ -174,22 +215,19 @@ pub struct ServerState {
     pub jwt_secret: SecretString,
     pub token_cache: TokenCache,
     pub environment: String,
+    pub identity: IdentityConfig,
+    pub report_template: std::sync::Arc<ReportTemplate>,
+    pub report_integrity: std::sync::Arc<ReportIntegrity>,
+    pub stats_cache: StatsCache,
+    pub review_expiry: ReviewExpiryConfig,
+    // Set when a ticketing system is configured
+    pub ticketing: Option<std::sync::Arc<TeamDynamixClient>>,
+    pub events: EventHub,
+    pub events_config: EventsConfig,
+    pub comments: CommentsConfig,
 }
 
-pub async fn setup_server(
-    db_pool: PgPool,
-    jwt_secret: SecretString,
-    token_cache: TokenCache,
-    environment: String,
-    origin: String,
-) -> Result<Router> {
-    let state = ServerState {
-        db_pool,
-        jwt_secret,
-        token_cache,
-        environment,
-    };
-
+pub async fn setup_server(state: ServerState, origin: String) -> Result<Router> {
     let origin = [
         "http://localhost:3080".parse().unwrap(),
         format!("https://{}", origin).parse().unwrap(),
@@ -213,7 +251,10 @@ pub async fn setup_server(
             header::CONTENT_DISPOSITION,
             header::CONTENT_LENGTH,
         ])
-        .expose_headers([header::CONTENT_DISPOSITION]);
+        .expose_headers([
+            header::CONTENT_DISPOSITION,
+            header::HeaderName::from_static("x-request-id"),
+        ]);
 
     let server = Router::new()
         .nest(
@@ -226,33 +267,52 @@ pub async fn setup_server(
                 .nest("/software", software_routes())
                 .nest("/requests", software_request_routes())
                 .nest("/reviews", software_review_routes())
+                .nest("/search", search_routes())
+                .nest("/identity", identity_routes())
+                .nest("/import", import_routes())
+                .nest("/reports", report_routes())
+                .nest("/stats", stats_routes())
+                .nest("/webhooks", webhook_routes())
+                .nest("/tickets", ticket_routes())
+                .nest("/events", event_routes())
                 .with_state(state),
         )
         .layer(axum::middleware::map_response(main_response_mapper))
         .layer(CompressionLayer::new())
         .layer(cors_layer)
         .layer(
-            ServiceBuilder::new().layer(
-                TraceLayer::new(
-                    // By default, the `new_for_http` method for TraceLayer
-                    // only classifies `5xx` errors as failures.
-                    // Now, any error with status from 400 to 599 is classified as an error
-                    StatusInRangeAsFailures::new(400..=599).into_make_classifier(),
-                )
-                .make_span_with(|request: &Request<_>| {
-                    let request_id = uuid::Uuid::new_v4().to_string();
-
-                    // Will be included with every request log
-                    tracing::span!(
-                        tracing::Level::INFO,
-                        "request",
-                        %request_id,
-                        method = ?request.method(),
-                        uri = %request.uri(),
-                        version = ?request.version(),
+            ServiceBuilder::new()
+                // Generates an `X-Request-Id` for the request, unless one was already
+                // provided (e.g. by nginx)
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new(
+                        // By default, the `new_for_http` method for TraceLayer
+                        // only classifies `5xx` errors as failures.
+                        // Now, any error with status from 400 to 599 is classified as an error
+                        StatusInRangeAsFailures::new(400..=599).into_make_classifier(),
                     )
-                }),
-            ),
+                    .make_span_with(|request: &Request<_>| {
+                        let request_id = request
+                            .extensions()
+                            .get::<RequestId>()
+                            .and_then(|request_id| request_id.header_value().to_str().ok())
+                            .unwrap_or_default()
+                            .to_string();
+
+                        // Will be included with every request log
+                        tracing::span!(
+                            tracing::Level::INFO,
+                            "request",
+                            %request_id,
+                            method = ?request.method(),
+                            uri = %request.uri(),
+                            version = ?request.version(),
+                        )
+                    }),
+                )
+                // Echoes the request id back to the client in the response headers
+                .layer(PropagateRequestIdLayer::x_request_id()),
         );
 
     Ok(server)
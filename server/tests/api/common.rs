@@ -5,11 +5,12 @@ use rand::Rng;
 use reqwest::header;
 use secrecy::SecretString;
 use serde::Serialize;
+use serde_json::{json, Value};
 use sqlx::{Connection, Executor, PgConnection, PgPool};
 use uuid::Uuid;
 
 use k6r::api::{poll_and_update_token_cache, TokenCache, UserRole};
-use k6r::config::{get_config, DatabaseConfig};
+use k6r::config::{get_config, Config, DatabaseConfig};
 use k6r::log::{get_subscriber, init_subscriber};
 use k6r::server::{get_db_pool, Server};
 
@@ -180,6 +181,109 @@ impl TestServer {
                 .map_err(|err| format!("failed to execute request. cause: {err}"))?),
         }
     }
+
+    // Logs in as `test_users[test_user]` and returns the JWT cookie
+    pub async fn login(&self, test_user: usize) -> Result<String> {
+        let login_url = format!("{}/api/v1/auth/login", self.addr);
+
+        let login_body = json!({
+            "email": self.test_users[test_user].email,
+            "password": self.test_users[test_user].password
+        });
+
+        let login_response = self
+            .post_request(&login_url, Some(login_body.to_string()), None)
+            .await?;
+        assert_eq!(204, login_response.status().as_u16());
+
+        let token = login_response
+            .headers()
+            .get(header::SET_COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        assert!(token.is_some(), "JWT should be present");
+
+        Ok(token.unwrap())
+    }
+
+    // Body of a review of new software requested by a new requester on the ticket
+    // `td_request_id`. Fields in `overrides` replace the defaults, and nested objects (e.g.
+    // `software_request.software`) are merged field by field
+    pub fn review_body(&self, td_request_id: &str, overrides: Value) -> Value {
+        let mut review_body = json!({
+            "software_request": {
+                "td_request_id": td_request_id,
+                "software": {
+                    "software_name": format!("Software {}", td_request_id),
+                    "software_version": "1.0.0",
+                    "developer_name": "Test Developer",
+                    "description": "A test software application",
+                },
+                "requester": {
+                    "name": "John",
+                    "email": format!("john{}@gmail.com", td_request_id),
+                    "department": "IT",
+                },
+            },
+            "reviewer_id": self.test_users[0].id,
+            "is_supported": "TRUE",
+            "is_current_version": "TRUE",
+            "is_reputation_good": "TRUE",
+            "is_installation_from_developer": "TRUE",
+            "is_local_admin_required": "FALSE",
+            "is_connected_to_brockport_cloud": "FALSE",
+            "is_connected_to_cloud_services_or_client": "FALSE",
+            "is_security_or_optimization_software": "FALSE",
+            "is_supported_by_current_os": "TRUE",
+            "review_notes": "All conditions satisfied."
+        });
+
+        merge_json(&mut review_body, overrides);
+
+        review_body
+    }
+
+    // Creates the review described by `review_body` and returns its id
+    pub async fn create_review(
+        &self,
+        token: &str,
+        td_request_id: &str,
+        overrides: Value,
+    ) -> Result<Uuid> {
+        let create_software_review_body = self.review_body(td_request_id, overrides);
+
+        let create_software_review_response = self
+            .post_request(
+                &format!("{}/api/v1/reviews", self.addr),
+                Some(create_software_review_body.to_string()),
+                Some(token),
+            )
+            .await?;
+        assert_eq!(201, create_software_review_response.status().as_u16());
+
+        let review_id = sqlx::query_scalar(
+            "SELECT rv.id FROM software_review rv \
+             INNER JOIN software_request r ON rv.software_request_id = r.id \
+             WHERE r.td_request_id = $1",
+        )
+        .bind(td_request_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(review_id)
+    }
+}
+
+// Replaces the fields of `value` with those in `overrides`, merging nested objects
+fn merge_json(value: &mut Value, overrides: Value) {
+    match (value, overrides) {
+        (Value::Object(value), Value::Object(overrides)) => {
+            for (key, override_value) in overrides {
+                merge_json(value.entry(key).or_insert(Value::Null), override_value);
+            }
+        }
+        (value, overrides) => *value = overrides,
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -255,11 +359,19 @@ impl TestUser {
 }
 
 pub async fn spawn_server() -> Result<TestServer> {
+    spawn_server_with_config(|_| ()).await
+}
+
+// Same as `spawn_server`, but `configure` can change the config first (e.g. to restrict
+// the allowed email domains)
+pub async fn spawn_server_with_config(configure: impl FnOnce(&mut Config)) -> Result<TestServer> {
     std::sync::LazyLock::force(&TRACING);
 
     let config = {
         let mut config = get_config().expect("failed to read config");
 
+        configure(&mut config);
+
         // Use a different database for each test case
         config.database.database = Uuid::new_v4().to_string();
 
@@ -0,0 +1 @@
+mod problem_details;
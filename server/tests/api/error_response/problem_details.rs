@@ -0,0 +1,84 @@
+use reqwest::header;
+use serde_json::{json, Value};
+
+use crate::common::{spawn_server, Result};
+
+#[tokio::test]
+async fn error_response_is_problem_details_with_request_id() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+
+    let response = server.get_request(&software_url, None).await?;
+    assert_eq!(401, response.status().as_u16());
+
+    assert_eq!(
+        "application/problem+json",
+        response.headers()[header::CONTENT_TYPE]
+    );
+
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .expect("X-Request-Id should be present")
+        .to_string();
+    assert!(!request_id.is_empty());
+
+    let body: Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    assert_eq!("urn:k6r:problem:missing-token", body["type"]);
+    assert_eq!("Missing token", body["title"]);
+    assert_eq!(401, body["status"]);
+    assert_eq!("The request is missing a valid token", body["detail"]);
+    assert_eq!("/api/v1/software", body["instance"]);
+    assert_eq!(request_id, body["request_id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn error_response_honors_inbound_request_id() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": "incorrect password"
+    });
+
+    let response = server
+        .client
+        .post(&login_url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-request-id", "3f2b9c0d7e6a41f8a5b4c3d2e1f0a9b8")
+        .body(login_body.to_string())
+        .send()
+        .await
+        .map_err(|err| format!("failed to execute request. cause: {err}"))?;
+    assert_eq!(401, response.status().as_u16());
+
+    assert_eq!(
+        "3f2b9c0d7e6a41f8a5b4c3d2e1f0a9b8",
+        response.headers()["x-request-id"]
+    );
+
+    let body: Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    assert_eq!("3f2b9c0d7e6a41f8a5b4c3d2e1f0a9b8", body["request_id"]);
+    assert_eq!("/api/v1/auth/login", body["instance"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn successful_response_includes_request_id() -> Result<()> {
+    let server = spawn_server().await?;
+    let health_url = format!("{}/api/v1/health", server.addr);
+
+    let response = server.get_request(&health_url, None).await?;
+    assert_eq!(204, response.status().as_u16());
+
+    assert!(response.headers().get("x-request-id").is_some());
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use reqwest::header;
+use serde_json::json;
+
+use crate::common::{spawn_server, spawn_server_with_config, Result, TestServer};
+
+async fn open_event_stream(server: &TestServer, token: &str) -> Result<reqwest::Response> {
+    let events_response = server
+        .get_request(&format!("{}/api/v1/events", server.addr), Some(token))
+        .await?;
+    assert_eq!(200, events_response.status().as_u16());
+    assert_eq!(
+        "text/event-stream",
+        events_response.headers()[header::CONTENT_TYPE]
+    );
+
+    Ok(events_response)
+}
+
+// Reads the stream until `event` is received and returns everything read so far
+async fn read_until(events_response: &mut reqwest::Response, event: &str) -> Result<String> {
+    let mut received = String::new();
+
+    while !received.contains(&format!("event: {}\n", event)) {
+        let chunk = tokio::time::timeout(Duration::from_secs(10), events_response.chunk())
+            .await
+            .map_err(|_| format!("timed out waiting for '{}', got: {}", event, received))??;
+
+        match chunk {
+            Some(chunk) => received.push_str(&String::from_utf8_lossy(&chunk)),
+            None => {
+                return Err(format!("stream ended before '{}', got: {}", event, received).into())
+            }
+        }
+    }
+
+    Ok(received)
+}
+
+async fn create_software(server: &TestServer, token: &str, software_name: &str) -> Result<()> {
+    let create_software_body = json!({
+        "software_name": software_name,
+        "software_version": "1.0.0",
+        "developer_name": "Test Developer",
+        "description": "A test software application",
+    });
+
+    let create_software_response = server
+        .post_request(
+            &format!("{}/api/v1/software", server.addr),
+            Some(create_software_body.to_string()),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(201, create_software_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_events_returns_401_without_token() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    // Act
+    let events_response = server
+        .get_request(&format!("{}/api/v1/events", server.addr), None)
+        .await?;
+
+    // Assert
+    assert_eq!(401, events_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_events_streams_software_changes() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let reviewer_token = server.login(0).await?;
+    let admin_token = server.login(1).await?;
+
+    let mut events_response = open_event_stream(&server, &reviewer_token).await?;
+
+    // Act
+    create_software(&server, &admin_token, "Streamed Software").await?;
+
+    // Assert
+    let received = read_until(&mut events_response, "software.created").await?;
+
+    let data = received
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("event should have data");
+    let event: serde_json::Value = serde_json::from_str(data)?;
+
+    assert_eq!("software", event["entity"]);
+    assert_eq!("created", event["action"]);
+    assert!(event["id"].is_string());
+    assert!(event["occurred_at"].is_string());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_events_only_streams_user_changes_to_admins() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let reviewer_token = server.login(0).await?;
+    let admin_token = server.login(1).await?;
+
+    let mut reviewer_events = open_event_stream(&server, &reviewer_token).await?;
+    let mut admin_events = open_event_stream(&server, &admin_token).await?;
+
+    // Act
+    let update_user_response = server
+        .patch_request(
+            &format!("{}/api/v1/users/{}", server.addr, server.test_users[0].id),
+            Some(json!({ "name": "Renamed Reviewer" }).to_string()),
+            Some(&admin_token),
+        )
+        .await?;
+    assert_eq!(204, update_user_response.status().as_u16());
+
+    // Marks where the reviewer's stream has caught up to
+    create_software(&server, &admin_token, "Marker Software").await?;
+
+    // Assert
+    let admin_received = read_until(&mut admin_events, "software.created").await?;
+    assert!(admin_received.contains("event: user.updated\n"));
+
+    let reviewer_received = read_until(&mut reviewer_events, "software.created").await?;
+    assert!(
+        !reviewer_received.contains("user."),
+        "reviewers should not receive user events, got: {}",
+        reviewer_received
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_review_streams_inline_records() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    let mut events_response = open_event_stream(&server, &token).await?;
+
+    // Act
+    server.create_review(&token, "48000001", json!({})).await?;
+
+    // Assert
+    let received = read_until(&mut events_response, "requester.created").await?;
+
+    for event in [
+        "software_review.created",
+        "software_request.created",
+        "software.created",
+    ] {
+        assert!(
+            received.contains(&format!("event: {}\n", event)),
+            "'{}' should be received, got: {}",
+            event,
+            received
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_events_ends_after_logout() -> Result<()> {
+    // Arrange
+    let server = spawn_server_with_config(|config| {
+        config.events.token_check_seconds = 1;
+    })
+    .await?;
+
+    let token = server.login(0).await?;
+
+    let mut events_response = open_event_stream(&server, &token).await?;
+
+    // Act
+    let logout_response = server
+        .post_request(
+            &format!("{}/api/v1/auth/logout", server.addr),
+            None,
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, logout_response.status().as_u16());
+
+    // Assert
+    let ended = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            match events_response.chunk().await {
+                // Keep-alive comments
+                Ok(Some(_)) => continue,
+                Ok(None) | Err(_) => return,
+            }
+        }
+    })
+    .await;
+    assert!(
+        ended.is_ok(),
+        "the stream should end once the token is revoked"
+    );
+
+    Ok(())
+}
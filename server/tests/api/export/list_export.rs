@@ -0,0 +1,231 @@
+use reqwest::header;
+use reqwest::multipart::Form;
+use serde_json::Value;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+// More than the largest page of the JSON list
+const EXPORTED_SOFTWARE: usize = 150;
+
+// Imports `EXPORTED_SOFTWARE` software from the developer `Exportco`, so exports can be
+// filtered to only these records
+async fn import_software(server: &TestServer, token: &str) -> Result<()> {
+    let mut csv = String::from("software_name,software_version,developer_name,description\n");
+    for i in 0..EXPORTED_SOFTWARE {
+        csv.push_str(&format!(
+            "Export Software {},1.0.{},Exportco,=HYPERLINK(\"http://example.com\")\n",
+            i, i
+        ));
+    }
+
+    let import_response = server
+        .client
+        .post(format!("{}/api/v1/import", server.addr))
+        .header(header::COOKIE, token)
+        .multipart(Form::new().text("software", csv))
+        .send()
+        .await
+        .map_err(|err| format!("failed to execute request: cause {err}"))?;
+    assert_eq!(200, import_response.status().as_u16());
+
+    Ok(())
+}
+
+async fn export(
+    server: &TestServer,
+    token: &str,
+    path: &str,
+    accept: Option<&str>,
+) -> Result<reqwest::Response> {
+    let mut request = server
+        .client
+        .get(format!("{}/api/v1/{}", server.addr, path))
+        .header(header::COOKIE, token);
+
+    if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+    }
+
+    Ok(request
+        .send()
+        .await
+        .map_err(|err| format!("failed to execute request: cause {err}"))?)
+}
+
+fn header_value(response: &reqwest::Response, name: header::HeaderName) -> String {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[tokio::test]
+async fn export_software_as_csv_with_accept_header() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+    import_software(&server, &token).await?;
+
+    let export_response = export(
+        &server,
+        &token,
+        "software?filter=developer_name:Exportco",
+        Some("text/csv"),
+    )
+    .await?;
+    assert_eq!(200, export_response.status().as_u16());
+    assert!(header_value(&export_response, header::CONTENT_TYPE).starts_with("text/csv"));
+
+    let content_disposition = header_value(&export_response, header::CONTENT_DISPOSITION);
+    assert!(
+        content_disposition.starts_with("attachment; filename=\"software-"),
+        "{}",
+        content_disposition
+    );
+    assert!(
+        content_disposition.contains(".csv\""),
+        "{}",
+        content_disposition
+    );
+
+    let body = export_response.text().await?;
+    let mut lines = body.lines();
+    assert_eq!(
+        Some("id,software_name,software_version,developer_name,description,created_at"),
+        lines.next()
+    );
+
+    // Every matching record is exported, not just the first page
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(EXPORTED_SOFTWARE, rows.len());
+
+    // Cells that spreadsheet apps would evaluate as formulas are escaped
+    assert!(
+        rows.iter()
+            .all(|row| row.contains("\"'=HYPERLINK(\"\"http://example.com\"\")\"")),
+        "{}",
+        rows[0]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_as_json_lines_with_format_param() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+    import_software(&server, &token).await?;
+
+    // The `format` param takes precedence over the `Accept` header
+    let export_response = export(
+        &server,
+        &token,
+        "software?format=jsonl&filter=developer_name:Exportco&sort=-software_name",
+        Some("text/csv"),
+    )
+    .await?;
+    assert_eq!(200, export_response.status().as_u16());
+    assert_eq!(
+        "application/x-ndjson",
+        header_value(&export_response, header::CONTENT_TYPE)
+    );
+    assert!(header_value(&export_response, header::CONTENT_DISPOSITION).contains(".jsonl\""));
+
+    let body = export_response.text().await?;
+    let software: Vec<Value> = body
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(EXPORTED_SOFTWARE, software.len());
+    assert!(software
+        .iter()
+        .all(|software| software["developer_name"] == "Exportco"));
+
+    // The sort of the JSON list is also applied
+    assert_eq!("Export Software 99", software[0]["software_name"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_as_xlsx() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let export_response = export(&server, &token, "software?format=xlsx", None).await?;
+    assert_eq!(200, export_response.status().as_u16());
+    assert_eq!(
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        header_value(&export_response, header::CONTENT_TYPE)
+    );
+    assert!(header_value(&export_response, header::CONTENT_DISPOSITION).contains(".xlsx\""));
+
+    // An XLSX workbook is a zip archive
+    let body = export_response.bytes().await?;
+    assert!(body.starts_with(b"PK"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_requests_and_reviews_as_csv() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let test_cases = [
+        (
+            "requests",
+            "id,td_request_id,software_name,software_version,developer_name,\
+             requester_name,requester_email,requester_department,created_at",
+        ),
+        ("requesters", "id,name,email,department,created_at"),
+        (
+            "reviews",
+            "id,td_request_id,software_name,software_version,reviewed_version,\
+             newer_version_available,developer_name,requester_name,requester_email,\
+             requester_department,reviewer_name,reviewer_email,is_supported,\
+             is_current_version,is_reputation_good,is_installation_from_developer,\
+             is_local_admin_required,is_connected_to_brockport_cloud,\
+             is_connected_to_cloud_services_or_client,is_security_or_optimization_software,\
+             is_supported_by_current_os,exported,review_notes,expires_at,created_at",
+        ),
+    ];
+
+    for (path, columns) in test_cases {
+        let export_response = export(&server, &token, path, Some("text/csv")).await?;
+        assert_eq!(200, export_response.status().as_u16(), "{}", path);
+
+        let body = export_response.text().await?;
+        assert_eq!(Some(columns), body.lines().next(), "{}", path);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_with_invalid_format_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let test_cases = [
+        ("software?format=pdf", "unsupported format"),
+        (
+            "software?format=csv&sort=created_at",
+            "invalid query param for sort",
+        ),
+        ("users?format=csv", "users cannot be exported"),
+    ];
+
+    for (path, error_message) in test_cases {
+        let export_response = export(&server, &token, path, None).await?;
+        assert_eq!(
+            400,
+            export_response.status().as_u16(),
+            "API did not fail with a 400 status when {}",
+            error_message
+        );
+    }
+
+    Ok(())
+}
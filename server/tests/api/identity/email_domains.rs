@@ -0,0 +1,235 @@
+use serde_json::{json, Value};
+
+use crate::common::{spawn_server_with_config, Result, TestServer};
+
+// Spawns a server which only allows `brockport.edu` emails for users and requesters
+async fn spawn_restricted_server() -> Result<TestServer> {
+    spawn_server_with_config(|config| {
+        config.identity.staff_email_domains = vec!["brockport.edu".into()];
+        config.identity.requester_email_domains = vec!["brockport.edu".into()];
+    })
+    .await
+}
+
+// Asserts the response is a 400 reporting a disallowed email domain for `field`
+async fn assert_domain_not_allowed(response: reqwest::Response, field: &str) -> Result<()> {
+    assert_eq!(400, response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&response.text().await?)?;
+    assert_eq!("domain_not_allowed", body["errors"][0]["code"]);
+    assert_eq!(field, body["errors"][0]["field"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_user_outside_allowed_domains_rejected() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+    let users_url = format!("{}/api/v1/users", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_user_body = json!({
+        "name": "John",
+        "email": "john@gmail.com",
+        "password": "password1234",
+        "role": "REVIEWER",
+    });
+
+    let create_user_response = server
+        .post_request(&users_url, Some(create_user_body.to_string()), Some(&token))
+        .await?;
+    assert_domain_not_allowed(create_user_response, "email").await?;
+
+    // Domains are matched case-insensitively
+    let create_user_body = json!({
+        "name": "John",
+        "email": "john@Brockport.edu",
+        "password": "password1234",
+        "role": "REVIEWER",
+    });
+
+    let create_user_response = server
+        .post_request(&users_url, Some(create_user_body.to_string()), Some(&token))
+        .await?;
+    assert_eq!(201, create_user_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_user_outside_allowed_domains_rejected() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+
+    let token = server.login(1).await?;
+
+    let update_user_response = server
+        .patch_request(
+            &format!("{}/api/v1/users/{}", server.addr, server.test_users[0].id),
+            Some(json!({ "email": "john@gmail.com" }).to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_domain_not_allowed(update_user_response, "email").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_and_update_requester_outside_allowed_domains_rejected() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+    let requesters_url = format!("{}/api/v1/requesters", server.addr);
+
+    let token = server.login(0).await?;
+
+    let create_requester_body = json!({
+        "name": "John",
+        "email": "john@gmail.com",
+        "department": "IT",
+    });
+
+    let create_requester_response = server
+        .post_request(
+            &requesters_url,
+            Some(create_requester_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_domain_not_allowed(create_requester_response, "email").await?;
+
+    let create_requester_body = json!({
+        "name": "John",
+        "email": "john@brockport.edu",
+        "department": "IT",
+    });
+
+    let create_requester_response = server
+        .post_request(
+            &requesters_url,
+            Some(create_requester_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_requester_response.status().as_u16());
+
+    let get_requesters_response = server.get_request(&requesters_url, Some(&token)).await?;
+    let body: Value = serde_json::from_str(&get_requesters_response.text().await?)?;
+    let requester_id = body["requesters"][0]["requester"]["id"]
+        .as_str()
+        .unwrap_or_default();
+
+    let update_requester_response = server
+        .patch_request(
+            &format!("{}/{}", requesters_url, requester_id),
+            Some(json!({ "email": "john@gmail.com" }).to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_domain_not_allowed(update_requester_response, "email").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_requester_outside_allowed_domains_rejected() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Test Software",
+                "software_version": "1.0.0",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "email": "john@gmail.com",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_domain_not_allowed(
+        create_software_review_response,
+        "software_request.requester.email",
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_email_domain_violations_successful() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+    let violations_url = format!("{}/api/v1/identity/violations", server.addr);
+
+    // Records created before the domains were restricted
+    sqlx::query(
+        "INSERT INTO user_account (name, email, password_hash, role) \
+         VALUES ('John', 'john@gmail.com', 'hash', 'REVIEWER')",
+    )
+    .execute(&server.db_pool)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO requester (name, email, department) \
+         VALUES ('Jane', 'jane@yahoo.com', 'IT'), ('Jim', 'jim@brockport.edu', 'IT')",
+    )
+    .execute(&server.db_pool)
+    .await?;
+
+    let token = server.login(1).await?;
+
+    let get_violations_response = server.get_request(&violations_url, Some(&token)).await?;
+    assert_eq!(200, get_violations_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_violations_response.text().await?)?;
+    assert_eq!(json!(["brockport.edu"]), body["staff_email_domains"]);
+
+    // The seeded admin and the test users all use `brockport.edu`
+    let users = body["users"].as_array().unwrap();
+    assert_eq!(1, users.len());
+    assert_eq!("john@gmail.com", users[0]["user"]["email"]);
+
+    let requesters = body["requesters"].as_array().unwrap();
+    assert_eq!(1, requesters.len());
+    assert_eq!("jane@yahoo.com", requesters[0]["requester"]["email"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_email_domain_violations_as_reviewer_rejected() -> Result<()> {
+    let server = spawn_restricted_server().await?;
+    let violations_url = format!("{}/api/v1/identity/violations", server.addr);
+
+    let token = server.login(0).await?;
+
+    let get_violations_response = server.get_request(&violations_url, Some(&token)).await?;
+    assert_eq!(403, get_violations_response.status().as_u16());
+
+    Ok(())
+}
@@ -0,0 +1 @@
+mod email_domains;
@@ -0,0 +1,321 @@
+use reqwest::header;
+use reqwest::multipart::Form;
+use serde_json::Value;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+const REVIEW_COLUMNS: &str =
+    "td_request_id,software_name,software_version,developer_name,description,\
+     requester_name,requester_email,requester_department,reviewer_email,\
+     is_supported,is_current_version,is_reputation_good,is_installation_from_developer,\
+     is_local_admin_required,is_connected_to_brockport_cloud,\
+     is_connected_to_cloud_services_or_client,is_security_or_optimization_software,\
+     is_supported_by_current_os,review_notes,exported,reviewed_at";
+
+// Sends each `(part name, CSV)` pair as a multipart form to the import endpoint
+async fn import(
+    server: &TestServer,
+    token: &str,
+    query: &str,
+    files: &[(&'static str, String)],
+) -> Result<reqwest::Response> {
+    let import_url = format!("{}/api/v1/import{}", server.addr, query);
+
+    let form = files.iter().fold(Form::new(), |form, (name, csv)| {
+        form.text(*name, csv.clone())
+    });
+
+    Ok(server
+        .client
+        .post(&import_url)
+        .header(header::COOKIE, token)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|err| format!("failed to execute request: cause {err}"))?)
+}
+
+async fn count_rows(server: &TestServer, table: &str) -> Result<i64> {
+    let count: i64 = sqlx::query_scalar(&format!("SELECT count(*) FROM {}", table))
+        .fetch_one(&server.db_pool)
+        .await?;
+
+    Ok(count)
+}
+
+#[tokio::test]
+async fn import_dry_run_reports_row_errors_without_writing() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let software_csv = "software_name,software_version,developer_name,description\n\
+                        Slack,4.41.105,Slack Technologies,Team messaging\n\
+                        Zoom,latest,Zoom Video Communications,Video conferencing\n"
+        .to_string();
+    let requesters_csv = "name,email,department\n\
+                          John Doe,john@brockport.edu,IT\n\
+                          Jane Doe,not-an-email,\n"
+        .to_string();
+
+    let import_response = import(
+        &server,
+        &token,
+        "?dry_run=true",
+        &[("software", software_csv), ("requesters", requesters_csv)],
+    )
+    .await?;
+    assert_eq!(200, import_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!(true, body["dry_run"]);
+
+    assert_eq!(1, body["summary"]["software"]["created"]);
+    assert_eq!(1, body["summary"]["software"]["rejected"]);
+    assert_eq!("created", body["results"]["software"][0]["status"]);
+
+    // Rows are numbered by their line in the file, counting the header
+    let rejected_software = &body["results"]["software"][1];
+    assert_eq!(3, rejected_software["row"]);
+    assert_eq!("rejected", rejected_software["status"]);
+    assert_eq!("software_version", rejected_software["errors"][0]["field"]);
+
+    // Every error in the row is reported
+    let rejected_requester = &body["results"]["requesters"][1];
+    assert_eq!("rejected", rejected_requester["status"]);
+    let fields: Vec<&str> = rejected_requester["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(vec!["email", "department"], fields);
+
+    // Nothing is written for a dry run
+    assert_eq!(0, count_rows(&server, "software").await?);
+    assert_eq!(0, count_rows(&server, "requester").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_creates_and_matches_records() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let software_csv = "software_name,software_version,developer_name,description\n\
+                        Slack,4.41.105,Slack Technologies,Team messaging\n\
+                        Zoom,6.2.11,Zoom Video Communications,Video conferencing\n"
+        .to_string();
+    let requesters_csv = "name,email,department\n\
+                          John Doe,john@brockport.edu,IT\n"
+        .to_string();
+    let requests_csv = "td_request_id,software_name,requester_email\n\
+                        12345678,slack,JOHN@brockport.edu\n\
+                        23456789,Missing Software,john@brockport.edu\n"
+        .to_string();
+    let reviews_csv = format!(
+        "{}\n\
+         12345678,Slack,4.41.105,Slack Technologies,Team messaging,John Doe,john@brockport.edu,IT,,\
+         TRUE,TRUE,TRUE,TRUE,FALSE,FALSE,TRUE,FALSE,TRUE,Approved for campus use,true,2023-05-01\n\
+         34567890,Notepad++,8.7.1,Don Ho,Text editor,Jane Doe,jane@brockport.edu,Biology,{},\
+         TRUE,TRUE,TRUE,TRUE,FALSE,FALSE,FALSE,FALSE,TRUE,Legacy review,,\n\
+         45678901,Notepad++,8.7.1,Don Ho,Text editor,Jane Doe,jane@brockport.edu,Biology,,\
+         TRUE,NOPE,TRUE,TRUE,FALSE,FALSE,FALSE,FALSE,TRUE,Invalid option,,\n",
+        REVIEW_COLUMNS, server.test_users[0].email
+    );
+
+    let import_response = import(
+        &server,
+        &token,
+        "",
+        &[
+            ("reviews", reviews_csv.clone()),
+            ("requests", requests_csv),
+            ("requesters", requesters_csv),
+            ("software", software_csv.clone()),
+        ],
+    )
+    .await?;
+    assert_eq!(200, import_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!(false, body["dry_run"]);
+    assert_eq!(2, body["summary"]["software"]["created"]);
+    assert_eq!(1, body["summary"]["requesters"]["created"]);
+
+    // Software and requesters are matched by name and email, ignoring case
+    assert_eq!(1, body["summary"]["requests"]["created"]);
+    assert_eq!(1, body["summary"]["requests"]["rejected"]);
+    assert_eq!(
+        "software_name",
+        body["results"]["requests"][1]["errors"][0]["field"]
+    );
+
+    // Reviews link to the imported ticket, or create the software and requester
+    assert_eq!(2, body["summary"]["reviews"]["created"]);
+    assert_eq!(1, body["summary"]["reviews"]["rejected"]);
+    assert!(body["results"]["reviews"][2]["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("NOPE"));
+
+    assert_eq!(3, count_rows(&server, "software").await?);
+    assert_eq!(2, count_rows(&server, "requester").await?);
+    assert_eq!(2, count_rows(&server, "software_request").await?);
+    assert_eq!(2, count_rows(&server, "software_review").await?);
+
+    // The review date and export status are kept, and the reviewer defaults to the
+    // importing user
+    let (exported, created_at, reviewer_email): (bool, chrono::DateTime<chrono::Utc>, String) =
+        sqlx::query_as(
+            r#"
+            SELECT sr.exported, sr.created_at, u.email
+            FROM software_review sr
+            INNER JOIN software_request r ON sr.software_request_id = r.id
+            INNER JOIN user_account u ON sr.reviewer_id = u.id
+            WHERE r.td_request_id = '12345678'
+            "#,
+        )
+        .fetch_one(&server.db_pool)
+        .await?;
+    assert!(exported);
+    assert_eq!("2023-05-01", created_at.format("%Y-%m-%d").to_string());
+    assert_eq!(server.test_users[1].email, reviewer_email);
+
+    // Importing again matches the existing software and rejects reviews for tickets
+    // that have already been reviewed
+    let import_response = import(
+        &server,
+        &token,
+        "",
+        &[("software", software_csv), ("reviews", reviews_csv)],
+    )
+    .await?;
+    assert_eq!(200, import_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!(2, body["summary"]["software"]["matched"]);
+    assert_eq!(3, body["summary"]["reviews"]["rejected"]);
+    assert_eq!(
+        "td_request_id",
+        body["results"]["reviews"][0]["errors"][0]["field"]
+    );
+
+    assert_eq!(3, count_rows(&server, "software").await?);
+    assert_eq!(2, count_rows(&server, "software_review").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_of_already_requested_software_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let software_csv = "software_name,software_version,developer_name,description\n\
+                        Slack,4.41.105,Slack Technologies,Team messaging\n"
+        .to_string();
+    let requesters_csv = "name,email,department\n\
+                          John Doe,john@brockport.edu,IT\n"
+        .to_string();
+    let requests_csv = "td_request_id,software_name,requester_email\n\
+                        12345678,Slack,john@brockport.edu\n\
+                        23456789,Slack,john@brockport.edu\n"
+        .to_string();
+    let reviews_csv = format!(
+        "{}\n\
+         34567890,Slack,4.41.105,Slack Technologies,Team messaging,John Doe,john@brockport.edu,IT,,\
+         TRUE,TRUE,TRUE,TRUE,FALSE,FALSE,TRUE,FALSE,TRUE,Reviewed again,,\n",
+        REVIEW_COLUMNS
+    );
+
+    let import_response = import(
+        &server,
+        &token,
+        "",
+        &[
+            ("software", software_csv),
+            ("requesters", requesters_csv),
+            ("requests", requests_csv),
+            ("reviews", reviews_csv),
+        ],
+    )
+    .await?;
+    assert_eq!(200, import_response.status().as_u16());
+
+    // Software can only be requested once, on the first ticket
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!(1, body["summary"]["requests"]["created"]);
+    assert_eq!(1, body["summary"]["requests"]["rejected"]);
+    assert_eq!(1, body["summary"]["reviews"]["rejected"]);
+
+    for rejected in [
+        &body["results"]["requests"][1],
+        &body["results"]["reviews"][0],
+    ] {
+        assert_eq!("software_name", rejected["errors"][0]["field"]);
+        assert_eq!(
+            "software 'Slack' has already been requested on ticket '12345678'",
+            rejected["errors"][0]["message"]
+        );
+    }
+
+    assert_eq!(1, count_rows(&server, "software_request").await?);
+    assert_eq!(0, count_rows(&server, "software_review").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_with_missing_columns_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let software_csv = "software_name,software_version\nSlack,4.41.105\n".to_string();
+
+    let import_response = import(&server, &token, "", &[("software", software_csv)]).await?;
+    assert_eq!(400, import_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!("software", body["errors"][0]["field"]);
+    assert_eq!("missing_field", body["errors"][0]["code"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_with_unknown_file_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let import_response = import(
+        &server,
+        &token,
+        "",
+        &[("users", "name,email\nJohn,john@brockport.edu\n".to_string())],
+    )
+    .await?;
+    assert_eq!(400, import_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&import_response.text().await?)?;
+    assert_eq!("users", body["errors"][0]["field"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_as_reviewer_forbidden() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let software_csv = "software_name,software_version,developer_name,description\n\
+                        Slack,4.41.105,Slack Technologies,Team messaging\n"
+        .to_string();
+
+    let import_response = import(&server, &token, "", &[("software", software_csv)]).await?;
+    assert_eq!(403, import_response.status().as_u16());
+
+    assert_eq!(0, count_rows(&server, "software").await?);
+
+    Ok(())
+}
@@ -0,0 +1 @@
+mod csv_import;
@@ -1,8 +1,19 @@
 mod auth;
 mod common;
+mod error_response;
+mod event;
+mod export;
 mod health;
+mod identity;
+mod import;
+mod report;
 mod requester;
+mod review_comment;
+mod search;
 mod software;
 mod software_request;
 mod software_review;
+mod stats;
+mod ticketing;
 mod user;
+mod webhook;
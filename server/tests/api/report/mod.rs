@@ -0,0 +1 @@
+mod summary_report;
@@ -0,0 +1,98 @@
+use reqwest::header;
+use serde_json::json;
+
+use crate::common::{spawn_server, Result};
+
+#[tokio::test]
+async fn get_summary_report_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    server
+        .create_review(
+            &token,
+            "12345678",
+            json!({
+                "is_installation_from_developer": "NOT_SURE",
+                "is_local_admin_required": "TRUE",
+            }),
+        )
+        .await?;
+    server
+        .create_review(
+            &token,
+            "87654321",
+            json!({
+                "is_installation_from_developer": "NOT_SURE",
+                "is_local_admin_required": "FALSE",
+            }),
+        )
+        .await?;
+
+    let today = chrono::Utc::now().date_naive();
+
+    // Defaults to the last 30 days
+    for query in [
+        String::new(),
+        format!("?from={}&to={}", today, today),
+        // A period without any reviews
+        "?from=2020-01-01&to=2020-01-31".to_string(),
+    ] {
+        let summary_response = server
+            .get_request(
+                &format!("{}/api/v1/reports/summary{}", server.addr, query),
+                Some(&token),
+            )
+            .await?;
+        assert_eq!(200, summary_response.status().as_u16(), "query: {}", query);
+        assert_eq!(
+            "application/pdf",
+            summary_response.headers()[header::CONTENT_TYPE]
+        );
+
+        let pdf = summary_response.bytes().await?;
+        let document = lopdf::Document::load_mem(&pdf)?;
+        assert!(!document.get_pages().is_empty());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_summary_report_with_invalid_period_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    for query in [
+        "from=2024-12-31&to=2024-12-01",
+        "from=12/01/2024",
+        "to=2024-13-01",
+        "since=2024-12-01",
+        "to=%2B262142-12-31",
+        "to=-262143-01-01",
+        "from=1969-12-31&to=2024-12-01",
+        "from=2024-12-01&to=10000-01-01",
+    ] {
+        let summary_response = server
+            .get_request(
+                &format!("{}/api/v1/reports/summary?{}", server.addr, query),
+                Some(&token),
+            )
+            .await?;
+        assert_eq!(400, summary_response.status().as_u16(), "query: {}", query);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_summary_report_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    let summary_response = server
+        .get_request(&format!("{}/api/v1/reports/summary", server.addr), None)
+        .await?;
+    assert_eq!(401, summary_response.status().as_u16());
+
+    Ok(())
+}
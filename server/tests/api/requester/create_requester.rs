@@ -167,7 +167,7 @@ async fn create_requester_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "name": "//John$)",
+                "name": "John\u{0}Doe",
                 "email": "john@gmail.com",
                 "department": "IT",
             }),
@@ -185,7 +185,7 @@ async fn create_requester_with_invalid_fields_rejected() -> Result<()> {
             json!({
                 "name": "John",
                 "email": "test@gmail.com",
-                "department": "--$(IT\'/",
+                "department": "I\u{7}T",
             }),
             "malformed department",
         ),
@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::common::{spawn_server, Result};
 
@@ -133,7 +133,7 @@ async fn get_requester_using_missing_token_rejected() -> Result<()> {
 }
 
 #[tokio::test]
-async fn sql_injection_get_requesters_rejected() -> Result<()> {
+async fn sql_injection_get_requesters_treated_as_search_term() -> Result<()> {
     let server = spawn_server().await?;
     let login_url = format!("{}/api/v1/auth/login", server.addr);
 
@@ -157,6 +157,7 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
     let test_cases = vec![
         (
             format!("{}/api/v1/requesters?filter=department:IT' OR 1=1 --", server.addr),
+            200,
             "SQL injection attempt on role filter with OR 1=1",
         ),
         (
@@ -164,6 +165,7 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=department:IT' UNION SELECT NULL, NULL, NULL --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with UNION SELECT",
         ),
         (
@@ -171,6 +173,7 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=email:admin@example.com' OR 1=1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt on email filter with OR 1=1",
         ),
         (
@@ -178,6 +181,7 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=department:IT; DROP TABLE user_account;",
                 server.addr
             ),
+            200,
             "SQL injection attempt with DROP TABLE statement in role filter",
         ),
         (
@@ -185,17 +189,25 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=department:IT' OR EXISTS(SELECT * FROM user_account WHERE 1=1) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with nested EXISTS query",
         ),
         (
             format!("{}/api/v1/requesters?filter=department:IT' --", server.addr),
+            200,
             "SQL injection attempt with single-line comment in role filter",
         ),
+        (
+            format!("{}/api/v1/requesters?department:IT' --", server.addr),
+            400,
+            "SQL injection attempt outside of the filter parameter",
+        ),
         (
             format!(
                 "{}/api/v1/requesters?filter=email:admin@example.com' /* comment */",
                 server.addr
             ),
+            200,
             "SQL injection attempt with multi-line comment in email filter",
         ),
         (
@@ -203,10 +215,12 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=department:IT' OR pg_sleep(5) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with time delay function in role filter",
         ),
         (
             format!("{}/api/v1/requesters?filter=department:IT' AND 1=0 --", server.addr),
+            200,
             "SQL injection attempt with AND 1=0 to bypass filtering",
         ),
         (
@@ -214,14 +228,17 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=email:admin@example.com' AND 1=1",
                 server.addr
             ),
+            200,
             "SQL injection attempt with AND 1=1 on email filter",
         ),
         (
             format!("{}/api/v1/requesters?filter=department:IT' OR 'a'='a", server.addr),
+            200,
             "SQL injection attempt with OR 'a'='a' in role filter",
         ),
         (
             format!("{}/api/v1/requesters?filter=department:IT' LIMIT 1 --", server.addr),
+            200,
             "SQL injection attempt with LIMIT clause in role filter",
         ),
         (
@@ -229,20 +246,48 @@ async fn sql_injection_get_requesters_rejected() -> Result<()> {
                 "{}/api/v1/requesters?filter=email:admin@example.com' ORDER BY 1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with ORDER BY clause in email filter",
         ),
     ];
 
-    for (invaild_url, error_message) in test_cases {
-        let get_requester_response = server
-            .get_request(&invaild_url, Some(&token.unwrap()))
-            .await?;
-        (assert_eq!(
-            400,
+    // Filter values are always bound as query parameters, so injection attempts are
+    // treated as plain search terms and can never match more records than the value
+    // before the injected SQL. Only malformed query strings are rejected
+    for (url, expected_status, error_message) in test_cases {
+        let get_requester_response = server.get_request(&url, Some(token.unwrap())).await?;
+        assert_eq!(
+            expected_status,
             get_requester_response.status().as_u16(),
-            "API did not fail with a 400 status when the payload was {}",
+            "API did not respond with a {} status when the payload was {}",
+            expected_status,
             error_message,
-        ),);
+        );
+
+        if expected_status == 200 {
+            let base_url = url
+                .split(['\'', ';'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let base_response = server.get_request(&base_url, Some(token.unwrap())).await?;
+
+            let count_records = |body: &str| {
+                let body: Value = serde_json::from_str(body).unwrap();
+                body["requesters"]
+                    .as_array()
+                    .map_or(0, |records| records.len())
+            };
+
+            let base_count = count_records(&base_response.text().await.unwrap());
+            let injected_count = count_records(&get_requester_response.text().await.unwrap());
+
+            assert!(
+                injected_count <= base_count,
+                "API returned additional records when the payload was {}",
+                error_message,
+            );
+        }
     }
 
     Ok(())
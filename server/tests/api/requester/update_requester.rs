@@ -246,7 +246,7 @@ async fn update_requester_with_invalid_fields_rejected() -> Result<()> {
     let test_cases = vec![
         (
             json!({
-                "name": "John)asdd$",
+                "name": "John\u{0}asdd",
             }),
             "invalid name",
         ),
@@ -258,7 +258,7 @@ async fn update_requester_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "role": "I(--'T",
+                "department": "I\u{7}T",
             }),
             "invalid department",
         ),
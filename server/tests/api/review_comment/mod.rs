@@ -0,0 +1 @@
+mod review_comments;
@@ -0,0 +1,368 @@
+use std::time::Duration;
+
+use reqwest::header;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+async fn create_comment(
+    server: &TestServer,
+    token: &str,
+    review_id: Uuid,
+    body: &str,
+) -> Result<Uuid> {
+    let create_comment_response = server
+        .post_request(
+            &format!("{}/api/v1/reviews/{}/comments", server.addr, review_id),
+            Some(json!({ "body": body }).to_string()),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(201, create_comment_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&create_comment_response.text().await?)?;
+
+    Ok(body["id"].as_str().unwrap().parse()?)
+}
+
+async fn get_comments(server: &TestServer, token: &str, review_id: Uuid) -> Result<Vec<Value>> {
+    let get_comments_response = server
+        .get_request(
+            &format!("{}/api/v1/reviews/{}/comments", server.addr, review_id),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, get_comments_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_comments_response.text().await?)?;
+
+    Ok(body["comments"].as_array().unwrap().clone())
+}
+
+#[tokio::test]
+async fn comment_thread_crud_successful() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+    let review_id = server.create_review(&token, "49000001", json!({})).await?;
+
+    // Act
+    let first_id = create_comment(&server, &token, review_id, "Checked with the vendor.").await?;
+    let second_id = create_comment(&server, &token, review_id, "Needs a second look.").await?;
+
+    let comments_url = format!("{}/api/v1/reviews/{}/comments", server.addr, review_id);
+
+    let update_comment_response = server
+        .patch_request(
+            &format!("{}/{}", comments_url, first_id),
+            Some(json!({ "body": "Checked with the vendor, no concerns." }).to_string()),
+            Some(&token),
+        )
+        .await?;
+
+    let delete_comment_response = server
+        .delete_request(&format!("{}/{}", comments_url, second_id), Some(&token))
+        .await?;
+
+    // Assert
+    assert_eq!(204, update_comment_response.status().as_u16());
+    assert_eq!(204, delete_comment_response.status().as_u16());
+
+    let comments = get_comments(&server, &token, review_id).await?;
+    assert_eq!(1, comments.len());
+    assert_eq!(first_id.to_string(), comments[0]["id"]);
+    assert_eq!("Checked with the vendor, no concerns.", comments[0]["body"]);
+    assert_eq!(
+        server.test_users[0].id.to_string(),
+        comments[0]["author"]["id"]
+    );
+    assert!(comments[0]["edited_at"].is_string());
+
+    // Deleted comments are kept
+    let deleted: bool =
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM review_comment WHERE id = $1")
+            .bind(second_id)
+            .fetch_one(&server.db_pool)
+            .await?;
+    assert!(deleted);
+
+    // ... but can't be changed again
+    let delete_again_response = server
+        .delete_request(&format!("{}/{}", comments_url, second_id), Some(&token))
+        .await?;
+    assert_eq!(404, delete_again_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_comment_returns_400_or_404_for_invalid_requests() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+    let review_id = server.create_review(&token, "49000002", json!({})).await?;
+
+    let test_cases = [
+        (review_id, json!({ "body": "" }), 400),
+        (review_id, json!({ "body": " \n " }), 400),
+        (review_id, json!({ "body": "a".repeat(2001) }), 400),
+        (review_id, json!({ "text": "Looks fine." }), 400),
+        (Uuid::new_v4(), json!({ "body": "Looks fine." }), 404),
+    ];
+
+    for (review_id, body, status) in test_cases {
+        // Act
+        let create_comment_response = server
+            .post_request(
+                &format!("{}/api/v1/reviews/{}/comments", server.addr, review_id),
+                Some(body.to_string()),
+                Some(&token),
+            )
+            .await?;
+
+        // Assert
+        assert_eq!(
+            status,
+            create_comment_response.status().as_u16(),
+            "API did not fail with a {} status for {}",
+            status,
+            body
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_authors_edit_comments_and_admins_delete_them() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let reviewer_token = server.login(0).await?;
+    let admin_token = server.login(1).await?;
+
+    let review_id = server
+        .create_review(&reviewer_token, "49000003", json!({}))
+        .await?;
+    let reviewer_comment_id = create_comment(
+        &server,
+        &reviewer_token,
+        review_id,
+        "Checked with the vendor.",
+    )
+    .await?;
+    let admin_comment_id =
+        create_comment(&server, &admin_token, review_id, "Please add the license.").await?;
+
+    let comments_url = format!("{}/api/v1/reviews/{}/comments", server.addr, review_id);
+
+    // Act
+    let admin_update_response = server
+        .patch_request(
+            &format!("{}/{}", comments_url, reviewer_comment_id),
+            Some(json!({ "body": "Changed by an admin." }).to_string()),
+            Some(&admin_token),
+        )
+        .await?;
+
+    let reviewer_delete_response = server
+        .delete_request(
+            &format!("{}/{}", comments_url, admin_comment_id),
+            Some(&reviewer_token),
+        )
+        .await?;
+
+    let admin_delete_response = server
+        .delete_request(
+            &format!("{}/{}", comments_url, reviewer_comment_id),
+            Some(&admin_token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(403, admin_update_response.status().as_u16());
+    assert_eq!(403, reviewer_delete_response.status().as_u16());
+    assert_eq!(204, admin_delete_response.status().as_u16());
+
+    let comments = get_comments(&server, &reviewer_token, review_id).await?;
+    assert_eq!(1, comments.len());
+    assert_eq!(admin_comment_id.to_string(), comments[0]["id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_comment_returns_403_after_edit_window() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+    let review_id = server.create_review(&token, "49000004", json!({})).await?;
+    let comment_id = create_comment(&server, &token, review_id, "Checked with the vendor.").await?;
+
+    sqlx::query("UPDATE review_comment SET created_at = now() - interval '1 hour' WHERE id = $1")
+        .bind(comment_id)
+        .execute(&server.db_pool)
+        .await?;
+
+    // Act
+    let update_comment_response = server
+        .patch_request(
+            &format!(
+                "{}/api/v1/reviews/{}/comments/{}",
+                server.addr, review_id, comment_id
+            ),
+            Some(json!({ "body": "Too late to change this." }).to_string()),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(403, update_comment_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&update_comment_response.text().await?)?;
+    assert_eq!("urn:k6r:problem:edit-window-closed", body["type"]);
+
+    // The author can still delete it
+    let delete_comment_response = server
+        .delete_request(
+            &format!(
+                "{}/api/v1/reviews/{}/comments/{}",
+                server.addr, review_id, comment_id
+            ),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, delete_comment_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mentioned_users_are_notified() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let reviewer_token = server.login(0).await?;
+    let admin_token = server.login(1).await?;
+
+    let review_id = server
+        .create_review(&reviewer_token, "49000005", json!({}))
+        .await?;
+
+    let events_response = server
+        .get_request(
+            &format!("{}/api/v1/events", server.addr),
+            Some(&admin_token),
+        )
+        .await?;
+    assert_eq!(200, events_response.status().as_u16());
+
+    // Act
+    let comment_id = create_comment(
+        &server,
+        &reviewer_token,
+        review_id,
+        &format!(
+            "@{} can you confirm the license? cc @{}",
+            server.test_users[1].email.to_uppercase(),
+            server.test_users[0].email
+        ),
+    )
+    .await?;
+
+    // Assert
+    let mut events_response = events_response;
+    let mut received = String::new();
+
+    while !received.contains("event: review_comment.mentioned\n") {
+        let chunk = tokio::time::timeout(Duration::from_secs(10), events_response.chunk())
+            .await
+            .map_err(|_| format!("timed out waiting for the mention, got: {}", received))??
+            .ok_or("stream ended before the mention")?;
+
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    assert!(received.contains(&comment_id.to_string()));
+
+    let get_mentions_response = server
+        .get_request(
+            &format!("{}/api/v1/users/mentions", server.addr),
+            Some(&admin_token),
+        )
+        .await?;
+    assert_eq!(200, get_mentions_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_mentions_response.text().await?)?;
+    assert_eq!(1, body["mentions"].as_array().unwrap().len());
+    assert_eq!(comment_id.to_string(), body["mentions"][0]["comment"]["id"]);
+    assert_eq!("49000005", body["mentions"][0]["td_request_id"]);
+
+    // Authors aren't notified of mentioning themselves
+    let get_mentions_response = server
+        .get_request(
+            &format!("{}/api/v1/users/mentions", server.addr),
+            Some(&reviewer_token),
+        )
+        .await?;
+    let body: Value = serde_json::from_str(&get_mentions_response.text().await?)?;
+    assert!(body["mentions"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_includes_comments_when_requested() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+    let review_id = server.create_review(&token, "49000006", json!({})).await?;
+    create_comment(&server, &token, review_id, "Checked with the vendor").await?;
+
+    let export_url = format!(
+        "{}/api/v1/reviews/{}/export?format=markdown",
+        server.addr, review_id
+    );
+
+    // Act
+    let without_comments_response = server.get_request(&export_url, Some(&token)).await?;
+    let with_comments_response = server
+        .get_request(
+            &format!("{}&include_comments=true", export_url),
+            Some(&token),
+        )
+        .await?;
+    let pdf_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/reviews/{}/export?include_comments=true",
+                server.addr, review_id
+            ),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, without_comments_response.status().as_u16());
+    assert!(!without_comments_response
+        .text()
+        .await?
+        .contains("## Comments"));
+
+    assert_eq!(200, with_comments_response.status().as_u16());
+    let markdown = with_comments_response.text().await?;
+    assert!(markdown.contains("## Comments"));
+    assert!(markdown.contains("Checked with the vendor"));
+
+    assert_eq!(200, pdf_response.status().as_u16());
+    assert_eq!(
+        "application/pdf",
+        pdf_response.headers()[header::CONTENT_TYPE]
+    );
+
+    Ok(())
+}
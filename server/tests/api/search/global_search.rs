@@ -0,0 +1,235 @@
+use reqwest::header;
+use serde_json::{json, Value};
+
+use crate::common::{spawn_server, Result};
+
+#[tokio::test]
+async fn global_search_returns_ranked_typed_hits() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Slack",
+                "software_version": "4.41.105",
+                "developer_name": "Salesforce",
+                "description": "Team messaging application",
+            },
+            "requester": {
+                "name": "Jane Smith",
+                "email": "jsmith@brockport.edu",
+                "department": "Marketing",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "TRUE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "Approved for campus wide messaging"
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    let test_cases = vec![
+        ("slack", vec!["software", "software_review"]),
+        ("salesforce", vec!["software", "software_review"]),
+        ("jsmith", vec!["requester"]),
+        ("jsmith@brockport.edu", vec!["requester"]),
+        ("12345678", vec!["software_request"]),
+        ("campus messaging", vec!["software_review"]),
+    ];
+
+    for (search_term, expected_types) in test_cases {
+        let search_response = server
+            .get_request(
+                &format!("{}/api/v1/search?q={}", server.addr, search_term),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(200, search_response.status().as_u16());
+
+        let body: Value = serde_json::from_str(&search_response.text().await?)?;
+
+        let mut hit_types: Vec<&str> = body["search_results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|hit| hit["search_result"]["type"].as_str().unwrap())
+            .collect();
+        hit_types.sort();
+
+        assert_eq!(
+            expected_types, hit_types,
+            "unexpected search results for search term '{}'",
+            search_term
+        );
+    }
+
+    let search_response = server
+        .get_request(
+            &format!("{}/api/v1/search?q=slack", server.addr),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, search_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&search_response.text().await?)?;
+
+    let software_hit = body["search_results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|hit| hit["search_result"]["type"] == "software")
+        .unwrap();
+
+    assert_eq!("Slack", software_hit["search_result"]["title"]);
+    assert!(software_hit["search_result"]["highlight"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>Slack</mark>"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn global_search_with_no_matches_returns_empty_results() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+
+    // Uses 'Reviewer' test user credentials
+    let login_body = json!({
+        "email": server.test_users[0].email,
+        "password": server.test_users[0].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let search_response = server
+        .get_request(
+            &format!("{}/api/v1/search?q=zoom", server.addr),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, search_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&search_response.text().await?)?;
+
+    assert_eq!(json!({}), body["metadata"]);
+    assert_eq!(json!([]), body["search_results"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn global_search_with_invalid_query_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+
+    // Uses 'Reviewer' test user credentials
+    let login_body = json!({
+        "email": server.test_users[0].email,
+        "password": server.test_users[0].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let test_cases = vec![
+        (
+            format!("{}/api/v1/search", server.addr),
+            "missing search term",
+        ),
+        (
+            format!("{}/api/v1/search?q=%20%20", server.addr),
+            "whitespace only search term",
+        ),
+        (
+            format!("{}/api/v1/search?q={}", server.addr, "a".repeat(101)),
+            "search term too long",
+        ),
+        (
+            format!("{}/api/v1/search?q=slack&per_page=101", server.addr),
+            "invalid query param for per_page",
+        ),
+        (
+            format!("{}/api/v1/search?q=slack&sort=rank", server.addr),
+            "query param does not exist",
+        ),
+    ];
+
+    for (invalid_url, error_message) in test_cases {
+        let search_response = server
+            .get_request(&invalid_url, Some(token.unwrap()))
+            .await?;
+        assert_eq!(
+            400,
+            search_response.status().as_u16(),
+            "API did not fail with a 400 status when the query was {}",
+            error_message,
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn global_search_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    let search_response = server
+        .get_request(&format!("{}/api/v1/search?q=slack", server.addr), None)
+        .await?;
+    assert_eq!(401, search_response.status().as_u16());
+
+    Ok(())
+}
@@ -0,0 +1 @@
+mod global_search;
@@ -45,6 +45,48 @@ async fn create_software_successful() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn create_software_with_punctuation_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_url = format!("{}/api/v1/software", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_body = json!({
+        "software_name": "Adobe Acrobat (64-bit)",
+        "software_version": "1.0.0",
+        "developer_name": "Adobe/O'Reilly & Co.",
+        "description": "PDF reader that doesn't require \"admin\" rights; costs $0",
+    });
+
+    let create_software_response = server
+        .post_request(
+            &software_url,
+            Some(create_software_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(201, create_software_response.status().as_u16());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn create_software_with_existing_name_rejected() -> Result<()> {
     let server = spawn_server().await?;
@@ -169,7 +211,7 @@ async fn create_software_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "software_name": "Malformed Software!@#-",
+                "software_name": "Malformed\u{0}Software",
                 "software_version": "1.0.0",
                 "developer_name": "Test Developer",
                 "description": "A test software application",
@@ -190,7 +232,7 @@ async fn create_software_with_invalid_fields_rejected() -> Result<()> {
                 "software_name": "Test Software",
                 "software_version": "1.0.0",
                 "developer_name": "Test Developer",
-                "description": "Invalid characters in description: @$#",
+                "description": "Invalid characters in description: \u{1b}[31m",
             }),
             "malformed description",
         ),
@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::common::{spawn_server, Result};
 
@@ -130,7 +130,7 @@ async fn get_software_using_missing_token_rejected() -> Result<()> {
 }
 
 #[tokio::test]
-async fn sql_injection_get_software_rejected() -> Result<()> {
+async fn sql_injection_get_software_treated_as_search_term() -> Result<()> {
     let server = spawn_server().await?;
     let login_url = format!("{}/api/v1/auth/login", server.addr);
 
@@ -154,6 +154,7 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
     let test_cases = vec![
         (
             format!("{}/api/v1/software?filter=software_name:IT' OR 1=1 --", server.addr),
+            200,
             "SQL injection attempt on role filter with OR 1=1",
         ),
         (
@@ -161,6 +162,7 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=software_name:IT' UNION SELECT NULL, NULL, NULL --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with UNION SELECT",
         ),
         (
@@ -168,6 +170,7 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=developer_name:admin@example.com' OR 1=1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt on developer_name filter with OR 1=1",
         ),
         (
@@ -175,6 +178,7 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=software_name:IT; DROP TABLE user_account;",
                 server.addr
             ),
+            200,
             "SQL injection attempt with DROP TABLE statement in role filter",
         ),
         (
@@ -182,17 +186,25 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=software_name:IT' OR EXISTS(SELECT * FROM user_account WHERE 1=1) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with nested EXISTS query",
         ),
         (
             format!("{}/api/v1/software?filter=software_name:IT' --", server.addr),
+            200,
             "SQL injection attempt with single-line comment in role filter",
         ),
+        (
+            format!("{}/api/v1/software?software_name:IT' --", server.addr),
+            400,
+            "SQL injection attempt outside of the filter parameter",
+        ),
         (
             format!(
                 "{}/api/v1/software?filter=developer_name:admin@example.com' /* comment */",
                 server.addr
             ),
+            200,
             "SQL injection attempt with multi-line comment in developer_name filter",
         ),
         (
@@ -200,10 +212,12 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=software_name:IT' OR pg_sleep(5) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with time delay function in role filter",
         ),
         (
             format!("{}/api/v1/software?filter=software_name:IT' AND 1=0 --", server.addr),
+            200,
             "SQL injection attempt with AND 1=0 to bypass filtering",
         ),
         (
@@ -211,14 +225,17 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=developer_name:admin@example.com' AND 1=1",
                 server.addr
             ),
+            200,
             "SQL injection attempt with AND 1=1 on developer_name filter",
         ),
         (
             format!("{}/api/v1/software?filter=software_name:IT' OR 'a'='a", server.addr),
+            200,
             "SQL injection attempt with OR 'a'='a' in role filter",
         ),
         (
             format!("{}/api/v1/software?filter=software_name:IT' LIMIT 1 --", server.addr),
+            200,
             "SQL injection attempt with LIMIT clause in role filter",
         ),
         (
@@ -226,20 +243,136 @@ async fn sql_injection_get_software_rejected() -> Result<()> {
                 "{}/api/v1/software?filter=developer_name:admin@example.com' ORDER BY 1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with ORDER BY clause in developer_name filter",
         ),
     ];
 
-    for (invaild_url, error_message) in test_cases {
-        let get_software_response = server
-            .get_request(&invaild_url, Some(&token.unwrap()))
-            .await?;
-        (assert_eq!(
-            400,
+    // Filter values are always bound as query parameters, so injection attempts are
+    // treated as plain search terms and can never match more records than the value
+    // before the injected SQL. Only malformed query strings are rejected
+    for (url, expected_status, error_message) in test_cases {
+        let get_software_response = server.get_request(&url, Some(token.unwrap())).await?;
+        assert_eq!(
+            expected_status,
             get_software_response.status().as_u16(),
-            "API did not fail with a 400 status when the payload was {}",
+            "API did not respond with a {} status when the payload was {}",
+            expected_status,
             error_message,
-        ),);
+        );
+
+        if expected_status == 200 {
+            let base_url = url
+                .split(['\'', ';'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let base_response = server.get_request(&base_url, Some(token.unwrap())).await?;
+
+            let count_records = |body: &str| {
+                let body: Value = serde_json::from_str(body).unwrap();
+                body["software"]
+                    .as_array()
+                    .map_or(0, |records| records.len())
+            };
+
+            let base_count = count_records(&base_response.text().await.unwrap());
+            let injected_count = count_records(&get_software_response.text().await.unwrap());
+
+            assert!(
+                injected_count <= base_count,
+                "API returned additional records when the payload was {}",
+                error_message,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_all_software_sorted_by_version_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_url = format!("{}/api/v1/software", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let versions = [
+        "10.0.19045.3803",
+        "2024.1",
+        "3.2.1",
+        "v5",
+        "3.2.1-beta",
+        "9.1",
+    ];
+
+    for (i, version) in versions.iter().enumerate() {
+        let create_software_body = json!({
+            "software_name": format!("Versioned Software {}", i),
+            "software_version": version,
+            "developer_name": "Test Developer",
+            "description": "A test software application",
+        });
+
+        let create_software_response = server
+            .post_request(
+                &software_url,
+                Some(create_software_body.to_string()),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(201, create_software_response.status().as_u16());
+    }
+
+    let expected = [
+        "3.2.1-beta",
+        "3.2.1",
+        "v5",
+        "9.1",
+        "10.0.19045.3803",
+        "2024.1",
+    ];
+
+    for (sort, expected) in [
+        ("software_version", expected.to_vec()),
+        (
+            "-software_version",
+            expected.iter().rev().copied().collect(),
+        ),
+    ] {
+        let get_software_response = server
+            .get_request(
+                &format!("{}?sort={}&per_page=100", software_url, sort),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(200, get_software_response.status().as_u16());
+
+        let body: Value = serde_json::from_str(&get_software_response.text().await?)?;
+        let sorted_versions: Vec<&str> = body["software"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|software| software["software"]["software_version"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(expected, sorted_versions, "sort was {}", sort);
     }
 
     Ok(())
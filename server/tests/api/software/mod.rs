@@ -1,4 +1,5 @@
 mod create_software;
 mod delete_software;
 mod get_software;
+mod similar_software;
 mod update_software;
@@ -0,0 +1,180 @@
+use reqwest::header;
+use serde_json::{json, Value};
+
+use crate::common::{spawn_server, Result};
+
+#[tokio::test]
+async fn get_similar_software_returns_likely_matches() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_url = format!("{}/api/v1/software", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let software = vec![
+        ("Zoom Client", "Zoom Video Communications"),
+        ("Slack", "Salesforce"),
+        ("Microsoft Teams", "Microsoft"),
+    ];
+
+    for (software_name, developer_name) in software {
+        let create_software_body = json!({
+            "software_name": software_name,
+            "software_version": "1.0.0",
+            "developer_name": developer_name,
+            "description": "A test software application",
+        });
+
+        let create_software_response = server
+            .post_request(
+                &software_url,
+                Some(create_software_body.to_string()),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(201, create_software_response.status().as_u16());
+    }
+
+    let test_cases = vec![
+        ("Zoom", vec!["Zoom Client"]),
+        ("Zoom%20Clent", vec!["Zoom Client"]),
+        ("zoom%20client", vec!["Zoom Client"]),
+        ("Teams", vec!["Microsoft Teams"]),
+        ("Photoshop", vec![]),
+    ];
+
+    for (name, expected_names) in test_cases {
+        let similar_software_response = server
+            .get_request(
+                &format!("{}/similar?name={}", software_url, name),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(200, similar_software_response.status().as_u16());
+
+        let body: Value = serde_json::from_str(&similar_software_response.text().await?)?;
+
+        let software_names: Vec<&str> = body["similar_software"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|similar| similar["software"]["software_name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(
+            expected_names, software_names,
+            "unexpected similar software for name '{}'",
+            name
+        );
+    }
+
+    let similar_software_response = server
+        .get_request(
+            &format!("{}/similar?name=Zoom", software_url),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, similar_software_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&similar_software_response.text().await?)?;
+    let similar = &body["similar_software"][0];
+
+    assert_eq!(
+        "Zoom Video Communications",
+        similar["software"]["developer_name"]
+    );
+    assert_eq!("1.0.0", similar["software"]["software_version"]);
+    assert!(similar["similarity"].as_f64().unwrap() > 0.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_similar_software_with_invalid_query_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+
+    // Uses 'Reviewer' test user credentials
+    let login_body = json!({
+        "email": server.test_users[0].email,
+        "password": server.test_users[0].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let test_cases = vec![
+        (
+            format!("{}/api/v1/software/similar", server.addr),
+            "missing name",
+        ),
+        (
+            format!("{}/api/v1/software/similar?name=%20", server.addr),
+            "whitespace only name",
+        ),
+        (
+            format!("{}/api/v1/software/similar?name=Zoom&limit=0", server.addr),
+            "invalid query param for limit",
+        ),
+        (
+            format!("{}/api/v1/software/similar?name=Zoom&limit=21", server.addr),
+            "invalid query param for limit",
+        ),
+        (
+            format!("{}/api/v1/software/similar?name=Zoom&page=2", server.addr),
+            "query param does not exist",
+        ),
+    ];
+
+    for (invalid_url, error_message) in test_cases {
+        let similar_software_response = server
+            .get_request(&invalid_url, Some(token.unwrap()))
+            .await?;
+        assert_eq!(
+            400,
+            similar_software_response.status().as_u16(),
+            "API did not fail with a 400 status when the query was {}",
+            error_message,
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_similar_software_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    let similar_software_response = server
+        .get_request(
+            &format!("{}/api/v1/software/similar?name=Zoom", server.addr),
+            None,
+        )
+        .await?;
+    assert_eq!(401, similar_software_response.status().as_u16());
+
+    Ok(())
+}
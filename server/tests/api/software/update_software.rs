@@ -279,7 +279,7 @@ async fn update_software_with_invalid_fields_rejected() -> Result<()> {
     let test_cases = vec![
         (
             json!({
-                "software_name": "Invalid!@#Name-",
+                "software_name": "Invalid\u{0}Name",
             }),
             "invalid software name",
         ),
@@ -291,13 +291,13 @@ async fn update_software_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "developer_name": "John@Dev$-",
+                "developer_name": "John\u{0}Dev",
             }),
             "invalid developer name",
         ),
         (
             json!({
-                "description": "Invalid description@@!-",
+                "description": "Invalid\u{0}description",
             }),
             "invalid description",
         ),
@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::common::{spawn_server, Result};
 
@@ -122,7 +122,7 @@ async fn get_software_requests_using_missing_token_rejected() -> Result<()> {
 }
 
 #[tokio::test]
-async fn sql_injection_get_software_requests_rejected() -> Result<()> {
+async fn sql_injection_get_software_requests_treated_as_search_term() -> Result<()> {
     let server = spawn_server().await?;
     let login_url = format!("{}/api/v1/auth/login", server.addr);
 
@@ -146,6 +146,7 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
     let test_cases = vec![
         (
             format!("{}/api/v1/requests?filter=td_request_id:36472091' OR 1=1 --", server.addr),
+            200,
             "SQL injection attempt on role filter with OR 1=1",
         ),
         (
@@ -153,6 +154,7 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' UNION SELECT NULL, NULL, NULL --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with UNION SELECT",
         ),
         (
@@ -160,6 +162,7 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' OR 1=1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt on developer_name filter with OR 1=1",
         ),
         (
@@ -167,6 +170,7 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091; DROP TABLE user_account;",
                 server.addr
             ),
+            200,
             "SQL injection attempt with DROP TABLE statement in role filter",
         ),
         (
@@ -174,10 +178,12 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' OR EXISTS(SELECT * FROM user_account WHERE 1=1) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with nested EXISTS query",
         ),
         (
             format!("{}/api/v1/requests?td_request_id:36472091' --", server.addr),
+            400,
             "SQL injection attempt with single-line comment in role filter",
         ),
         (
@@ -185,6 +191,7 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' /* comment */",
                 server.addr
             ),
+            200,
             "SQL injection attempt with multi-line comment in developer_name filter",
         ),
         (
@@ -192,10 +199,12 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' OR pg_sleep(5) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with time delay function in role filter",
         ),
         (
             format!("{}/api/v1/requests?filter=td_request_id:36472091' AND 1=0 --", server.addr),
+            200,
             "SQL injection attempt with AND 1=0 to bypass filtering",
         ),
         (
@@ -203,14 +212,17 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' AND 1=1",
                 server.addr
             ),
+            200,
             "SQL injection attempt with AND 1=1 on developer_name filter",
         ),
         (
             format!("{}/api/v1/requests?filter=td_request_id:36472091' OR 'a'='a", server.addr),
+            200,
             "SQL injection attempt with OR 'a'='a' in role filter",
         ),
         (
             format!("{}/api/v1/requests?filter=td_request_id:36472091' LIMIT 1 --", server.addr),
+            200,
             "SQL injection attempt with LIMIT clause in role filter",
         ),
         (
@@ -218,20 +230,49 @@ async fn sql_injection_get_software_requests_rejected() -> Result<()> {
                 "{}/api/v1/requests?filter=td_request_id:36472091' ORDER BY 1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with ORDER BY clause in developer_name filter",
         ),
     ];
 
-    for (invaild_url, error_message) in test_cases {
-        let get_software_requests_response = server
-            .get_request(&invaild_url, Some(&token.unwrap()))
-            .await?;
-        (assert_eq!(
-            400,
+    // Filter values are always bound as query parameters, so injection attempts are
+    // treated as plain search terms and can never match more records than the value
+    // before the injected SQL. Only malformed query strings are rejected
+    for (url, expected_status, error_message) in test_cases {
+        let get_software_requests_response = server.get_request(&url, Some(token.unwrap())).await?;
+        assert_eq!(
+            expected_status,
             get_software_requests_response.status().as_u16(),
-            "API did not fail with a 400 status when the payload was {}",
+            "API did not respond with a {} status when the payload was {}",
+            expected_status,
             error_message,
-        ),);
+        );
+
+        if expected_status == 200 {
+            let base_url = url
+                .split(['\'', ';'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let base_response = server.get_request(&base_url, Some(token.unwrap())).await?;
+
+            let count_records = |body: &str| {
+                let body: Value = serde_json::from_str(body).unwrap();
+                body["software_requests"]
+                    .as_array()
+                    .map_or(0, |records| records.len())
+            };
+
+            let base_count = count_records(&base_response.text().await.unwrap());
+            let injected_count =
+                count_records(&get_software_requests_response.text().await.unwrap());
+
+            assert!(
+                injected_count <= base_count,
+                "API returned additional records when the payload was {}",
+                error_message,
+            );
+        }
     }
 
     Ok(())
@@ -1,4 +1,5 @@
 mod create_software_request;
 mod delete_software_request;
 mod get_software_request;
+mod software_request_queue;
 mod update_software_request;
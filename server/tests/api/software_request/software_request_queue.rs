@@ -0,0 +1,238 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+// Creates a software request along with a review, and returns the request's id
+async fn create_reviewed_software_request(
+    server: &TestServer,
+    token: &str,
+    td_request_id: &str,
+) -> Result<Uuid> {
+    server
+        .create_review(token, td_request_id, json!({}))
+        .await?;
+
+    let request_id = sqlx::query_scalar("SELECT id FROM software_request WHERE td_request_id = $1")
+        .bind(td_request_id)
+        .fetch_one(&server.db_pool)
+        .await?;
+
+    Ok(request_id)
+}
+
+// Creates a software request created `age_days` ago, without a review
+async fn create_unreviewed_software_request(
+    server: &TestServer,
+    token: &str,
+    td_request_id: &str,
+    age_days: i32,
+) -> Result<Uuid> {
+    let request_id = create_reviewed_software_request(server, token, td_request_id).await?;
+
+    sqlx::query("DELETE FROM software_review WHERE software_request_id = $1")
+        .bind(request_id)
+        .execute(&server.db_pool)
+        .await?;
+
+    sqlx::query(
+        "UPDATE software_request SET created_at = now() - make_interval(days => $1) \
+         WHERE id = $2",
+    )
+    .bind(age_days)
+    .bind(request_id)
+    .execute(&server.db_pool)
+    .await?;
+
+    Ok(request_id)
+}
+
+async fn get_queue(server: &TestServer, token: &str, path: &str) -> Result<Vec<Value>> {
+    let get_queue_response = server
+        .get_request(
+            &format!("{}/api/v1/requests/{}", server.addr, path),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, get_queue_response.status().as_u16(), "path: {}", path);
+
+    let body: Value = serde_json::from_str(&get_queue_response.text().await?)?;
+
+    Ok(body["software_requests"].as_array().unwrap().clone())
+}
+
+async fn assign(
+    server: &TestServer,
+    token: &str,
+    request_id: Uuid,
+    assignee_id: Option<Uuid>,
+) -> Result<u16> {
+    let assign_response = server
+        .patch_request(
+            &format!("{}/api/v1/requests/{}/assignee", server.addr, request_id),
+            Some(json!({ "assignee_id": assignee_id }).to_string()),
+            Some(token),
+        )
+        .await?;
+
+    Ok(assign_response.status().as_u16())
+}
+
+#[tokio::test]
+async fn get_software_request_queue_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    create_unreviewed_software_request(&server, &token, "12345678", 0).await?;
+    create_unreviewed_software_request(&server, &token, "87654321", 3).await?;
+    create_reviewed_software_request(&server, &token, "11223344").await?;
+
+    // Oldest first by default
+    let queue = get_queue(&server, &token, "queue").await?;
+    assert_eq!(2, queue.len());
+    assert_eq!("87654321", queue[0]["software_request"]["td_request_id"]);
+    assert_eq!(3, queue[0]["age_days"]);
+    assert_eq!("12345678", queue[1]["software_request"]["td_request_id"]);
+    assert_eq!(0, queue[1]["age_days"]);
+    assert!(queue[0]["assignee"].is_null());
+
+    let queue = get_queue(&server, &token, "queue?sort=age").await?;
+    assert_eq!("12345678", queue[0]["software_request"]["td_request_id"]);
+
+    let queue = get_queue(&server, &token, "queue?sort=-age").await?;
+    assert_eq!("87654321", queue[0]["software_request"]["td_request_id"]);
+
+    let queue = get_queue(&server, &token, "queue?filter=td_request_id:87654321").await?;
+    assert_eq!(1, queue.len());
+
+    let queue = get_queue(&server, &token, "queue?page=2&per_page=1").await?;
+    assert_eq!(1, queue.len());
+    assert_eq!("12345678", queue[0]["software_request"]["td_request_id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn assign_software_request_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let request_id = create_unreviewed_software_request(&server, &token, "12345678", 1).await?;
+    create_unreviewed_software_request(&server, &token, "87654321", 0).await?;
+
+    assert!(get_queue(&server, &token, "queue/mine").await?.is_empty());
+
+    assert_eq!(
+        204,
+        assign(&server, &token, request_id, Some(server.test_users[0].id)).await?
+    );
+
+    let queue = get_queue(&server, &token, "queue/mine").await?;
+    assert_eq!(1, queue.len());
+    assert_eq!("12345678", queue[0]["software_request"]["td_request_id"]);
+    assert_eq!(
+        json!({
+            "id": server.test_users[0].id,
+            "name": server.test_users[0].name,
+            "email": server.test_users[0].email,
+        }),
+        queue[0]["assignee"]
+    );
+
+    let queue = get_queue(
+        &server,
+        &token,
+        &format!("queue?filter=assignee_email:{}", server.test_users[0].email),
+    )
+    .await?;
+    assert_eq!(1, queue.len());
+
+    // Reassigned to another user, so it leaves this user's queue
+    assert_eq!(
+        204,
+        assign(&server, &token, request_id, Some(server.test_users[1].id)).await?
+    );
+    assert!(get_queue(&server, &token, "queue/mine").await?.is_empty());
+
+    assert_eq!(204, assign(&server, &token, request_id, None).await?);
+
+    let queue = get_queue(&server, &token, "queue").await?;
+    assert!(queue.iter().all(|request| request["assignee"].is_null()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn assign_software_request_with_unknown_id_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let request_id = create_unreviewed_software_request(&server, &token, "12345678", 0).await?;
+
+    assert_eq!(
+        404,
+        assign(
+            &server,
+            &token,
+            Uuid::new_v4(),
+            Some(server.test_users[0].id)
+        )
+        .await?
+    );
+    assert_eq!(
+        409,
+        assign(&server, &token, request_id, Some(Uuid::new_v4())).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_software_request_queue_with_invalid_query_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    for path in [
+        "queue?sort=td_request_id",
+        "queue?filter=developer_name:Test",
+        "queue/mine?per_page=0",
+        "queue?status=unreviewed",
+    ] {
+        let get_queue_response = server
+            .get_request(
+                &format!("{}/api/v1/requests/{}", server.addr, path),
+                Some(&token),
+            )
+            .await?;
+        assert_eq!(400, get_queue_response.status().as_u16(), "path: {}", path);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn software_request_queue_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    for path in ["queue", "queue/mine"] {
+        let get_queue_response = server
+            .get_request(&format!("{}/api/v1/requests/{}", server.addr, path), None)
+            .await?;
+        assert_eq!(401, get_queue_response.status().as_u16(), "path: {}", path);
+    }
+
+    let assign_response = server
+        .patch_request(
+            &format!(
+                "{}/api/v1/requests/{}/assignee",
+                server.addr,
+                Uuid::new_v4()
+            ),
+            Some(json!({ "assignee_id": null }).to_string()),
+            None,
+        )
+        .await?;
+    assert_eq!(401, assign_response.status().as_u16());
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde_json::json;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::common::{spawn_server, Result};
@@ -563,3 +563,161 @@ async fn create_software_with_missing_fields_rejected() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn create_software_review_with_invalid_fields_reports_every_field() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "1234",
+            "software": {
+                "software_name": "",
+                "software_version": "1.0.0",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "email": "not-an-email",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(400, create_software_review_response.status().as_u16());
+
+    let body: Value =
+        serde_json::from_str(&create_software_review_response.text().await.unwrap()).unwrap();
+
+    let errors: Vec<(&str, &str)> = body["errors"]
+        .as_array()
+        .expect("errors should be present")
+        .iter()
+        .map(|error| {
+            (
+                error["field"].as_str().unwrap_or_default(),
+                error["code"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        vec![
+            ("software_request.td_request_id", "invalid_value"),
+            ("software_request.software.software_name", "invalid_value"),
+            ("software_request.requester.email", "invalid_value"),
+        ],
+        errors
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_missing_nested_field_reports_path() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Test Software",
+                "software_version": "1.0.0",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(400, create_software_review_response.status().as_u16());
+
+    let body: Value =
+        serde_json::from_str(&create_software_review_response.text().await.unwrap()).unwrap();
+
+    assert_eq!(
+        "software_request.requester.email",
+        body["errors"][0]["field"]
+    );
+    assert_eq!("missing_field", body["errors"][0]["code"]);
+
+    Ok(())
+}
@@ -0,0 +1,738 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+// Returns the id of the first record in a list response, e.g. `software[0].software.id`
+async fn fetch_first_id(
+    server: &TestServer,
+    url: &str,
+    list_key: &str,
+    item_key: &str,
+    token: &str,
+) -> Result<String> {
+    let response = server.get_request(&url.to_string(), Some(token)).await?;
+    assert_eq!(200, response.status().as_u16());
+
+    let parsed: Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    Ok(parsed[list_key][0][item_key]["id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string())
+}
+
+fn review_body(software_request: Value, server: &TestServer) -> Value {
+    let mut body = json!({
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    if let (Some(body), Some(software_request)) =
+        (body.as_object_mut(), software_request.as_object())
+    {
+        body.extend(software_request.clone());
+    }
+
+    body
+}
+
+fn new_software(name: &str) -> Value {
+    json!({
+        "software_name": name,
+        "software_version": "1.0.0",
+        "developer_name": "Test Developer",
+        "description": "A test software application",
+    })
+}
+
+fn new_requester(email: &str) -> Value {
+    json!({
+        "name": "John",
+        "email": email,
+        "department": "IT",
+    })
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_software_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_software_response = server
+        .post_request(
+            &software_url,
+            Some(new_software("Test Software").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_response.status().as_u16());
+
+    let software_id =
+        fetch_first_id(&server, &software_url, "software", "software", &token).await?;
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software_id": software_id,
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    // The existing software should have been linked rather than duplicated
+    let get_software_response = server.get_request(&software_url, Some(&token)).await?;
+    let parsed: Value = serde_json::from_str(&get_software_response.text().await.unwrap()).unwrap();
+    assert_eq!(1, parsed["software"].as_array().unwrap().len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_requester_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_requester_response = server
+        .post_request(
+            &requester_url,
+            Some(new_requester("john@gmail.com").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_requester_response.status().as_u16());
+
+    let requester_id =
+        fetch_first_id(&server, &requester_url, "requesters", "requester", &token).await?;
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester_id": requester_id,
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_software_and_requester_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_software_response = server
+        .post_request(
+            &software_url,
+            Some(new_software("Test Software").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_response.status().as_u16());
+
+    let create_requester_response = server
+        .post_request(
+            &requester_url,
+            Some(new_requester("john@gmail.com").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_requester_response.status().as_u16());
+
+    let software_id =
+        fetch_first_id(&server, &software_url, "software", "software", &token).await?;
+    let requester_id =
+        fetch_first_id(&server, &requester_url, "requesters", "requester", &token).await?;
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software_id": software_id,
+                "requester_id": requester_id,
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_reviews_for_same_requester_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let first_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let first_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(first_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, first_software_review_response.status().as_u16());
+
+    // The requester created with the first review is reused for the second
+    let requester_id =
+        fetch_first_id(&server, &requester_url, "requesters", "requester", &token).await?;
+
+    let second_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "87654321",
+                "software": new_software("Other Software"),
+                "requester_id": requester_id,
+            },
+        }),
+        &server,
+    );
+
+    let second_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(second_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, second_software_review_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_software_request_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_request_url = format!("{}/api/v1/requests", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    // Deleting the review leaves the software request in place so it can be reviewed again
+    let software_review_id = fetch_first_id(
+        &server,
+        &software_review_url,
+        "software_reviews",
+        "software_review",
+        &token,
+    )
+    .await?;
+
+    let delete_software_review_response = server
+        .delete_request(
+            &format!("{}/{}", software_review_url, software_review_id),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, delete_software_review_response.status().as_u16());
+
+    let software_request_id = fetch_first_id(
+        &server,
+        &software_request_url,
+        "software_requests",
+        "software_request",
+        &token,
+    )
+    .await?;
+
+    let rereview_body = review_body(
+        json!({ "software_request_id": software_request_id }),
+        &server,
+    );
+
+    let rereview_response = server
+        .post_request(
+            &software_review_url,
+            Some(rereview_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, rereview_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_nonexistent_ids_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let test_cases = [
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software_id": Uuid::new_v4(),
+                    "requester": new_requester("john@gmail.com"),
+                },
+            }),
+            "nonexistent software_id",
+        ),
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software": new_software("Test Software"),
+                    "requester_id": Uuid::new_v4(),
+                },
+            }),
+            "nonexistent requester_id",
+        ),
+        (
+            json!({ "software_request_id": Uuid::new_v4() }),
+            "nonexistent software_request_id",
+        ),
+    ];
+
+    for (software_request, error_message) in test_cases {
+        let response = server
+            .post_request(
+                &software_review_url,
+                Some(review_body(software_request, &server).to_string()),
+                Some(&token),
+            )
+            .await?;
+
+        assert_eq!(
+            409,
+            response.status().as_u16(),
+            "API did not fail with 409 Conflict when the payload had {}.",
+            error_message
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_conflicting_references_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let test_cases = [
+        (
+            json!({}),
+            "neither software_request_id nor software_request",
+        ),
+        (
+            json!({
+                "software_request_id": Uuid::new_v4(),
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software": new_software("Test Software"),
+                    "requester": new_requester("john@gmail.com"),
+                },
+            }),
+            "both software_request_id and software_request",
+        ),
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software_id": Uuid::new_v4(),
+                    "software": new_software("Test Software"),
+                    "requester": new_requester("john@gmail.com"),
+                },
+            }),
+            "both software_id and software",
+        ),
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "requester": new_requester("john@gmail.com"),
+                },
+            }),
+            "neither software_id nor software",
+        ),
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software": new_software("Test Software"),
+                    "requester_id": Uuid::new_v4(),
+                    "requester": new_requester("john@gmail.com"),
+                },
+            }),
+            "both requester_id and requester",
+        ),
+        (
+            json!({
+                "software_request": {
+                    "td_request_id": "12345678",
+                    "software": new_software("Test Software"),
+                },
+            }),
+            "neither requester_id nor requester",
+        ),
+    ];
+
+    for (software_request, error_message) in test_cases {
+        let response = server
+            .post_request(
+                &software_review_url,
+                Some(review_body(software_request, &server).to_string()),
+                Some(&token),
+            )
+            .await?;
+
+        assert_eq!(
+            400,
+            response.status().as_u16(),
+            "API did not fail with 400 Bad Request when the payload had {}.",
+            error_message
+        );
+    }
+
+    Ok(())
+}
+
+// Returns the number of records in a list response
+async fn count_records(
+    server: &TestServer,
+    url: &str,
+    list_key: &str,
+    token: &str,
+) -> Result<usize> {
+    let response = server.get_request(&url.to_string(), Some(token)).await?;
+    assert_eq!(200, response.status().as_u16());
+
+    let parsed: Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+
+    Ok(parsed[list_key]
+        .as_array()
+        .map_or(0, |records| records.len()))
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_requester_email_rolled_back() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_requester_response = server
+        .post_request(
+            &requester_url,
+            Some(new_requester("john@gmail.com").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_requester_response.status().as_u16());
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(409, create_software_review_response.status().as_u16());
+
+    let error = create_software_review_response.text().await.unwrap();
+    assert!(
+        error.contains("requester email"),
+        "unexpected error: {}",
+        error
+    );
+
+    // The software inserted before the conflict should have been rolled back
+    assert_eq!(
+        0,
+        count_records(&server, &software_url, "software", &token).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_software_name_rolled_back() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let create_software_response = server
+        .post_request(
+            &software_url,
+            Some(new_software("Test Software").to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, create_software_response.status().as_u16());
+
+    let create_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(409, create_software_review_response.status().as_u16());
+
+    let error = create_software_review_response.text().await.unwrap();
+    assert!(
+        error.contains("software name"),
+        "unexpected error: {}",
+        error
+    );
+
+    assert_eq!(
+        0,
+        count_records(&server, &requester_url, "requesters", &token).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_existing_ticket_number_rolled_back() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let first_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let first_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(first_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, first_software_review_response.status().as_u16());
+
+    let second_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Other Software"),
+                "requester": new_requester("jane@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let second_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(second_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(409, second_software_review_response.status().as_u16());
+
+    let error = second_software_review_response.text().await.unwrap();
+    assert!(
+        error.contains("ticket number"),
+        "unexpected error: {}",
+        error
+    );
+
+    // Only the records from the first review should remain
+    assert_eq!(
+        1,
+        count_records(&server, &software_url, "software", &token).await?
+    );
+    assert_eq!(
+        1,
+        count_records(&server, &requester_url, "requesters", &token).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_software_review_with_already_requested_software_rolled_back() -> Result<()> {
+    let server = spawn_server().await?;
+    let software_url = format!("{}/api/v1/software", server.addr);
+    let requester_url = format!("{}/api/v1/requesters", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    let token = server.login(1).await?;
+
+    let first_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "12345678",
+                "software": new_software("Test Software"),
+                "requester": new_requester("john@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let first_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(first_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(201, first_software_review_response.status().as_u16());
+
+    let software_id =
+        fetch_first_id(&server, &software_url, "software", "software", &token).await?;
+
+    // The software already has a request, so it can't be linked to another ticket
+    let second_software_review_body = review_body(
+        json!({
+            "software_request": {
+                "td_request_id": "87654321",
+                "software_id": software_id,
+                "requester": new_requester("jane@gmail.com"),
+            },
+        }),
+        &server,
+    );
+
+    let second_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(second_software_review_body.to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(409, second_software_review_response.status().as_u16());
+
+    let error = second_software_review_response.text().await.unwrap();
+    assert!(
+        error.contains("A record with the specified software already exists"),
+        "unexpected error: {}",
+        error
+    );
+
+    // The requester inserted before the conflict should have been rolled back
+    assert_eq!(
+        1,
+        count_records(&server, &requester_url, "requesters", &token).await?
+    );
+
+    Ok(())
+}
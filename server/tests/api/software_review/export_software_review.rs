@@ -142,3 +142,184 @@ async fn export_software_with_invalid_id_rejected() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn export_software_review_in_other_formats_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Test Software",
+                "software_version": "1.0.0",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "email": "john@gmail.com",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "Uses \\<script\\> tags & *markdown*."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    let get_software_review_response = server
+        .get_request(
+            &format!("{}?filter=td_request_id:12345678", software_review_url),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, get_software_review_response.status().as_u16());
+
+    let parsed_software_review: Value =
+        serde_json::from_str(&get_software_review_response.text().await?)?;
+
+    let software_review_id = parsed_software_review["software_reviews"][0]["software_review"]["id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let export_url = format!("{}/{}/export", software_review_url, software_review_id);
+
+    let test_cases = [
+        ("pdf", "application/pdf", "Test Software.pdf"),
+        ("html", "text/html; charset=utf-8", "Test Software.html"),
+        (
+            "markdown",
+            "text/markdown; charset=utf-8",
+            "Test Software.md",
+        ),
+        ("md", "text/markdown; charset=utf-8", "Test Software.md"),
+        (
+            "docx",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "Test Software.docx",
+        ),
+    ];
+
+    for (format, content_type, filename) in test_cases {
+        let export_software_review_response = server
+            .get_request(
+                &format!("{}?format={}", export_url, format),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(
+            200,
+            export_software_review_response.status().as_u16(),
+            "format: {}",
+            format
+        );
+
+        let headers = export_software_review_response.headers();
+        assert_eq!(content_type, headers[header::CONTENT_TYPE]);
+        assert!(headers[header::CONTENT_DISPOSITION]
+            .to_str()?
+            .contains(&format!("filename=\"{}\"", filename)));
+
+        let body = export_software_review_response.bytes().await?;
+
+        match format {
+            "html" => {
+                let html = String::from_utf8(body.to_vec())?;
+                assert!(html.contains("Uses &lt;script&gt; tags &amp; <em>markdown</em>."));
+                assert!(html.contains("✓</span>"));
+            }
+            "markdown" | "md" => {
+                let markdown = String::from_utf8(body.to_vec())?;
+                assert!(markdown.contains("Uses \\<script\\> tags \\& *markdown*."));
+                assert!(markdown.contains("- ✖ "));
+            }
+            // A DOCX document is a ZIP archive
+            "docx" => assert!(body.starts_with(b"PK")),
+            _ => assert!(body.starts_with(b"%PDF")),
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_review_with_invalid_format_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    for query in ["format=odt", "format=csv", "output=html"] {
+        let export_software_review_response = server
+            .get_request(
+                &format!(
+                    "{}/api/v1/reviews/{}/export?{}",
+                    server.addr,
+                    Uuid::new_v4(),
+                    query
+                ),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(
+            400,
+            export_software_review_response.status().as_u16(),
+            "query: {}",
+            query
+        );
+    }
+
+    Ok(())
+}
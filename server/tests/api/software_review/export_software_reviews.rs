@@ -0,0 +1,220 @@
+use async_zip::base::read::mem::ZipFileReader;
+use reqwest::header;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::{spawn_server, Result};
+
+// Returns the name and contents of every file in the archive, in order
+async fn read_archive(archive: Vec<u8>) -> Result<Vec<(String, Vec<u8>)>> {
+    let archive = ZipFileReader::new(archive).await?;
+    let mut files = vec![];
+
+    for index in 0..archive.file().entries().len() {
+        let file_name = archive.file().entries()[index]
+            .filename()
+            .as_str()?
+            .to_string();
+
+        let mut data = vec![];
+        archive
+            .reader_with_entry(index)
+            .await?
+            .read_to_end_checked(&mut data)
+            .await?;
+
+        files.push((file_name, data));
+    }
+
+    Ok(files)
+}
+
+#[tokio::test]
+async fn export_software_reviews_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+    let export_url = format!("{}/api/v1/reviews/export", server.addr);
+
+    let first_review_id = server
+        .create_review(
+            &token,
+            "87654321",
+            json!({ "software_request": { "software": { "software_name": "Slack" } } }),
+        )
+        .await?
+        .to_string();
+    let second_review_id = server
+        .create_review(
+            &token,
+            "87654322",
+            json!({ "software_request": { "software": { "software_name": "Zoom" } } }),
+        )
+        .await?
+        .to_string();
+    let missing_review_id = Uuid::new_v4().to_string();
+
+    let export_body = json!({
+        "review_ids": [first_review_id, missing_review_id, second_review_id]
+    });
+
+    let export_response = server
+        .post_request(&export_url, Some(export_body.to_string()), Some(&token))
+        .await?;
+    assert_eq!(200, export_response.status().as_u16());
+    assert_eq!(
+        "application/zip",
+        export_response.headers()[header::CONTENT_TYPE]
+    );
+
+    let files = read_archive(export_response.bytes().await?.to_vec()).await?;
+    let file_names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        vec![
+            "87654321 - Slack.pdf",
+            "87654322 - Zoom.pdf",
+            "manifest.json"
+        ],
+        file_names
+    );
+    assert!(files[0].1.starts_with(b"%PDF"));
+
+    // The review that doesn't exist is reported in the manifest, without ending the export
+    let manifest: Value = serde_json::from_slice(&files[2].1)?;
+    assert_eq!(2, manifest["exported"]);
+    assert_eq!(1, manifest["failed"]);
+    assert_eq!("exported", manifest["reviews"][0]["status"]);
+    assert_eq!("87654321 - Slack.pdf", manifest["reviews"][0]["file"]);
+    assert_eq!("failed", manifest["reviews"][1]["status"]);
+    assert_eq!(missing_review_id, manifest["reviews"][1]["review_id"]);
+    assert_eq!(Value::Null, manifest["reviews"][1]["file"]);
+
+    // Every review in the archive is marked exported
+    let get_software_review_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/reviews?filter=exported:true&per_page=100",
+                server.addr
+            ),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(200, get_software_review_response.status().as_u16());
+
+    let software_reviews: Value =
+        serde_json::from_str(&get_software_review_response.text().await?)?;
+    let exported_ids: Vec<&str> = software_reviews["software_reviews"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|software_review| software_review["software_review"]["id"].as_str())
+        .collect();
+    assert!(exported_ids.contains(&first_review_id.as_str()));
+    assert!(exported_ids.contains(&second_review_id.as_str()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_reviews_reports_missing_reviews() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+    let export_url = format!("{}/api/v1/reviews/export", server.addr);
+
+    let missing_review_id = Uuid::new_v4().to_string();
+
+    let export_body = json!({
+        "review_ids": [missing_review_id]
+    });
+
+    let export_response = server
+        .post_request(&export_url, Some(export_body.to_string()), Some(&token))
+        .await?;
+    assert_eq!(200, export_response.status().as_u16());
+
+    let content_disposition = export_response.headers()[header::CONTENT_DISPOSITION]
+        .to_str()?
+        .to_string();
+    assert!(
+        content_disposition.starts_with("attachment; filename=\"software-reviews-"),
+        "{}",
+        content_disposition
+    );
+
+    let files = read_archive(export_response.bytes().await?.to_vec()).await?;
+    assert_eq!(1, files.len());
+    assert_eq!("manifest.json", files[0].0);
+
+    let manifest: Value = serde_json::from_slice(&files[0].1)?;
+    assert_eq!(0, manifest["exported"]);
+    assert_eq!(1, manifest["failed"]);
+    assert_eq!(missing_review_id, manifest["reviews"][0]["review_id"]);
+    assert_eq!(
+        "The requested resource could not be found",
+        manifest["reviews"][0]["error"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_reviews_with_invalid_payload_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+    let export_url = format!("{}/api/v1/reviews/export", server.addr);
+
+    let too_many_review_ids: Vec<String> = (0..201).map(|_| Uuid::new_v4().to_string()).collect();
+
+    let test_cases = vec![
+        (json!({}), "no reviews were provided"),
+        (
+            json!({"review_ids": [Uuid::new_v4()], "filter": "exported:false"}),
+            "both review ids and a filter were provided",
+        ),
+        (json!({"review_ids": []}), "the review ids were empty"),
+        (
+            json!({"review_ids": too_many_review_ids}),
+            "too many review ids were provided",
+        ),
+        (json!({"review_ids": ["1234"]}), "a review id was invalid"),
+        (
+            json!({"filter": "created_at:2024"}),
+            "the filter field was invalid",
+        ),
+        (
+            json!({"filter": "td_request_id:00000000"}),
+            "the filter matched no reviews",
+        ),
+        (json!({"ids": []}), "an unknown field was provided"),
+    ];
+
+    for (invalid_body, error_message) in test_cases {
+        let export_response = server
+            .post_request(&export_url, Some(invalid_body.to_string()), Some(&token))
+            .await?;
+        assert_eq!(
+            400,
+            export_response.status().as_u16(),
+            "API did not fail with a 400 status when {}",
+            error_message
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn export_software_reviews_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let export_url = format!("{}/api/v1/reviews/export", server.addr);
+
+    let export_body = json!({
+        "review_ids": [Uuid::new_v4()]
+    });
+
+    let export_response = server
+        .post_request(&export_url, Some(export_body.to_string()), None)
+        .await?;
+    assert_eq!(401, export_response.status().as_u16());
+
+    Ok(())
+}
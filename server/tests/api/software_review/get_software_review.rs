@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::common::{spawn_server, Result};
 
@@ -127,7 +127,7 @@ async fn get_software_reviews_using_missing_token_rejected() -> Result<()> {
 }
 
 #[tokio::test]
-async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
+async fn sql_injection_get_software_reviews_treated_as_search_term() -> Result<()> {
     let server = spawn_server().await?;
     let login_url = format!("{}/api/v1/auth/login", server.addr);
 
@@ -151,6 +151,7 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
     let test_cases = vec![
         (
             format!("{}/api/v1/reviews?filter=td_request_id:36472091' OR 1=1 --", server.addr),
+            200,
             "SQL injection attempt on role filter with OR 1=1",
         ),
         (
@@ -158,6 +159,7 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' UNION SELECT NULL, NULL, NULL --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with UNION SELECT",
         ),
         (
@@ -165,6 +167,7 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' OR 1=1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt on developer_name filter with OR 1=1",
         ),
         (
@@ -172,6 +175,7 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091; DROP TABLE user_account;",
                 server.addr
             ),
+            200,
             "SQL injection attempt with DROP TABLE statement in role filter",
         ),
         (
@@ -179,10 +183,12 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' OR EXISTS(SELECT * FROM user_account WHERE 1=1) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with nested EXISTS query",
         ),
         (
             format!("{}/api/v1/reviews?td_request_id:36472091' --", server.addr),
+            400,
             "SQL injection attempt with single-line comment in role filter",
         ),
         (
@@ -190,6 +196,7 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' /* comment */",
                 server.addr
             ),
+            200,
             "SQL injection attempt with multi-line comment in developer_name filter",
         ),
         (
@@ -197,10 +204,12 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' OR pg_sleep(5) --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with time delay function in role filter",
         ),
         (
             format!("{}/api/v1/reviews?filter=td_request_id:36472091' AND 1=0 --", server.addr),
+            200,
             "SQL injection attempt with AND 1=0 to bypass filtering",
         ),
         (
@@ -208,14 +217,17 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' AND 1=1",
                 server.addr
             ),
+            200,
             "SQL injection attempt with AND 1=1 on developer_name filter",
         ),
         (
             format!("{}/api/v1/reviews?filter=td_request_id:36472091' OR 'a'='a", server.addr),
+            200,
             "SQL injection attempt with OR 'a'='a' in role filter",
         ),
         (
             format!("{}/api/v1/reviews?filter=td_request_id:36472091' LIMIT 1 --", server.addr),
+            200,
             "SQL injection attempt with LIMIT clause in role filter",
         ),
         (
@@ -223,20 +235,154 @@ async fn sql_injection_get_software_reviews_rejected() -> Result<()> {
                 "{}/api/v1/reviews?filter=td_request_id:36472091' ORDER BY 1 --",
                 server.addr
             ),
+            200,
             "SQL injection attempt with ORDER BY clause in developer_name filter",
         ),
     ];
 
-    for (invaild_url, error_message) in test_cases {
-        let get_software_reviews_response = server
-            .get_request(&invaild_url, Some(&token.unwrap()))
-            .await?;
-        (assert_eq!(
-            400,
+    // Filter values are always bound as query parameters, so injection attempts are
+    // treated as plain search terms and can never match more records than the value
+    // before the injected SQL. Only malformed query strings are rejected
+    for (url, expected_status, error_message) in test_cases {
+        let get_software_reviews_response = server.get_request(&url, Some(token.unwrap())).await?;
+        assert_eq!(
+            expected_status,
             get_software_reviews_response.status().as_u16(),
-            "API did not fail with a 400 status when the payload was {}",
+            "API did not respond with a {} status when the payload was {}",
+            expected_status,
             error_message,
-        ),);
+        );
+
+        if expected_status == 200 {
+            let base_url = url
+                .split(['\'', ';'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let base_response = server.get_request(&base_url, Some(token.unwrap())).await?;
+
+            let count_records = |body: &str| {
+                let body: Value = serde_json::from_str(body).unwrap();
+                body["software_reviews"]
+                    .as_array()
+                    .map_or(0, |records| records.len())
+            };
+
+            let base_count = count_records(&base_response.text().await.unwrap());
+            let injected_count =
+                count_records(&get_software_reviews_response.text().await.unwrap());
+
+            assert!(
+                injected_count <= base_count,
+                "API returned additional records when the payload was {}",
+                error_message,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_software_reviews_reports_newer_version_available() -> Result<()> {
+    let server = spawn_server().await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Test Software",
+                "software_version": "3.2.1-beta",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "email": "john@gmail.com",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    let get_software_reviews_response = server
+        .get_request(&software_review_url, Some(token.unwrap()))
+        .await?;
+    assert_eq!(200, get_software_reviews_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_software_reviews_response.text().await?)?;
+    let review = &body["software_reviews"][0]["software_review"];
+    assert_eq!("3.2.1-beta", review["reviewed_version"]);
+    assert_eq!(false, review["newer_version_available"]);
+
+    let software_id = review["software_request"]["software"]["id"]
+        .as_str()
+        .unwrap_or_default();
+
+    // Build metadata does not make a version newer, the release of the beta does
+    for (software_version, newer_version_available) in
+        [("3.2.1-beta+build.7", false), ("3.2.1", true)]
+    {
+        let update_software_response = server
+            .patch_request(
+                &format!("{}/api/v1/software/{}", server.addr, software_id),
+                Some(json!({ "software_version": software_version }).to_string()),
+                Some(token.unwrap()),
+            )
+            .await?;
+        assert_eq!(204, update_software_response.status().as_u16());
+
+        let get_software_reviews_response = server
+            .get_request(&software_review_url, Some(token.unwrap()))
+            .await?;
+        assert_eq!(200, get_software_reviews_response.status().as_u16());
+
+        let body: Value = serde_json::from_str(&get_software_reviews_response.text().await?)?;
+        let review = &body["software_reviews"][0]["software_review"];
+        assert_eq!("3.2.1-beta", review["reviewed_version"]);
+        assert_eq!(
+            newer_version_available, review["newer_version_available"],
+            "software version was {}",
+            software_version
+        );
     }
 
     Ok(())
@@ -0,0 +1,122 @@
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+async fn stored_notes(server: &TestServer, review_id: Uuid) -> Result<String> {
+    let review_notes = sqlx::query_scalar("SELECT review_notes FROM software_review WHERE id = $1")
+        .bind(review_id)
+        .fetch_one(&server.db_pool)
+        .await?;
+
+    Ok(review_notes)
+}
+
+#[tokio::test]
+async fn review_notes_are_stored_as_sanitized_markdown() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let findings = "The installer is signed by the vendor. ".repeat(20);
+    let review_notes = format!(
+        "# Findings\n\n<script>alert(1)</script>\n\n{}**No** telemetry.\n\n\
+         * Runs as a user\n* [Vendor page](https://example.com)",
+        findings
+    );
+
+    // Act
+    let review_id = server
+        .create_review(&token, "50000001", json!({ "review_notes": review_notes }))
+        .await?;
+
+    // Assert
+    assert_eq!(
+        format!(
+            "# Findings\n\n{}**No** telemetry.\n\n\
+             - Runs as a user\n- Vendor page (https://example.com)",
+            findings
+        ),
+        stored_notes(&server, review_id).await?
+    );
+
+    // Updated notes are sanitized as well
+    let update_software_review_response = server
+        .patch_request(
+            &format!("{}/api/v1/reviews/{}", server.addr, review_id),
+            Some(json!({ "review_notes": "Updated <i>notes</i> with _emphasis_" }).to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, update_software_review_response.status().as_u16());
+    assert_eq!(
+        "Updated notes with *emphasis*",
+        stored_notes(&server, review_id).await?
+    );
+
+    // Headings in the notes are nested below the heading of the section
+    let export_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/reviews/{}/export?format=markdown",
+                server.addr, review_id
+            ),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(200, export_response.status().as_u16());
+    assert!(export_response
+        .text()
+        .await?
+        .contains("\n## Notes\n\nUpdated notes with *emphasis*\n"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn review_notes_empty_once_sanitized_or_too_long_are_rejected() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let test_cases = [
+        (
+            "50000002",
+            "<div>\n<img src=x onerror=alert(1)>\n</div>".to_string(),
+        ),
+        ("50000003", "---".to_string()),
+        ("50000004", "a".repeat(10_001)),
+    ];
+
+    for (td_request_id, review_notes) in test_cases {
+        // Act
+        let create_software_review_response = server
+            .post_request(
+                &format!("{}/api/v1/reviews", server.addr),
+                Some(
+                    server
+                        .review_body(td_request_id, json!({ "review_notes": review_notes }))
+                        .to_string(),
+                ),
+                Some(&token),
+            )
+            .await?;
+
+        // Assert
+        assert_eq!(
+            400,
+            create_software_review_response.status().as_u16(),
+            "API did not fail with a 400 status for {:?}",
+            review_notes
+        );
+
+        let request_count: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM software_request WHERE td_request_id = $1")
+                .bind(td_request_id)
+                .fetch_one(&server.db_pool)
+                .await?;
+        assert_eq!(0, request_count);
+    }
+
+    Ok(())
+}
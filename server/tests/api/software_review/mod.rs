@@ -1,5 +1,11 @@
 mod create_software_review;
+mod create_software_review_with_existing_records;
 mod delete_software_review;
 mod export_software_review;
+mod export_software_reviews;
 mod get_software_review;
+mod markdown_review_notes;
+mod report_template;
+mod review_expiry;
 mod update_software_review;
+mod verify_software_review;
@@ -0,0 +1,177 @@
+use reqwest::header;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::{spawn_server_with_config, Result};
+
+// Writes a JSON report template without a logo to a temporary file, using the fonts
+// shipped with the server
+fn write_template(header_title: &str) -> Result<std::path::PathBuf> {
+    let font_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join("font");
+
+    let template = json!({
+        "page": { "width_mm": 215.9, "height_mm": 279.4, "margin_mm": 20, "content_top_mm": 10 },
+        "fonts": {
+            "regular": font_dir.join("NotoSans-Regular.ttf"),
+            "symbols": font_dir.join("NotoSansSymbols2-Regular.ttf"),
+            "title_size": 18,
+            "heading_size": 14,
+            "text_size": 10,
+            "symbol_size": 14,
+            "line_height": 28
+        },
+        "date": { "format": "%Y-%m-%d", "utc_offset_hours": 0 },
+        "header": {
+            "title": header_title,
+            "lines": ["{software_name} {software_version}", "Reviewed by {reviewer_name}"]
+        },
+        "sections": [
+            {
+                "kind": "criteria",
+                "title": "Checklist",
+                "criteria": [{ "field": "is_supported", "label": "Vendor still supports it" }]
+            },
+            { "kind": "text", "title": "Disclaimer", "lines": ["Reviewed for {requester_department}"] },
+            { "kind": "notes", "title": "Comments" }
+        ]
+    });
+
+    let path = std::env::temp_dir().join(format!("k6r-template-{}.json", Uuid::new_v4()));
+    std::fs::write(&path, template.to_string())?;
+
+    Ok(path)
+}
+
+#[tokio::test]
+async fn export_software_review_with_custom_template_successful() -> Result<()> {
+    let template_path = write_template("Software Review for {software_name}")?;
+
+    let server = spawn_server_with_config(|config| {
+        config.report.template_path = Some(template_path.clone());
+    })
+    .await?;
+    let login_url = format!("{}/api/v1/auth/login", server.addr);
+    let software_review_url = format!("{}/api/v1/reviews", server.addr);
+
+    // Uses 'Admin' test user credentials
+    let login_body = json!({
+        "email": server.test_users[1].email,
+        "password": server.test_users[1].password
+    });
+
+    let login_response = server
+        .post_request(&login_url, Some(login_body.to_string()), None)
+        .await?;
+    assert_eq!(204, login_response.status().as_u16());
+
+    let token = login_response
+        .headers()
+        .get(header::SET_COOKIE)
+        .and_then(|value| value.to_str().ok());
+    assert!(token.is_some(), "JWT should be present");
+
+    let create_software_review_body = json!({
+        "software_request": {
+            "td_request_id": "12345678",
+            "software": {
+                "software_name": "Test Software",
+                "software_version": "1.0.0",
+                "developer_name": "Test Developer",
+                "description": "A test software application",
+            },
+            "requester": {
+                "name": "John",
+                "email": "john@gmail.com",
+                "department": "IT",
+            },
+        },
+        "reviewer_id": server.test_users[1].id,
+        "is_supported": "TRUE",
+        "is_current_version": "TRUE",
+        "is_reputation_good": "TRUE",
+        "is_installation_from_developer": "TRUE",
+        "is_local_admin_required": "FALSE",
+        "is_connected_to_brockport_cloud": "FALSE",
+        "is_connected_to_cloud_services_or_client": "FALSE",
+        "is_security_or_optimization_software": "FALSE",
+        "is_supported_by_current_os": "TRUE",
+        "review_notes": "All conditions satisfied."
+    });
+
+    let create_software_review_response = server
+        .post_request(
+            &software_review_url,
+            Some(create_software_review_body.to_string()),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(201, create_software_review_response.status().as_u16());
+
+    let get_software_review_response = server
+        .get_request(
+            &format!("{}?filter=td_request_id:12345678", software_review_url),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, get_software_review_response.status().as_u16());
+
+    let software_reviews: Value =
+        serde_json::from_str(&get_software_review_response.text().await?)?;
+    let software_review_id = software_reviews["software_reviews"][0]["software_review"]["id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let export_software_review_response = server
+        .get_request(
+            &format!("{}/{}/export", software_review_url, software_review_id),
+            Some(token.unwrap()),
+        )
+        .await?;
+    assert_eq!(200, export_software_review_response.status().as_u16());
+    assert_eq!(
+        "application/pdf",
+        export_software_review_response.headers()[header::CONTENT_TYPE]
+    );
+
+    let pdf = export_software_review_response.bytes().await?;
+    assert!(pdf.starts_with(b"%PDF"));
+
+    std::fs::remove_file(template_path)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn server_with_invalid_report_template_fails_to_start() -> Result<()> {
+    let test_cases = vec![
+        (
+            Some(write_template("Review of {software}")?),
+            "the template has an unknown placeholder",
+        ),
+        (
+            Some(std::env::temp_dir().join(format!("k6r-template-{}.toml", Uuid::new_v4()))),
+            "the template does not exist",
+        ),
+    ];
+
+    for (template_path, error_message) in test_cases {
+        let server = spawn_server_with_config(|config| {
+            config.report.template_path = template_path.clone();
+        })
+        .await;
+        assert!(
+            server.is_err(),
+            "server did not fail to start when {}",
+            error_message
+        );
+
+        if let Some(template_path) = template_path.filter(|path| path.exists()) {
+            std::fs::remove_file(template_path)?;
+        }
+    }
+
+    Ok(())
+}
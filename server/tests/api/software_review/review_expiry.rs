@@ -0,0 +1,295 @@
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use k6r::api::check_review_expiry;
+use k6r::config::ReviewExpiryConfig;
+
+use crate::common::{spawn_server, spawn_server_with_config, Result, TestServer};
+
+// Whole days between the review's creation and expiry
+async fn validity_days(server: &TestServer, review_id: Uuid) -> Result<i32> {
+    Ok(sqlx::query_scalar(
+        "SELECT EXTRACT(DAY FROM expires_at - created_at)::INT FROM software_review WHERE id = $1",
+    )
+    .bind(review_id)
+    .fetch_one(&server.db_pool)
+    .await?)
+}
+
+async fn set_expires_in(server: &TestServer, review_id: Uuid, days: i32) -> Result<()> {
+    sqlx::query(
+        "UPDATE software_review SET expires_at = now() + make_interval(days => $1) WHERE id = $2",
+    )
+    .bind(days)
+    .bind(review_id)
+    .execute(&server.db_pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn get_queue(server: &TestServer, token: &str) -> Result<Vec<Value>> {
+    let get_queue_response = server
+        .get_request(
+            &format!("{}/api/v1/requests/queue", server.addr),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, get_queue_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_queue_response.text().await?)?;
+
+    Ok(body["software_requests"].as_array().unwrap().clone())
+}
+
+#[tokio::test]
+async fn review_expiry_depends_on_outcome() -> Result<()> {
+    // Arrange
+    let server = spawn_server_with_config(|config| {
+        config.review_expiry.no_concerns_days = 500;
+        config.review_expiry.unsure_days = 200;
+        config.review_expiry.concerns_days = 50;
+    })
+    .await?;
+
+    let token = server.login(0).await?;
+
+    // Act
+    let no_concerns_id = server.create_review(&token, "10000001", json!({})).await?;
+    let unsure_id = server
+        .create_review(
+            &token,
+            "10000002",
+            json!({ "is_reputation_good": "NOT_SURE" }),
+        )
+        .await?;
+    let concerns_id = server
+        .create_review(
+            &token,
+            "10000003",
+            json!({ "is_local_admin_required": "TRUE", "is_reputation_good": "NOT_SURE" }),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(500, validity_days(&server, no_concerns_id).await?);
+    assert_eq!(200, validity_days(&server, unsure_id).await?);
+    assert_eq!(50, validity_days(&server, concerns_id).await?);
+
+    let get_software_review_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/reviews?filter=td_request_id:10000003",
+                server.addr
+            ),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(200, get_software_review_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_software_review_response.text().await?)?;
+    assert!(
+        body["software_reviews"][0]["software_review"]["expires_at"].is_string(),
+        "expires_at should be returned with the review, got {}",
+        body
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_reviews_expiring_within_successful() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    let expiring_id = server.create_review(&token, "20000001", json!({})).await?;
+    let expired_id = server.create_review(&token, "20000002", json!({})).await?;
+    server.create_review(&token, "20000003", json!({})).await?;
+
+    set_expires_in(&server, expiring_id, 10).await?;
+    set_expires_in(&server, expired_id, -5).await?;
+
+    // Act
+    let get_software_reviews_response = server
+        .get_request(
+            &format!("{}/api/v1/reviews?expiring_within=30d", server.addr),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, get_software_reviews_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_software_reviews_response.text().await?)?;
+
+    let mut review_ids: Vec<String> = body["software_reviews"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|review| {
+            review["software_review"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    review_ids.sort();
+
+    let mut expected_ids = vec![expiring_id.to_string(), expired_id.to_string()];
+    expected_ids.sort();
+
+    assert_eq!(expected_ids, review_ids);
+
+    // The expiry filter combines with the other filters
+    let get_filtered_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/reviews?expiring_within=30d&filter=td_request_id:20000001",
+                server.addr
+            ),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(200, get_filtered_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_filtered_response.text().await?)?;
+    assert_eq!(1, body["software_reviews"].as_array().unwrap().len());
+    assert_eq!(
+        expiring_id.to_string(),
+        body["software_reviews"][0]["software_review"]["id"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_reviews_expiring_within_returns_400_for_invalid_values() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    let test_cases = ["30", "0d", "-1d", "3651d", "30days", "d", "abc"];
+
+    for expiring_within in test_cases {
+        // Act
+        let get_software_reviews_response = server
+            .get_request(
+                &format!(
+                    "{}/api/v1/reviews?expiring_within={}",
+                    server.addr, expiring_within
+                ),
+                Some(&token),
+            )
+            .await?;
+
+        // Assert
+        assert_eq!(
+            400,
+            get_software_reviews_response.status().as_u16(),
+            "API did not fail with a 400 status when expiring_within was {}",
+            expiring_within
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn check_review_expiry_flags_and_reopens_expired_reviews() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    let expired_id = server.create_review(&token, "30000001", json!({})).await?;
+    let expiring_id = server.create_review(&token, "30000002", json!({})).await?;
+    let valid_id = server.create_review(&token, "30000003", json!({})).await?;
+
+    set_expires_in(&server, expired_id, -1).await?;
+    set_expires_in(&server, expiring_id, 10).await?;
+
+    let config = ReviewExpiryConfig {
+        warning_days: 30,
+        reopen_expired: true,
+        ..Default::default()
+    };
+
+    // Act
+    check_review_expiry(&config, &server.db_pool).await?;
+
+    // Assert
+    for (review_id, flagged) in [(expired_id, true), (expiring_id, true), (valid_id, false)] {
+        let expiry_flagged: bool = sqlx::query_scalar(
+            "SELECT expiry_flagged_at IS NOT NULL FROM software_review WHERE id = $1",
+        )
+        .bind(review_id)
+        .fetch_one(&server.db_pool)
+        .await?;
+        assert_eq!(flagged, expiry_flagged, "review: {}", review_id);
+    }
+
+    // Only the request of the expired review is back in the queue
+    let queue = get_queue(&server, &token).await?;
+    assert_eq!(1, queue.len());
+    assert_eq!("30000001", queue[0]["software_request"]["td_request_id"]);
+    assert!(queue[0]["reopened_at"].is_string());
+
+    // Reviewing the software again takes it out of the queue and renews the review
+    let update_software_review_response = server
+        .patch_request(
+            &format!("{}/api/v1/reviews/{}", server.addr, expired_id),
+            Some(json!({ "review_notes": "Reviewed again." }).to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, update_software_review_response.status().as_u16());
+
+    assert!(get_queue(&server, &token).await?.is_empty());
+
+    let (renewed, expiry_flagged): (bool, bool) = sqlx::query_as(
+        "SELECT expires_at > now() + interval '700 days', expiry_flagged_at IS NOT NULL \
+         FROM software_review WHERE id = $1",
+    )
+    .bind(expired_id)
+    .fetch_one(&server.db_pool)
+    .await?;
+    assert!(renewed, "the review should be valid for another 730 days");
+    assert!(!expiry_flagged, "the review should no longer be flagged");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn check_review_expiry_only_reopens_when_enabled() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    let expired_id = server.create_review(&token, "40000001", json!({})).await?;
+    set_expires_in(&server, expired_id, -1).await?;
+
+    let config = ReviewExpiryConfig {
+        reopen_expired: false,
+        ..Default::default()
+    };
+
+    // Act
+    check_review_expiry(&config, &server.db_pool).await?;
+
+    // Assert
+    let expiry_flagged: bool = sqlx::query_scalar(
+        "SELECT expiry_flagged_at IS NOT NULL FROM software_review WHERE id = $1",
+    )
+    .bind(expired_id)
+    .fetch_one(&server.db_pool)
+    .await?;
+    assert!(expiry_flagged);
+
+    assert!(get_queue(&server, &token).await?.is_empty());
+
+    Ok(())
+}
@@ -460,7 +460,7 @@ async fn update_software_review_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "review_notes": "A".repeat(1001),
+                "review_notes": "A".repeat(10_001),
             }),
             "review notes too long",
         ),
@@ -0,0 +1,248 @@
+use openssl::asn1::Asn1Time;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::x509::{X509NameBuilder, X509};
+use reqwest::header;
+use reqwest::multipart::{Form, Part};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use k6r::config::ReportSigningConfig;
+
+use crate::common::{spawn_server, spawn_server_with_config, Result, TestServer};
+
+async fn export_software_review(
+    server: &TestServer,
+    token: &str,
+    review_id: &str,
+) -> Result<Vec<u8>> {
+    let export_response = server
+        .get_request(
+            &format!("{}/api/v1/reviews/{}/export", server.addr, review_id),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, export_response.status().as_u16());
+
+    Ok(export_response.bytes().await?.to_vec())
+}
+
+// Uploads `pdf` to the verify endpoint
+async fn verify(server: &TestServer, token: &str, pdf: Vec<u8>) -> Result<reqwest::Response> {
+    let form = Form::new().part("file", Part::bytes(pdf).file_name("review.pdf"));
+
+    Ok(server
+        .client
+        .post(format!("{}/api/v1/reviews/verify", server.addr))
+        .header(header::COOKIE, token)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|err| format!("failed to execute request: cause {err}"))?)
+}
+
+// Writes a self-signed certificate and its private key to temporary files
+fn write_signing_key() -> Result<ReportSigningConfig> {
+    let private_key = PKey::from_rsa(Rsa::generate(2048)?)?;
+
+    let mut name = X509NameBuilder::new()?;
+    name.append_entry_by_text("CN", "k6r test")?;
+    let name = name.build();
+
+    let mut certificate = X509::builder()?;
+    certificate.set_version(2)?;
+    certificate.set_subject_name(&name)?;
+    certificate.set_issuer_name(&name)?;
+    certificate.set_pubkey(&private_key)?;
+    certificate.set_not_before(Asn1Time::days_from_now(0)?.as_ref())?;
+    certificate.set_not_after(Asn1Time::days_from_now(1)?.as_ref())?;
+    certificate.sign(&private_key, MessageDigest::sha256())?;
+
+    let id = Uuid::new_v4();
+    let certificate_path = std::env::temp_dir().join(format!("k6r-signing-{}.crt", id));
+    let private_key_path = std::env::temp_dir().join(format!("k6r-signing-{}.key", id));
+
+    std::fs::write(&certificate_path, certificate.build().to_pem()?)?;
+    std::fs::write(&private_key_path, private_key.private_key_to_pem_pkcs8()?)?;
+
+    Ok(ReportSigningConfig {
+        certificate_path,
+        private_key_path,
+    })
+}
+
+#[tokio::test]
+async fn verify_software_review_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let review_id = server
+        .create_review(
+            &token,
+            "12345678",
+            json!({ "software_request": { "software": { "software_name": "Test Software" } } }),
+        )
+        .await?
+        .to_string();
+    let pdf = export_software_review(&server, &token, &review_id).await?;
+
+    let verify_response = verify(&server, &token, pdf.clone()).await?;
+    assert_eq!(200, verify_response.status().as_u16());
+
+    // Marking the review exported bumps its version, without changing its content
+    let verification: Value = serde_json::from_str(&verify_response.text().await?)?;
+    let verification = &verification["verification"];
+    assert_eq!(review_id, verification["review_id"]);
+    // Without a signature, only the stamp can be checked
+    assert_eq!("metadata_only", verification["status"]);
+    assert_eq!(
+        verification["report_version"].as_i64().unwrap() + 1,
+        verification["current_version"].as_i64().unwrap()
+    );
+    assert_eq!(64, verification["content_hash"].as_str().unwrap().len());
+    assert_eq!(false, verification["signed"]);
+    assert_eq!(Value::Null, verification["signature_valid"]);
+
+    // Once the review changes, the report no longer matches it
+    let update_software_review_response = server
+        .patch_request(
+            &format!("{}/api/v1/reviews/{}", server.addr, review_id),
+            Some(json!({"review_notes": "Needs another look."}).to_string()),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, update_software_review_response.status().as_u16());
+
+    let verify_response = verify(&server, &token, pdf).await?;
+    assert_eq!(200, verify_response.status().as_u16());
+
+    let verification: Value = serde_json::from_str(&verify_response.text().await?)?;
+    assert_eq!("outdated", verification["verification"]["status"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_signed_software_review_successful() -> Result<()> {
+    let signing = write_signing_key()?;
+
+    let server = spawn_server_with_config(|config| {
+        config.report.signing = Some(signing.clone());
+    })
+    .await?;
+    let token = server.login(1).await?;
+
+    let review_id = server
+        .create_review(
+            &token,
+            "12345678",
+            json!({ "software_request": { "software": { "software_name": "Test Software" } } }),
+        )
+        .await?
+        .to_string();
+    let pdf = export_software_review(&server, &token, &review_id).await?;
+    assert!(pdf.starts_with(b"%PDF"));
+
+    let verify_response = verify(&server, &token, pdf.clone()).await?;
+    assert_eq!(200, verify_response.status().as_u16());
+
+    let verification: Value = serde_json::from_str(&verify_response.text().await?)?;
+    let verification = &verification["verification"];
+    assert_eq!("valid", verification["status"]);
+    assert_eq!(true, verification["signed"]);
+    assert_eq!(true, verification["signature_valid"]);
+
+    // Changing any signed byte, such as the document title, breaks the signature
+    let title = b"(Test Software)";
+    let title_start = pdf
+        .windows(title.len())
+        .position(|window| window == title)
+        .expect("title should be in the document");
+
+    let mut tampered_pdf = pdf.clone();
+    tampered_pdf[title_start..title_start + title.len()].copy_from_slice(b"(Fake Software)");
+
+    let verify_response = verify(&server, &token, tampered_pdf).await?;
+    assert_eq!(200, verify_response.status().as_u16());
+
+    let verification: Value = serde_json::from_str(&verify_response.text().await?)?;
+    let verification = &verification["verification"];
+    assert_eq!("tampered", verification["status"]);
+    assert_eq!(true, verification["signed"]);
+    assert_eq!(false, verification["signature_valid"]);
+
+    // Nothing may be added after the signed bytes
+    let mut appended_pdf = pdf;
+    appended_pdf.extend_from_slice(b"\n% appended\n");
+
+    let verify_response = verify(&server, &token, appended_pdf).await?;
+    assert_eq!(200, verify_response.status().as_u16());
+
+    let verification: Value = serde_json::from_str(&verify_response.text().await?)?;
+    assert_eq!("tampered", verification["verification"]["status"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_software_review_with_invalid_file_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(1).await?;
+
+    let verify_response = verify(&server, &token, b"not a pdf".to_vec()).await?;
+    assert_eq!(400, verify_response.status().as_u16());
+
+    // A PDF that wasn't exported by the server
+    let empty_pdf =
+        printpdf::PdfDocument::new("Other", printpdf::Mm(210.0), printpdf::Mm(297.0), "Layer 1")
+            .0
+            .save_to_bytes()?;
+
+    let verify_response = verify(&server, &token, empty_pdf).await?;
+    assert_eq!(400, verify_response.status().as_u16());
+
+    let verify_response = server
+        .client
+        .post(format!("{}/api/v1/reviews/verify", server.addr))
+        .header(header::COOKIE, &token)
+        .multipart(Form::new().text("report", "review.pdf"))
+        .send()
+        .await?;
+    assert_eq!(400, verify_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_software_review_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    let verify_response = server
+        .client
+        .post(format!("{}/api/v1/reviews/verify", server.addr))
+        .multipart(Form::new().part("file", Part::bytes(b"%PDF".to_vec())))
+        .send()
+        .await?;
+    assert_eq!(401, verify_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn server_with_invalid_signing_key_fails_to_start() -> Result<()> {
+    let signing = write_signing_key()?;
+    let other_signing = write_signing_key()?;
+
+    // The private key belongs to another certificate
+    let result = spawn_server_with_config(|config| {
+        config.report.signing = Some(ReportSigningConfig {
+            certificate_path: signing.certificate_path.clone(),
+            private_key_path: other_signing.private_key_path.clone(),
+        });
+    })
+    .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
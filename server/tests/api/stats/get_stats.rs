@@ -0,0 +1,277 @@
+use serde_json::{json, Value};
+
+use crate::common::{spawn_server, spawn_server_with_config, Result, TestServer};
+
+async fn get_stats(server: &TestServer, token: &str, path: &str) -> Result<Value> {
+    let stats_response = server
+        .get_request(
+            &format!("{}/api/v1/stats/{}", server.addr, path),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, stats_response.status().as_u16(), "path: {}", path);
+
+    let body: Value = serde_json::from_str(&stats_response.text().await?)?;
+
+    Ok(body["stats"].clone())
+}
+
+#[tokio::test]
+async fn get_stats_successful() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    server
+        .create_review(
+            &token,
+            "12345678",
+            json!({
+                "software_request": { "requester": { "department": "IT" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+    server
+        .create_review(
+            &token,
+            "87654321",
+            json!({
+                "software_request": { "requester": { "department": "Biology" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+    server
+        .create_review(
+            &token,
+            "11223344",
+            json!({
+                "software_request": { "requester": { "department": "Biology" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    // Leaves a software request that hasn't been reviewed
+    server
+        .create_review(
+            &token,
+            "99999999",
+            json!({
+                "software_request": { "requester": { "department": "Biology" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    sqlx::query(
+        "DELETE FROM software_review WHERE software_request_id = \
+         (SELECT id FROM software_request WHERE td_request_id = '99999999')",
+    )
+    .execute(&server.db_pool)
+    .await?;
+
+    let reviews = get_stats(&server, &token, "reviews").await?;
+    assert_eq!("week", reviews["interval"]);
+    let periods = reviews["periods"].as_array().unwrap();
+    // Every week of the default period is listed
+    assert!(periods.len() >= 52);
+    assert_eq!(3, periods.last().unwrap()["count"]);
+
+    let answers = get_stats(&server, &token, "answers").await?;
+    let criteria = answers["criteria"].as_array().unwrap();
+    assert_eq!(9, criteria.len());
+    assert_eq!(
+        json!({ "criterion": "is_installation_from_developer", "TRUE": 0, "FALSE": 0, "NOT_SURE": 3 }),
+        criteria[3]
+    );
+
+    let exports = get_stats(&server, &token, "exports").await?;
+    assert_eq!(0, exports["exported"]);
+    assert_eq!(3, exports["not_exported"]);
+
+    let unreviewed = get_stats(&server, &token, "unreviewed").await?;
+    assert_eq!(1, unreviewed["count"]);
+    assert!(unreviewed["oldest_created_at"].is_string());
+
+    let reviewers = get_stats(&server, &token, "reviewers").await?;
+    let reviewers = reviewers["reviewers"].as_array().unwrap();
+    assert_eq!(server.test_users[0].id.to_string(), reviewers[0]["user_id"]);
+    assert_eq!(3, reviewers[0]["reviews"]);
+    assert_eq!(0, reviewers[0]["assigned_requests"]);
+    // Users without reviews are included
+    assert!(reviewers
+        .iter()
+        .skip(1)
+        .all(|reviewer| reviewer["reviews"] == 0));
+
+    let departments = get_stats(&server, &token, "departments?limit=1").await?;
+    assert_eq!(
+        json!([{ "name": "Biology", "count": 3 }]),
+        departments["departments"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_review_stats_lists_every_period() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    let reviews = get_stats(
+        &server,
+        &token,
+        "reviews?interval=month&from=2024-01-15&to=2024-03-10",
+    )
+    .await?;
+    assert_eq!(
+        json!([
+            { "period_start": "2024-01-01", "count": 0 },
+            { "period_start": "2024-02-01", "count": 0 },
+            { "period_start": "2024-03-01", "count": 0 },
+        ]),
+        reviews["periods"]
+    );
+
+    // Weeks start on Monday
+    let reviews = get_stats(
+        &server,
+        &token,
+        "reviews?interval=week&from=2024-12-04&to=2024-12-16",
+    )
+    .await?;
+    assert_eq!(
+        json!([
+            { "period_start": "2024-12-02", "count": 0 },
+            { "period_start": "2024-12-09", "count": 0 },
+            { "period_start": "2024-12-16", "count": 0 },
+        ]),
+        reviews["periods"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_stats_are_cached() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    server
+        .create_review(
+            &token,
+            "12345678",
+            json!({
+                "software_request": { "requester": { "department": "IT" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    let exports = get_stats(&server, &token, "exports").await?;
+    assert_eq!(1, exports["not_exported"]);
+
+    server
+        .create_review(
+            &token,
+            "87654321",
+            json!({
+                "software_request": { "requester": { "department": "IT" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    // Still within the cache period
+    let exports = get_stats(&server, &token, "exports").await?;
+    assert_eq!(1, exports["not_exported"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_stats_with_caching_disabled_successful() -> Result<()> {
+    let server = spawn_server_with_config(|config| config.stats.cache_seconds = 0).await?;
+    let token = server.login(0).await?;
+
+    server
+        .create_review(
+            &token,
+            "12345678",
+            json!({
+                "software_request": { "requester": { "department": "IT" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    let exports = get_stats(&server, &token, "exports").await?;
+    assert_eq!(1, exports["not_exported"]);
+
+    server
+        .create_review(
+            &token,
+            "87654321",
+            json!({
+                "software_request": { "requester": { "department": "IT" } },
+                "is_installation_from_developer": "NOT_SURE",
+            }),
+        )
+        .await?;
+
+    let exports = get_stats(&server, &token, "exports").await?;
+    assert_eq!(2, exports["not_exported"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_stats_with_invalid_params_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+    let token = server.login(0).await?;
+
+    for path in [
+        "reviews?from=2024-12-31&to=2024-12-01",
+        "reviews?interval=day",
+        "answers?from=12/01/2024",
+        "departments?limit=0",
+        "departments?limit=101",
+        "exports?since=2024-12-01",
+        "reviews?to=%2B262142-12-31",
+        "answers?to=-262143-01-01",
+        "reviewers?from=1969-12-31&to=2024-12-01",
+        "departments?to=10000-01-01",
+    ] {
+        let stats_response = server
+            .get_request(
+                &format!("{}/api/v1/stats/{}", server.addr, path),
+                Some(&token),
+            )
+            .await?;
+        assert_eq!(400, stats_response.status().as_u16(), "path: {}", path);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_stats_using_missing_token_rejected() -> Result<()> {
+    let server = spawn_server().await?;
+
+    for path in [
+        "reviews",
+        "answers",
+        "exports",
+        "unreviewed",
+        "reviewers",
+        "departments",
+    ] {
+        let stats_response = server
+            .get_request(&format!("{}/api/v1/stats/{}", server.addr, path), None)
+            .await?;
+        assert_eq!(401, stats_response.status().as_u16(), "path: {}", path);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,372 @@
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Multipart, Path};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use reqwest::header;
+use secrecy::SecretString;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use k6r::config::TeamDynamixConfig;
+
+use crate::common::{spawn_server, spawn_server_with_config, Result, TestServer};
+
+const BEID: &str = "test-beid";
+const WEB_SERVICES_KEY: &str = "test-web-services-key";
+const TOKEN: &str = "tdx-token";
+const TICKET_ID: &str = "12345678";
+
+// What the mock TeamDynamix API received
+#[derive(Default)]
+struct Received {
+    logins: usize,
+    attachments: Vec<(String, Vec<u8>)>,
+    comments: Vec<Value>,
+}
+
+// A local stand-in for the TeamDynamix Web API, which knows a single ticket
+struct MockTeamDynamix {
+    base_url: String,
+    received: Arc<Mutex<Received>>,
+}
+
+impl MockTeamDynamix {
+    async fn spawn() -> Result<Self> {
+        let received: Arc<Mutex<Received>> = Arc::default();
+
+        let app = axum::Router::new()
+            .route(
+                "/TDWebApi/api/auth/loginadmin",
+                post({
+                    let received = received.clone();
+
+                    move |axum::Json(body): axum::Json<Value>| async move {
+                        received.lock().unwrap().logins += 1;
+
+                        if body["BEID"] == BEID && body["WebServicesKey"] == WEB_SERVICES_KEY {
+                            (StatusCode::OK, TOKEN).into_response()
+                        } else {
+                            StatusCode::UNAUTHORIZED.into_response()
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/TDWebApi/api/:app_id/tickets/:ticket_id",
+                get(
+                    |headers: HeaderMap, Path((app_id, ticket_id)): Path<(u32, String)>| async move {
+                        if !authorized(&headers) {
+                            return StatusCode::UNAUTHORIZED.into_response();
+                        }
+
+                        if app_id != 31 || ticket_id != TICKET_ID {
+                            return StatusCode::NOT_FOUND.into_response();
+                        }
+
+                        axum::Json(json!({
+                            "ID": 12345678,
+                            "Title": "Install Zoom",
+                            "Description": "Needed for remote lectures",
+                            "RequestorName": "John Doe",
+                            "RequestorEmail": "jdoe@brockport.edu",
+                            "AccountName": "Computer Science",
+                            "Attributes": [
+                                { "ID": 101, "Value": "Zoom Workplace", "ValueText": "Zoom Workplace" },
+                                { "ID": 102, "Value": "6.2.0", "ValueText": "6.2.0" },
+                                { "ID": 103, "Value": "417", "ValueText": "Zoom Video Communications" }
+                            ]
+                        }))
+                        .into_response()
+                    },
+                ),
+            )
+            .route(
+                "/TDWebApi/api/:app_id/tickets/:ticket_id/attachments",
+                post({
+                    let received = received.clone();
+
+                    move |headers: HeaderMap, mut multipart: Multipart| async move {
+                        if !authorized(&headers) {
+                            return StatusCode::UNAUTHORIZED;
+                        }
+
+                        while let Ok(Some(field)) = multipart.next_field().await {
+                            let file_name = field.file_name().unwrap_or_default().to_string();
+                            let content = field.bytes().await.unwrap().to_vec();
+
+                            received.lock().unwrap().attachments.push((file_name, content));
+                        }
+
+                        StatusCode::OK
+                    }
+                }),
+            )
+            .route(
+                "/TDWebApi/api/:app_id/tickets/:ticket_id/feed",
+                post({
+                    let received = received.clone();
+
+                    move |headers: HeaderMap, axum::Json(body): axum::Json<Value>| async move {
+                        if !authorized(&headers) {
+                            return StatusCode::UNAUTHORIZED;
+                        }
+
+                        received.lock().unwrap().comments.push(body);
+
+                        StatusCode::CREATED
+                    }
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}/TDWebApi", listener.local_addr()?);
+
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(MockTeamDynamix { base_url, received })
+    }
+
+    fn config(&self, web_services_key: &str) -> TeamDynamixConfig {
+        TeamDynamixConfig {
+            base_url: self.base_url.clone(),
+            app_id: 31,
+            beid: SecretString::new(BEID.into()),
+            web_services_key: SecretString::new(web_services_key.into()),
+            timeout_seconds: 5,
+            software_name_attribute_id: Some(101),
+            software_version_attribute_id: Some(102),
+            developer_attribute_id: Some(103),
+            review_status_id: Some(7),
+        }
+    }
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .is_some_and(|value| value == format!("Bearer {}", TOKEN).as_str())
+}
+
+async fn spawn_server_with_teamdynamix(
+    teamdynamix: &MockTeamDynamix,
+    web_services_key: &str,
+) -> Result<TestServer> {
+    let config = teamdynamix.config(web_services_key);
+
+    spawn_server_with_config(|server_config| server_config.teamdynamix = Some(config)).await
+}
+
+#[tokio::test]
+async fn get_ticket_prefills_software_request() -> Result<()> {
+    // Arrange
+    let teamdynamix = MockTeamDynamix::spawn().await?;
+    let server = spawn_server_with_teamdynamix(&teamdynamix, WEB_SERVICES_KEY).await?;
+
+    let token = server.login(0).await?;
+
+    // Act
+    let get_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/{}", server.addr, TICKET_ID),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, get_ticket_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_ticket_response.text().await?)?;
+    assert_eq!(
+        json!({
+            "software_request": {
+                "td_request_id": TICKET_ID,
+                "software": {
+                    "software_name": "Zoom Workplace",
+                    "software_version": "6.2.0",
+                    "developer_name": "Zoom Video Communications",
+                    "description": "Needed for remote lectures"
+                },
+                "requester": {
+                    "name": "John Doe",
+                    "email": "jdoe@brockport.edu",
+                    "department": "Computer Science"
+                }
+            }
+        }),
+        body
+    );
+
+    // The token is reused for later requests
+    let get_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/{}", server.addr, TICKET_ID),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(200, get_ticket_response.status().as_u16());
+
+    assert_eq!(1, teamdynamix.received.lock().unwrap().logins);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_ticket_returns_404_for_unknown_ticket_and_400_for_invalid_id() -> Result<()> {
+    // Arrange
+    let teamdynamix = MockTeamDynamix::spawn().await?;
+    let server = spawn_server_with_teamdynamix(&teamdynamix, WEB_SERVICES_KEY).await?;
+
+    let token = server.login(0).await?;
+
+    // Act
+    let unknown_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/87654321", server.addr),
+            Some(&token),
+        )
+        .await?;
+
+    let invalid_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/1234", server.addr),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(404, unknown_ticket_response.status().as_u16());
+    assert_eq!(400, invalid_ticket_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticketing_failures_return_502() -> Result<()> {
+    // Arrange
+    let teamdynamix = MockTeamDynamix::spawn().await?;
+    let server = spawn_server_with_teamdynamix(&teamdynamix, "wrong-key").await?;
+
+    let token = server.login(0).await?;
+
+    // Act
+    let get_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/{}", server.addr, TICKET_ID),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(502, get_ticket_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ticketing_returns_503_when_not_configured() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+    let review_id = server
+        .create_review(
+            &token,
+            TICKET_ID,
+            json!({ "software_request": { "software": { "software_name": "Zoom Workplace" } } }),
+        )
+        .await?;
+
+    // Act
+    let get_ticket_response = server
+        .get_request(
+            &format!("{}/api/v1/tickets/{}", server.addr, TICKET_ID),
+            Some(&token),
+        )
+        .await?;
+
+    let post_review_response = server
+        .post_request(
+            &format!("{}/api/v1/reviews/{}/ticket", server.addr, review_id),
+            None,
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(503, get_ticket_response.status().as_u16());
+    assert_eq!(503, post_review_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn post_review_to_ticket_attaches_report_and_comments() -> Result<()> {
+    // Arrange
+    let teamdynamix = MockTeamDynamix::spawn().await?;
+    let server = spawn_server_with_teamdynamix(&teamdynamix, WEB_SERVICES_KEY).await?;
+
+    let token = server.login(0).await?;
+    let review_id = server
+        .create_review(
+            &token,
+            TICKET_ID,
+            json!({ "software_request": { "software": { "software_name": "Zoom Workplace" } } }),
+        )
+        .await?;
+
+    // Act
+    let post_review_response = server
+        .post_request(
+            &format!("{}/api/v1/reviews/{}/ticket", server.addr, review_id),
+            None,
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(204, post_review_response.status().as_u16());
+
+    {
+        let received = teamdynamix.received.lock().unwrap();
+
+        assert_eq!(1, received.attachments.len());
+        let (file_name, content) = &received.attachments[0];
+        assert_eq!("12345678 - Zoom Workplace.pdf", file_name);
+        assert!(
+            content.starts_with(b"%PDF"),
+            "the attachment should be a PDF"
+        );
+
+        assert_eq!(1, received.comments.len());
+        let comment = &received.comments[0];
+        assert_eq!(7, comment["NewStatusID"]);
+
+        let comments = comment["Comments"].as_str().unwrap();
+        assert!(comments.contains("Outcome: No concerns"), "{}", comments);
+        assert!(
+            comments.contains(&server.test_users[0].name),
+            "{}",
+            comments
+        );
+    }
+
+    let exported: bool = sqlx::query_scalar("SELECT exported FROM software_review WHERE id = $1")
+        .bind(review_id)
+        .fetch_one(&server.db_pool)
+        .await?;
+    assert!(exported, "the review should be marked exported");
+
+    // An unknown review is not posted
+    let post_unknown_review_response = server
+        .post_request(
+            &format!("{}/api/v1/reviews/{}/ticket", server.addr, Uuid::new_v4()),
+            None,
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(404, post_unknown_review_response.status().as_u16());
+
+    Ok(())
+}
@@ -191,7 +191,7 @@ async fn create_user_with_invalid_fields_rejected() -> Result<()> {
         ),
         (
             json!({
-                "name": "//John$)",
+                "name": "John\u{0}Doe",
                 "email": "test@gmail.com",
                 "password": "testing123456",
                 "role": "REVIEWER"
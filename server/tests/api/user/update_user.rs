@@ -210,7 +210,7 @@ async fn update_user_with_invalid_fields_rejected() -> Result<()> {
     let test_cases = vec![
         (
             json!({
-                "name": "John)asdd$",
+                "name": "John\u{0}asdd",
             }),
             "invalid name",
         ),
@@ -0,0 +1,458 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::http::{HeaderMap, StatusCode};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use k6r::api::{deliver_webhooks, sign_webhook_payload, webhook_client};
+use k6r::config::WebhookConfig;
+
+use crate::common::{spawn_server, Result, TestServer};
+
+const SECRET: &str = "0123456789abcdef";
+
+// A local endpoint which records every request it receives. The first `failures`
+// requests are answered with a 500
+#[derive(Clone)]
+struct Receiver {
+    url: String,
+    requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+}
+
+impl Receiver {
+    async fn spawn(failures: usize) -> Result<Self> {
+        let requests: Arc<Mutex<Vec<(HeaderMap, String)>>> = Arc::default();
+        let received = Arc::new(AtomicUsize::new(0));
+
+        let app = axum::Router::new().route(
+            "/hooks",
+            axum::routing::post({
+                let requests = requests.clone();
+
+                move |headers: HeaderMap, body: String| async move {
+                    requests.lock().unwrap().push((headers, body));
+
+                    if received.fetch_add(1, Ordering::SeqCst) < failures {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/hooks", listener.local_addr()?);
+
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(Receiver { url, requests })
+    }
+
+    fn requests(&self) -> Vec<(HeaderMap, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn create_webhook(
+    server: &TestServer,
+    token: &str,
+    url: &str,
+    event_types: Value,
+) -> Result<Uuid> {
+    let create_webhook_response = server
+        .post_request(
+            &format!("{}/api/v1/webhooks", server.addr),
+            Some(
+                json!({
+                    "url": url,
+                    "event_types": event_types,
+                    "secret": SECRET
+                })
+                .to_string(),
+            ),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(201, create_webhook_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&create_webhook_response.text().await?)?;
+
+    Ok(body["id"].as_str().unwrap().parse()?)
+}
+
+async fn get_deliveries(server: &TestServer, token: &str, webhook_id: Uuid) -> Result<Vec<Value>> {
+    let get_deliveries_response = server
+        .get_request(
+            &format!("{}/api/v1/webhooks/{}/deliveries", server.addr, webhook_id),
+            Some(token),
+        )
+        .await?;
+    assert_eq!(200, get_deliveries_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_deliveries_response.text().await?)?;
+
+    Ok(body["deliveries"].as_array().unwrap().clone())
+}
+
+// Makes any pending deliveries due now, instead of after their retry delay
+async fn make_deliveries_due(server: &TestServer) -> Result<()> {
+    sqlx::query("UPDATE webhook_delivery SET next_attempt_at = now() WHERE status = 'PENDING'")
+        .execute(&server.db_pool)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn manage_webhooks_successful() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(1).await?;
+
+    let webhook_id = create_webhook(
+        &server,
+        &token,
+        "https://automation.example.edu/hooks/k6r",
+        json!(["review.created", "review.exported"]),
+    )
+    .await?;
+
+    // Act
+    let update_webhook_response = server
+        .patch_request(
+            &format!("{}/api/v1/webhooks/{}", server.addr, webhook_id),
+            Some(json!({ "active": false }).to_string()),
+            Some(&token),
+        )
+        .await?;
+
+    let get_webhooks_response = server
+        .get_request(&format!("{}/api/v1/webhooks", server.addr), Some(&token))
+        .await?;
+
+    // Assert
+    assert_eq!(204, update_webhook_response.status().as_u16());
+    assert_eq!(200, get_webhooks_response.status().as_u16());
+
+    let body = get_webhooks_response.text().await?;
+    assert!(
+        !body.contains(SECRET),
+        "the secret should never be returned"
+    );
+
+    let body: Value = serde_json::from_str(&body)?;
+    let webhook = &body["webhooks"][0]["webhook"];
+
+    assert_eq!(webhook_id.to_string(), webhook["id"]);
+    assert_eq!("https://automation.example.edu/hooks/k6r", webhook["url"]);
+    assert_eq!(
+        json!(["review.created", "review.exported"]),
+        webhook["event_types"]
+    );
+    assert_eq!(false, webhook["active"]);
+
+    let delete_webhook_response = server
+        .delete_request(
+            &format!("{}/api/v1/webhooks/{}", server.addr, webhook_id),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(204, delete_webhook_response.status().as_u16());
+
+    let delete_webhook_response = server
+        .delete_request(
+            &format!("{}/api/v1/webhooks/{}", server.addr, webhook_id),
+            Some(&token),
+        )
+        .await?;
+    assert_eq!(404, delete_webhook_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_webhook_returns_400_for_invalid_data() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(1).await?;
+
+    let test_cases = [
+        (
+            json!({ "url": "ftp://example.edu/hook", "event_types": ["review.created"], "secret": SECRET }),
+            "non-http url",
+        ),
+        (
+            json!({ "url": "https://example.edu/hook", "event_types": [], "secret": SECRET }),
+            "no event types",
+        ),
+        (
+            json!({ "url": "https://example.edu/hook", "event_types": ["review.approved"], "secret": SECRET }),
+            "unknown event type",
+        ),
+        (
+            json!({ "url": "https://example.edu/hook", "event_types": ["review.created"], "secret": "short" }),
+            "short secret",
+        ),
+    ];
+
+    for (body, description) in test_cases {
+        // Act
+        let create_webhook_response = server
+            .post_request(
+                &format!("{}/api/v1/webhooks", server.addr),
+                Some(body.to_string()),
+                Some(&token),
+            )
+            .await?;
+
+        // Assert
+        assert_eq!(
+            400,
+            create_webhook_response.status().as_u16(),
+            "API did not fail with a 400 status for {}",
+            description
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn webhooks_are_admin_only() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(0).await?;
+
+    // Act
+    let get_webhooks_response = server
+        .get_request(&format!("{}/api/v1/webhooks", server.addr), Some(&token))
+        .await?;
+
+    let create_webhook_response = server
+        .post_request(
+            &format!("{}/api/v1/webhooks", server.addr),
+            Some(
+                json!({
+                    "url": "https://example.edu/hook",
+                    "event_types": ["review.created"],
+                    "secret": SECRET
+                })
+                .to_string(),
+            ),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(403, get_webhooks_response.status().as_u16());
+    assert_eq!(403, create_webhook_response.status().as_u16());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn review_events_are_delivered_with_a_valid_signature() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(1).await?;
+
+    let receiver = Receiver::spawn(0).await?;
+    let webhook_id =
+        create_webhook(&server, &token, &receiver.url, json!(["review.created"])).await?;
+
+    // A webhook which isn't subscribed to the event receives nothing
+    let unsubscribed = Receiver::spawn(0).await?;
+    create_webhook(
+        &server,
+        &token,
+        &unsubscribed.url,
+        json!(["review.deleted"]),
+    )
+    .await?;
+
+    let config = WebhookConfig::default();
+    let client = webhook_client(&config)?;
+
+    // Act
+    server.create_review(&token, "50000001", json!({})).await?;
+
+    let delivered = deliver_webhooks(&config, &client, &server.db_pool).await?;
+
+    // Assert
+    assert_eq!(1, delivered);
+    assert!(unsubscribed.requests().is_empty());
+
+    let requests = receiver.requests();
+    assert_eq!(1, requests.len());
+
+    let (headers, body) = &requests[0];
+
+    assert_eq!("review.created", headers["x-k6r-event"]);
+
+    let timestamp: i64 = headers["x-k6r-timestamp"].to_str()?.parse()?;
+    assert_eq!(
+        sign_webhook_payload(SECRET, timestamp, body)?,
+        headers["x-k6r-signature"].to_str()?
+    );
+
+    let payload: Value = serde_json::from_str(body)?;
+    assert_eq!("review.created", payload["event"]);
+    assert_eq!(
+        "50000001",
+        payload["data"]["software_review"]["software_request"]["td_request_id"]
+    );
+
+    let deliveries = get_deliveries(&server, &token, webhook_id).await?;
+    assert_eq!(1, deliveries.len());
+    assert_eq!(headers["x-k6r-delivery"].to_str()?, deliveries[0]["id"]);
+    assert_eq!("DELIVERED", deliveries[0]["status"]);
+    assert_eq!(204, deliveries[0]["attempt_log"][0]["status_code"]);
+
+    // Nothing is left to deliver
+    assert_eq!(
+        0,
+        deliver_webhooks(&config, &client, &server.db_pool).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_deliveries_are_retried_until_max_attempts() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(1).await?;
+
+    let retried = Receiver::spawn(1).await?;
+    let retried_id =
+        create_webhook(&server, &token, &retried.url, json!(["review.created"])).await?;
+
+    let failing = Receiver::spawn(usize::MAX).await?;
+    let failing_id =
+        create_webhook(&server, &token, &failing.url, json!(["review.created"])).await?;
+
+    let config = WebhookConfig {
+        max_attempts: 3,
+        ..Default::default()
+    };
+    let client = webhook_client(&config)?;
+
+    server.create_review(&token, "60000001", json!({})).await?;
+
+    // Act
+    assert_eq!(
+        2,
+        deliver_webhooks(&config, &client, &server.db_pool).await?
+    );
+
+    // Failed deliveries wait for their retry delay
+    assert_eq!(
+        0,
+        deliver_webhooks(&config, &client, &server.db_pool).await?
+    );
+
+    for _ in 0..3 {
+        make_deliveries_due(&server).await?;
+        deliver_webhooks(&config, &client, &server.db_pool).await?;
+    }
+
+    // Assert
+    assert_eq!(2, retried.requests().len());
+    assert_eq!(3, failing.requests().len());
+
+    let deliveries = get_deliveries(&server, &token, retried_id).await?;
+    assert_eq!("DELIVERED", deliveries[0]["status"]);
+    assert_eq!(2, deliveries[0]["attempts"]);
+    assert_eq!(500, deliveries[0]["attempt_log"][0]["status_code"]);
+    assert!(deliveries[0]["attempt_log"][0]["error"].is_string());
+    assert_eq!(204, deliveries[0]["attempt_log"][1]["status_code"]);
+
+    let deliveries = get_deliveries(&server, &token, failing_id).await?;
+    assert_eq!("FAILED", deliveries[0]["status"]);
+    assert_eq!(3, deliveries[0]["attempt_log"].as_array().unwrap().len());
+    assert!(deliveries[0]["next_attempt_at"].is_null());
+
+    // The same delivery is sent every time, so receivers can tell retries apart
+    let delivery_ids: Vec<String> = failing
+        .requests()
+        .iter()
+        .map(|(headers, _)| headers["x-k6r-delivery"].to_str().unwrap().to_string())
+        .collect();
+    assert!(delivery_ids.iter().all(|id| *id == delivery_ids[0]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_webhook_deliveries_filters_by_status() -> Result<()> {
+    // Arrange
+    let server = spawn_server().await?;
+
+    let token = server.login(1).await?;
+
+    let receiver = Receiver::spawn(0).await?;
+    let webhook_id =
+        create_webhook(&server, &token, &receiver.url, json!(["review.created"])).await?;
+
+    server.create_review(&token, "70000001", json!({})).await?;
+
+    let config = WebhookConfig::default();
+    deliver_webhooks(&config, &webhook_client(&config)?, &server.db_pool).await?;
+
+    server.create_review(&token, "70000002", json!({})).await?;
+
+    // Act
+    let get_pending_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/webhooks/{}/deliveries?status=PENDING",
+                server.addr, webhook_id
+            ),
+            Some(&token),
+        )
+        .await?;
+
+    let get_invalid_limit_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/webhooks/{}/deliveries?limit=0",
+                server.addr, webhook_id
+            ),
+            Some(&token),
+        )
+        .await?;
+
+    let get_unknown_webhook_response = server
+        .get_request(
+            &format!(
+                "{}/api/v1/webhooks/{}/deliveries",
+                server.addr,
+                Uuid::new_v4()
+            ),
+            Some(&token),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, get_pending_response.status().as_u16());
+
+    let body: Value = serde_json::from_str(&get_pending_response.text().await?)?;
+    let deliveries = body["deliveries"].as_array().unwrap();
+    assert_eq!(1, deliveries.len());
+    assert_eq!("PENDING", deliveries[0]["status"]);
+    assert!(deliveries[0]["next_attempt_at"].is_string());
+
+    assert_eq!(2, get_deliveries(&server, &token, webhook_id).await?.len());
+
+    assert_eq!(400, get_invalid_limit_response.status().as_u16());
+    assert_eq!(404, get_unknown_webhook_response.status().as_u16());
+
+    Ok(())
+}